@@ -447,6 +447,16 @@ impl
     }
 }
 
+impl api::ConnectorCapability for {{project-name | downcase | pascal_case}} {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::{{project-name | downcase | pascal_case}},
+            flows: vec![api::ConnectorFlow::Authorize],
+            payment_methods: vec![api::PaymentMethodCapability::new(api::enums::PaymentMethod::Card)],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for {{project-name | downcase | pascal_case}} {
     fn get_webhook_object_reference_id(
@@ -75,6 +75,10 @@ pub struct MerchantAccountCreate {
     #[cfg(not(feature = "multiple_mca"))]
     #[schema(value_type = Option<PrimaryBusinessDetails>)]
     pub primary_business_details: Option<Vec<PrimaryBusinessDetails>>,
+
+    /// Time (in seconds) after creation within which a payment intent has to be confirmed. Intents not completed within this time are cancelled. Defaults to 900 seconds (15 minutes)
+    #[schema(minimum = 60, maximum = 7776000, example = 900)]
+    pub intent_fulfillment_time: Option<u32>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema)]
@@ -135,6 +139,10 @@ pub struct MerchantAccountUpdate {
 
     ///Default business details for connector routing
     pub primary_business_details: Option<Vec<PrimaryBusinessDetails>>,
+
+    /// Time (in seconds) after creation within which a payment intent has to be confirmed. Intents not completed within this time are cancelled. Defaults to 900 seconds (15 minutes)
+    #[schema(minimum = 60, maximum = 7776000, example = 900)]
+    pub intent_fulfillment_time: Option<u32>,
 }
 
 #[derive(Clone, Debug, ToSchema, Serialize)]
@@ -201,6 +209,10 @@ pub struct MerchantAccountResponse {
     ///Default business details for connector routing
     #[schema(value_type = Vec<PrimaryBusinessDetails>)]
     pub primary_business_details: Vec<PrimaryBusinessDetails>,
+
+    /// Time (in seconds) after creation within which a payment intent has to be confirmed
+    #[schema(example = 900)]
+    pub intent_fulfillment_time: Option<i64>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
@@ -629,6 +641,25 @@ pub struct ToggleKVResponse {
     /// Status of KV for the specific merchant
     #[schema(example = true)]
     pub kv_enabled: bool,
+    /// Status of KV for the specific merchant, including whether KV is being turned off
+    #[schema(example = "enabled")]
+    pub kv_status: KvStatus,
+    /// The time at which KV was turned off, while the data of the merchant in Redis is being
+    /// drained to the database
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub kv_drain_started_at: Option<time::PrimitiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KvStatus {
+    /// The merchant is served through Redis
+    Enabled,
+    /// KV has been turned off, the merchant is served through Redis until the drainer has
+    /// drained all of its data to the database
+    Draining,
+    /// The merchant is served from the database
+    Disabled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -0,0 +1,174 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::enums as api_enums;
+
+/// The flows a connector may implement on top of a plain authorization
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, ToSchema, strum::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectorFlow {
+    Authorize,
+    Capture,
+    MultipleCaptures,
+    Void,
+    PaymentSync,
+    Refund,
+    RefundSync,
+    Mandates,
+    ThreeDs,
+    NetworkTokens,
+}
+
+/// A payment method supported by a connector, optionally restricted to a set of payment method
+/// types, currencies and countries. A restriction left as `None` means no restriction.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, ToSchema)]
+pub struct PaymentMethodCapability {
+    #[schema(value_type = PaymentMethod, example = "card")]
+    pub payment_method: api_enums::PaymentMethod,
+    #[schema(value_type = Option<Vec<PaymentMethodType>>, example = json!(["credit", "debit"]))]
+    pub payment_method_types: Option<Vec<api_enums::PaymentMethodType>>,
+    #[schema(value_type = Option<Vec<Currency>>, example = json!(["USD", "EUR"]))]
+    pub currencies: Option<Vec<api_enums::Currency>>,
+    #[schema(value_type = Option<Vec<CountryCode>>, example = json!(["US", "NL"]))]
+    pub countries: Option<Vec<api_enums::CountryCode>>,
+}
+
+impl PaymentMethodCapability {
+    /// Supports every payment method type of `payment_method`, in every currency and country
+    pub fn new(payment_method: api_enums::PaymentMethod) -> Self {
+        Self {
+            payment_method,
+            payment_method_types: None,
+            currencies: None,
+            countries: None,
+        }
+    }
+
+    pub fn with_payment_method_types(
+        mut self,
+        payment_method_types: Vec<api_enums::PaymentMethodType>,
+    ) -> Self {
+        self.payment_method_types = Some(payment_method_types);
+        self
+    }
+
+    pub fn with_currencies(mut self, currencies: Vec<api_enums::Currency>) -> Self {
+        self.currencies = Some(currencies);
+        self
+    }
+
+    pub fn with_countries(mut self, countries: Vec<api_enums::CountryCode>) -> Self {
+        self.countries = Some(countries);
+        self
+    }
+
+    fn allows<T: PartialEq>(allowed: &Option<Vec<T>>, value: Option<&T>) -> bool {
+        match (allowed, value) {
+            (Some(allowed), Some(value)) => allowed.contains(value),
+            _ => true,
+        }
+    }
+}
+
+/// Everything a connector declares it can do
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, ToSchema)]
+pub struct ConnectorCapabilities {
+    #[schema(value_type = Connector, example = "stripe")]
+    pub connector: api_enums::Connector,
+    pub flows: Vec<ConnectorFlow>,
+    pub payment_methods: Vec<PaymentMethodCapability>,
+}
+
+impl ConnectorCapabilities {
+    pub fn supports_flow(&self, flow: ConnectorFlow) -> bool {
+        self.flows.contains(&flow)
+    }
+
+    /// Checks a payment method against the declared capabilities. Any of the optional arguments
+    /// that is not known yet is not used to reject the payment method.
+    pub fn supports_payment_method(
+        &self,
+        payment_method: api_enums::PaymentMethod,
+        payment_method_type: Option<api_enums::PaymentMethodType>,
+        currency: Option<api_enums::Currency>,
+        country: Option<api_enums::CountryCode>,
+    ) -> bool {
+        self.payment_methods.iter().any(|capability| {
+            capability.payment_method == payment_method
+                && PaymentMethodCapability::allows(
+                    &capability.payment_method_types,
+                    payment_method_type.as_ref(),
+                )
+                && PaymentMethodCapability::allows(&capability.currencies, currency.as_ref())
+                && PaymentMethodCapability::allows(&capability.countries, country.as_ref())
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConnectorCapabilitiesResponse {
+    /// The capabilities of every connector payments can be routed to
+    pub connectors: Vec<ConnectorCapabilities>,
+}
+
+#[cfg(test)]
+mod connector_capabilities_tests {
+    use super::*;
+
+    fn capabilities() -> ConnectorCapabilities {
+        ConnectorCapabilities {
+            connector: api_enums::Connector::Mollie,
+            flows: vec![ConnectorFlow::Authorize, ConnectorFlow::Refund],
+            payment_methods: vec![
+                PaymentMethodCapability::new(api_enums::PaymentMethod::Card),
+                PaymentMethodCapability::new(api_enums::PaymentMethod::BankRedirect)
+                    .with_payment_method_types(vec![api_enums::PaymentMethodType::Ideal])
+                    .with_currencies(vec![api_enums::Currency::EUR])
+                    .with_countries(vec![api_enums::CountryCode::NL]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_supports_flow() {
+        let capabilities = capabilities();
+        assert!(capabilities.supports_flow(ConnectorFlow::Refund));
+        assert!(!capabilities.supports_flow(ConnectorFlow::Capture));
+    }
+
+    #[test]
+    fn test_supports_payment_method() {
+        let capabilities = capabilities();
+        assert!(capabilities.supports_payment_method(
+            api_enums::PaymentMethod::Card,
+            Some(api_enums::PaymentMethodType::Credit),
+            Some(api_enums::Currency::USD),
+            Some(api_enums::CountryCode::US),
+        ));
+        assert!(capabilities.supports_payment_method(
+            api_enums::PaymentMethod::BankRedirect,
+            Some(api_enums::PaymentMethodType::Ideal),
+            Some(api_enums::Currency::EUR),
+            None,
+        ));
+        assert!(!capabilities.supports_payment_method(
+            api_enums::PaymentMethod::BankRedirect,
+            Some(api_enums::PaymentMethodType::Sofort),
+            Some(api_enums::Currency::EUR),
+            Some(api_enums::CountryCode::NL),
+        ));
+        assert!(!capabilities.supports_payment_method(
+            api_enums::PaymentMethod::BankRedirect,
+            Some(api_enums::PaymentMethodType::Ideal),
+            Some(api_enums::Currency::USD),
+            Some(api_enums::CountryCode::NL),
+        ));
+        assert!(!capabilities.supports_payment_method(
+            api_enums::PaymentMethod::Wallet,
+            None,
+            None,
+            None,
+        ));
+    }
+}
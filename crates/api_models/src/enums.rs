@@ -252,6 +252,115 @@ pub enum Currency {
     ZAR,
 }
 
+impl Currency {
+    /// Number of digits after the decimal point in the minor unit of the currency, as defined by
+    /// ISO 4217
+    pub fn number_of_digits_after_decimal_point(self) -> u8 {
+        match self {
+            Self::JPY | Self::KRW => 0,
+            Self::AED
+            | Self::ALL
+            | Self::AMD
+            | Self::ANG
+            | Self::ARS
+            | Self::AUD
+            | Self::AWG
+            | Self::AZN
+            | Self::BBD
+            | Self::BDT
+            | Self::BMD
+            | Self::BND
+            | Self::BOB
+            | Self::BRL
+            | Self::BSD
+            | Self::BWP
+            | Self::BZD
+            | Self::CAD
+            | Self::CHF
+            | Self::CNY
+            | Self::COP
+            | Self::CRC
+            | Self::CUP
+            | Self::CZK
+            | Self::DKK
+            | Self::DOP
+            | Self::DZD
+            | Self::EGP
+            | Self::ETB
+            | Self::EUR
+            | Self::FJD
+            | Self::GBP
+            | Self::GHS
+            | Self::GIP
+            | Self::GMD
+            | Self::GTQ
+            | Self::GYD
+            | Self::HKD
+            | Self::HNL
+            | Self::HRK
+            | Self::HTG
+            | Self::HUF
+            | Self::IDR
+            | Self::ILS
+            | Self::INR
+            | Self::JMD
+            | Self::KES
+            | Self::KGS
+            | Self::KHR
+            | Self::KYD
+            | Self::KZT
+            | Self::LAK
+            | Self::LBP
+            | Self::LKR
+            | Self::LRD
+            | Self::LSL
+            | Self::MAD
+            | Self::MDL
+            | Self::MKD
+            | Self::MMK
+            | Self::MNT
+            | Self::MOP
+            | Self::MUR
+            | Self::MVR
+            | Self::MWK
+            | Self::MXN
+            | Self::MYR
+            | Self::NAD
+            | Self::NGN
+            | Self::NIO
+            | Self::NOK
+            | Self::NPR
+            | Self::NZD
+            | Self::PEN
+            | Self::PGK
+            | Self::PHP
+            | Self::PKR
+            | Self::PLN
+            | Self::QAR
+            | Self::RUB
+            | Self::SAR
+            | Self::SCR
+            | Self::SEK
+            | Self::SGD
+            | Self::SLL
+            | Self::SOS
+            | Self::SSP
+            | Self::SVC
+            | Self::SZL
+            | Self::THB
+            | Self::TTD
+            | Self::TWD
+            | Self::TZS
+            | Self::USD
+            | Self::UYU
+            | Self::UZS
+            | Self::YER
+            | Self::ZAR => 2,
+            Self::BHD | Self::JOD | Self::KWD | Self::OMR => 3,
+        }
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -269,6 +378,7 @@ pub enum Currency {
 pub enum EventType {
     PaymentSucceeded,
     PaymentProcessing,
+    PaymentCancelled,
     ActionRequired,
     RefundSucceeded,
     RefundFailed,
@@ -574,6 +684,7 @@ pub enum MandateStatus {
     serde::Serialize,
     strum::Display,
     strum::EnumString,
+    strum::EnumIter,
     frunk::LabelledGeneric,
     Hash,
 )]
@@ -591,19 +702,19 @@ pub enum Connector {
     Coinbase,
     Cybersource,
     #[default]
-    Dummy,
+    Simulator,
     Opennode,
     Bambora,
     Dlocal,
     Fiserv,
-    //Forte,
+    Forte,
     Globalpay,
     Klarna,
     Mollie,
     Multisafepay,
-    // Nexinets, added as template code for future use
+    Nexinets,
     Nuvei,
-    // Payeezy, As psync and rsync are not supported by this connector, it is added as template code for future usage
+    Payeezy,
     Paypal,
     Payu,
     Rapyd,
@@ -657,19 +768,20 @@ pub enum RoutableConnectors {
     Cybersource,
     Dlocal,
     Fiserv,
-    //Forte,
+    Forte,
     Globalpay,
     Klarna,
     Mollie,
     Multisafepay,
-    // Nexinets, added as template code for future use
+    Nexinets,
     Nuvei,
     Opennode,
-    // Payeezy, As psync and rsync are not supported by this connector, it is added as template code for future usage
+    Payeezy,
     Paypal,
     Payu,
     Rapyd,
     Shift4,
+    Simulator,
     Stripe,
     Trustpay,
     Worldline,
@@ -949,3 +1061,110 @@ pub struct UnresolvedResponseReason {
     /// A message to merchant to give hint on next action he/she should do to resolve
     pub message: String,
 }
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    frunk::LabelledGeneric,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentLinkStatus {
+    /// The link can be used to complete the payment
+    #[default]
+    Active,
+    /// The payment was completed through the link
+    Completed,
+    /// The link expired before the payment was completed
+    Expired,
+}
+
+/// Connector agnostic category of the error a connector failed a payment with. Error codes are
+/// currently mapped for Adyen, Authorize.net, Checkout, Shift4, Stripe and the simulator only, the
+/// errors of every other connector are categorized as `unknown`.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The account of the customer does not have enough funds
+    InsufficientFunds,
+    /// The issuer declined the payment without giving a reason
+    DoNotHonor,
+    /// The card has expired
+    ExpiredCard,
+    /// The CVC of the card is incorrect
+    IncorrectCvc,
+    /// The card number is incorrect
+    InvalidCardNumber,
+    /// The card was reported lost or stolen
+    LostOrStolenCard,
+    /// The issuer or the connector suspects the payment to be fraudulent
+    FraudSuspected,
+    /// The issuer requires the customer to be authenticated, for instance with 3DS
+    AuthenticationRequired,
+    /// The customer failed to authenticate
+    AuthenticationFailed,
+    /// The card cannot be used for this kind of payment
+    CardNotSupported,
+    /// The payment exceeds the amount or frequency limits of the card
+    LimitExceeded,
+    /// The issuer could not be reached
+    IssuerUnavailable,
+    /// The connector failed to process the payment
+    ProcessingError,
+    /// The connector rejected the request because too many requests were made
+    RateLimited,
+    /// The connector rejected the request as invalid
+    InvalidRequest,
+    /// The error code is not mapped to a category
+    #[default]
+    Unknown,
+}
+
+impl ErrorCategory {
+    /// Whether a payment that failed with this error can be confirmed again, either with the same
+    /// payment method or with a different one. Payments suspected to be fraudulent, paid with a
+    /// lost or stolen card, rejected as invalid or failed for an unknown reason cannot be retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::InsufficientFunds
+            | Self::DoNotHonor
+            | Self::ExpiredCard
+            | Self::IncorrectCvc
+            | Self::InvalidCardNumber
+            | Self::AuthenticationRequired
+            | Self::AuthenticationFailed
+            | Self::CardNotSupported
+            | Self::LimitExceeded
+            | Self::IssuerUnavailable
+            | Self::ProcessingError
+            | Self::RateLimited => true,
+            Self::LostOrStolenCard
+            | Self::FraudSuspected
+            | Self::InvalidRequest
+            | Self::Unknown => false,
+        }
+    }
+}
@@ -3,6 +3,7 @@ pub mod admin;
 pub mod api_keys;
 pub mod bank_accounts;
 pub mod cards_info;
+pub mod connector_capabilities;
 pub mod customers;
 pub mod disputes;
 pub mod enums;
@@ -10,8 +11,10 @@ pub mod enums;
 pub mod errors;
 pub mod files;
 pub mod mandates;
+pub mod payment_link;
 pub mod payment_methods;
 pub mod payments;
 pub mod payouts;
 pub mod refunds;
+pub mod simulator;
 pub mod webhooks;
@@ -0,0 +1,111 @@
+use common_utils::{custom_serde, pii};
+use masking::Secret;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::{enums as api_enums, payments};
+
+/// The request body for creating a payment link.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentLinkCreateRequest {
+    /// The amount to be collected through the link, in the lowest denomination of the currency
+    #[schema(minimum = 1, example = 6540)]
+    pub amount: i64,
+
+    /// The three letter ISO currency code in which the amount is to be collected
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+
+    /// A description of the payment, displayed to the customer on the checkout page
+    #[schema(max_length = 255, example = "Order #1234 from Example Store")]
+    pub description: Option<String>,
+
+    /// The number of seconds after which the link expires. Defaults to 15 minutes.
+    #[schema(minimum = 60, maximum = 7776000, example = 900)]
+    pub expires_in: Option<u32>,
+
+    /// The payment method types the customer may use to complete the payment
+    #[schema(value_type = Option<Vec<PaymentMethodType>>)]
+    pub allowed_payment_method_types: Option<Vec<api_enums::PaymentMethodType>>,
+
+    /// The URL to which the customer is redirected after completing the payment
+    #[schema(value_type = Option<String>, example = "https://example.com/payment/complete")]
+    pub return_url: Option<url::Url>,
+
+    /// The identifier for the customer making the payment
+    #[schema(max_length = 255, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: Option<String>,
+}
+
+/// The response body for a payment link.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaymentLinkResponse {
+    /// The identifier for the payment link
+    #[schema(max_length = 64, example = "plink_hGPDBzVuiMw2ZPzDuMGZ")]
+    pub payment_link_id: String,
+
+    /// The identifier for the payment the link collects
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The shareable URL of the hosted checkout page
+    #[schema(
+        example = "https://sandbox.hyperswitch.io/payment_link/pay/plink_hGPDBzVuiMw2ZPzDuMGZ"
+    )]
+    pub link: String,
+
+    /// The amount to be collected through the link
+    #[schema(example = 6540)]
+    pub amount: i64,
+
+    /// The currency in which the amount is to be collected
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+
+    /// A description of the payment
+    pub description: Option<String>,
+
+    /// The status of the payment link
+    #[schema(value_type = PaymentLinkStatus, example = "active")]
+    pub status: api_enums::PaymentLinkStatus,
+
+    /// The time at which the payment link was created
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+
+    /// The time after which the payment link can no longer be used
+    #[schema(example = "2022-09-10T10:26:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub expires_at: PrimitiveDateTime,
+}
+
+/// The card details submitted by the customer through the hosted checkout page.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaymentLinkCheckoutForm {
+    pub card_holder_name: Secret<String>,
+    pub card_number: Secret<String, pii::CardNumber>,
+    pub card_exp_month: Secret<String>,
+    pub card_exp_year: Secret<String>,
+    pub card_cvc: Secret<String>,
+}
+
+impl From<PaymentLinkCheckoutForm> for payments::Card {
+    fn from(form: PaymentLinkCheckoutForm) -> Self {
+        Self {
+            card_number: form.card_number,
+            card_exp_month: form.card_exp_month,
+            card_exp_year: form.card_exp_year,
+            card_holder_name: form.card_holder_name,
+            card_cvc: form.card_cvc,
+            card_issuer: None,
+            card_network: None,
+        }
+    }
+}
@@ -596,3 +596,23 @@ pub struct TokenizedWalletValue1 {
 pub struct TokenizedWalletValue2 {
     pub customer_id: Option<String>,
 }
+
+/// Lifecycle update for a network token, sent by the token service provider
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct NetworkTokenNotification {
+    pub network_token_ref_id: String,
+    pub event_type: NetworkTokenEventType,
+    /// Expiry month of the token, present when the token was re-issued for an updated PAN
+    pub token_exp_month: Option<masking::Secret<String>>,
+    /// Expiry year of the token, present when the token was re-issued for an updated PAN
+    pub token_exp_year: Option<masking::Secret<String>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkTokenEventType {
+    Suspended,
+    Resumed,
+    Deleted,
+    PanUpdated,
+}
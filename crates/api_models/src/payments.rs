@@ -218,6 +218,14 @@ pub struct PaymentsRequest {
 
     /// Business sub label for the payment
     pub business_sub_label: Option<String>,
+
+    /// The currency in which the payment is settled with the connector, if it differs from `currency`. The amount is converted into this currency using the exchange rate available when the payment is created
+    #[schema(value_type = Option<Currency>, example = "EUR")]
+    pub settlement_currency: Option<api_enums::Currency>,
+
+    /// Time (in seconds) after creation within which the payment has to be confirmed. Overrides the `intent_fulfillment_time` configured for the merchant
+    #[schema(example = 900)]
+    pub session_expiry: Option<u32>,
 }
 
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq)]
@@ -1103,6 +1111,12 @@ pub struct PaymentsResponse {
     #[schema(example = "Failed while verifying the card")]
     pub error_message: Option<String>,
 
+    /// Connector agnostic category of the error code, failed payments can only be confirmed again
+    /// when the category is retryable. Only the error codes of Adyen, Authorize.net, Checkout,
+    /// Shift4, Stripe and the simulator are mapped, other connectors report `unknown`
+    #[schema(value_type = Option<ErrorCategory>, example = "insufficient_funds")]
+    pub error_category: Option<api_enums::ErrorCategory>,
+
     /// Payment Experience for the current payment
     #[schema(value_type = Option<PaymentExperience>, example = "redirect_to_url")]
     pub payment_experience: Option<api_enums::PaymentExperience>,
@@ -1127,6 +1141,23 @@ pub struct PaymentsResponse {
     /// Allowed Payment Method Types for a given PaymentIntent
     #[schema(value_type = Option<Vec<PaymentMethodType>>)]
     pub allowed_payment_method_types: Option<Vec<api_enums::PaymentMethodType>>,
+
+    /// The amount settled with the connector, in the lowest denomination of the settlement currency
+    #[schema(example = 6010)]
+    pub settlement_amount: Option<i64>,
+
+    /// The currency in which the payment is settled with the connector
+    #[schema(value_type = Option<Currency>, example = "EUR")]
+    pub settlement_currency: Option<api_enums::Currency>,
+
+    /// The exchange rate applied to convert the payment amount into the settlement currency
+    #[schema(example = "0.919")]
+    pub fx_rate: Option<String>,
+
+    /// Time after which the payment can no longer be confirmed, and is cancelled if not completed
+    #[schema(example = "2022-09-10T10:26:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_on: Option<PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, ToSchema)]
@@ -1376,12 +1407,46 @@ pub struct OrderDetails {
     /// The quantity of the product to be purchased
     #[schema(example = 1)]
     pub quantity: u16,
+    /// Price of a single unit of the product, in the lowest denomination of the currency
+    #[schema(example = 6540)]
+    pub unit_price: Option<i64>,
+    /// Tax applied on this line item, in the lowest denomination of the currency
+    #[schema(example = 520)]
+    pub tax_amount: Option<i64>,
+    /// Discount applied on this line item, in the lowest denomination of the currency
+    #[schema(example = 0)]
+    pub discount_amount: Option<i64>,
+    /// Stock keeping unit or product code of the product
+    #[schema(max_length = 255, example = "SKU-1234")]
+    pub sku: Option<String>,
+    /// Commodity code of the product, as used for Level 3 card data (e.g. UNSPSC)
+    #[schema(max_length = 12, example = "43211503")]
+    pub commodity_code: Option<String>,
+    /// Unit in which the quantity of the product is measured
+    #[schema(max_length = 12, example = "EA")]
+    pub unit_of_measure: Option<String>,
+}
+
+/// Order level data used by connectors supporting Level 2 and Level 3 card data, which helps
+/// qualify payments on corporate and purchasing cards for reduced interchange rates
+#[derive(Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+pub struct PurchaseDetails {
+    /// Reference provided by the customer for this purchase, usually the purchase order number
+    #[schema(max_length = 25, example = "PO-2023-0042")]
+    pub customer_reference: Option<String>,
+    /// Total tax amount of the order, in the lowest denomination of the currency
+    #[schema(example = 520)]
+    pub tax_amount: Option<i64>,
+    /// Line items of the order
+    pub line_items: Option<Vec<OrderDetails>>,
 }
 
 #[derive(Default, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
 pub struct Metadata {
     /// Information about the product and quantity for specific connectors. (e.g. Klarna)
     pub order_details: Option<OrderDetails>,
+    /// Line items, customer reference and tax totals used for Level 2 / Level 3 card data
+    pub purchase_details: Option<PurchaseDetails>,
     /// Any other metadata that is to be provided
     #[schema(value_type = Object, example = r#"{ "city": "NY", "unit": "245" }"#)]
     #[serde(flatten)]
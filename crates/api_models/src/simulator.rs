@@ -0,0 +1,184 @@
+//! Requests and responses of the sandbox simulator, the built-in payment processor that the
+//! `simulator` connector talks to.
+
+use common_utils::pii;
+use masking::Secret;
+use serde::{Deserialize, Serialize};
+
+use crate::enums as api_enums;
+
+/// Header carrying the hex encoded HMAC-SHA256 signature of the body of simulator webhooks
+pub const SIMULATOR_SIGNATURE_HEADER: &str = "X-Simulator-Signature";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorPaymentRequest {
+    pub amount: i64,
+    pub currency: api_enums::Currency,
+    /// Absent when the payment is made with a previously set up mandate
+    pub payment_method: Option<SimulatorPaymentMethod>,
+    /// Whether the payment should be captured right after it is authorized
+    pub capture: bool,
+    pub three_ds: bool,
+    /// Whether the payment method should be stored as a mandate for later payments
+    pub setup_mandate: bool,
+    pub mandate_id: Option<String>,
+    pub return_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SimulatorPaymentMethod {
+    Card(SimulatorCard),
+    Wallet {
+        wallet_type: api_enums::PaymentMethodType,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorCard {
+    pub number: Secret<String, pii::CardNumber>,
+    pub expiry_month: Secret<String>,
+    pub expiry_year: Secret<String>,
+    pub cvc: Secret<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatorPaymentStatus {
+    Succeeded,
+    RequiresCapture,
+    RequiresCustomerAction,
+    Processing,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorPaymentResponse {
+    pub id: String,
+    pub status: SimulatorPaymentStatus,
+    pub amount: i64,
+    pub amount_captured: i64,
+    pub currency: api_enums::Currency,
+    pub mandate_id: Option<String>,
+    pub next_action: Option<SimulatorNextAction>,
+    pub error: Option<SimulatorError>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SimulatorNextAction {
+    RedirectToUrl { url: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorCaptureRequest {
+    pub amount: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorAuthenticationParams {
+    /// `failure` fails the 3DS challenge, anything else completes it
+    pub outcome: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorRefundRequest {
+    pub payment_id: String,
+    pub amount: i64,
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatorRefundStatus {
+    Succeeded,
+    Pending,
+    Failed,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorRefundResponse {
+    pub id: String,
+    pub payment_id: String,
+    pub amount: i64,
+    pub currency: api_enums::Currency,
+    pub status: SimulatorRefundStatus,
+    pub error: Option<SimulatorError>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatorDisputeStatus {
+    Opened,
+    Accepted,
+    Challenged,
+    Won,
+    Lost,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorDisputeResponse {
+    pub id: String,
+    pub payment_id: String,
+    pub amount: i64,
+    pub currency: api_enums::Currency,
+    pub status: SimulatorDisputeStatus,
+    pub reason: String,
+    pub reason_code: String,
+    /// Date before which evidence has to be submitted, in ISO 8601 format
+    pub evidence_due_by: String,
+    /// Creation date of the dispute, in ISO 8601 format
+    pub created_at: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorEvidenceRequest {
+    pub uncategorized_text: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, strum::Display)]
+pub enum SimulatorWebhookEventType {
+    #[serde(rename = "payment.succeeded")]
+    #[strum(serialize = "payment.succeeded")]
+    PaymentSucceeded,
+    #[serde(rename = "payment.failed")]
+    #[strum(serialize = "payment.failed")]
+    PaymentFailed,
+    #[serde(rename = "refund.succeeded")]
+    #[strum(serialize = "refund.succeeded")]
+    RefundSucceeded,
+    #[serde(rename = "refund.failed")]
+    #[strum(serialize = "refund.failed")]
+    RefundFailed,
+    #[serde(rename = "dispute.opened")]
+    #[strum(serialize = "dispute.opened")]
+    DisputeOpened,
+}
+
+/// Body of the webhooks sent by the simulator, signed with the merchant's webhook secret
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorWebhook {
+    pub id: String,
+    pub event_type: SimulatorWebhookEventType,
+    pub data: SimulatorWebhookObject,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "object", rename_all = "snake_case")]
+pub enum SimulatorWebhookObject {
+    Payment(SimulatorPaymentResponse),
+    Refund(SimulatorRefundResponse),
+    Dispute(SimulatorDisputeResponse),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorError {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SimulatorErrorResponse {
+    pub error: SimulatorError,
+}
@@ -13,3 +13,7 @@ pub(crate) const ALPHABETS: [char; 62] = [
 
 /// TTL for token
 pub const TOKEN_TTL: i64 = 900;
+
+/// Redis channel on which keys to be invalidated in the in-memory caches of the router are
+/// published
+pub const PUB_SUB_CHANNEL: &str = "hyperswitch_invalidate";
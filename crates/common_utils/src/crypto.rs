@@ -157,6 +157,13 @@ pub struct GcmAes256 {
     nonce: Vec<u8>,
 }
 
+impl GcmAes256 {
+    /// Creates the algorithm with the nonce (initialization vector) used to encrypt the message
+    pub fn new(nonce: Vec<u8>) -> Self {
+        Self { nonce }
+    }
+}
+
 impl EncodeMessage for GcmAes256 {
     fn encode_message(
         &self,
@@ -1,9 +1,12 @@
 use bb8::PooledConnection;
 use diesel::PgConnection;
-#[cfg(feature = "kms")]
-use external_services::kms;
+use error_stack::{IntoReport, ResultExt};
+use external_services::secrets_management::SecretsManagementConfig;
 
-use crate::settings::Database;
+use crate::{
+    errors::{DrainerError, DrainerResult},
+    settings::Database,
+};
 
 pub type PgPool = bb8::Pool<async_bb8_diesel::ConnectionManager<PgConnection>>;
 
@@ -20,17 +23,12 @@ pub async fn redis_connection(
 pub async fn diesel_make_pg_pool(
     database: &Database,
     _test_transaction: bool,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> PgPool {
-    #[cfg(feature = "kms")]
-    let password = kms::get_kms_client(kms_config)
+    let password = secrets_management
+        .get_secret(&database.password)
         .await
-        .decrypt(&database.kms_encrypted_password)
-        .await
-        .expect("Failed to KMS decrypt database password");
-
-    #[cfg(not(feature = "kms"))]
-    let password = &database.password;
+        .expect("Failed to retrieve database password");
 
     let database_url = format!(
         "postgres://{}:{}@{}:{}/{}",
@@ -46,11 +44,12 @@ pub async fn diesel_make_pg_pool(
         .expect("Failed to create PostgreSQL connection pool")
 }
 
-#[allow(clippy::expect_used)]
 pub async fn pg_connection(
     pool: &PgPool,
-) -> PooledConnection<'_, async_bb8_diesel::ConnectionManager<PgConnection>> {
+) -> DrainerResult<PooledConnection<'_, async_bb8_diesel::ConnectionManager<PgConnection>>> {
     pool.get()
         .await
-        .expect("Couldn't retrieve PostgreSQL connection")
+        .into_report()
+        .change_context(DrainerError::DatabaseConnectionError)
+        .attach_printable("Couldn't retrieve PostgreSQL connection")
 }
@@ -0,0 +1,448 @@
+//! Dead-letter streams holding the entries that could not be applied to the database.
+//!
+//! Every partition of the drainer stream has its own dead-letter stream, which lives in the same
+//! Redis hash slot as the partition. Entries carry the original `typed_sql` along with the
+//! context of the failure, and can be moved back to the partition to be drained again.
+//!
+//! Once an entry is dead-lettered, the row it touches is parked: later entries for the same row
+//! are dead-lettered as well instead of being applied on top of a row that is missing the failed
+//! change. The row is released once all of its entries have been replayed.
+
+use std::collections::{HashMap, HashSet};
+
+use error_stack::{IntoReport, ResultExt};
+use redis_interface as redis;
+use storage_models::kv;
+
+use crate::{
+    errors::{self, DrainerError},
+    kv_drain::MERCHANT_ID,
+    logger, metrics, services,
+    settings::DeadLetterCommand,
+};
+
+const TYPED_SQL: &str = "typed_sql";
+
+/// Stage at which draining a stream entry failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FailureKind {
+    /// The `typed_sql` of the entry could not be deserialized
+    Deserialization,
+    /// The query failed with a non-transient error, or exhausted its retries
+    Database,
+    /// An earlier entry for the same row is in the dead-letter stream
+    Parked,
+}
+
+impl FailureKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deserialization => "deserialization",
+            Self::Database => "database",
+            Self::Parked => "parked",
+        }
+    }
+}
+
+/// Context of a failed stream entry, recorded alongside the entry in the dead-letter stream.
+#[derive(Debug)]
+pub struct Failure<'a> {
+    pub source_stream: &'a str,
+    pub source_entry_id: &'a str,
+    pub merchant_id: Option<String>,
+    pub kind: FailureKind,
+    pub error: String,
+    pub attempts: u8,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct DeadLetterEntry {
+    pub id: String,
+    pub source_stream: Option<String>,
+    pub source_entry_id: Option<String>,
+    pub failure_kind: Option<String>,
+    pub error: Option<String>,
+    pub attempts: Option<String>,
+    pub failed_at: Option<String>,
+    pub typed_sql: Option<String>,
+    pub merchant_id: Option<String>,
+}
+
+impl DeadLetterEntry {
+    fn from_stream_entry((id, mut fields): (String, HashMap<String, String>)) -> Self {
+        Self {
+            id,
+            source_stream: fields.remove("source_stream"),
+            source_entry_id: fields.remove("source_entry_id"),
+            failure_kind: fields.remove("failure_kind"),
+            error: fields.remove("error"),
+            attempts: fields.remove("attempts"),
+            failed_at: fields.remove("failed_at"),
+            typed_sql: fields.remove(TYPED_SQL),
+            merchant_id: fields.remove(MERCHANT_ID),
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct DeadLetterStreamStats {
+    pub partition: u8,
+    pub stream: String,
+    pub depth: usize,
+}
+
+pub fn get_dead_letter_stream_name(store: &services::Store, partition: u8) -> String {
+    store.drainer_dead_letter_stream(format!("shard_{partition}").as_str())
+}
+
+fn get_partition_stream_name(store: &services::Store, partition: u8) -> String {
+    store.drainer_stream(format!("shard_{partition}").as_str())
+}
+
+/// Name of the set holding the rows parked in a dead-letter stream.
+fn get_parked_rows_set_name(dead_letter_stream: &str) -> String {
+    // Example: {shard_5}_drainer_stream_dlq_parked_rows
+    format!("{dead_letter_stream}_parked_rows")
+}
+
+/// Returns the key of the row touched by the query of a stream entry, or `None` if the query
+/// cannot be deserialized or does not touch a row.
+pub fn get_row_key(typed_sql: &str) -> Option<String> {
+    let db_op = serde_json::from_str::<kv::DBOperation>(typed_sql).ok()?;
+    let row_key = match db_op {
+        kv::DBOperation::Insert { insertable } => match insertable {
+            kv::Insertable::PaymentIntent(a) => {
+                format!("payment_intent_{}_{}", a.merchant_id, a.payment_id)
+            }
+            kv::Insertable::PaymentAttempt(a) => {
+                format!("payment_attempt_{}_{}", a.merchant_id, a.attempt_id)
+            }
+            kv::Insertable::Refund(a) => format!("refund_{}", a.internal_reference_id),
+            kv::Insertable::Address(a) => format!("address_{}", a.address_id),
+            kv::Insertable::ConnectorResponse(a) => format!(
+                "connector_response_{}_{}_{}",
+                a.merchant_id, a.payment_id, a.attempt_id
+            ),
+            kv::Insertable::Customer(a) => {
+                format!("customers_{}_{}", a.merchant_id, a.customer_id)
+            }
+            kv::Insertable::ReverseLookUp(a) => format!("reverse_lookup_{}", a.lookup_id),
+        },
+        kv::DBOperation::Update { updatable } => match updatable {
+            kv::Updateable::PaymentIntentUpdate(a) => format!(
+                "payment_intent_{}_{}",
+                a.orig.merchant_id, a.orig.payment_id
+            ),
+            kv::Updateable::PaymentAttemptUpdate(a) => format!(
+                "payment_attempt_{}_{}",
+                a.orig.merchant_id, a.orig.attempt_id
+            ),
+            kv::Updateable::RefundUpdate(a) => format!("refund_{}", a.orig.internal_reference_id),
+            kv::Updateable::AddressUpdate(a) => format!("address_{}", a.orig.address_id),
+            kv::Updateable::ConnectorResponseUpdate(a) => format!(
+                "connector_response_{}_{}_{}",
+                a.orig.merchant_id, a.orig.payment_id, a.orig.attempt_id
+            ),
+            kv::Updateable::CustomerUpdate(a) => {
+                format!("customers_{}_{}", a.orig.merchant_id, a.orig.customer_id)
+            }
+        },
+        kv::DBOperation::Delete => return None,
+    };
+    Some(row_key)
+}
+
+/// Returns whether a row has been parked in a dead-letter stream.
+pub async fn is_row_parked(
+    store: &services::Store,
+    dead_letter_stream: &str,
+    row_key: &str,
+) -> errors::DrainerResult<bool> {
+    store
+        .redis_conn
+        .set_is_member(&get_parked_rows_set_name(dead_letter_stream), row_key)
+        .await
+        .map_err(DrainerError::from)
+        .into_report()
+}
+
+/// Parks a row in a dead-letter stream, so that later entries for the row are dead-lettered too.
+pub async fn park_row(
+    store: &services::Store,
+    dead_letter_stream: &str,
+    row_key: &str,
+) -> errors::DrainerResult<()> {
+    logger::warn!(%row_key, "Parking row until its dead-lettered entries are replayed");
+    store
+        .redis_conn
+        .set_add_member(&get_parked_rows_set_name(dead_letter_stream), row_key)
+        .await
+        .map_err(DrainerError::from)
+        .into_report()
+}
+
+/// Appends a stream entry that could not be drained to the dead-letter stream of its partition.
+pub async fn move_to_dead_letter_stream(
+    store: &services::Store,
+    dead_letter_stream: &str,
+    typed_sql: &str,
+    failure: Failure<'_>,
+) -> errors::DrainerResult<()> {
+    logger::error!(
+        source_stream = %failure.source_stream,
+        source_entry_id = %failure.source_entry_id,
+        failure_kind = %failure.kind.as_str(),
+        error = %failure.error,
+        attempts = %failure.attempts,
+        "Moving stream entry to the dead-letter stream"
+    );
+
+    let mut fields = vec![
+        (TYPED_SQL, typed_sql.to_string()),
+        ("source_stream", failure.source_stream.to_string()),
+        ("source_entry_id", failure.source_entry_id.to_string()),
+        ("failure_kind", failure.kind.as_str().to_string()),
+        ("error", failure.error),
+        ("attempts", failure.attempts.to_string()),
+        ("failed_at", common_utils::date_time::now().to_string()),
+    ];
+    // Retained so that KV is not turned off for the merchant while its entries are dead-lettered
+    if let Some(merchant_id) = failure.merchant_id {
+        fields.push((MERCHANT_ID, merchant_id));
+    }
+
+    store
+        .redis_conn
+        .stream_append_entry(
+            dead_letter_stream,
+            &redis::RedisEntryId::AutoGeneratedID,
+            fields,
+        )
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?;
+
+    metrics::DEAD_LETTER_ENTRIES.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::KeyValue::new("stream", failure.source_stream.to_owned()),
+            metrics::KeyValue::new("failure_kind", failure.kind.as_str()),
+        ],
+    );
+    record_depth(store, dead_letter_stream).await;
+
+    Ok(())
+}
+
+async fn record_depth(store: &services::Store, dead_letter_stream: &str) {
+    match store.redis_conn.stream_get_length(dead_letter_stream).await {
+        Ok(depth) => {
+            #[allow(clippy::as_conversions)]
+            let depth = depth as f64;
+            metrics::DEAD_LETTER_STREAM_DEPTH.record(
+                &metrics::CONTEXT,
+                depth,
+                &[metrics::KeyValue::new(
+                    "stream",
+                    dead_letter_stream.to_owned(),
+                )],
+            );
+        }
+        Err(error) => logger::error!(?error, "Failed to get length of dead-letter stream"),
+    }
+}
+
+pub async fn get_stats(
+    store: &services::Store,
+) -> errors::DrainerResult<Vec<DeadLetterStreamStats>> {
+    let mut stats = Vec::with_capacity(store.config.drainer_num_partitions.into());
+    for partition in 0..store.config.drainer_num_partitions {
+        let stream = get_dead_letter_stream_name(store, partition);
+        let depth = store
+            .redis_conn
+            .stream_get_length(stream.as_str())
+            .await
+            .map_err(DrainerError::from)
+            .into_report()?;
+        stats.push(DeadLetterStreamStats {
+            partition,
+            stream,
+            depth,
+        });
+    }
+    Ok(stats)
+}
+
+pub async fn list_entries(
+    store: &services::Store,
+    partition: u8,
+    count: u64,
+) -> errors::DrainerResult<Vec<DeadLetterEntry>> {
+    let stream = get_dead_letter_stream_name(store, partition);
+    read_entries(store, &stream, "-", "+", Some(count)).await
+}
+
+/// Moves entries from the dead-letter stream of a partition back to the partition, so that they
+/// are picked up by the drainer again, and releases the rows they touch. Returns the number of
+/// entries replayed.
+///
+/// A row is released only if none of its entries are left in the dead-letter stream, so that
+/// they are never overtaken by new ones. Entries of a row that would stay parked are not
+/// replayed, as they would only be dead-lettered again behind the entries left.
+pub async fn replay_entries(
+    store: &services::Store,
+    partition: u8,
+    entry_id: Option<&str>,
+    count: u64,
+) -> errors::DrainerResult<usize> {
+    let dead_letter_stream = get_dead_letter_stream_name(store, partition);
+    let partition_stream = get_partition_stream_name(store, partition);
+    let entries = match entry_id {
+        Some(entry_id) => {
+            read_entries(store, &dead_letter_stream, entry_id, entry_id, Some(1)).await?
+        }
+        None => read_entries(store, &dead_letter_stream, "-", "+", Some(count)).await?,
+    };
+
+    let selected_ids = entries
+        .iter()
+        .map(|entry| entry.id.clone())
+        .collect::<HashSet<_>>();
+    let rows_left_parked = read_entries(store, &dead_letter_stream, "-", "+", None)
+        .await?
+        .into_iter()
+        .filter(|entry| !selected_ids.contains(&entry.id))
+        .filter_map(|entry| entry.typed_sql.as_deref().and_then(get_row_key))
+        .collect::<HashSet<_>>();
+
+    let (entries, skipped_entries): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| {
+        entry
+            .typed_sql
+            .as_deref()
+            .and_then(get_row_key)
+            .map_or(true, |row_key| !rows_left_parked.contains(&row_key))
+    });
+    if !skipped_entries.is_empty() {
+        logger::warn!(
+            skipped_entries = ?skipped_entries.iter().map(|entry| &entry.id).collect::<Vec<_>>(),
+            "Not replaying entries of rows with other entries left in {dead_letter_stream}"
+        );
+    }
+
+    let row_keys = entries
+        .iter()
+        .filter_map(|entry| entry.typed_sql.as_deref().and_then(get_row_key))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    if !row_keys.is_empty() {
+        // Released before the entries are appended, so that they are not parked again
+        store
+            .redis_conn
+            .set_remove_members(&get_parked_rows_set_name(&dead_letter_stream), row_keys)
+            .await
+            .map_err(DrainerError::from)
+            .into_report()?;
+    }
+
+    let mut replayed = 0;
+    for entry in entries {
+        let typed_sql = entry
+            .typed_sql
+            .ok_or_else(|| {
+                DrainerError::UnexpectedError(format!(
+                    "Dead-letter entry {} does not contain `typed_sql`",
+                    entry.id
+                ))
+            })
+            .into_report()?;
+
+        let mut fields = vec![(TYPED_SQL, typed_sql)];
+        if let Some(merchant_id) = entry.merchant_id {
+            fields.push((MERCHANT_ID, merchant_id));
+        }
+        store
+            .redis_conn
+            .stream_append_entry(
+                &partition_stream,
+                &redis::RedisEntryId::AutoGeneratedID,
+                fields,
+            )
+            .await
+            .map_err(DrainerError::from)
+            .into_report()?;
+
+        store
+            .redis_conn
+            .stream_delete_entries(&dead_letter_stream, entry.id.as_str())
+            .await
+            .map_err(DrainerError::from)
+            .into_report()
+            .attach_printable_lazy(|| {
+                format!(
+                    "Entry {} was replayed but not removed from {dead_letter_stream}",
+                    entry.id
+                )
+            })?;
+
+        replayed += 1;
+    }
+
+    metrics::DEAD_LETTER_ENTRIES_REPLAYED.add(
+        &metrics::CONTEXT,
+        u64::try_from(replayed).unwrap_or(u64::MIN),
+        &[metrics::KeyValue::new("stream", dead_letter_stream.clone())],
+    );
+    record_depth(store, &dead_letter_stream).await;
+
+    Ok(replayed)
+}
+
+async fn read_entries(
+    store: &services::Store,
+    stream: &str,
+    start: &str,
+    end: &str,
+    count: Option<u64>,
+) -> errors::DrainerResult<Vec<DeadLetterEntry>> {
+    let entries = store
+        .redis_conn
+        .stream_read_range(stream, start, end, count)
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?;
+
+    Ok(entries
+        .into_iter()
+        .map(DeadLetterEntry::from_stream_entry)
+        .collect())
+}
+
+/// Runs a dead-letter command issued from the command line, printing its result as JSON.
+pub async fn run_command(
+    store: &services::Store,
+    command: DeadLetterCommand,
+) -> errors::DrainerResult<()> {
+    let output = match command {
+        DeadLetterCommand::Stats => serde_json::to_string_pretty(&get_stats(store).await?),
+        DeadLetterCommand::List { partition, count } => {
+            serde_json::to_string_pretty(&list_entries(store, partition, count).await?)
+        }
+        DeadLetterCommand::Replay {
+            partition,
+            entry_id,
+            count,
+        } => {
+            let replayed = replay_entries(store, partition, entry_id.as_deref(), count).await?;
+            serde_json::to_string_pretty(&serde_json::json!({ "replayed": replayed }))
+        }
+    }
+    .into_report()
+    .change_context(DrainerError::UnexpectedError(
+        "Failed to serialize dead-letter command output".to_string(),
+    ))?;
+
+    println!("{output}");
+    Ok(())
+}
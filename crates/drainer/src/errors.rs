@@ -15,6 +15,8 @@ pub enum DrainerError {
     SignalError(String),
     #[error("Unexpected error occurred: {0}")]
     UnexpectedError(String),
+    #[error("Failed to get a connection to the database")]
+    DatabaseConnectionError,
 }
 
 pub type DrainerResult<T> = error_stack::Result<T, DrainerError>;
@@ -0,0 +1,156 @@
+//! Completion of KV being turned off for merchants.
+//!
+//! Turning off KV for a merchant only marks the merchant as draining, the merchant continues to
+//! be served through Redis while its entries are still in the drainer streams. This module
+//! periodically looks for draining merchants which have no entries left in any partition (or in
+//! its dead-letter stream), and switches them to Postgres.
+
+use std::{collections::HashSet, sync::Arc};
+
+use error_stack::{IntoReport, ResultExt};
+use redis_interface::PubsubInterface;
+use storage_models::{enums, merchant_account};
+
+use crate::{
+    connection::pg_connection,
+    dead_letter,
+    errors::{self, DrainerError},
+    logger, metrics,
+    services::Store,
+};
+
+/// Stream entry field holding the ID of the merchant the entry belongs to.
+pub(crate) const MERCHANT_ID: &str = "merchant_id";
+
+/// Number of entries read from a stream at a time when looking for entries of draining merchants.
+const STREAM_SCAN_COUNT: u64 = 500;
+
+pub async fn monitor_kv_drains(store: Arc<Store>, check_interval: u64) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(check_interval));
+    loop {
+        interval.tick().await;
+        if let Err(error) = complete_kv_drains(&store).await {
+            logger::error!(?error, "Failed to complete KV drains");
+        }
+    }
+}
+
+async fn complete_kv_drains(store: &Store) -> errors::DrainerResult<()> {
+    let conn = pg_connection(&store.master_pool).await?;
+    let draining_merchants = merchant_account::MerchantAccount::find_kv_draining(&conn)
+        .await
+        .change_context(DrainerError::UnexpectedError(
+            "Failed to find merchants being drained".to_string(),
+        ))?;
+
+    if draining_merchants.is_empty() {
+        return Ok(());
+    }
+
+    let draining_merchant_ids = draining_merchants
+        .iter()
+        .map(|merchant| merchant.merchant_id.clone())
+        .collect();
+    let merchants_with_entries =
+        get_merchants_with_stream_entries(store, &draining_merchant_ids).await?;
+
+    for merchant in draining_merchants {
+        let merchant_id = merchant.merchant_id.clone();
+        if merchants_with_entries.contains(&merchant_id) {
+            logger::debug!(%merchant_id, "Merchant still has entries in the drainer streams");
+            continue;
+        }
+
+        // The router does not cache accounts of draining merchants, this clears any copy cached
+        // by an instance which has not caught up with the drain yet, so that it reads the switch
+        invalidate_cached_merchant_account(store, &merchant_id).await;
+
+        merchant
+            .update(
+                &conn,
+                merchant_account::MerchantAccountUpdate::StorageSchemeUpdate {
+                    storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+                },
+            )
+            .await
+            .change_context(DrainerError::UnexpectedError(format!(
+                "Failed to switch merchant {merchant_id} to Postgres"
+            )))?;
+
+        invalidate_cached_merchant_account(store, &merchant_id).await;
+
+        logger::info!(%merchant_id, "Completed KV drain, merchant switched to Postgres");
+        metrics::KV_DRAINS_COMPLETED.add(&metrics::CONTEXT, 1, &[]);
+    }
+
+    Ok(())
+}
+
+/// The router caches merchant accounts in Redis and in memory, keyed by the merchant ID.
+async fn invalidate_cached_merchant_account(store: &Store, merchant_id: &str) {
+    if let Err(error) = store.redis_conn.delete_key(merchant_id).await {
+        logger::error!(?error, %merchant_id, "Failed to invalidate cached merchant account");
+    }
+    if let Err(error) = store
+        .redis_conn
+        .publisher
+        .publish::<usize, _, _>(common_utils::consts::PUB_SUB_CHANNEL, merchant_id)
+        .await
+    {
+        logger::error!(?error, %merchant_id, "Failed to publish merchant account invalidation");
+    }
+}
+
+/// Returns the IDs of the draining merchants having entries in any partition or dead-letter
+/// stream. The entries in the dead-letter streams are considered as well, as switching a merchant
+/// to Postgres before those are replayed could have the replayed entries overwrite newer data.
+///
+/// Streams are read in batches, and the scan stops as soon as every draining merchant has been
+/// found to have entries.
+async fn get_merchants_with_stream_entries(
+    store: &Store,
+    draining_merchant_ids: &HashSet<String>,
+) -> errors::DrainerResult<HashSet<String>> {
+    let mut merchant_ids = HashSet::new();
+    for partition in 0..store.config.drainer_num_partitions {
+        let streams = [
+            store.drainer_stream(format!("shard_{partition}").as_str()),
+            dead_letter::get_dead_letter_stream_name(store, partition),
+        ];
+        for stream in streams {
+            let mut start = "-".to_string();
+            loop {
+                if merchant_ids.len() == draining_merchant_ids.len() {
+                    return Ok(merchant_ids);
+                }
+
+                let entries = store
+                    .redis_conn
+                    .stream_read_range(&stream, &start, "+", Some(STREAM_SCAN_COUNT))
+                    .await
+                    .map_err(DrainerError::from)
+                    .into_report()?;
+                let last_entry_id = match entries.last() {
+                    Some((entry_id, _)) => entry_id.clone(),
+                    None => break,
+                };
+                let is_last_batch =
+                    entries.len() < usize::try_from(STREAM_SCAN_COUNT).unwrap_or(usize::MAX);
+
+                merchant_ids.extend(
+                    entries
+                        .into_iter()
+                        .filter_map(|(_, mut fields)| fields.remove(MERCHANT_ID))
+                        .filter(|merchant_id| draining_merchant_ids.contains(merchant_id)),
+                );
+
+                if is_last_batch {
+                    break;
+                }
+                // Exclusive range start, resuming after the last entry read
+                start = format!("({last_entry_id}");
+            }
+        }
+    }
+    Ok(merchant_ids)
+}
@@ -1,6 +1,8 @@
 mod connection;
+pub mod dead_letter;
 pub mod env;
 pub mod errors;
+mod kv_drain;
 pub(crate) mod metrics;
 pub mod services;
 pub mod settings;
@@ -10,10 +12,10 @@ use std::sync::{atomic, Arc};
 use common_utils::signals::get_allowed_signals;
 pub use env as logger;
 use error_stack::{IntoReport, ResultExt};
-use storage_models::kv;
+use storage_models::{errors::DatabaseError, kv, StorageResult};
 use tokio::sync::mpsc;
 
-use crate::{connection::pg_connection, services::Store};
+use crate::{connection::pg_connection, kv_drain::MERCHANT_ID, services::Store};
 
 pub async fn start_drainer(
     store: Arc<Store>,
@@ -21,6 +23,7 @@ pub async fn start_drainer(
     max_read_count: u64,
     shutdown_interval: u32,
     loop_interval: u32,
+    kv_drain_check_interval: u64,
 ) -> errors::DrainerResult<()> {
     let mut stream_index: u8 = 0;
     let mut jobs_picked: u8 = 0;
@@ -41,6 +44,12 @@ pub async fn start_drainer(
     let handle = signal.handle();
     let task_handle = tokio::spawn(common_utils::signals::signal_handler(signal, tx));
 
+    utils::create_consumer_groups(store.clone()).await;
+    let kv_drain_handle = tokio::spawn(kv_drain::monitor_kv_drains(
+        store.clone(),
+        kv_drain_check_interval,
+    ));
+
     let active_tasks = Arc::new(atomic::AtomicU64::new(0));
     'event: loop {
         match rx.try_recv() {
@@ -66,6 +75,7 @@ pub async fn start_drainer(
                 metrics::SHUTDOWN_SIGNAL_RECEIVED.add(&metrics::CONTEXT, 1, &[]);
                 let shutdown_started = tokio::time::Instant::now();
                 rx.close();
+                kv_drain_handle.abort();
                 loop {
                     if active_tasks.load(atomic::Ordering::Acquire) == 0 {
                         logger::info!("Terminating drainer");
@@ -99,7 +109,14 @@ async fn drainer_handler(
     active_tasks.fetch_add(1, atomic::Ordering::Release);
 
     let stream_name = utils::get_drainer_stream_name(store.clone(), stream_index);
-    let drainer_result = drainer(store.clone(), max_read_count, stream_name.as_str()).await;
+    let dead_letter_stream_name = dead_letter::get_dead_letter_stream_name(&store, stream_index);
+    let drainer_result = drainer(
+        store.clone(),
+        max_read_count,
+        stream_name.as_str(),
+        dead_letter_stream_name.as_str(),
+    )
+    .await;
 
     if let Err(error) = drainer_result {
         logger::error!(?error)
@@ -107,22 +124,33 @@ async fn drainer_handler(
 
     let flag_stream_name = utils::get_stream_key_flag(store.clone(), stream_index);
     //TODO: USE THE RESULT FOR LOGGING
-    let output =
-        utils::make_stream_available(flag_stream_name.as_str(), store.redis_conn.as_ref()).await;
+    let output = utils::make_stream_available(flag_stream_name.as_str(), &store).await;
     active_tasks.fetch_sub(1, atomic::Ordering::Release);
     output
 }
 
+/// Drains a batch of entries from the stream of a partition. Entries are acknowledged only after
+/// they have been applied to the database (or moved to the dead-letter stream), so that entries
+/// of an instance dying midway are claimed and drained by another instance. Such entries may
+/// already have been applied: they are drained again in order, with inserts that were already
+/// applied being skipped, which leaves the database in the same state.
+///
+/// Entries for a row that has been parked in the dead-letter stream are dead-lettered without
+/// being applied, so that updates to the row are never applied out of order.
 async fn drainer(
     store: Arc<Store>,
     max_read_count: u64,
     stream_name: &str,
+    dead_letter_stream_name: &str,
 ) -> errors::DrainerResult<()> {
-    let stream_read =
-        utils::read_from_stream(stream_name, max_read_count, store.redis_conn.as_ref()).await?; // this returns the error.
+    if !utils::claim_pending_entries(stream_name, max_read_count, &store).await? {
+        return Ok(());
+    }
+
+    let stream_read = utils::read_from_stream(stream_name, max_read_count, &store).await?; // this returns the error.
 
     // parse_stream_entries returns error if no entries is found, handle it
-    let (entries, last_entry_id) = utils::parse_stream_entries(&stream_read, stream_name)?;
+    let (entries, _) = utils::parse_stream_entries(&stream_read, stream_name)?;
     let read_count = entries.len();
 
     metrics::JOBS_PICKED_PER_STREAM.add(
@@ -134,111 +162,463 @@ async fn drainer(
         }],
     );
 
-    // TODO: Handle errors when deserialization fails and when DB error occurs
-    for entry in entries {
-        let typed_sql = entry.1.get("typed_sql").map_or(String::new(), Clone::clone);
-        let result = serde_json::from_str::<kv::DBOperation>(&typed_sql);
-        let db_op = match result {
-            Ok(f) => f,
-            Err(_err) => continue, // TODO: handle error
-        };
+    // Entries which have either been applied to the database or moved to the dead-letter stream,
+    // and can be acknowledged
+    let mut drained_entry_ids = Vec::with_capacity(read_count);
+    let mut drain_result = Ok(());
 
-        let conn = pg_connection(&store.master_pool).await;
-        let insert_op = "insert";
-        let update_op = "update";
-        let payment_intent = "payment_intent";
-        let payment_attempt = "payment_attempt";
-        let refund = "refund";
-        match db_op {
-            // TODO: Handle errors
-            kv::DBOperation::Insert { insertable } => {
-                let (_, execution_time) = common_utils::date_time::time_it(|| async {
-                    match insertable {
-                        kv::Insertable::PaymentIntent(a) => {
-                            macro_util::handle_resp!(
-                                a.insert(&conn).await,
-                                insert_op,
-                                payment_intent
-                            )
-                        }
-                        kv::Insertable::PaymentAttempt(a) => {
-                            macro_util::handle_resp!(
-                                a.insert(&conn).await,
-                                insert_op,
-                                payment_attempt
-                            )
-                        }
-                        kv::Insertable::Refund(a) => {
-                            macro_util::handle_resp!(a.insert(&conn).await, insert_op, refund)
-                        }
-                    }
-                })
-                .await;
-                metrics::QUERY_EXECUTION_TIME.record(
-                    &metrics::CONTEXT,
-                    execution_time,
-                    &[metrics::KeyValue {
-                        key: "operation".into(),
-                        value: insert_op.into(),
-                    }],
-                );
+    for (entry_id, fields) in entries {
+        let typed_sql = fields
+            .get("typed_sql")
+            .cloned()
+            .flatten()
+            .unwrap_or_default();
+
+        if let Err(error) = drain_entry(
+            &store,
+            stream_name,
+            dead_letter_stream_name,
+            entry_id,
+            fields.get(MERCHANT_ID).cloned().flatten(),
+            &typed_sql,
+        )
+        .await
+        {
+            // The entry and the ones following it are left pending with this consumer, to be
+            // drained again in the next cycle
+            drain_result = Err(error);
+            break;
+        }
+
+        drained_entry_ids.push(entry_id.clone());
+    }
+
+    if !drained_entry_ids.is_empty() {
+        let drained_count = drained_entry_ids.len();
+        let entries_deleted =
+            utils::acknowledge_entries(stream_name, drained_entry_ids, &store).await?;
+
+        if drained_count != entries_deleted {
+            logger::error!(
+                read_entries = %read_count,
+                drained_entries = %drained_count,
+                deleted_entries = %entries_deleted,
+                ?entries,
+                "Assertion Failed no. of entries drained from the stream doesn't match no. of entries deleted"
+            );
+        }
+    }
+
+    drain_result
+}
+
+/// Applies a stream entry to the database, or moves it to the dead-letter stream if it fails or
+/// touches a parked row. Returns an error only if the entry could not be dead-lettered.
+async fn drain_entry(
+    store: &Store,
+    stream_name: &str,
+    dead_letter_stream_name: &str,
+    entry_id: &str,
+    merchant_id: Option<String>,
+    typed_sql: &str,
+) -> errors::DrainerResult<()> {
+    let row_key = dead_letter::get_row_key(typed_sql);
+    let is_parked = match &row_key {
+        Some(row_key) => {
+            dead_letter::is_row_parked(store, dead_letter_stream_name, row_key).await?
+        }
+        None => false,
+    };
+
+    let failure = if is_parked {
+        QueryFailure {
+            kind: dead_letter::FailureKind::Parked,
+            error: "An earlier entry for the row is in the dead-letter stream".to_string(),
+            attempts: 0,
+        }
+    } else {
+        match execute_with_retries(store, typed_sql).await {
+            Ok(()) => return Ok(()),
+            Err(failure) => failure,
+        }
+    };
+
+    // The row is parked before the entry is dead-lettered, so that a later entry for the row is
+    // never applied if this instance dies in between
+    if let Some(row_key) = row_key.as_deref().filter(|_| !is_parked) {
+        dead_letter::park_row(store, dead_letter_stream_name, row_key).await?;
+    }
+
+    dead_letter::move_to_dead_letter_stream(
+        store,
+        dead_letter_stream_name,
+        typed_sql,
+        dead_letter::Failure {
+            source_stream: stream_name,
+            source_entry_id: entry_id,
+            merchant_id,
+            kind: failure.kind,
+            error: failure.error,
+            attempts: failure.attempts,
+        },
+    )
+    .await
+}
+
+/// Failure to apply a stream entry to the database.
+struct QueryFailure {
+    kind: dead_letter::FailureKind,
+    error: String,
+    attempts: u8,
+}
+
+/// Executes the query of a stream entry, retrying with exponential back-off as long as it fails
+/// with a transient database error and the retries are not exhausted.
+async fn execute_with_retries(store: &Store, typed_sql: &str) -> Result<(), QueryFailure> {
+    let mut attempts: u8 = 0;
+
+    loop {
+        // Deserialized on every attempt, since executing the operation consumes it
+        let db_op =
+            serde_json::from_str::<kv::DBOperation>(typed_sql).map_err(|error| QueryFailure {
+                kind: dead_letter::FailureKind::Deserialization,
+                error: error.to_string(),
+                attempts,
+            })?;
+        attempts = attempts.saturating_add(1);
+
+        match execute_db_operation(store, db_op).await {
+            Ok(()) => return Ok(()),
+            Err(error) if is_transient(&error) && attempts <= store.config.drainer_max_retries => {
+                logger::warn!(?error, %attempts, "Retrying query after transient database error");
+                metrics::QUERY_RETRIES.add(&metrics::CONTEXT, 1, &[]);
+                tokio::time::sleep(retry_backoff(store.config.drainer_retry_backoff, attempts))
+                    .await;
             }
-            kv::DBOperation::Update { updatable } => {
-                let (_, execution_time) = common_utils::date_time::time_it(|| async {
-                    match updatable {
-                        kv::Updateable::PaymentIntentUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update(&conn, a.update_data).await,
-                                update_op,
-                                payment_intent
-                            )
-                        }
-                        kv::Updateable::PaymentAttemptUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update_with_attempt_id(&conn, a.update_data).await,
-                                update_op,
-                                payment_attempt
-                            )
-                        }
-                        kv::Updateable::RefundUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update(&conn, a.update_data).await,
-                                update_op,
-                                refund
-                            )
-                        }
-                    }
+            Err(error) => {
+                return Err(QueryFailure {
+                    kind: dead_letter::FailureKind::Database,
+                    error: format!("{error:?}"),
+                    attempts,
                 })
-                .await;
-                metrics::QUERY_EXECUTION_TIME.record(
-                    &metrics::CONTEXT,
-                    execution_time,
-                    &[metrics::KeyValue {
-                        key: "operation".into(),
-                        value: update_op.into(),
-                    }],
-                );
-            }
-            kv::DBOperation::Delete => {
-                // [#224]: Implement this
-                logger::error!("Not implemented!");
             }
-        };
+        }
     }
+}
 
-    let entries_trimmed =
-        utils::trim_from_stream(stream_name, last_entry_id.as_str(), &store.redis_conn).await?;
+/// Returns whether a query failed because of the connection to the database rather than the
+/// query itself, in which case it may succeed when retried.
+fn is_transient(error: &error_stack::Report<DatabaseError>) -> bool {
+    match error.current_context() {
+        DatabaseError::DatabaseConnectionError => true,
+        DatabaseError::Others => matches!(
+            error.downcast_ref::<async_bb8_diesel::ConnectionError>(),
+            Some(async_bb8_diesel::ConnectionError::Checkout(_))
+                | Some(async_bb8_diesel::ConnectionError::Query(
+                    diesel::result::Error::DatabaseError(
+                        diesel::result::DatabaseErrorKind::ClosedConnection
+                            | diesel::result::DatabaseErrorKind::UnableToSendCommand,
+                        _,
+                    ),
+                ))
+        ),
+        DatabaseError::NotFound
+        | DatabaseError::UniqueViolation
+        | DatabaseError::NoFieldsToUpdate
+        | DatabaseError::QueryGenerationFailed => false,
+    }
+}
 
-    if read_count != entries_trimmed {
-        logger::error!(
-            read_entries = %read_count,
-            trimmed_entries = %entries_trimmed,
-            ?entries,
-            "Assertion Failed no. of entries read from the stream doesn't match no. of entries trimmed"
-        );
+fn retry_backoff(initial_backoff: u32, attempt: u8) -> std::time::Duration {
+    let multiplier = 2_u64.saturating_pow(u32::from(attempt.saturating_sub(1)));
+    std::time::Duration::from_millis(u64::from(initial_backoff).saturating_mul(multiplier))
+}
+
+async fn execute_db_operation(store: &Store, db_op: kv::DBOperation) -> StorageResult<()> {
+    // Failing to get a connection is a transient failure, retried like the failed queries
+    let conn = pg_connection(&store.master_pool)
+        .await
+        .change_context(DatabaseError::DatabaseConnectionError)?;
+    let insert_op = "insert";
+    let update_op = "update";
+    let payment_intent = "payment_intent";
+    let payment_attempt = "payment_attempt";
+    let refund = "refund";
+    let address = "address";
+    let connector_response = "connector_response";
+    let customers = "customers";
+    let reverse_lookup = "reverse_lookup";
+    match db_op {
+        kv::DBOperation::Insert { insertable } => {
+            let (result, execution_time) = common_utils::date_time::time_it(|| async {
+                // Rows carrying the creation time of the insert are matched on it, the others on the
+                // fields which are never updated
+                match insertable {
+                    kv::Insertable::PaymentIntent(a) => {
+                        let (payment_id, merchant_id) = (a.payment_id.clone(), a.merchant_id.clone());
+                        let created_at = a.created_at;
+                        let result = a.insert(&conn).await;
+                        macro_util::handle_resp!(
+                            skip_applied_insert(result, payment_intent, || async {
+                                storage_models::payment_intent::PaymentIntent::find_by_payment_id_merchant_id(
+                                    &conn,
+                                    &payment_id,
+                                    &merchant_id,
+                                )
+                                .await
+                                .map(|row| Some(row.created_at) == created_at)
+                            })
+                            .await,
+                            insert_op,
+                            payment_intent
+                        )
+                    }
+                    kv::Insertable::PaymentAttempt(a) => {
+                        let (payment_id, merchant_id, attempt_id) =
+                            (a.payment_id.clone(), a.merchant_id.clone(), a.attempt_id.clone());
+                        let created_at = a.created_at;
+                        let result = a.insert(&conn).await;
+                        macro_util::handle_resp!(
+                            skip_applied_insert(result, payment_attempt, || async {
+                                storage_models::payment_attempt::PaymentAttempt::find_by_merchant_id_attempt_id(
+                                    &conn,
+                                    &merchant_id,
+                                    &attempt_id,
+                                )
+                                .await
+                                .map(|row| {
+                                    row.payment_id == payment_id && Some(row.created_at) == created_at
+                                })
+                            })
+                            .await,
+                            insert_op,
+                            payment_attempt
+                        )
+                    }
+                    kv::Insertable::Refund(a) => {
+                        let (internal_reference_id, merchant_id, refund_id) = (
+                            a.internal_reference_id.clone(),
+                            a.merchant_id.clone(),
+                            a.refund_id.clone(),
+                        );
+                        let created_at = a.created_at;
+                        let result = a.insert(&conn).await;
+                        macro_util::handle_resp!(
+                            skip_applied_insert(result, refund, || async {
+                                storage_models::refund::Refund::find_by_internal_reference_id_merchant_id(
+                                    &conn,
+                                    &internal_reference_id,
+                                    &merchant_id,
+                                )
+                                .await
+                                .map(|row| row.refund_id == refund_id && Some(row.created_at) == created_at)
+                            })
+                            .await,
+                            insert_op,
+                            refund
+                        )
+                    }
+                    kv::Insertable::Address(a) => {
+                        let (address_id, customer_id, merchant_id) =
+                            (a.address_id.clone(), a.customer_id.clone(), a.merchant_id.clone());
+                        let result = a.insert(&conn).await;
+                        macro_util::handle_resp!(
+                            skip_applied_insert(result, address, || async {
+                                storage_models::address::Address::find_by_address_id(&conn, &address_id)
+                                    .await
+                                    .map(|row| {
+                                        row.customer_id == customer_id && row.merchant_id == merchant_id
+                                    })
+                            })
+                            .await,
+                            insert_op,
+                            address
+                        )
+                    }
+                    kv::Insertable::ConnectorResponse(a) => {
+                        let (payment_id, merchant_id, attempt_id) =
+                            (a.payment_id.clone(), a.merchant_id.clone(), a.attempt_id.clone());
+                        let created_at = a.created_at;
+                        let result = a.insert(&conn).await;
+                        macro_util::handle_resp!(
+                            skip_applied_insert(result, connector_response, || async {
+                                storage_models::connector_response::ConnectorResponse::find_by_payment_id_merchant_id_attempt_id(
+                                    &conn,
+                                    &payment_id,
+                                    &merchant_id,
+                                    &attempt_id,
+                                )
+                                .await
+                                .map(|row| row.created_at == created_at)
+                            })
+                            .await,
+                            insert_op,
+                            connector_response
+                        )
+                    }
+                    kv::Insertable::Customer(a) => {
+                        // The customer ID and merchant ID are the only fields never updated, and
+                        // are also the key of the row
+                        let (customer_id, merchant_id) = (a.customer_id.clone(), a.merchant_id.clone());
+                        let result = a.insert(&conn).await;
+                        macro_util::handle_resp!(
+                            skip_applied_insert(result, customers, || async {
+                                storage_models::customers::Customer::find_by_customer_id_merchant_id(
+                                    &conn,
+                                    &customer_id,
+                                    &merchant_id,
+                                )
+                                .await
+                                .map(|_| true)
+                            })
+                            .await,
+                            insert_op,
+                            customers
+                        )
+                    }
+                    kv::Insertable::ReverseLookUp(a) => {
+                        let (lookup_id, pk_id, sk_id, source) =
+                            (a.lookup_id.clone(), a.pk_id.clone(), a.sk_id.clone(), a.source.clone());
+                        let result = a.insert(&conn).await;
+                        macro_util::handle_resp!(
+                            skip_applied_insert(result, reverse_lookup, || async {
+                                storage_models::reverse_lookup::ReverseLookup::find_by_lookup_id(
+                                    &lookup_id, &conn,
+                                )
+                                .await
+                                .map(|row| row.pk_id == pk_id && row.sk_id == sk_id && row.source == source)
+                            })
+                            .await,
+                            insert_op,
+                            reverse_lookup
+                        )
+                    }
+                }
+            })
+            .await;
+            metrics::QUERY_EXECUTION_TIME.record(
+                &metrics::CONTEXT,
+                execution_time,
+                &[metrics::KeyValue {
+                    key: "operation".into(),
+                    value: insert_op.into(),
+                }],
+            );
+            result
+        }
+        kv::DBOperation::Update { updatable } => {
+            let (result, execution_time) = common_utils::date_time::time_it(|| async {
+                match updatable {
+                    kv::Updateable::PaymentIntentUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update(&conn, a.update_data).await,
+                            update_op,
+                            payment_intent
+                        )
+                    }
+                    kv::Updateable::PaymentAttemptUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update_with_attempt_id(&conn, a.update_data).await,
+                            update_op,
+                            payment_attempt
+                        )
+                    }
+                    kv::Updateable::RefundUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update(&conn, a.update_data).await,
+                            update_op,
+                            refund
+                        )
+                    }
+                    kv::Updateable::AddressUpdate(a) => {
+                        macro_util::handle_resp!(
+                            storage_models::address::Address::update_by_address_id(
+                                &conn,
+                                a.orig.address_id,
+                                a.update_data
+                            )
+                            .await,
+                            update_op,
+                            address
+                        )
+                    }
+                    kv::Updateable::ConnectorResponseUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update(&conn, a.update_data).await,
+                            update_op,
+                            connector_response
+                        )
+                    }
+                    kv::Updateable::CustomerUpdate(a) => {
+                        macro_util::handle_resp!(
+                            storage_models::customers::Customer::update_by_customer_id_merchant_id(
+                                &conn,
+                                a.orig.customer_id,
+                                a.orig.merchant_id,
+                                a.update_data
+                            )
+                            .await,
+                            update_op,
+                            customers
+                        )
+                    }
+                }
+            })
+            .await;
+            metrics::QUERY_EXECUTION_TIME.record(
+                &metrics::CONTEXT,
+                execution_time,
+                &[metrics::KeyValue {
+                    key: "operation".into(),
+                    value: update_op.into(),
+                }],
+            );
+            result
+        }
+        kv::DBOperation::Delete => {
+            // [#224]: Implement this
+            logger::error!("Not implemented!");
+            Ok(())
+        }
     }
+}
 
-    Ok(())
+/// Treats an insert failing with a unique violation as successful if the conflicting row is the
+/// one inserted by the entry, which happens when an entry that was already applied is drained
+/// again. Any other conflict is returned as is, so that the entry is dead-lettered.
+async fn skip_applied_insert<T, F, Fut>(
+    result: StorageResult<T>,
+    table: &str,
+    is_row_inserted_by_entry: F,
+) -> StorageResult<Option<T>>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = StorageResult<bool>>,
+{
+    match result {
+        Ok(inserted) => Ok(Some(inserted)),
+        Err(error) if matches!(error.current_context(), DatabaseError::UniqueViolation) => {
+            match is_row_inserted_by_entry().await {
+                Ok(true) => {
+                    logger::warn!(%table, "Skipping insert which has already been applied");
+                    metrics::DUPLICATE_INSERTS_SKIPPED.add(
+                        &metrics::CONTEXT,
+                        1,
+                        &[metrics::KeyValue::new("table", table.to_owned())],
+                    );
+                    Ok(None)
+                }
+                Ok(false) => {
+                    Err(error.attach_printable("Conflicting row was not inserted by this entry"))
+                }
+                // Retried if transient, like a failure of the insert itself
+                Err(lookup_error) => Err(lookup_error.attach_printable(format!(
+                    "Failed to find the row in {table} conflicting with the insert"
+                ))),
+            }
+        }
+        Err(error) => Err(error),
+    }
 }
 
 mod macro_util {
@@ -254,6 +634,7 @@ mod macro_util {
                             value: $table.into(),
                         }
                     ]);
+                    Ok(())
                 }
                 Err(err) => {
                     logger::error!(operation = %$op_type, table = %$table, ?err);
@@ -263,6 +644,7 @@ mod macro_util {
                             value: $table.into(),
                         }
                     ]);
+                    Err(err)
                 }
             }
         };
@@ -1,4 +1,6 @@
-use drainer::{errors, errors::DrainerResult, logger::logger, services, settings, start_drainer};
+use drainer::{
+    dead_letter, errors, errors::DrainerResult, logger::logger, services, settings, start_drainer,
+};
 use error_stack::ResultExt;
 
 #[tokio::main]
@@ -20,9 +22,16 @@ async fn main() -> DrainerResult<()> {
     let max_read_count = conf.drainer.max_read_count;
     let shutdown_intervals = conf.drainer.shutdown_interval;
     let loop_interval = conf.drainer.loop_interval;
+    let kv_drain_check_interval = conf.drainer.kv_drain_check_interval;
 
     let _guard = logger::setup(&conf.log).change_context(errors::DrainerError::MetricsError)?;
 
+    if let Some(settings::Subcommand::DeadLetter(command)) = cmd_line.subcommand {
+        let result = dead_letter::run_command(&store, command).await;
+        store.close().await;
+        return result;
+    }
+
     logger::info!("Drainer started [{:?}] [{:?}]", conf.drainer, conf.log);
 
     start_drainer(
@@ -31,6 +40,7 @@ async fn main() -> DrainerResult<()> {
         max_read_count,
         shutdown_intervals,
         loop_interval,
+        kv_drain_check_interval,
     )
     .await?;
 
@@ -11,8 +11,15 @@ counter_metric!(ERRORS_WHILE_QUERY_EXECUTION, DRAINER_METER);
 counter_metric!(SUCCESSFUL_QUERY_EXECUTION, DRAINER_METER);
 counter_metric!(SHUTDOWN_SIGNAL_RECEIVED, DRAINER_METER);
 counter_metric!(SUCCESSFUL_SHUTDOWN, DRAINER_METER);
+counter_metric!(QUERY_RETRIES, DRAINER_METER);
+counter_metric!(DEAD_LETTER_ENTRIES, DRAINER_METER);
+counter_metric!(DEAD_LETTER_ENTRIES_REPLAYED, DRAINER_METER);
+counter_metric!(PENDING_ENTRIES_CLAIMED, DRAINER_METER);
+counter_metric!(DUPLICATE_INSERTS_SKIPPED, DRAINER_METER);
+counter_metric!(KV_DRAINS_COMPLETED, DRAINER_METER);
 
 histogram_metric!(QUERY_EXECUTION_TIME, DRAINER_METER); // Time in (ms) milliseconds
 histogram_metric!(REDIS_STREAM_READ_TIME, DRAINER_METER); // Time in (ms) milliseconds
 histogram_metric!(REDIS_STREAM_TRIM_TIME, DRAINER_METER); // Time in (ms) milliseconds
 histogram_metric!(CLEANUP_TIME, DRAINER_METER); // Time in (ms) milliseconds
+histogram_metric!(DEAD_LETTER_STREAM_DEPTH, DRAINER_METER); // No. of entries in the stream
@@ -13,6 +13,12 @@ pub struct Store {
 pub struct StoreConfig {
     pub drainer_stream_name: String,
     pub drainer_num_partitions: u8,
+    pub drainer_max_retries: u8,
+    pub drainer_retry_backoff: u32,
+    pub drainer_consumer_group: String,
+    pub drainer_consumer_name: String,
+    pub drainer_min_idle_time: u64,
+    pub drainer_partition_lease_ttl: u32,
 }
 
 impl Store {
@@ -21,14 +27,23 @@ impl Store {
             master_pool: diesel_make_pg_pool(
                 &config.master_database,
                 test_transaction,
-                #[cfg(feature = "kms")]
-                &config.kms,
+                &config.secrets_management,
             )
             .await,
             redis_conn: Arc::new(crate::connection::redis_connection(config).await),
             config: StoreConfig {
                 drainer_stream_name: config.drainer.stream_name.clone(),
                 drainer_num_partitions: config.drainer.num_partitions,
+                drainer_max_retries: config.drainer.max_retries,
+                drainer_retry_backoff: config.drainer.retry_backoff,
+                drainer_consumer_group: config.drainer.consumer_group.clone(),
+                drainer_consumer_name: config
+                    .drainer
+                    .consumer_name
+                    .clone()
+                    .unwrap_or_else(|| common_utils::generate_id_with_default_len("drainer")),
+                drainer_min_idle_time: config.drainer.min_idle_time,
+                drainer_partition_lease_ttl: config.drainer.partition_lease_ttl,
             },
         }
     }
@@ -38,6 +53,11 @@ impl Store {
         format!("{{{}}}_{}", shard_key, self.config.drainer_stream_name,)
     }
 
+    pub fn drainer_dead_letter_stream(&self, shard_key: &str) -> String {
+        // Example: {shard_5}_drainer_stream_dlq
+        format!("{}_dlq", self.drainer_stream(shard_key))
+    }
+
     #[allow(clippy::expect_used)]
     pub async fn close(mut self: Arc<Self>) {
         Arc::get_mut(&mut self)
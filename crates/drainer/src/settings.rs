@@ -2,8 +2,7 @@ use std::path::PathBuf;
 
 use common_utils::ext_traits::ConfigExt;
 use config::{Environment, File};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::SecretsManagementConfig;
 use redis_interface as redis;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use router_env::{env, logger};
@@ -18,6 +17,41 @@ pub struct CmdLineConf {
     /// Application will look for "config/config.toml" if this option isn't specified.
     #[arg(short = 'f', long, value_name = "FILE")]
     pub config_path: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub subcommand: Option<Subcommand>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Subcommand {
+    /// Inspect and replay the dead-letter streams of the drainer partitions
+    #[command(subcommand)]
+    DeadLetter(DeadLetterCommand),
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum DeadLetterCommand {
+    /// Print the number of entries in the dead-letter stream of every partition
+    Stats,
+    /// Print the entries in the dead-letter stream of a partition
+    List {
+        #[arg(short, long)]
+        partition: u8,
+        /// Maximum number of entries to print
+        #[arg(short, long, default_value_t = 100)]
+        count: u64,
+    },
+    /// Move entries from the dead-letter stream of a partition back to the partition
+    Replay {
+        #[arg(short, long)]
+        partition: u8,
+        /// Replay only the entry with this ID
+        #[arg(short, long)]
+        entry_id: Option<String>,
+        /// Maximum number of entries to replay, when no entry ID is specified
+        #[arg(short, long, default_value_t = 100)]
+        count: u64,
+    },
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -27,23 +61,21 @@ pub struct Settings {
     pub redis: redis::RedisSettings,
     pub log: Log,
     pub drainer: DrainerSettings,
-    #[cfg(feature = "kms")]
-    pub kms: kms::KmsConfig,
+    pub secrets_management: SecretsManagementConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct Database {
     pub username: String,
-    #[cfg(not(feature = "kms"))]
+    /// Database password, as stored in the configured secrets manager
+    #[serde(alias = "kms_encrypted_password")]
     pub password: String,
     pub host: String,
     pub port: u16,
     pub dbname: String,
     pub pool_size: u32,
     pub connection_timeout: u64,
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_password: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -54,21 +86,26 @@ pub struct DrainerSettings {
     pub max_read_count: u64,
     pub shutdown_interval: u32, // in milliseconds
     pub loop_interval: u32,     // in milliseconds
+    pub max_retries: u8,
+    pub retry_backoff: u32, // in milliseconds
+    pub consumer_group: String,
+    /// Name of this instance in the consumer group, generated on startup when not specified
+    pub consumer_name: Option<String>,
+    pub min_idle_time: u64,           // in milliseconds
+    pub partition_lease_ttl: u32,     // in seconds
+    pub kv_drain_check_interval: u64, // in seconds
 }
 
 impl Default for Database {
     fn default() -> Self {
         Self {
             username: String::new(),
-            #[cfg(not(feature = "kms"))]
             password: String::new(),
             host: "localhost".into(),
             port: 5432,
             dbname: String::new(),
             pool_size: 5,
             connection_timeout: 10,
-            #[cfg(feature = "kms")]
-            kms_encrypted_password: String::new(),
         }
     }
 }
@@ -81,6 +118,13 @@ impl Default for DrainerSettings {
             max_read_count: 100,
             shutdown_interval: 1000, // in milliseconds
             loop_interval: 500,      // in milliseconds
+            max_retries: 3,
+            retry_backoff: 100, // in milliseconds
+            consumer_group: "DRAINER_GROUP".into(),
+            consumer_name: None,
+            min_idle_time: 60000,        // in milliseconds
+            partition_lease_ttl: 60,     // in seconds
+            kv_drain_check_interval: 30, // in seconds
         }
     }
 }
@@ -107,32 +151,40 @@ impl Database {
             ))
         })?;
 
-        #[cfg(not(feature = "kms"))]
-        {
-            when(self.password.is_default_or_empty(), || {
-                Err(errors::DrainerError::ConfigParsingError(
-                    "database user password must not be empty".into(),
-                ))
-            })
-        }
-
-        #[cfg(feature = "kms")]
-        {
-            when(self.kms_encrypted_password.is_default_or_empty(), || {
-                Err(errors::DrainerError::ConfigParsingError(
-                    "database KMS encrypted password must not be empty".into(),
-                ))
-            })
-        }
+        when(self.password.is_default_or_empty(), || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "database user password must not be empty".into(),
+            ))
+        })
     }
 }
 
 impl DrainerSettings {
     fn validate(&self) -> Result<(), errors::DrainerError> {
-        common_utils::fp_utils::when(self.stream_name.is_default_or_empty(), || {
+        use common_utils::fp_utils::when;
+
+        when(self.stream_name.is_default_or_empty(), || {
             Err(errors::DrainerError::ConfigParsingError(
                 "drainer stream name must not be empty".into(),
             ))
+        })?;
+
+        when(self.consumer_group.is_default_or_empty(), || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer consumer group must not be empty".into(),
+            ))
+        })?;
+
+        when(self.partition_lease_ttl == 0, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer partition lease TTL must be greater than zero".into(),
+            ))
+        })?;
+
+        when(self.kv_drain_check_interval == 0, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer KV drain check interval must be greater than zero".into(),
+            ))
         })
     }
 }
@@ -169,12 +221,18 @@ impl Settings {
                     .with_list_parse_key("redis.cluster_urls"),
             )
             .build()?;
+        let raw_config = config.clone().try_deserialize::<serde_json::Value>()?;
 
-        serde_path_to_error::deserialize(config).map_err(|error| {
+        let mut settings: Self = serde_path_to_error::deserialize(config).map_err(|error| {
             logger::error!(%error, "Unable to deserialize application configuration");
             eprintln!("Unable to deserialize application configuration: {error}");
             errors::DrainerError::from(error.into_inner())
-        })
+        })?;
+        settings
+            .secrets_management
+            .record_legacy_kms_encrypted_keys(&raw_config);
+
+        Ok(settings)
     }
 
     pub fn validate(&self) -> Result<(), errors::DrainerError> {
@@ -184,6 +242,11 @@ impl Settings {
             errors::DrainerError::ConfigParsingError("invalid Redis configuration".into())
         })?;
         self.drainer.validate()?;
+        self.secrets_management.validate().map_err(|error| {
+            errors::DrainerError::ConfigParsingError(format!(
+                "invalid secrets management configuration: {error}"
+            ))
+        })?;
 
         Ok(())
     }
@@ -8,15 +8,21 @@ use crate::{
     logger, metrics, services,
 };
 
-pub type StreamEntries = Vec<(String, HashMap<String, String>)>;
+pub type StreamEntries = Vec<(String, HashMap<String, Option<String>>)>;
 pub type StreamReadResult = HashMap<String, StreamEntries>;
 
+/// Takes the lease on a partition, so that its entries are drained by a single instance at a
+/// time and in order. The lease expires on its own if the instance holding it dies.
 pub async fn is_stream_available(stream_index: u8, store: Arc<services::Store>) -> bool {
     let stream_key_flag = get_stream_key_flag(store.clone(), stream_index);
 
     match store
         .redis_conn
-        .set_key_if_not_exist(stream_key_flag.as_str(), true)
+        .set_key_if_not_exist_with_expiry(
+            stream_key_flag.as_str(),
+            store.config.drainer_consumer_name.as_str(),
+            store.config.drainer_partition_lease_ttl.into(),
+        )
         .await
     {
         Ok(resp) => resp == redis::types::SetnxReply::KeySet,
@@ -28,20 +34,133 @@ pub async fn is_stream_available(stream_index: u8, store: Arc<services::Store>)
     }
 }
 
+/// Creates the consumer group on the stream of every partition, starting from the first entry in
+/// the stream. Groups which already exist are left untouched.
+pub async fn create_consumer_groups(store: Arc<services::Store>) {
+    let first_entry_id = redis::RedisEntryId::UserSpecifiedID {
+        milliseconds: "0".to_string(),
+        sequence_number: "0".to_string(),
+    };
+
+    for stream_index in 0..store.config.drainer_num_partitions {
+        let stream_name = get_drainer_stream_name(store.clone(), stream_index);
+        if let Err(error) = store
+            .redis_conn
+            .consumer_group_create(
+                &stream_name,
+                &store.config.drainer_consumer_group,
+                &first_entry_id,
+            )
+            .await
+        {
+            // Fails with `BUSYGROUP` when the group has been created by an earlier run
+            logger::debug!(?error, stream = %stream_name, "Consumer group was not created");
+        }
+    }
+}
+
+/// Claims the entries of the partition left unacknowledged by other consumers of the group, such
+/// as instances that died while draining the partition, so that they are drained by this
+/// instance. Returns `false` if some of them are not idle for long enough to be claimed yet, in
+/// which case the partition must not be drained until they are, to keep the entries in order.
+pub async fn claim_pending_entries(
+    stream_name: &str,
+    max_read_count: u64,
+    store: &services::Store,
+) -> errors::DrainerResult<bool> {
+    let group = store.config.drainer_consumer_group.as_str();
+    let consumer = store.config.drainer_consumer_name.as_str();
+
+    let pending_entries = store
+        .redis_conn
+        .consumer_group_get_pending_entries(stream_name, group, max_read_count)
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?;
+
+    let mut claimable_ids = Vec::new();
+    for (entry_id, owner, idle_time, _) in pending_entries {
+        if owner == consumer {
+            continue;
+        }
+        if idle_time < store.config.drainer_min_idle_time {
+            logger::debug!(
+                stream = %stream_name,
+                %owner,
+                %idle_time,
+                "Partition has recently delivered entries pending with another consumer"
+            );
+            return Ok(false);
+        }
+        claimable_ids.push(entry_id);
+    }
+
+    if !claimable_ids.is_empty() {
+        let claimed_count = store
+            .redis_conn
+            .consumer_group_set_message_owner::<_, StreamEntries>(
+                stream_name,
+                group,
+                consumer,
+                store.config.drainer_min_idle_time,
+                claimable_ids,
+            )
+            .await
+            .map_err(DrainerError::from)
+            .into_report()?
+            .len();
+
+        logger::info!(stream = %stream_name, %claimed_count, "Claimed pending stream entries");
+        metrics::PENDING_ENTRIES_CLAIMED.add(
+            &metrics::CONTEXT,
+            u64::try_from(claimed_count).unwrap_or(u64::MIN),
+            &[metrics::KeyValue::new("stream", stream_name.to_owned())],
+        );
+    }
+
+    Ok(true)
+}
+
+/// Reads entries from the stream as a consumer of the drainer group. Entries delivered to this
+/// consumer earlier but not acknowledged yet are read first, followed by new entries.
 pub async fn read_from_stream(
     stream_name: &str,
     max_read_count: u64,
-    redis: &redis::RedisConnectionPool,
+    store: &services::Store,
 ) -> errors::DrainerResult<StreamReadResult> {
-    // "0-0" id gives first entry
-    let stream_id = "0-0";
+    let group = Some((
+        store.config.drainer_consumer_group.as_str(),
+        store.config.drainer_consumer_name.as_str(),
+    ));
+
     let (output, execution_time) = common_utils::date_time::time_it(|| async {
-        let entries = redis
-            .stream_read_entries(stream_name, stream_id, Some(max_read_count))
+        // "0" id gives the entries pending with this consumer
+        let pending_entries = store
+            .redis_conn
+            .stream_read_with_options(stream_name, "0", Some(max_read_count), None, group)
             .await
             .map_err(DrainerError::from)
             .into_report()?;
-        Ok(entries)
+
+        if pending_entries
+            .get(stream_name)
+            .map_or(false, |entries| !entries.is_empty())
+        {
+            return Ok(pending_entries);
+        }
+
+        store
+            .redis_conn
+            .stream_read_with_options(
+                stream_name,
+                redis::RedisEntryId::UndeliveredEntryID,
+                Some(max_read_count),
+                None,
+                group,
+            )
+            .await
+            .map_err(DrainerError::from)
+            .into_report()
     })
     .await;
 
@@ -54,31 +173,36 @@ pub async fn read_from_stream(
     output
 }
 
-pub async fn trim_from_stream(
+/// Acknowledges entries that have been drained, and deletes them from the stream. Returns the
+/// number of entries deleted.
+pub async fn acknowledge_entries(
     stream_name: &str,
-    minimum_entry_id: &str,
-    redis: &redis::RedisConnectionPool,
+    entry_ids: Vec<String>,
+    store: &services::Store,
 ) -> errors::DrainerResult<usize> {
-    let trim_kind = redis::StreamCapKind::MinID;
-    let trim_type = redis::StreamCapTrim::Exact;
-    let trim_id = minimum_entry_id;
-    let (trim_result, execution_time) =
+    let (ack_result, execution_time) =
         common_utils::date_time::time_it::<errors::DrainerResult<_>, _, _>(|| async {
-            let trim_result = redis
-                .stream_trim_entries(stream_name, (trim_kind, trim_type, trim_id))
+            store
+                .redis_conn
+                .stream_acknowledge_entries(
+                    stream_name,
+                    &store.config.drainer_consumer_group,
+                    entry_ids.clone(),
+                )
                 .await
                 .map_err(DrainerError::from)
                 .into_report()?;
 
-            // Since xtrim deletes entries below given id excluding the given id.
-            // Hence, deleting the minimum entry id
-            redis
-                .stream_delete_entries(stream_name, minimum_entry_id)
+            // Acknowledged entries are not read by the group again, and are only deleted to
+            // keep the stream from growing
+            let deleted_count = store
+                .redis_conn
+                .stream_delete_entries(stream_name, entry_ids)
                 .await
                 .map_err(DrainerError::from)
                 .into_report()?;
 
-            Ok(trim_result)
+            Ok(deleted_count)
         })
         .await;
 
@@ -88,19 +212,33 @@ pub async fn trim_from_stream(
         &[metrics::KeyValue::new("stream", stream_name.to_owned())],
     );
 
-    // adding 1 because we are deleting the given id too
-    Ok(trim_result? + 1)
+    ack_result
 }
 
+/// Releases the lease on a partition, provided it is still held by this instance. The lease may
+/// have expired while the partition was being drained and been taken by another instance, whose
+/// lease must be left in place.
 pub async fn make_stream_available(
     stream_name_flag: &str,
-    redis: &redis::RedisConnectionPool,
+    store: &services::Store,
 ) -> errors::DrainerResult<()> {
-    redis
-        .delete_key(stream_name_flag)
+    let released = store
+        .redis_conn
+        .delete_key_if_value_matches(
+            stream_name_flag,
+            store.config.drainer_consumer_name.as_str(),
+        )
         .await
         .map_err(DrainerError::from)
-        .into_report()
+        .into_report()?;
+
+    if !released {
+        logger::warn!(
+            stream_flag = %stream_name_flag,
+            "Partition lease expired while draining and was not released"
+        );
+    }
+    Ok(())
 }
 
 pub fn parse_stream_entries<'a>(
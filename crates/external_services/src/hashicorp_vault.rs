@@ -0,0 +1,176 @@
+//! Interactions with the HashiCorp Vault transit secrets engine
+
+use base64::Engine;
+use common_utils::errors::CustomResult;
+use error_stack::{IntoReport, ResultExt};
+use router_env::logger;
+
+use crate::{
+    consts, metrics,
+    secrets_management::{SecretManagementInterface, SecretsManagementError},
+};
+
+/// Configuration parameters required for constructing a [`HashiCorpVault`] client.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct HashiCorpVaultConfig {
+    /// The address of the Vault server, for example `https://vault.example.com:8200`.
+    pub url: String,
+
+    /// The token used to authenticate with Vault. The `VAULT_TOKEN` environment variable is used
+    /// if this is empty.
+    pub token: String,
+
+    /// The path at which the transit secrets engine is mounted.
+    pub transit_mount: String,
+
+    /// The name of the transit key used to encrypt or decrypt data.
+    pub key_name: String,
+}
+
+impl Default for HashiCorpVaultConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            token: String::new(),
+            transit_mount: "transit".into(),
+            key_name: String::new(),
+        }
+    }
+}
+
+/// Client for HashiCorp Vault transit operations.
+#[derive(Debug)]
+pub struct HashiCorpVault {
+    client: reqwest::Client,
+    decrypt_url: String,
+    token: String,
+}
+
+#[derive(serde::Serialize)]
+struct DecryptRequest<'a> {
+    ciphertext: &'a str,
+}
+
+#[derive(serde::Deserialize)]
+struct DecryptResponse {
+    data: DecryptResponseData,
+}
+
+#[derive(serde::Deserialize)]
+struct DecryptResponseData {
+    plaintext: String,
+}
+
+impl HashiCorpVault {
+    /// Constructs a new HashiCorp Vault client.
+    pub fn new(config: &HashiCorpVaultConfig) -> CustomResult<Self, HashiCorpError> {
+        let token = if config.token.is_empty() {
+            std::env::var("VAULT_TOKEN")
+                .into_report()
+                .change_context(HashiCorpError::MissingToken)?
+        } else {
+            config.token.clone()
+        };
+
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .build()
+                .into_report()
+                .change_context(HashiCorpError::ClientCreationFailed)?,
+            decrypt_url: format!(
+                "{}/v1/{}/decrypt/{}",
+                config.url.trim_end_matches('/'),
+                config.transit_mount.trim_matches('/'),
+                config.key_name
+            ),
+            token,
+        })
+    }
+
+    /// Decrypts the provided ciphertext (of the form `vault:v<version>:<base64 data>`) using the
+    /// transit secrets engine.
+    pub async fn decrypt(&self, ciphertext: &str) -> CustomResult<String, HashiCorpError> {
+        let response = self
+            .client
+            .post(&self.decrypt_url)
+            .header("X-Vault-Token", &self.token)
+            .json(&DecryptRequest { ciphertext })
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|error| {
+                logger::error!(vault_error=?error, "Failed to decrypt data using HashiCorp Vault");
+                metrics::HASHICORP_VAULT_FAILURES.add(&metrics::CONTEXT, 1, &[]);
+                error
+            })
+            .into_report()
+            .change_context(HashiCorpError::DecryptionFailed)?
+            .json::<DecryptResponse>()
+            .await
+            .into_report()
+            .change_context(HashiCorpError::DecryptionFailed)
+            .attach_printable("Failed to parse HashiCorp Vault decrypt response")?;
+
+        let plaintext = consts::BASE64_ENGINE
+            .decode(response.data.plaintext)
+            .into_report()
+            .change_context(HashiCorpError::Base64DecodingFailed)?;
+
+        String::from_utf8(plaintext)
+            .into_report()
+            .change_context(HashiCorpError::Utf8DecodingFailed)
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretManagementInterface for HashiCorpVault {
+    async fn get_secret(&self, input: &str) -> CustomResult<String, SecretsManagementError> {
+        self.decrypt(input)
+            .await
+            .change_context(SecretsManagementError::FetchSecretFailed)
+    }
+}
+
+/// Errors that could occur during HashiCorp Vault operations.
+#[derive(Debug, thiserror::Error)]
+pub enum HashiCorpError {
+    /// No token was configured, nor set in the `VAULT_TOKEN` environment variable.
+    #[error("Missing HashiCorp Vault token")]
+    MissingToken,
+
+    /// An error occurred when constructing the HTTP client.
+    #[error("Failed to construct HashiCorp Vault client")]
+    ClientCreationFailed,
+
+    /// An error occurred when decrypting input data using the transit secrets engine.
+    #[error("Failed to decrypt input data using HashiCorp Vault")]
+    DecryptionFailed,
+
+    /// An error occurred when base64 decoding the decrypted output.
+    #[error("Failed to base64 decode decryption output")]
+    Base64DecodingFailed,
+
+    /// An error occurred UTF-8 decoding the decrypted output.
+    #[error("Failed to UTF-8 decode decryption output")]
+    Utf8DecodingFailed,
+}
+
+impl HashiCorpVaultConfig {
+    /// Verifies that the [`HashiCorpVault`] client configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        use common_utils::{ext_traits::ConfigExt, fp_utils::when};
+
+        when(self.url.is_default_or_empty(), || {
+            Err("HashiCorp Vault URL must not be empty")
+        })?;
+
+        when(self.transit_mount.is_default_or_empty(), || {
+            Err("HashiCorp Vault transit mount must not be empty")
+        })?;
+
+        when(self.key_name.is_default_or_empty(), || {
+            Err("HashiCorp Vault transit key name must not be empty")
+        })
+    }
+}
@@ -7,7 +7,10 @@ use common_utils::errors::CustomResult;
 use error_stack::{IntoReport, ResultExt};
 use router_env::logger;
 
-use crate::{consts, metrics};
+use crate::{
+    consts, metrics,
+    secrets_management::{SecretManagementInterface, SecretsManagementError},
+};
 
 static KMS_CLIENT: tokio::sync::OnceCell<KmsClient> = tokio::sync::OnceCell::const_new();
 
@@ -87,6 +90,15 @@ impl KmsClient {
     }
 }
 
+#[async_trait::async_trait]
+impl SecretManagementInterface for KmsClient {
+    async fn get_secret(&self, input: &str) -> CustomResult<String, SecretsManagementError> {
+        self.decrypt(input)
+            .await
+            .change_context(SecretsManagementError::FetchSecretFailed)
+    }
+}
+
 /// Errors that could occur during KMS operations.
 #[derive(Debug, thiserror::Error)]
 pub enum KmsError {
@@ -3,11 +3,14 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs, missing_debug_implementations)]
 
+#[cfg(feature = "hashicorp-vault")]
+pub mod hashicorp_vault;
 #[cfg(feature = "kms")]
 pub mod kms;
+pub mod secrets_management;
 
 /// Crate specific constants
-#[cfg(feature = "kms")]
+#[cfg(any(feature = "kms", feature = "hashicorp-vault"))]
 pub mod consts {
     /// General purpose base64 engine
     pub(crate) const BASE64_ENGINE: base64::engine::GeneralPurpose =
@@ -15,7 +18,7 @@ pub mod consts {
 }
 
 /// Metrics for interactions with external systems.
-#[cfg(feature = "kms")]
+#[cfg(any(feature = "kms", feature = "hashicorp-vault"))]
 pub mod metrics {
     use router_env::{counter_metric, global_meter, metrics_context};
 
@@ -24,4 +27,6 @@ pub mod metrics {
 
     #[cfg(feature = "kms")]
     counter_metric!(AWS_KMS_FAILURES, GLOBAL_METER); // No. of AWS KMS API failures
+    #[cfg(feature = "hashicorp-vault")]
+    counter_metric!(HASHICORP_VAULT_FAILURES, GLOBAL_METER); // No. of HashiCorp Vault API failures
 }
@@ -0,0 +1,254 @@
+//! Retrieval of secrets stored in the application configuration, backed by a pluggable secrets
+//! manager.
+
+use std::sync::Arc;
+
+use common_utils::errors::CustomResult;
+use error_stack::{IntoReport, ResultExt};
+use router_env::logger;
+
+#[cfg(feature = "hashicorp-vault")]
+use crate::hashicorp_vault;
+#[cfg(feature = "kms")]
+use crate::kms;
+
+/// Interface for retrieving the plaintext of secrets from the form in which they are stored in
+/// the application configuration.
+#[async_trait::async_trait]
+pub trait SecretManagementInterface: Send + Sync {
+    /// Returns the plaintext of the secret stored as `input`.
+    async fn get_secret(&self, input: &str) -> CustomResult<String, SecretsManagementError>;
+}
+
+/// Prefix of the configuration keys under which secrets encrypted with AWS KMS were stored
+/// before the secrets manager was made pluggable.
+const LEGACY_KMS_ENCRYPTED_KEY_PREFIX: &str = "kms_encrypted_";
+
+/// Configuration of the secrets manager used to retrieve secrets stored in the application
+/// configuration.
+#[derive(Clone, Default, serde::Deserialize)]
+pub struct SecretsManagementConfig {
+    /// The secrets manager the secrets are stored with
+    #[serde(flatten)]
+    pub manager: SecretsManager,
+
+    /// Configuration keys of the `kms_encrypted_*` form found in the application configuration
+    #[serde(skip)]
+    legacy_kms_encrypted_keys: Vec<String>,
+
+    /// Client for the configured secrets manager, shared by clones of this configuration
+    #[serde(skip)]
+    client: Arc<tokio::sync::OnceCell<Box<dyn SecretManagementInterface>>>,
+}
+
+impl std::fmt::Debug for SecretsManagementConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretsManagementConfig")
+            .field("manager", &self.manager)
+            .field("legacy_kms_encrypted_keys", &self.legacy_kms_encrypted_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The secrets manager the secrets stored in the application configuration are stored with.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(tag = "secrets_manager", rename_all = "snake_case")]
+pub enum SecretsManager {
+    /// Secrets are base64-encoded ciphertexts encrypted with an AWS KMS key.
+    #[cfg(feature = "kms")]
+    AwsKms {
+        /// AWS KMS configuration
+        aws_kms: kms::KmsConfig,
+    },
+
+    /// Secrets are ciphertexts encrypted with a HashiCorp Vault transit key.
+    #[cfg(feature = "hashicorp-vault")]
+    #[serde(rename = "hashicorp_vault")]
+    HashiCorpVault {
+        /// HashiCorp Vault configuration
+        hc_vault: hashicorp_vault::HashiCorpVaultConfig,
+    },
+
+    /// Secrets are stored in plaintext, or read from environment variables or files. Meant for
+    /// development and tests only.
+    #[default]
+    Local,
+}
+
+impl SecretsManagementConfig {
+    /// Returns the client for the configured secrets manager, or initializes a new one if not
+    /// previously initialized for this configuration.
+    pub async fn get_secret_management_client(
+        &self,
+    ) -> CustomResult<&dyn SecretManagementInterface, SecretsManagementError> {
+        self.client
+            .get_or_try_init(|| async {
+                let client: Box<dyn SecretManagementInterface> = match &self.manager {
+                    #[cfg(feature = "kms")]
+                    SecretsManager::AwsKms { aws_kms } => {
+                        Box::new(kms::KmsClient::new(aws_kms).await)
+                    }
+                    #[cfg(feature = "hashicorp-vault")]
+                    SecretsManager::HashiCorpVault { hc_vault } => Box::new(
+                        hashicorp_vault::HashiCorpVault::new(hc_vault)
+                            .change_context(SecretsManagementError::ClientCreationFailed)?,
+                    ),
+                    SecretsManager::Local => Box::new(LocalSecretManager),
+                };
+                Ok::<_, error_stack::Report<SecretsManagementError>>(client)
+            })
+            .await
+            .map(|client| client.as_ref())
+    }
+
+    /// Returns the plaintext of the secret stored as `input`, using the configured secrets
+    /// manager.
+    pub async fn get_secret(&self, input: &str) -> CustomResult<String, SecretsManagementError> {
+        self.get_secret_management_client()
+            .await?
+            .get_secret(input)
+            .await
+    }
+
+    /// Records the `kms_encrypted_*` keys present in the raw application configuration, so that
+    /// [`Self::validate`] can reject them unless the secrets are stored with AWS KMS.
+    pub fn record_legacy_kms_encrypted_keys(&mut self, raw_config: &serde_json::Value) {
+        self.legacy_kms_encrypted_keys = find_legacy_kms_encrypted_keys(raw_config, "");
+    }
+
+    /// Verifies that the secrets manager configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match &self.manager {
+            #[cfg(feature = "kms")]
+            SecretsManager::AwsKms { aws_kms } => aws_kms.validate(),
+            #[cfg(feature = "hashicorp-vault")]
+            SecretsManager::HashiCorpVault { hc_vault } => {
+                self.validate_no_legacy_kms_encrypted_keys()?;
+                hc_vault.validate()
+            }
+            SecretsManager::Local => self.validate_no_legacy_kms_encrypted_keys(),
+        }
+    }
+
+    /// Values stored under `kms_encrypted_*` keys are AWS KMS ciphertexts, which any other
+    /// secrets manager would silently hand out as the secrets themselves.
+    fn validate_no_legacy_kms_encrypted_keys(&self) -> Result<(), &'static str> {
+        if self.legacy_kms_encrypted_keys.is_empty() {
+            Ok(())
+        } else {
+            logger::error!(
+                keys = ?self.legacy_kms_encrypted_keys,
+                "`kms_encrypted_*` configuration keys require the `aws_kms` secrets manager"
+            );
+            Err("`kms_encrypted_*` configuration keys require the `aws_kms` secrets manager")
+        }
+    }
+}
+
+/// Returns the dotted paths of all `kms_encrypted_*` keys within `value`.
+fn find_legacy_kms_encrypted_keys(value: &serde_json::Value, path: &str) -> Vec<String> {
+    match value {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .flat_map(|(key, value)| {
+                let key_path = if path.is_empty() {
+                    key.to_owned()
+                } else {
+                    format!("{path}.{key}")
+                };
+                if key.starts_with(LEGACY_KMS_ENCRYPTED_KEY_PREFIX) {
+                    vec![key_path]
+                } else {
+                    find_legacy_kms_encrypted_keys(value, &key_path)
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Secrets manager for development and tests. Secrets are stored in plaintext, unless they are
+/// of the form `env:<VARIABLE>` or `file:<PATH>`, in which case they are read from the
+/// environment variable or the file respectively.
+#[derive(Debug, Default)]
+pub struct LocalSecretManager;
+
+#[async_trait::async_trait]
+impl SecretManagementInterface for LocalSecretManager {
+    async fn get_secret(&self, input: &str) -> CustomResult<String, SecretsManagementError> {
+        if let Some(variable) = input.strip_prefix("env:") {
+            std::env::var(variable)
+                .into_report()
+                .change_context(SecretsManagementError::FetchSecretFailed)
+                .attach_printable_lazy(|| format!("Failed to read environment variable {variable}"))
+        } else if let Some(path) = input.strip_prefix("file:") {
+            std::fs::read_to_string(path)
+                .map(|secret| secret.trim_end().to_owned())
+                .into_report()
+                .change_context(SecretsManagementError::FetchSecretFailed)
+                .attach_printable_lazy(|| format!("Failed to read secret from file {path}"))
+        } else {
+            Ok(input.to_owned())
+        }
+    }
+}
+
+/// Errors that could occur when retrieving secrets.
+#[derive(Debug, thiserror::Error)]
+pub enum SecretsManagementError {
+    /// An error occurred when constructing the secrets manager client.
+    #[error("Failed to construct secrets manager client")]
+    ClientCreationFailed,
+
+    /// An error occurred when retrieving the plaintext of a secret.
+    #[error("Failed to retrieve secret")]
+    FetchSecretFailed,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_secret_manager() {
+        let manager = LocalSecretManager;
+
+        let secret = manager
+            .get_secret("plaintext")
+            .await
+            .expect("failed to get plaintext secret");
+        assert_eq!(secret, "plaintext");
+
+        std::env::set_var("EXTERNAL_SERVICES_TEST_SECRET", "from_env");
+        let secret = manager
+            .get_secret("env:EXTERNAL_SERVICES_TEST_SECRET")
+            .await
+            .expect("failed to get secret from environment");
+        assert_eq!(secret, "from_env");
+
+        assert!(manager
+            .get_secret("env:EXTERNAL_SERVICES_MISSING_SECRET")
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_legacy_kms_encrypted_keys_rejected_without_aws_kms() {
+        let raw_config = serde_json::json!({
+            "master_database": { "kms_encrypted_password": "AQICAHg..." },
+            "secrets": { "admin_api_key": "test_admin" },
+        });
+
+        let mut config = SecretsManagementConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.record_legacy_kms_encrypted_keys(&raw_config);
+        assert_eq!(
+            config.legacy_kms_encrypted_keys,
+            vec!["master_database.kms_encrypted_password".to_string()]
+        );
+        assert!(config.validate().is_err());
+    }
+}
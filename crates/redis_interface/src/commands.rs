@@ -6,7 +6,7 @@
 //!
 //!
 
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 use common_utils::{
     errors::CustomResult,
@@ -15,7 +15,10 @@ use common_utils::{
 };
 use error_stack::{IntoReport, ResultExt};
 use fred::{
-    interfaces::{HashesInterface, KeysInterface, StreamsInterface},
+    interfaces::{
+        ClientLike, HashesInterface, KeysInterface, LuaInterface, SetsInterface,
+        SortedSetsInterface, StreamsInterface,
+    },
     types::{
         Expiration, FromRedis, MultipleIDs, MultipleKeys, MultipleOrderedPairs, MultipleStrings,
         RedisKey, RedisMap, RedisValue, Scanner, SetOptions, XCap, XReadResponse,
@@ -30,6 +33,15 @@ use crate::{
 };
 
 impl super::RedisConnectionPool {
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn ping(&self) -> CustomResult<(), errors::RedisError> {
+        self.pool
+            .ping()
+            .await
+            .into_report()
+            .change_context(errors::RedisError::PingFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_key<V>(&self, key: &str, value: V) -> CustomResult<(), errors::RedisError>
     where
@@ -130,6 +142,30 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::DeleteFailed)
     }
 
+    /// Deletes a key only if it holds the specified value, atomically. Returns whether the key
+    /// was deleted.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn delete_key_if_value_matches(
+        &self,
+        key: &str,
+        value: &str,
+    ) -> CustomResult<bool, errors::RedisError> {
+        const COMPARE_AND_DELETE: &str = r#"
+            if redis.call("GET", KEYS[1]) == ARGV[1] then
+                return redis.call("DEL", KEYS[1])
+            end
+            return 0
+        "#;
+
+        let deleted_count: u64 = self
+            .pool
+            .eval(COMPARE_AND_DELETE, key, value)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::DeleteFailed)?;
+        Ok(deleted_count > 0)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_key_with_expiry<V>(
         &self,
@@ -171,6 +207,30 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetFailed)
     }
 
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn set_key_if_not_exist_with_expiry<V>(
+        &self,
+        key: &str,
+        value: V,
+        seconds: i64,
+    ) -> CustomResult<SetnxReply, errors::RedisError>
+    where
+        V: TryInto<RedisValue> + Debug + Send + Sync,
+        V::Error: Into<fred::error::RedisError> + Send + Sync,
+    {
+        self.pool
+            .set(
+                key,
+                value,
+                Some(Expiration::EX(seconds)),
+                Some(SetOptions::NX),
+                false,
+            )
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SetFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_expiry(
         &self,
@@ -370,6 +430,99 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::JsonDeserializationFailed)
     }
 
+    /// Adds a member to a sorted set, or updates its score if it is already present. The expiry
+    /// of the sorted set is extended to that of hashes, so that the set lives at least as long
+    /// as the hashes it indexes.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn sorted_set_add_member(
+        &self,
+        key: &str,
+        score: f64,
+        member: &str,
+    ) -> CustomResult<(), errors::RedisError> {
+        let output: Result<(), _> = self
+            .pool
+            .zadd(key, None, None, false, false, (score, member))
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SortedSetAddFailed);
+
+        output
+            .async_and_then(|_| self.set_expiry(key, self.config.default_hash_ttl.into()))
+            .await
+    }
+
+    /// Returns the members of a sorted set with scores in the range `[min, max]`, ordered from
+    /// the highest score to the lowest.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn sorted_set_get_members_by_score_rev(
+        &self,
+        key: &str,
+        max: f64,
+        min: f64,
+        count: Option<i64>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        self.pool
+            .zrevrangebyscore(key, max, min, false, count.map(|count| (0, count)))
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SortedSetGetFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn sorted_set_remove_members_by_score(
+        &self,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> CustomResult<usize, errors::RedisError> {
+        self.pool
+            .zremrangebyscore(key, min, max)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SortedSetRemoveFailed)
+    }
+
+    /// Adds a member to a set. Unlike hashes and sorted sets, the set is not given an expiry.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn set_add_member(
+        &self,
+        key: &str,
+        member: &str,
+    ) -> CustomResult<(), errors::RedisError> {
+        self.pool
+            .sadd(key, member)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SetAddMemberFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn set_is_member(
+        &self,
+        key: &str,
+        member: &str,
+    ) -> CustomResult<bool, errors::RedisError> {
+        self.pool
+            .sismember(key, member)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SetIsMemberFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn set_remove_members(
+        &self,
+        key: &str,
+        members: Vec<String>,
+    ) -> CustomResult<usize, errors::RedisError> {
+        self.pool
+            .srem(key, members)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SetRemoveMembersFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn stream_append_entry<F>(
         &self,
@@ -498,6 +651,21 @@ impl super::RedisConnectionPool {
         .change_context(errors::RedisError::StreamReadFailed)
     }
 
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_read_range(
+        &self,
+        stream: &str,
+        start: &str,
+        end: &str,
+        count: Option<u64>,
+    ) -> CustomResult<Vec<(String, HashMap<String, String>)>, errors::RedisError> {
+        self.pool
+            .xrange_values(stream, start, end, count)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::StreamReadFailed)
+    }
+
     //                                              Consumer Group API
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -564,6 +732,22 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::ConsumerGroupSetIdFailed)
     }
 
+    /// Returns the ID, consumer, idle time (in milliseconds) and delivery count of the entries
+    /// that have been delivered to consumers of the group but not acknowledged yet, oldest first.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn consumer_group_get_pending_entries(
+        &self,
+        stream: &str,
+        group: &str,
+        count: u64,
+    ) -> CustomResult<Vec<(String, String, u64, u64)>, errors::RedisError> {
+        self.pool
+            .xpending(stream, group, ("-", "+", count))
+            .await
+            .into_report()
+            .change_context(errors::RedisError::ConsumerGroupGetPendingFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn consumer_group_set_message_owner<Ids, R>(
         &self,
@@ -38,6 +38,8 @@ pub enum RedisError {
     ConsumerGroupSetIdFailed,
     #[error("Failed to set Redis stream message owner")]
     ConsumerGroupClaimFailed,
+    #[error("Failed to get pending entries of Redis consumer group")]
+    ConsumerGroupGetPendingFailed,
     #[error("Failed to serialize application type to JSON")]
     JsonSerializationFailed,
     #[error("Failed to deserialize application type from JSON")]
@@ -48,6 +50,18 @@ pub enum RedisError {
     SetHashFieldFailed,
     #[error("Failed to get hash field in Redis")]
     GetHashFieldFailed,
+    #[error("Failed to add member to Redis sorted set")]
+    SortedSetAddFailed,
+    #[error("Failed to get members of Redis sorted set")]
+    SortedSetGetFailed,
+    #[error("Failed to remove members from Redis sorted set")]
+    SortedSetRemoveFailed,
+    #[error("Failed to add member to Redis set")]
+    SetAddMemberFailed,
+    #[error("Failed to check membership of Redis set")]
+    SetIsMemberFailed,
+    #[error("Failed to remove members from Redis set")]
+    SetRemoveMembersFailed,
     #[error("The requested value was not found in Redis")]
     NotFound,
     #[error("Invalid RedisEntryId provided")]
@@ -58,4 +72,6 @@ pub enum RedisError {
     SubscribeError,
     #[error("Failed to publish to a channel")]
     PublishError,
+    #[error("Redis did not respond to ping")]
+    PingFailed,
 }
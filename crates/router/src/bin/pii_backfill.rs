@@ -0,0 +1,34 @@
+use router::{
+    configs::settings::{CmdLineConf, Settings},
+    core::errors::{self, CustomResult},
+    db::pii_encryption,
+    logger, services,
+};
+use tokio::sync::oneshot;
+
+/// Encrypts the PII columns of the rows written before PII encryption was enabled. See
+/// [`pii_encryption::backfill`] for when the job can be run.
+#[tokio::main]
+async fn main() -> CustomResult<(), errors::StorageError> {
+    let cmd_line = <CmdLineConf as clap::Parser>::parse();
+
+    #[allow(clippy::expect_used)]
+    let conf = Settings::with_config_path(cmd_line.config_path)
+        .expect("Unable to construct application configuration");
+    #[allow(clippy::expect_used)]
+    conf.validate()
+        .expect("Failed to validate router configuration");
+    #[allow(clippy::expect_used)]
+    let _guard = logger::setup(&conf.log).expect("Failed to set up the logger");
+
+    let (redis_shutdown_signal_tx, _redis_shutdown_signal_rx) = oneshot::channel();
+    let store = services::Store::new(&conf, false, redis_shutdown_signal_tx).await;
+
+    let summary = pii_encryption::backfill(&store, conf.pii_encryption.backfill_batch_size).await?;
+
+    #[allow(clippy::expect_used)]
+    let summary =
+        serde_json::to_string_pretty(&summary).expect("Failed to serialize backfill summary");
+    println!("{summary}");
+    Ok(())
+}
@@ -4,6 +4,8 @@ use dyn_clone::DynClone;
 use moka::future::Cache as MokaCache;
 use once_cell::sync::Lazy;
 
+use crate::routes::metrics;
+
 /// Time to live 30 mins
 const CACHE_TTL: u64 = 30 * 60;
 
@@ -11,7 +13,75 @@ const CACHE_TTL: u64 = 30 * 60;
 const CACHE_TTI: u64 = 10 * 60;
 
 /// Config Cache with time_to_live as 30 mins and time_to_idle as 10 mins.
-pub static CONFIG_CACHE: Lazy<Cache> = Lazy::new(|| Cache::new(CACHE_TTL, CACHE_TTI));
+pub static CONFIG_CACHE: Lazy<Cache> = Lazy::new(|| Cache::new("config", CACHE_TTL, CACHE_TTI));
+
+/// Merchant account and merchant connector account Cache with time_to_live as 30 mins and
+/// time_to_idle as 10 mins.
+pub static ACCOUNTS_CACHE: Lazy<Cache> = Lazy::new(|| Cache::new("accounts", CACHE_TTL, CACHE_TTI));
+
+/// API key Cache with time_to_live as 30 mins and time_to_idle as 10 mins.
+pub static API_KEY_CACHE: Lazy<Cache> = Lazy::new(|| Cache::new("api_key", CACHE_TTL, CACHE_TTI));
+
+/// Decrypted data keys of merchants with time_to_live as 30 mins and time_to_idle as 10 mins. Data
+/// keys never change once generated, so this cache is not invalidated.
+pub static MERCHANT_KEY_CACHE: Lazy<Cache> =
+    Lazy::new(|| Cache::new("merchant_key", CACHE_TTL, CACHE_TTI));
+
+/// The in-memory caches, which are kept consistent across instances by publishing the keys to be
+/// invalidated on the [`common_utils::consts::PUB_SUB_CHANNEL`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheKind {
+    Config,
+    Accounts,
+    ApiKey,
+    /// Every cache holding the key
+    All,
+}
+
+impl CacheKind {
+    fn caches(self) -> Vec<&'static Cache> {
+        match self {
+            Self::Config => vec![&*CONFIG_CACHE],
+            Self::Accounts => vec![&*ACCOUNTS_CACHE],
+            Self::ApiKey => vec![&*API_KEY_CACHE],
+            Self::All => vec![&*CONFIG_CACHE, &*ACCOUNTS_CACHE, &*API_KEY_CACHE],
+        }
+    }
+}
+
+/// Message published on the invalidation channel for a key to be removed from a cache on every
+/// instance.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CacheInvalidation {
+    pub kind: CacheKind,
+    pub key: String,
+}
+
+impl CacheInvalidation {
+    /// Parses a message received on the invalidation channel. Messages consisting of only the key
+    /// (as published by older instances and the drainer) invalidate the key in every cache.
+    pub fn from_message(message: &str) -> Self {
+        serde_json::from_str(message).unwrap_or_else(|_| Self {
+            kind: CacheKind::All,
+            key: message.to_string(),
+        })
+    }
+
+    pub async fn invalidate(&self) {
+        for cache in self.kind.caches() {
+            cache.invalidate(&self.key).await;
+        }
+    }
+}
+
+/// Removes every entry from the in-memory caches, used when invalidation messages may have been
+/// missed.
+pub fn invalidate_all() {
+    for cache in CacheKind::All.caches() {
+        cache.invalidate_all();
+    }
+}
 
 /// Trait which defines the behaviour of types that's gonna be stored in Cache
 pub trait Cacheable: Any + Send + Sync + DynClone {
@@ -30,6 +100,7 @@ where
 dyn_clone::clone_trait_object!(Cacheable);
 
 pub struct Cache {
+    name: &'static str,
     inner: MokaCache<String, Arc<dyn Cacheable>>,
 }
 
@@ -43,12 +114,22 @@ impl std::ops::Deref for Cache {
 impl Cache {
     /// With given `time_to_live` and `time_to_idle` creates a moka cache.
     ///
+    /// `name`: Name of the cache, with which its metrics are recorded
     /// `time_to_live`: Time in seconds before an object is stored in a caching system before it’s deleted
     /// `time_to_idle`: Time in seconds before a `get` or `insert` operation an object is stored in a caching system before it's deleted
-    pub fn new(time_to_live: u64, time_to_idle: u64) -> Self {
+    pub fn new(name: &'static str, time_to_live: u64, time_to_idle: u64) -> Self {
         Self {
+            name,
             inner: MokaCache::builder()
-                .eviction_listener_with_queued_delivery_mode(|_, _, _| {})
+                .eviction_listener_with_queued_delivery_mode(move |_, _, cause| {
+                    if cause.was_evicted() {
+                        metrics::CACHE_EVICTION_COUNT.add(
+                            &metrics::CONTEXT,
+                            1,
+                            &[metrics::request::add_attributes("cache", name)],
+                        );
+                    }
+                })
                 .time_to_live(std::time::Duration::from_secs(time_to_live))
                 .time_to_idle(std::time::Duration::from_secs(time_to_idle))
                 .build(),
@@ -60,8 +141,20 @@ impl Cache {
     }
 
     pub fn get_val<T: Clone + Cacheable>(&self, key: &str) -> Option<T> {
-        let val = self.get(key)?;
-        (*val).as_any().downcast_ref::<T>().cloned()
+        let val = self
+            .get(key)
+            .and_then(|val| (*val).as_any().downcast_ref::<T>().cloned());
+        let metric = if val.is_some() {
+            &metrics::CACHE_HIT
+        } else {
+            &metrics::CACHE_MISS
+        };
+        metric.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::request::add_attributes("cache", self.name)],
+        );
+        val
     }
 }
 
@@ -71,8 +164,19 @@ mod cache_tests {
 
     #[tokio::test]
     async fn construct_and_get_cache() {
-        let cache = Cache::new(1800, 1800);
+        let cache = Cache::new("test", 1800, 1800);
         cache.push("key".to_string(), "val".to_string()).await;
         assert_eq!(cache.get_val::<String>("key"), Some(String::from("val")));
     }
+
+    #[test]
+    fn parse_invalidation_message() {
+        let invalidation = CacheInvalidation::from_message(r#"{"kind":"api_key","key":"abc"}"#);
+        assert_eq!(invalidation.kind, CacheKind::ApiKey);
+        assert_eq!(invalidation.key, "abc");
+
+        let invalidation = CacheInvalidation::from_message("merchant_123");
+        assert_eq!(invalidation.kind, CacheKind::All);
+        assert_eq!(invalidation.key, "merchant_123");
+    }
 }
@@ -413,6 +413,14 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
                 connector,
                 status_code,
             },
+            errors::ApiErrorResponse::ConnectorUnavailable { connector } => {
+                Self::ExternalConnectorError {
+                    code: "connector_unavailable".to_owned(),
+                    message: "The connector is temporarily unavailable. Retry later".to_owned(),
+                    connector,
+                    status_code: 503,
+                }
+            }
             errors::ApiErrorResponse::IncorrectConnectorNameGiven => {
                 Self::IncorrectConnectorNameGiven
             }
@@ -476,6 +484,12 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::DuplicatePayment { payment_id } => {
                 Self::DuplicatePayment { payment_id }
             }
+            errors::ApiErrorResponse::PaymentSessionExpired => Self::PaymentIntentUnexpectedState {
+                current_flow: "confirmed".to_owned(),
+                field_name: "session".to_owned(),
+                current_value: "expired".to_owned(),
+                states: "active".to_owned(),
+            },
             errors::ApiErrorResponse::DisputeNotFound { dispute_id } => Self::ResourceMissing {
                 object: "dispute".to_owned(),
                 id: dispute_id,
@@ -490,6 +504,12 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::MissingDisputeId => Self::MissingDisputeId,
             errors::ApiErrorResponse::FileNotFound => Self::FileNotFound,
             errors::ApiErrorResponse::FileNotAvailable => Self::FileNotAvailable,
+            errors::ApiErrorResponse::PaymentLinkNotFound { payment_link_id } => {
+                Self::ResourceMissing {
+                    object: "payment_link".to_owned(),
+                    id: payment_link_id,
+                }
+            }
             errors::ApiErrorResponse::NotSupported { .. } => Self::InternalServerError,
         }
     }
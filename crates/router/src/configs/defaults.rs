@@ -15,15 +15,12 @@ impl Default for super::settings::Database {
     fn default() -> Self {
         Self {
             username: String::new(),
-            #[cfg(not(feature = "kms"))]
             password: String::new(),
             host: "localhost".into(),
             port: 5432,
             dbname: String::new(),
             pool_size: 5,
             connection_timeout: 10,
-            #[cfg(feature = "kms")]
-            kms_encrypted_password: String::new(),
         }
     }
 }
@@ -31,15 +28,8 @@ impl Default for super::settings::Database {
 impl Default for super::settings::Secrets {
     fn default() -> Self {
         Self {
-            #[cfg(not(feature = "kms"))]
             jwt_secret: "secret".into(),
-            #[cfg(not(feature = "kms"))]
             admin_api_key: "test_admin".into(),
-
-            #[cfg(feature = "kms")]
-            kms_encrypted_jwt_secret: "".into(),
-            #[cfg(feature = "kms")]
-            kms_encrypted_admin_api_key: "".into(),
         }
     }
 }
@@ -85,6 +75,12 @@ impl Default for super::settings::Refund {
     }
 }
 
+impl Default for super::settings::PaymentRetry {
+    fn default() -> Self {
+        Self { max_attempts: 5 }
+    }
+}
+
 impl Default for super::settings::EphemeralConfig {
     fn default() -> Self {
         Self { validity: 1 }
@@ -111,6 +107,7 @@ impl Default for super::settings::ProducerSettings {
             lock_key: "PRODUCER_LOCKING_KEY".into(),
             lock_ttl: 160,
             batch_size: 200,
+            intent_expiry_batch_size: 50,
         }
     }
 }
@@ -124,6 +121,64 @@ impl Default for super::settings::ConsumerSettings {
     }
 }
 
+impl Default for super::settings::ApiKeys {
+    fn default() -> Self {
+        Self {
+            hash_key: "".into(),
+            hash_key_id: "1".into(),
+            previous_hash_keys: vec![],
+        }
+    }
+}
+
+impl Default for super::settings::PiiEncryptionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            master_key: "".into(),
+            backfill_batch_size: 500,
+        }
+    }
+}
+
+impl Default for super::settings::ConnectorClientSettings {
+    fn default() -> Self {
+        Self {
+            request_timeout: crate::consts::REQUEST_TIME_OUT,
+            connect_timeout: 10,
+            pool_max_idle_per_host: 32,
+            pool_idle_timeout: 90,
+            connect_retries: 1,
+            circuit_breaker: super::settings::CircuitBreakerSettings::default(),
+        }
+    }
+}
+
+impl Default for super::settings::CircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_rate_threshold: 50,
+            minimum_requests: 20,
+            window: 60,
+            open_duration: 30,
+            half_open_probes: 3,
+        }
+    }
+}
+
+impl Default for super::settings::HealthCheckSettings {
+    fn default() -> Self {
+        Self {
+            check_timeout: 2000,
+            check_scheduler: false,
+            max_heartbeat_age: 60,
+            check_drainer: false,
+            max_drainer_stream_length: 10000,
+        }
+    }
+}
+
 #[cfg(feature = "kv_store")]
 impl Default for super::settings::DrainerSettings {
     fn default() -> Self {
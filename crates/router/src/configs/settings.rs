@@ -6,11 +6,11 @@ use std::{
 
 use common_utils::ext_traits::ConfigExt;
 use config::{Environment, File};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::SecretsManagementConfig;
 use redis_interface::RedisSettings;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use serde::{de::Error, Deserialize, Deserializer};
+use time::PrimitiveDateTime;
 
 use crate::{
     core::errors::{ApplicationError, ApplicationResult},
@@ -51,6 +51,7 @@ pub struct Settings {
     pub locker: Locker,
     pub connectors: Connectors,
     pub refund: Refund,
+    pub payment_retry: PaymentRetry,
     pub eph_key: EphemeralConfig,
     pub scheduler: Option<SchedulerSettings>,
     #[cfg(feature = "kv_store")]
@@ -60,11 +61,15 @@ pub struct Settings {
     pub pm_filters: ConnectorFilters,
     pub bank_config: BankRedirectConfig,
     pub api_keys: ApiKeys,
-    #[cfg(feature = "kms")]
-    pub kms: kms::KmsConfig,
+    pub secrets_management: SecretsManagementConfig,
     #[cfg(feature = "s3")]
     pub file_upload_config: FileUploadConfig,
     pub tokenization: TokenizationConfig,
+    pub fx: FxSettings,
+    pub network_tokenization: NetworkTokenizationSettings,
+    pub pii_encryption: PiiEncryptionSettings,
+    pub health_check: HealthCheckSettings,
+    pub connector_audit_log: ConnectorAuditLogConfig,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -185,15 +190,10 @@ where
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct Secrets {
-    #[cfg(not(feature = "kms"))]
+    #[serde(alias = "kms_encrypted_jwt_secret")]
     pub jwt_secret: String,
-    #[cfg(not(feature = "kms"))]
+    #[serde(alias = "kms_encrypted_admin_api_key")]
     pub admin_api_key: String,
-
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_jwt_secret: String,
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_admin_api_key: String,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -221,6 +221,13 @@ pub struct Refund {
     pub max_age: i64,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct PaymentRetry {
+    /// Maximum number of attempts of a payment, including the first one
+    pub max_attempts: i16,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct EphemeralConfig {
@@ -238,6 +245,13 @@ pub struct Jwekey {
     pub locker_decryption_key2: String,
     pub vault_encryption_key: String,
     pub vault_private_key: String,
+    /// Identifier of the locker key pair used for new ciphertexts, one of
+    /// `locker_key_identifier1` and `locker_key_identifier2`. Defaults to
+    /// `locker_key_identifier1` when empty.
+    pub active_locker_key_identifier: String,
+    /// Ciphertexts under the inactive locker key pair are accepted until this time
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub locker_key_grace_period_ends_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -262,15 +276,13 @@ pub struct Server {
 #[serde(default)]
 pub struct Database {
     pub username: String,
-    #[cfg(not(feature = "kms"))]
+    #[serde(alias = "kms_encrypted_password")]
     pub password: String,
     pub host: String,
     pub port: u16,
     pub dbname: String,
     pub pool_size: u32,
     pub connection_timeout: u64,
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_password: String,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -308,6 +320,7 @@ pub struct Connectors {
     pub payu: ConnectorParams,
     pub rapyd: ConnectorParams,
     pub shift4: ConnectorParams,
+    pub simulator: ConnectorParams,
     pub stripe: ConnectorParamsWithFileUploadUrl,
     pub worldline: ConnectorParams,
     pub worldpay: ConnectorParams,
@@ -315,6 +328,87 @@ pub struct Connectors {
 
     // Keep this field separate from the remaining fields
     pub supported: SupportedConnectors,
+    /// HTTP client settings used for every connector, unless overridden in `client_overrides`
+    pub client: ConnectorClientSettings,
+    /// Overrides of the HTTP client settings, keyed by connector name
+    pub client_overrides: HashMap<String, ConnectorClientOverrides>,
+}
+
+impl Connectors {
+    /// HTTP client settings of the given connector, with its overrides applied.
+    pub fn get_client_settings(&self, connector: &str) -> ConnectorClientSettings {
+        let defaults = self.client.clone();
+        match self.client_overrides.get(connector) {
+            Some(overrides) => ConnectorClientSettings {
+                request_timeout: overrides
+                    .request_timeout
+                    .unwrap_or(defaults.request_timeout),
+                connect_timeout: overrides
+                    .connect_timeout
+                    .unwrap_or(defaults.connect_timeout),
+                pool_max_idle_per_host: overrides
+                    .pool_max_idle_per_host
+                    .unwrap_or(defaults.pool_max_idle_per_host),
+                pool_idle_timeout: overrides
+                    .pool_idle_timeout
+                    .unwrap_or(defaults.pool_idle_timeout),
+                connect_retries: overrides
+                    .connect_retries
+                    .unwrap_or(defaults.connect_retries),
+                circuit_breaker: overrides
+                    .circuit_breaker
+                    .clone()
+                    .unwrap_or(defaults.circuit_breaker),
+            },
+            None => defaults,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorClientSettings {
+    /// Time (in seconds) within which the connector must respond to a request
+    pub request_timeout: u64,
+    /// Time (in seconds) within which a connection to the connector must be established
+    pub connect_timeout: u64,
+    /// Maximum number of idle connections kept open to the connector
+    pub pool_max_idle_per_host: usize,
+    /// Time (in seconds) after which an idle connection to the connector is closed
+    pub pool_idle_timeout: u64,
+    /// Number of times a request is sent again when no connection could be established, which
+    /// guarantees that the connector never received it
+    pub connect_retries: u8,
+    pub circuit_breaker: CircuitBreakerSettings,
+}
+
+/// Overrides of [`ConnectorClientSettings`] for a single connector, unset fields take the value
+/// configured for every connector.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ConnectorClientOverrides {
+    pub request_timeout: Option<u64>,
+    pub connect_timeout: Option<u64>,
+    pub pool_max_idle_per_host: Option<usize>,
+    pub pool_idle_timeout: Option<u64>,
+    pub connect_retries: Option<u8>,
+    pub circuit_breaker: Option<CircuitBreakerSettings>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct CircuitBreakerSettings {
+    pub enabled: bool,
+    /// Percentage of failed requests within a window beyond which the circuit is opened
+    pub failure_rate_threshold: u32,
+    /// Number of requests within a window below which the circuit is never opened
+    pub minimum_requests: u32,
+    /// Duration (in seconds) of the window over which the failure rate is computed
+    pub window: u64,
+    /// Time (in seconds) for which requests fail fast once the circuit is opened
+    pub open_duration: u64,
+    /// Number of successful probe requests needed to close a half-open circuit
+    pub half_open_probes: u32,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -356,6 +450,8 @@ pub struct ProducerSettings {
     pub lock_key: String,
     pub lock_ttl: i64,
     pub batch_size: usize,
+    /// Maximum number of expired payment intents cancelled in a single run of the producer
+    pub intent_expiry_batch_size: i64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -382,18 +478,137 @@ pub struct WebhooksSettings {
     pub outgoing_enabled: bool,
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct ApiKeys {
-    /// Base64-encoded (KMS encrypted) ciphertext of the key used for calculating hashes of API
-    /// keys
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_hash_key: String,
-
     /// Hex-encoded 32-byte long (64 characters long when hex-encoded) key used for calculating
-    /// hashes of API keys
-    #[cfg(not(feature = "kms"))]
+    /// hashes of API keys, as stored in the configured secrets manager
+    #[serde(alias = "kms_encrypted_hash_key")]
+    pub hash_key: String,
+
+    /// Identifier of the current hash key, stored along with the hash of every API key
+    pub hash_key_id: String,
+
+    /// Hash keys which have been rotated out, but are still accepted until their grace period
+    /// ends. API keys hashed with one of these keys are rehashed with the current key on use.
+    pub previous_hash_keys: Vec<PreviousApiKeyHashKey>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PreviousApiKeyHashKey {
+    pub key_id: String,
+
+    #[serde(alias = "kms_encrypted_hash_key")]
     pub hash_key: String,
+
+    /// API keys hashed with this key are rejected after this time
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub accepted_until: PrimitiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct PiiEncryptionSettings {
+    /// Whether PII columns are encrypted with per-merchant data keys when written. Encrypted
+    /// values are decrypted when read regardless of this setting.
+    pub enabled: bool,
+
+    /// Hex-encoded 32-byte long (64 characters long when hex-encoded) master key used for
+    /// encrypting the data keys of merchants, as stored in the configured secrets manager
+    #[serde(alias = "kms_encrypted_master_key")]
+    pub master_key: String,
+
+    /// Number of rows of a table encrypted at a time by the backfill job
+    pub backfill_batch_size: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct HealthCheckSettings {
+    /// Time (in milliseconds) within which each dependency must respond to be considered healthy
+    pub check_timeout: u64,
+    /// Whether the heartbeats of the scheduler producer and consumer are checked
+    pub check_scheduler: bool,
+    /// Age (in seconds) beyond which a scheduler heartbeat is considered stale
+    pub max_heartbeat_age: i64,
+    /// Whether the length of the drainer streams is checked
+    pub check_drainer: bool,
+    /// Number of pending entries beyond which a drainer stream is considered lagging
+    pub max_drainer_stream_length: usize,
+}
+
+/// Destination of the audit events recording every request sent to a connector, along with the
+/// response received.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(tag = "sink", rename_all = "snake_case")]
+pub enum ConnectorAuditLogConfig {
+    #[default]
+    Disabled,
+    /// Events are stored in the `connector_audit_log` table, where they can be queried by payment
+    Postgres,
+    /// Events are appended to the file, one JSON object per line
+    File { path: PathBuf },
+    /// Events are appended to the Redis stream, for consumption by an external log pipeline
+    RedisStream { stream: String },
+}
+
+impl ConnectorAuditLogConfig {
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Self::Disabled)
+    }
+
+    /// Whether the events can be queried through the admin API
+    pub fn is_queryable(&self) -> bool {
+        matches!(self, Self::Postgres)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct FxSettings {
+    /// Exchange rates keyed by `<from>_<to>` currency codes (for example, `usd_eur`), each
+    /// specifying how many units of the target currency one unit of the source currency buys.
+    /// Rates are written as strings so that they are parsed without going through a float.
+    #[serde(deserialize_with = "fx_rates_deser")]
+    pub rates: HashMap<String, crate::core::fx::Rate>,
+}
+
+fn fx_rates_deser<'a, D>(
+    deserializer: D,
+) -> Result<HashMap<String, crate::core::fx::Rate>, D::Error>
+where
+    D: Deserializer<'a>,
+{
+    let value = <HashMap<String, String>>::deserialize(deserializer)?;
+    value
+        .into_iter()
+        .map(|(currency_pair, rate)| {
+            let rate = crate::core::fx::Rate::from_str(&rate).map_err(|_| {
+                D::Error::custom(format!(
+                    "FX rate for `{currency_pair}` must be a positive decimal number"
+                ))
+            })?;
+            Ok((currency_pair, rate))
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct NetworkTokenizationSettings {
+    /// Whether cards saved for future usage are provisioned with network tokens
+    pub enabled: bool,
+    /// The token service provider used to provision network tokens
+    pub provider: NetworkTokenProviderKind,
+    /// Secret used to verify the signature of token lifecycle notifications
+    pub notification_secret: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkTokenProviderKind {
+    #[default]
+    Mock,
 }
 
 #[cfg(feature = "s3")]
@@ -439,12 +654,18 @@ impl Settings {
                     .with_list_parse_key("connectors.supported.wallets"),
             )
             .build()?;
+        let raw_config = config.clone().try_deserialize::<serde_json::Value>()?;
 
-        serde_path_to_error::deserialize(config).map_err(|error| {
+        let mut settings: Self = serde_path_to_error::deserialize(config).map_err(|error| {
             logger::error!(%error, "Unable to deserialize application configuration");
             eprintln!("Unable to deserialize application configuration: {error}");
             ApplicationError::from(error.into_inner())
-        })
+        })?;
+        settings
+            .secrets_management
+            .record_legacy_kms_encrypted_keys(&raw_config);
+
+        Ok(settings)
     }
 
     pub fn validate(&self) -> ApplicationResult<()> {
@@ -471,6 +692,7 @@ impl Settings {
         }
         self.secrets.validate()?;
         self.locker.validate()?;
+        self.jwekey.validate()?;
         self.connectors.validate()?;
 
         self.scheduler
@@ -480,8 +702,12 @@ impl Settings {
         #[cfg(feature = "kv_store")]
         self.drainer.validate()?;
         self.api_keys.validate()?;
-        #[cfg(feature = "kms")]
-        self.kms
+        self.fx.validate()?;
+        self.network_tokenization.validate()?;
+        self.pii_encryption.validate()?;
+        self.health_check.validate()?;
+        self.connector_audit_log.validate()?;
+        self.secrets_management
             .validate()
             .map_err(|error| ApplicationError::InvalidConfigurationValueError(error.into()))?;
         #[cfg(feature = "s3")]
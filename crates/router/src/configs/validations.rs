@@ -6,38 +6,17 @@ impl super::settings::Secrets {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         use common_utils::fp_utils::when;
 
-        #[cfg(not(feature = "kms"))]
-        {
-            when(self.jwt_secret.is_default_or_empty(), || {
-                Err(ApplicationError::InvalidConfigurationValueError(
-                    "JWT secret must not be empty".into(),
-                ))
-            })?;
-
-            when(self.admin_api_key.is_default_or_empty(), || {
-                Err(ApplicationError::InvalidConfigurationValueError(
-                    "admin API key must not be empty".into(),
-                ))
-            })
-        }
-
-        #[cfg(feature = "kms")]
-        {
-            when(self.kms_encrypted_jwt_secret.is_default_or_empty(), || {
-                Err(ApplicationError::InvalidConfigurationValueError(
-                    "KMS encrypted JWT secret must not be empty".into(),
-                ))
-            })?;
+        when(self.jwt_secret.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "JWT secret must not be empty".into(),
+            ))
+        })?;
 
-            when(
-                self.kms_encrypted_admin_api_key.is_default_or_empty(),
-                || {
-                    Err(ApplicationError::InvalidConfigurationValueError(
-                        "KMS encrypted admin API key must not be empty".into(),
-                    ))
-                },
-            )
-        }
+        when(self.admin_api_key.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "admin API key must not be empty".into(),
+            ))
+        })
     }
 }
 
@@ -62,6 +41,23 @@ impl super::settings::Locker {
     }
 }
 
+impl super::settings::Jwekey {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        common_utils::fp_utils::when(
+            !self.active_locker_key_identifier.is_empty()
+                && self.active_locker_key_identifier != self.locker_key_identifier1
+                && self.active_locker_key_identifier != self.locker_key_identifier2,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "active locker key identifier must be one of the configured locker key \
+                     identifiers"
+                        .into(),
+                ))
+            },
+        )
+    }
+}
+
 impl super::settings::Server {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         common_utils::fp_utils::when(self.host.is_default_or_empty(), || {
@@ -94,23 +90,11 @@ impl super::settings::Database {
             ))
         })?;
 
-        #[cfg(not(feature = "kms"))]
-        {
-            when(self.password.is_default_or_empty(), || {
-                Err(ApplicationError::InvalidConfigurationValueError(
-                    "database user password must not be empty".into(),
-                ))
-            })
-        }
-
-        #[cfg(feature = "kms")]
-        {
-            when(self.kms_encrypted_password.is_default_or_empty(), || {
-                Err(ApplicationError::InvalidConfigurationValueError(
-                    "database KMS encrypted password must not be empty".into(),
-                ))
-            })
-        }
+        when(self.password.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "database user password must not be empty".into(),
+            ))
+        })
     }
 }
 
@@ -140,11 +124,52 @@ impl super::settings::Connectors {
         self.worldpay.validate()?;
 
         self.supported.validate()?;
+        self.client.validate()?;
+        self.client_overrides
+            .values()
+            .filter_map(|overrides| overrides.circuit_breaker.as_ref())
+            .try_for_each(|circuit_breaker| circuit_breaker.validate())?;
 
         Ok(())
     }
 }
 
+impl super::settings::ConnectorClientSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.request_timeout == 0 || self.connect_timeout == 0,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "connector request and connect timeouts must be greater than 0".into(),
+                ))
+            },
+        )?;
+
+        self.circuit_breaker.validate()
+    }
+}
+
+impl super::settings::CircuitBreakerSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(!(1..=100).contains(&self.failure_rate_threshold), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker failure rate threshold must be a percentage between 1 and 100"
+                    .into(),
+            ))
+        })?;
+
+        when(self.half_open_probes == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker half-open probes must be greater than 0".into(),
+            ))
+        })
+    }
+}
+
 impl super::settings::ConnectorParams {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         common_utils::fp_utils::when(self.base_url.is_default_or_empty(), || {
@@ -198,6 +223,12 @@ impl super::settings::ProducerSettings {
             Err(ApplicationError::InvalidConfigurationValueError(
                 "producer lock key must not be empty".into(),
             ))
+        })?;
+
+        common_utils::fp_utils::when(self.intent_expiry_batch_size <= 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "producer intent expiry batch size must be positive".into(),
+            ))
         })
     }
 }
@@ -236,18 +267,135 @@ impl super::settings::ApiKeys {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         use common_utils::fp_utils::when;
 
-        #[cfg(feature = "kms")]
-        return when(self.kms_encrypted_hash_key.is_default_or_empty(), || {
+        when(self.hash_key_id.is_default_or_empty(), || {
             Err(ApplicationError::InvalidConfigurationValueError(
-                "API key hashing key must not be empty when KMS feature is enabled".into(),
+                "API key hashing key ID must not be empty".into(),
             ))
-        });
+        })?;
 
-        #[cfg(not(feature = "kms"))]
-        when(self.hash_key.is_empty(), || {
+        let mut key_ids = std::collections::HashSet::from([self.hash_key_id.as_str()]);
+        self.previous_hash_keys
+            .iter()
+            .try_for_each(|previous_key| {
+                when(!key_ids.insert(previous_key.key_id.as_str()), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "API key hashing key ID `{}` must be unique across the current and \
+                         previous hash keys",
+                        previous_key.key_id
+                    )))
+                })?;
+
+                when(previous_key.hash_key.is_default_or_empty(), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "previous API key hashing key `{}` must not be empty",
+                        previous_key.key_id
+                    )))
+                })
+            })?;
+
+        when(self.hash_key.is_default_or_empty(), || {
             Err(ApplicationError::InvalidConfigurationValueError(
                 "API key hashing key must not be empty".into(),
             ))
         })
     }
 }
+
+impl super::settings::PiiEncryptionSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.backfill_batch_size == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "PII encryption backfill batch size must be greater than zero".into(),
+            ))
+        })?;
+
+        when(
+            self.enabled && self.master_key.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "PII encryption master key must not be empty when PII encryption is enabled"
+                        .into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::HealthCheckSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.check_timeout == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "health check timeout must be greater than zero".into(),
+            ))
+        })?;
+
+        when(self.max_heartbeat_age <= 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "maximum scheduler heartbeat age must be greater than zero".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::ConnectorAuditLogConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        match self {
+            Self::File { path } => when(path.as_os_str().is_empty(), || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "connector audit log file path must not be empty".into(),
+                ))
+            }),
+            Self::RedisStream { stream } => when(stream.is_default_or_empty(), || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "connector audit log stream name must not be empty".into(),
+                ))
+            }),
+            Self::Disabled | Self::Postgres => Ok(()),
+        }
+    }
+}
+
+impl super::settings::FxSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use std::str::FromStr;
+
+        use storage_models::enums::Currency;
+
+        self.rates.keys().try_for_each(|currency_pair| {
+            let is_valid_pair = currency_pair
+                .split_once('_')
+                .map(|(from, to)| {
+                    Currency::from_str(&from.to_uppercase()).is_ok()
+                        && Currency::from_str(&to.to_uppercase()).is_ok()
+                })
+                .unwrap_or(false);
+
+            common_utils::fp_utils::when(!is_valid_pair, || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "FX rate key `{currency_pair}` must be of the form `<from>_<to>`"
+                )))
+            })
+        })
+    }
+}
+
+impl super::settings::NetworkTokenizationSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.enabled && self.notification_secret.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "network token notification secret must not be empty".into(),
+                ))
+            },
+        )
+    }
+}
@@ -2,8 +2,7 @@ use async_bb8_diesel::{AsyncConnection, ConnectionError};
 use bb8::{CustomizeConnection, PooledConnection};
 use diesel::PgConnection;
 use error_stack::{IntoReport, ResultExt};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::SecretsManagementConfig;
 
 use crate::{configs::settings::Database, errors};
 
@@ -42,17 +41,12 @@ pub async fn redis_connection(
 pub async fn diesel_make_pg_pool(
     database: &Database,
     test_transaction: bool,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> PgPool {
-    #[cfg(feature = "kms")]
-    let password = kms::get_kms_client(kms_config)
+    let password = secrets_management
+        .get_secret(&database.password)
         .await
-        .decrypt(&database.kms_encrypted_password)
-        .await
-        .expect("Failed to KMS decrypt database password");
-
-    #[cfg(not(feature = "kms"))]
-    let password = &database.password;
+        .expect("Failed to retrieve database password");
 
     let database_url = format!(
         "postgres://{}:{}@{}:{}/{}",
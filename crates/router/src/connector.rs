@@ -22,6 +22,7 @@ pub mod paypal;
 pub mod payu;
 pub mod rapyd;
 pub mod shift4;
+pub mod simulator;
 pub mod stripe;
 pub mod trustpay;
 pub mod utils;
@@ -36,6 +37,6 @@ pub use self::{
     coinbase::Coinbase, cybersource::Cybersource, dlocal::Dlocal, fiserv::Fiserv, forte::Forte,
     globalpay::Globalpay, klarna::Klarna, mollie::Mollie, multisafepay::Multisafepay,
     nexinets::Nexinets, nuvei::Nuvei, opennode::Opennode, payeezy::Payeezy, paypal::Paypal,
-    payu::Payu, rapyd::Rapyd, shift4::Shift4, stripe::Stripe, trustpay::Trustpay,
-    worldline::Worldline, worldpay::Worldpay,
+    payu::Payu, rapyd::Rapyd, shift4::Shift4, simulator::Simulator, stripe::Stripe,
+    trustpay::Trustpay, worldline::Worldline, worldpay::Worldpay,
 };
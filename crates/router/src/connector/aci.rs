@@ -7,13 +7,15 @@ use transformers as aci;
 
 use crate::{
     configs::settings,
+    connector::utils as conn_utils,
     core::errors::{self, CustomResult},
+    db::StorageInterface,
     headers, services,
     types::{
         self,
         api::{self, ConnectorCommon},
     },
-    utils::{self, BytesExt},
+    utils::{self, crypto, BytesExt, ValueExt},
 };
 
 #[derive(Debug, Clone)]
@@ -524,26 +526,123 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
 {
 }
 
+fn get_webhook_payment(
+    request: &api::IncomingWebhookRequestDetails<'_>,
+) -> CustomResult<aci::AciWebhookPayment, errors::ParsingError> {
+    request
+        .body
+        .parse_struct::<aci::AciWebhookBody>("AciWebhookBody")?
+        .payload
+        .parse_value("AciWebhookPayment")
+}
+
+impl api::ConnectorCapability for Aci {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Aci,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Card,
+            )],
+        }
+    }
+}
+
+// ACI encrypts notifications with AES-256-GCM using the merchant's webhook secret. A body that
+// decrypts successfully is authenticated by the GCM tag, so no separate signature is verified.
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Aci {
+    fn get_webhook_body_decoding_algorithm(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Box<dyn crypto::DecodeMessage + Send>, errors::ConnectorError> {
+        let initialization_vector =
+            conn_utils::get_header_key_value("X-Initialization-Vector", request.headers)?;
+        let nonce = hex::decode(initialization_vector)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+        Ok(Box::new(crypto::GcmAes256::new(nonce)))
+    }
+
+    async fn get_webhook_body_decoding_merchant_secret(
+        &self,
+        db: &dyn StorageInterface,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        let key = format!("whsec_verification_{}_{}", self.id(), merchant_id);
+        let secret = db
+            .get_key(&key)
+            .await
+            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)?;
+        hex::decode(secret)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)
+    }
+
+    fn get_webhook_body_decoding_message(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        let auth_tag = conn_utils::get_header_key_value("X-Authentication-Tag", request.headers)?;
+        let mut message = hex::decode(request.body)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+        message.extend(
+            hex::decode(auth_tag)
+                .into_report()
+                .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?,
+        );
+        Ok(message)
+    }
+
     fn get_webhook_object_reference_id(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api_models::webhooks::ObjectReferenceId, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let payment = get_webhook_payment(request)
+            .change_context(errors::ConnectorError::WebhookReferenceIdNotFound)?;
+        Ok(if payment.is_refund() {
+            api_models::webhooks::ObjectReferenceId::RefundId(
+                api_models::webhooks::RefundIdType::ConnectorRefundId(payment.id),
+            )
+        } else {
+            api_models::webhooks::ObjectReferenceId::PaymentId(
+                api_models::payments::PaymentIdType::ConnectorTransactionId(payment.id),
+            )
+        })
     }
 
     fn get_webhook_event_type(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api::IncomingWebhookEvent, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: aci::AciWebhookBody = request
+            .body
+            .parse_struct("AciWebhookBody")
+            .change_context(errors::ConnectorError::WebhookEventTypeNotFound)?;
+        if details.webhook_type != aci::AciWebhookType::Payment {
+            return Ok(api::IncomingWebhookEvent::EventNotSupported);
+        }
+        let payment = get_webhook_payment(request)
+            .change_context(errors::ConnectorError::WebhookEventTypeNotFound)?;
+        api::IncomingWebhookEvent::try_from(&payment)
+            .change_context(errors::ConnectorError::WebhookEventTypeNotFound)
     }
 
     fn get_webhook_resource_object(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<serde_json::Value, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        // The payload has the same shape as the response of the payment status API
+        let details: aci::AciWebhookBody = request
+            .body
+            .parse_struct("AciWebhookBody")
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)?;
+        Ok(details.payload)
     }
 }
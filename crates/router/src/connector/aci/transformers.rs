@@ -80,7 +80,7 @@ pub struct BankDetails {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum AciPaymentType {
     #[serde(rename = "PA")]
     Preauthorization,
@@ -320,3 +320,59 @@ impl<F> TryFrom<types::RefundsResponseRouterData<F, AciRefundResponse>>
         })
     }
 }
+
+// WEBHOOKS :
+// Notifications are delivered encrypted, the types below describe the decrypted body
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum AciWebhookType {
+    Payment,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AciWebhookBody {
+    #[serde(rename = "type")]
+    pub webhook_type: AciWebhookType,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AciWebhookPayment {
+    pub id: String,
+    pub payment_type: AciPaymentType,
+    pub result: ResultCode,
+}
+
+impl AciWebhookPayment {
+    pub fn is_refund(&self) -> bool {
+        self.payment_type == AciPaymentType::Refund
+    }
+}
+
+impl TryFrom<&AciWebhookPayment> for api::IncomingWebhookEvent {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &AciWebhookPayment) -> Result<Self, Self::Error> {
+        let status = AciPaymentStatus::from_str(&item.result.code)?;
+        Ok(match (&item.payment_type, status) {
+            (AciPaymentType::Refund, AciPaymentStatus::Succeeded) => Self::RefundSuccess,
+            (AciPaymentType::Refund, AciPaymentStatus::Failed) => Self::RefundFailure,
+            (
+                AciPaymentType::Preauthorization | AciPaymentType::Debit | AciPaymentType::Capture,
+                AciPaymentStatus::Succeeded,
+            ) => Self::PaymentIntentSuccess,
+            (
+                AciPaymentType::Preauthorization | AciPaymentType::Debit | AciPaymentType::Capture,
+                AciPaymentStatus::Failed,
+            ) => Self::PaymentIntentFailure,
+            (
+                AciPaymentType::Preauthorization | AciPaymentType::Debit | AciPaymentType::Capture,
+                AciPaymentStatus::Pending,
+            ) => Self::PaymentIntentProcessing,
+            _ => Self::EventNotSupported,
+        })
+    }
+}
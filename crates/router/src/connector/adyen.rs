@@ -31,6 +31,10 @@ impl ConnectorCommon for Adyen {
         "adyen"
     }
 
+    fn get_error_category(&self, error_code: &str) -> api::enums::ErrorCategory {
+        adyen::get_error_category(error_code)
+    }
+
     fn get_auth_header(
         &self,
         auth_type: &types::ConnectorAuthType,
@@ -651,6 +655,60 @@ fn get_webhook_object_from_body(
     Ok(item_object.notification_request_item)
 }
 
+impl api::ConnectorCapability for Adyen {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Adyen,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::MultipleCaptures,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::Mandates,
+                api::ConnectorFlow::ThreeDs,
+                api::ConnectorFlow::NetworkTokens,
+            ],
+            payment_methods: vec![
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Card),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Wallet)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::AliPay,
+                        api::enums::PaymentMethodType::ApplePay,
+                        api::enums::PaymentMethodType::GooglePay,
+                        api::enums::PaymentMethodType::MbWay,
+                        api::enums::PaymentMethodType::MobilePay,
+                        api::enums::PaymentMethodType::Paypal,
+                        api::enums::PaymentMethodType::WeChatPay,
+                    ]),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::PayLater)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::Affirm,
+                        api::enums::PaymentMethodType::AfterpayClearpay,
+                        api::enums::PaymentMethodType::Klarna,
+                        api::enums::PaymentMethodType::PayBright,
+                        api::enums::PaymentMethodType::Walley,
+                    ]),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::BankRedirect)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::BancontactCard,
+                        api::enums::PaymentMethodType::Blik,
+                        api::enums::PaymentMethodType::Eps,
+                        api::enums::PaymentMethodType::Giropay,
+                        api::enums::PaymentMethodType::Ideal,
+                        api::enums::PaymentMethodType::OnlineBankingCzechRepublic,
+                        api::enums::PaymentMethodType::OnlineBankingFinland,
+                        api::enums::PaymentMethodType::OnlineBankingPoland,
+                        api::enums::PaymentMethodType::OnlineBankingSlovakia,
+                        api::enums::PaymentMethodType::Sofort,
+                        api::enums::PaymentMethodType::Trustly,
+                    ]),
+            ],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Adyen {
     fn get_webhook_source_verification_algorithm(
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use api_models::{enums::DisputeStage, webhooks::IncomingWebhookEvent};
 use masking::PeekInterface;
 use reqwest::Url;
@@ -46,8 +48,13 @@ pub enum AuthType {
 #[derive(Default, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AdditionalData {
-    authorisation_type: AuthType,
-    manual_capture: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    authorisation_type: Option<AuthType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    manual_capture: Option<bool>,
+    /// Level 2 / Level 3 data, sent as flat `enhancedSchemeData.*` keys
+    #[serde(flatten)]
+    enhanced_scheme_data: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -100,6 +107,17 @@ pub struct AdyenPaymentRequest<'a> {
     delivery_address: Option<Address>,
     country_code: Option<api_enums::CountryCode>,
     line_items: Option<Vec<LineItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mpi_data: Option<AdyenMpiData>,
+}
+
+/// Authentication data sent along with a network token
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenMpiData {
+    directory_response: String,
+    authentication_response: String,
+    token_authentication_verification_value: Secret<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -597,6 +615,8 @@ pub enum PaymentType {
     OnlineBankingPoland,
     #[serde(rename = "onlineBanking_SK")]
     OnlineBankingSlovakia,
+    #[serde(rename = "networkToken")]
+    NetworkToken,
     PayBright,
     Paypal,
     Scheme,
@@ -753,14 +773,81 @@ fn get_browser_info(item: &types::PaymentsAuthorizeRouterData) -> Option<AdyenBr
     }
 }
 
-fn get_additional_data(item: &types::PaymentsAuthorizeRouterData) -> Option<AdditionalData> {
-    match item.request.capture_method {
-        Some(storage_models::enums::CaptureMethod::Manual) => Some(AdditionalData {
-            authorisation_type: AuthType::PreAuth,
-            manual_capture: true,
+fn get_additional_data(
+    item: &types::PaymentsAuthorizeRouterData,
+) -> Result<Option<AdditionalData>, Error> {
+    let (authorisation_type, manual_capture) = match item.request.capture_method {
+        Some(storage_models::enums::CaptureMethod::Manual) => (Some(AuthType::PreAuth), Some(true)),
+        _ => (None, None),
+    };
+    let enhanced_scheme_data = item
+        .request
+        .purchase_details
+        .as_ref()
+        .map(get_enhanced_scheme_data)
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(
+        (manual_capture.is_some() || !enhanced_scheme_data.is_empty()).then_some(AdditionalData {
+            authorisation_type,
+            manual_capture,
+            enhanced_scheme_data,
         }),
-        _ => None,
+    )
+}
+
+fn get_enhanced_scheme_data(
+    purchase_details: &api_models::payments::PurchaseDetails,
+) -> Result<BTreeMap<String, String>, Error> {
+    let mut data = BTreeMap::new();
+    if let Some(customer_reference) = &purchase_details.customer_reference {
+        data.insert(
+            "enhancedSchemeData.customerReference".to_string(),
+            customer_reference.clone(),
+        );
+    }
+    if let Some(tax_amount) = purchase_details.tax_amount {
+        data.insert(
+            "enhancedSchemeData.totalTaxAmount".to_string(),
+            tax_amount.to_string(),
+        );
+    }
+    for (index, line_item) in purchase_details.line_items.iter().flatten().enumerate() {
+        // Adyen numbers the item detail lines starting from 1
+        let prefix = format!("enhancedSchemeData.itemDetailLine{}", index + 1);
+        let total_amount = line_item
+            .unit_price
+            .map(|unit_price| {
+                unit_price
+                    .checked_mul(i64::from(line_item.quantity))
+                    .and_then(|amount| amount.checked_sub(line_item.discount_amount.unwrap_or(0)))
+                    .ok_or(errors::ConnectorError::RequestEncodingFailed)
+            })
+            .transpose()?;
+        let fields = [
+            ("description", Some(line_item.product_name.clone())),
+            ("quantity", Some(line_item.quantity.to_string())),
+            (
+                "unitPrice",
+                line_item.unit_price.map(|amount| amount.to_string()),
+            ),
+            (
+                "discountAmount",
+                line_item.discount_amount.map(|amount| amount.to_string()),
+            ),
+            ("productCode", line_item.sku.clone()),
+            ("commodityCode", line_item.commodity_code.clone()),
+            ("unitOfMeasure", line_item.unit_of_measure.clone()),
+            ("totalAmount", total_amount.map(|amount| amount.to_string())),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                data.insert(format!("{prefix}.{field}"), value);
+            }
+        }
     }
+    Ok(data)
 }
 
 fn get_amount_data(item: &types::PaymentsAuthorizeRouterData) -> Amount {
@@ -1032,9 +1119,25 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::Card)> for AdyenPay
         let shopper_interaction = AdyenShopperInteraction::from(item);
         let recurring_processing_model = get_recurring_processing_model(item);
         let browser_info = get_browser_info(item);
-        let additional_data = get_additional_data(item);
+        let additional_data = get_additional_data(item)?;
         let return_url = item.request.get_return_url()?;
-        let payment_method = AdyenPaymentMethod::try_from(card_data)?;
+        let (payment_method, mpi_data) = match item.request.network_token_cryptogram.clone() {
+            Some(cryptogram) => (
+                AdyenPaymentMethod::AdyenCard(Box::new(AdyenCard {
+                    payment_type: PaymentType::NetworkToken,
+                    number: card_data.card_number.clone(),
+                    expiry_month: card_data.card_exp_month.clone(),
+                    expiry_year: card_data.card_exp_year.clone(),
+                    cvc: None,
+                })),
+                Some(AdyenMpiData {
+                    directory_response: "Y".to_string(),
+                    authentication_response: "Y".to_string(),
+                    token_authentication_verification_value: cryptogram,
+                }),
+            ),
+            None => (AdyenPaymentMethod::try_from(card_data)?, None),
+        };
         Ok(AdyenPaymentRequest {
             amount,
             merchant_account: auth_type.merchant_account,
@@ -1053,6 +1156,7 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::Card)> for AdyenPay
             delivery_address: None,
             country_code: None,
             line_items: None,
+            mpi_data,
         })
     }
 }
@@ -1076,7 +1180,7 @@ impl<'a>
         let shopper_interaction = AdyenShopperInteraction::from(item);
         let recurring_processing_model = get_recurring_processing_model(item);
         let browser_info = get_browser_info(item);
-        let additional_data = get_additional_data(item);
+        let additional_data = get_additional_data(item)?;
         let return_url = item.request.get_return_url()?;
         let payment_method = AdyenPaymentMethod::try_from(bank_redirect_data)?;
         let (shopper_locale, country) = get_sofort_extra_details(item);
@@ -1100,6 +1204,7 @@ impl<'a>
             delivery_address: None,
             country_code: country,
             line_items,
+            mpi_data: None,
         })
     }
 }
@@ -1138,7 +1243,7 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::WalletData)>
         let amount = get_amount_data(item);
         let auth_type = AdyenAuthType::try_from(&item.connector_auth_type)?;
         let browser_info = get_browser_info(item);
-        let additional_data = get_additional_data(item);
+        let additional_data = get_additional_data(item)?;
         let payment_method = AdyenPaymentMethod::try_from(wallet_data)?;
         let shopper_interaction = AdyenShopperInteraction::from(item);
         let recurring_processing_model = get_recurring_processing_model(item);
@@ -1161,6 +1266,7 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::WalletData)>
             delivery_address: None,
             country_code: None,
             line_items: None,
+            mpi_data: None,
         })
     }
 }
@@ -1176,7 +1282,7 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::PayLaterData)>
         let amount = get_amount_data(item);
         let auth_type = AdyenAuthType::try_from(&item.connector_auth_type)?;
         let browser_info = get_browser_info(item);
-        let additional_data = get_additional_data(item);
+        let additional_data = get_additional_data(item)?;
         let payment_method = AdyenPaymentMethod::try_from(paylater_data)?;
         let shopper_interaction = AdyenShopperInteraction::from(item);
         let recurring_processing_model = get_recurring_processing_model(item);
@@ -1206,6 +1312,7 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::PayLaterData)>
             delivery_address,
             country_code,
             line_items,
+            mpi_data: None,
         })
     }
 }
@@ -1686,3 +1793,35 @@ impl From<AdyenNotificationRequestItemWH> for AdyenResponse {
         }
     }
 }
+
+/// Maps the `refusalReasonCode` of a refused Adyen payment to a connector agnostic category.
+pub fn get_error_category(refusal_reason_code: &str) -> api_enums::ErrorCategory {
+    match refusal_reason_code {
+        // Refused, Referral, Declined Non Generic
+        "2" | "3" | "27" => api_enums::ErrorCategory::DoNotHonor,
+        // Acquirer Error
+        "4" => api_enums::ErrorCategory::ProcessingError,
+        // Blocked Card
+        "5" => api_enums::ErrorCategory::LostOrStolenCard,
+        "6" => api_enums::ErrorCategory::ExpiredCard,
+        // Invalid Amount
+        "7" => api_enums::ErrorCategory::InvalidRequest,
+        "8" => api_enums::ErrorCategory::InvalidCardNumber,
+        "9" => api_enums::ErrorCategory::IssuerUnavailable,
+        // Not supported, Restricted Card
+        "10" | "25" => api_enums::ErrorCategory::CardNotSupported,
+        // 3D Not Authenticated
+        "11" => api_enums::ErrorCategory::AuthenticationFailed,
+        // Not enough balance
+        "12" => api_enums::ErrorCategory::InsufficientFunds,
+        // Acquirer Fraud, FRAUD, FRAUD-CANCELLED, Issuer Suspected Fraud
+        "14" | "20" | "22" | "31" => api_enums::ErrorCategory::FraudSuspected,
+        // CVC Declined
+        "24" => api_enums::ErrorCategory::IncorrectCvc,
+        // Withdrawal amount exceeded, Withdrawal count exceeded
+        "28" | "29" => api_enums::ErrorCategory::LimitExceeded,
+        // Authentication required
+        "38" => api_enums::ErrorCategory::AuthenticationRequired,
+        _ => api_enums::ErrorCategory::Unknown,
+    }
+}
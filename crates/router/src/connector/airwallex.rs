@@ -887,6 +887,26 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Airwallex {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Airwallex,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+                api::ConnectorFlow::ThreeDs,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Card,
+            )],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Airwallex {
     fn get_webhook_source_verification_algorithm(
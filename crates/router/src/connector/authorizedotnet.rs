@@ -8,15 +8,17 @@ use transformers as authorizedotnet;
 
 use crate::{
     configs::settings,
+    connector::utils as conn_utils,
     consts,
     core::errors::{self, CustomResult},
+    db::StorageInterface,
     headers,
     services::{self, logger},
     types::{
         self,
         api::{self, ConnectorCommon},
     },
-    utils::{self, BytesExt},
+    utils::{self, crypto, BytesExt},
 };
 
 #[derive(Debug, Clone)]
@@ -27,6 +29,10 @@ impl ConnectorCommon for Authorizedotnet {
         "authorizedotnet"
     }
 
+    fn get_error_category(&self, error_code: &str) -> api::enums::ErrorCategory {
+        authorizedotnet::get_error_category(error_code)
+    }
+
     fn common_get_content_type(&self) -> &'static str {
         "application/json"
     }
@@ -567,27 +573,111 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
     }
 }
 
+impl api::ConnectorCapability for Authorizedotnet {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Authorizedotnet,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Card,
+            )],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Authorizedotnet {
-    fn get_webhook_object_reference_id(
+    fn get_webhook_source_verification_algorithm(
         &self,
         _request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Box<dyn crypto::VerifySignature + Send>, errors::ConnectorError> {
+        Ok(Box::new(crypto::HmacSha512))
+    }
+
+    fn get_webhook_source_verification_signature(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        let header = conn_utils::get_header_key_value("X-ANET-Signature", request.headers)?;
+        let signature = authorizedotnet::get_signature_from_header(header)
+            .ok_or(errors::ConnectorError::WebhookSignatureNotFound)
+            .into_report()?;
+        hex::decode(signature)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookSignatureNotFound)
+    }
+
+    fn get_webhook_source_verification_message(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+        _merchant_id: &str,
+        _secret: &[u8],
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        Ok(request.body.to_vec())
+    }
+
+    async fn get_webhook_source_verification_merchant_secret(
+        &self,
+        db: &dyn StorageInterface,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        let key = format!("whsec_verification_{}_{}", self.id(), merchant_id);
+        let secret = db
+            .get_key(&key)
+            .await
+            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)?;
+        Ok(secret)
+    }
+
+    fn get_webhook_object_reference_id(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api_models::webhooks::ObjectReferenceId, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: authorizedotnet::AuthorizedotnetWebhookObjectId = request
+            .body
+            .parse_struct("AuthorizedotnetWebhookObjectId")
+            .change_context(errors::ConnectorError::WebhookReferenceIdNotFound)?;
+        Ok(if details.event_type.is_refund_event() {
+            api_models::webhooks::ObjectReferenceId::RefundId(
+                api_models::webhooks::RefundIdType::ConnectorRefundId(details.payload.id),
+            )
+        } else {
+            api_models::webhooks::ObjectReferenceId::PaymentId(
+                api_models::payments::PaymentIdType::ConnectorTransactionId(details.payload.id),
+            )
+        })
     }
 
     fn get_webhook_event_type(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api::IncomingWebhookEvent, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: authorizedotnet::AuthorizedotnetWebhookEventType = request
+            .body
+            .parse_struct("AuthorizedotnetWebhookEventType")
+            .change_context(errors::ConnectorError::WebhookEventTypeNotFound)?;
+        Ok(api::IncomingWebhookEvent::from(details.event_type))
     }
 
     fn get_webhook_resource_object(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<serde_json::Value, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        // The notification only carries the transaction id, the event is translated into the
+        // transaction status that a sync would have returned
+        let details: authorizedotnet::AuthorizedotnetWebhookObjectId = request
+            .body
+            .parse_struct("AuthorizedotnetWebhookObjectId")
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)?;
+        serde_json::to_value(authorizedotnet::AuthorizedotnetSyncResponse::from(details))
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)
     }
 }
 
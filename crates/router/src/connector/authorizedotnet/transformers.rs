@@ -521,7 +521,7 @@ impl TryFrom<&types::PaymentsSyncRouterData> for AuthorizedotnetCreateSyncReques
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum SyncStatus {
     RefundSettledSuccessfully,
@@ -534,7 +534,7 @@ pub enum SyncStatus {
     CouldNotVoid,
     GeneralError,
 }
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncTransactionResponse {
     #[serde(rename = "transId")]
@@ -542,7 +542,7 @@ pub struct SyncTransactionResponse {
     transaction_status: SyncStatus,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AuthorizedotnetSyncResponse {
     transaction: SyncTransactionResponse,
 }
@@ -623,6 +623,101 @@ impl<F, Req>
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub enum AuthorizedotnetWebhookEvent {
+    #[serde(rename = "net.authorize.payment.authorization.created")]
+    AuthorizationCreated,
+    #[serde(rename = "net.authorize.payment.priorAuthCapture.created")]
+    PriorAuthCaptureCreated,
+    #[serde(rename = "net.authorize.payment.authcapture.created")]
+    AuthCapCreated,
+    #[serde(rename = "net.authorize.payment.capture.created")]
+    CaptureCreated,
+    #[serde(rename = "net.authorize.payment.void.created")]
+    VoidCreated,
+    #[serde(rename = "net.authorize.payment.refund.created")]
+    RefundCreated,
+    #[serde(rename = "net.authorize.payment.fraud.declined")]
+    FraudDeclined,
+    #[serde(other)]
+    Unknown,
+}
+
+impl AuthorizedotnetWebhookEvent {
+    pub fn is_refund_event(&self) -> bool {
+        matches!(self, Self::RefundCreated)
+    }
+}
+
+impl From<AuthorizedotnetWebhookEvent> for api::IncomingWebhookEvent {
+    fn from(event: AuthorizedotnetWebhookEvent) -> Self {
+        match event {
+            AuthorizedotnetWebhookEvent::AuthorizationCreated
+            | AuthorizedotnetWebhookEvent::PriorAuthCaptureCreated
+            | AuthorizedotnetWebhookEvent::AuthCapCreated
+            | AuthorizedotnetWebhookEvent::CaptureCreated => Self::PaymentIntentSuccess,
+            AuthorizedotnetWebhookEvent::FraudDeclined => Self::PaymentIntentFailure,
+            AuthorizedotnetWebhookEvent::RefundCreated => Self::RefundSuccess,
+            AuthorizedotnetWebhookEvent::VoidCreated | AuthorizedotnetWebhookEvent::Unknown => {
+                Self::EventNotSupported
+            }
+        }
+    }
+}
+
+impl From<AuthorizedotnetWebhookEvent> for SyncStatus {
+    fn from(event: AuthorizedotnetWebhookEvent) -> Self {
+        match event {
+            AuthorizedotnetWebhookEvent::AuthorizationCreated => Self::AuthorizedPendingCapture,
+            AuthorizedotnetWebhookEvent::PriorAuthCaptureCreated
+            | AuthorizedotnetWebhookEvent::AuthCapCreated
+            | AuthorizedotnetWebhookEvent::CaptureCreated => Self::CapturedPendingSettlement,
+            AuthorizedotnetWebhookEvent::VoidCreated => Self::Voided,
+            AuthorizedotnetWebhookEvent::RefundCreated => Self::RefundPendingSettlement,
+            AuthorizedotnetWebhookEvent::FraudDeclined => Self::Declined,
+            AuthorizedotnetWebhookEvent::Unknown => Self::GeneralError,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthorizedotnetWebhookPayload {
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizedotnetWebhookObjectId {
+    pub event_type: AuthorizedotnetWebhookEvent,
+    pub payload: AuthorizedotnetWebhookPayload,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizedotnetWebhookEventType {
+    pub event_type: AuthorizedotnetWebhookEvent,
+}
+
+impl From<AuthorizedotnetWebhookObjectId> for AuthorizedotnetSyncResponse {
+    fn from(item: AuthorizedotnetWebhookObjectId) -> Self {
+        Self {
+            transaction: SyncTransactionResponse {
+                transaction_id: item.payload.id,
+                transaction_status: SyncStatus::from(item.event_type),
+            },
+        }
+    }
+}
+
+/// The `X-ANET-Signature` header holds the upper case hex encoded signature prefixed with
+/// `sha512=`
+pub fn get_signature_from_header(header: &str) -> Option<&str> {
+    header
+        .split_once('=')
+        .filter(|(algorithm, _)| algorithm.eq_ignore_ascii_case("sha512"))
+        .map(|(_, signature)| signature)
+}
+
 #[derive(Debug, Default, Eq, PartialEq, Deserialize)]
 pub struct ErrorDetails {
     pub code: Option<String>,
@@ -644,3 +739,26 @@ fn construct_refund_payment_details(masked_number: String) -> PaymentDetails {
         card_code: None,
     })
 }
+
+/// Maps the response reason code of a declined Authorize.net transaction to a connector agnostic
+/// category.
+pub fn get_error_category(error_code: &str) -> api::enums::ErrorCategory {
+    match error_code {
+        // This transaction has been declined, Referral
+        "2" | "3" => api::enums::ErrorCategory::DoNotHonor,
+        // Pick up card
+        "4" => api::enums::ErrorCategory::LostOrStolenCard,
+        // A valid amount is required
+        "5" => api::enums::ErrorCategory::InvalidRequest,
+        // The credit card number is invalid
+        "6" | "37" => api::enums::ErrorCategory::InvalidCardNumber,
+        "8" => api::enums::ErrorCategory::ExpiredCard,
+        // Card code mismatch or invalid
+        "44" | "45" | "65" | "78" => api::enums::ErrorCategory::IncorrectCvc,
+        // Declined by the fraud detection suite
+        "250" | "251" | "254" => api::enums::ErrorCategory::FraudSuspected,
+        // An error occurred during processing, please try again
+        "19" | "20" | "21" | "22" | "23" | "57" => api::enums::ErrorCategory::ProcessingError,
+        _ => api::enums::ErrorCategory::Unknown,
+    }
+}
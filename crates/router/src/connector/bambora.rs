@@ -13,6 +13,7 @@ use crate::{
         errors::{self, CustomResult},
         payments,
     },
+    db::StorageInterface,
     headers, logger,
     services::{self, ConnectorIntegration},
     types::{
@@ -610,27 +611,76 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Bambora {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Bambora,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+                api::ConnectorFlow::ThreeDs,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Card,
+            )],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Bambora {
-    fn get_webhook_object_reference_id(
+    async fn verify_webhook_source(
         &self,
+        _db: &dyn StorageInterface,
         _request: &api::IncomingWebhookRequestDetails<'_>,
+        _merchant_id: &str,
+    ) -> CustomResult<bool, errors::ConnectorError> {
+        // Transaction callbacks are not signed, the transaction is synced to confirm the outcome
+        Ok(false)
+    }
+
+    fn get_webhook_object_reference_id(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api_models::webhooks::ObjectReferenceId, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: bambora::BamboraWebhookBody = serde_urlencoded::from_bytes(request.body)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookReferenceIdNotFound)?;
+        Ok(if details.is_refund() {
+            api_models::webhooks::ObjectReferenceId::RefundId(
+                api_models::webhooks::RefundIdType::ConnectorRefundId(details.trn_id),
+            )
+        } else {
+            api_models::webhooks::ObjectReferenceId::PaymentId(
+                api_models::payments::PaymentIdType::ConnectorTransactionId(details.trn_id),
+            )
+        })
     }
 
     fn get_webhook_event_type(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api::IncomingWebhookEvent, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: bambora::BamboraWebhookBody = serde_urlencoded::from_bytes(request.body)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookEventTypeNotFound)?;
+        Ok(api::IncomingWebhookEvent::from(&details))
     }
 
     fn get_webhook_resource_object(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<serde_json::Value, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: bambora::BamboraWebhookBody = serde_urlencoded::from_bytes(request.body)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)?;
+        serde_json::to_value(details)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)
     }
 }
 
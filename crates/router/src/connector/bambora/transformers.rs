@@ -562,3 +562,58 @@ pub struct CardValidation {
     amount: f64,
     cvd_id: i32,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BamboraTransactionType {
+    #[serde(rename = "P")]
+    Purchase,
+    #[serde(rename = "PA")]
+    PreAuth,
+    #[serde(rename = "PAC")]
+    PreAuthCompletion,
+    #[serde(rename = "R")]
+    Return,
+    #[serde(other)]
+    Other,
+}
+
+/// Transaction callback posted by Bambora as a form encoded body
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BamboraWebhookBody {
+    pub trn_id: String,
+    pub trn_approved: String,
+    pub trn_type: BamboraTransactionType,
+}
+
+impl BamboraWebhookBody {
+    pub fn is_refund(&self) -> bool {
+        self.trn_type == BamboraTransactionType::Return
+    }
+
+    fn is_approved(&self) -> bool {
+        self.trn_approved == "1"
+    }
+}
+
+impl From<&BamboraWebhookBody> for api::IncomingWebhookEvent {
+    fn from(item: &BamboraWebhookBody) -> Self {
+        match (&item.trn_type, item.is_approved()) {
+            (
+                BamboraTransactionType::Purchase
+                | BamboraTransactionType::PreAuth
+                | BamboraTransactionType::PreAuthCompletion,
+                true,
+            ) => Self::PaymentIntentSuccess,
+            (
+                BamboraTransactionType::Purchase
+                | BamboraTransactionType::PreAuth
+                | BamboraTransactionType::PreAuthCompletion,
+                false,
+            ) => Self::PaymentIntentFailure,
+            (BamboraTransactionType::Return, true) => Self::RefundSuccess,
+            (BamboraTransactionType::Return, false) => Self::RefundFailure,
+            (BamboraTransactionType::Other, _) => Self::EventNotSupported,
+        }
+    }
+}
@@ -638,6 +638,25 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Bluesnap {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Bluesnap,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Card,
+            )],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Bluesnap {
     fn get_webhook_source_verification_algorithm(
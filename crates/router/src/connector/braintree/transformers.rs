@@ -1,204 +1,710 @@
 use api_models::payments;
 use base64::Engine;
-use masking::Secret;
+use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, Secret};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    connector::utils,
+    connector::utils::{self, PaymentsAuthorizeRequestData, RefundsRequestData, RouterData},
     consts,
     core::errors,
+    services,
     types::{self, api, storage::enums},
 };
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
-pub struct DeviceData;
+pub const TOKENIZE_CREDIT_CARD_MUTATION: &str = "mutation TokenizeCreditCard($input: TokenizeCreditCardInput!) { tokenizeCreditCard(input: $input) { paymentMethod { id } } }";
+pub const CLIENT_TOKEN_MUTATION: &str = "mutation CreateClientToken($input: CreateClientTokenInput) { createClientToken(input: $input) { clientToken } }";
+pub const CHARGE_PAYMENT_METHOD_MUTATION: &str = "mutation ChargePaymentMethod($input: ChargePaymentMethodInput!) { payment: chargePaymentMethod(input: $input) { transaction { id status paymentMethod { id usage } processorResponse { legacyCode message } gatewayRejectionReason } } }";
+pub const AUTHORIZE_PAYMENT_METHOD_MUTATION: &str = "mutation AuthorizePaymentMethod($input: AuthorizePaymentMethodInput!) { payment: authorizePaymentMethod(input: $input) { transaction { id status paymentMethod { id usage } processorResponse { legacyCode message } gatewayRejectionReason } } }";
+pub const CAPTURE_TRANSACTION_MUTATION: &str = "mutation CaptureTransaction($input: CaptureTransactionInput!) { payment: captureTransaction(input: $input) { transaction { id status paymentMethod { id usage } processorResponse { legacyCode message } gatewayRejectionReason } } }";
+pub const VOID_TRANSACTION_MUTATION: &str = "mutation VoidTransaction($input: ReverseTransactionInput!) { payment: reverseTransaction(input: $input) { transaction: reversal { ... on Transaction { id status paymentMethod { id usage } processorResponse { legacyCode message } gatewayRejectionReason } } } }";
+pub const REFUND_TRANSACTION_MUTATION: &str = "mutation RefundTransaction($input: RefundTransactionInput!) { refundTransaction(input: $input) { refund { id status } } }";
+pub const TRANSACTION_QUERY: &str = "query SearchTransaction($input: TransactionSearchInput!) { search { transactions(input: $input, first: 1) { edges { node { id status paymentMethod { id usage } processorResponse { legacyCode message } gatewayRejectionReason } } } } }";
+pub const REFUND_QUERY: &str = "query SearchRefund($input: RefundSearchInput!) { search { refunds(input: $input, first: 1) { edges { node { id status } } } } }";
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
-pub struct PaymentOptions {
-    submit_for_settlement: bool,
+/// Every Braintree GraphQL request is a query document along with the variables it refers to
+#[derive(Debug, Serialize)]
+pub struct BraintreeGraphQlRequest<T> {
+    query: &'static str,
+    variables: GraphQlVariables<T>,
 }
 
-#[derive(Debug, Serialize, Eq, PartialEq)]
-pub struct BraintreePaymentsRequest {
-    transaction: TransactionBody,
+impl<T> BraintreeGraphQlRequest<T> {
+    fn new(query: &'static str, input: T) -> Self {
+        Self {
+            query,
+            variables: GraphQlVariables { input },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GraphQlVariables<T> {
+    input: T,
+}
+
+pub struct BraintreeAuthType {
+    pub(super) auth_header: String,
+}
+
+impl TryFrom<&types::ConnectorAuthType> for BraintreeAuthType {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::ConnectorAuthType) -> Result<Self, Self::Error> {
+        // The merchant is identified by the API keys alone, `key1` (the merchant id) was only
+        // needed to build the URLs of the REST API
+        if let types::ConnectorAuthType::SignatureKey {
+            api_key: public_key,
+            api_secret: private_key,
+            ..
+        } = item
+        {
+            let auth_key = format!("{public_key}:{private_key}");
+            let auth_header = format!("Basic {}", consts::BASE64_ENGINE.encode(auth_key));
+            Ok(Self { auth_header })
+        } else {
+            Err(errors::ConnectorError::FailedToObtainAuthType)?
+        }
+    }
+}
+
+// Payment method tokenization
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenizeCreditCardInput {
+    credit_card: CreditCardInput,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditCardInput {
+    number: Secret<String, common_utils::pii::CardNumber>,
+    expiration_month: Secret<String>,
+    expiration_year: Secret<String>,
+    cvv: Secret<String>,
+    cardholder_name: Secret<String>,
+}
+
+pub type BraintreeTokenRequest = BraintreeGraphQlRequest<TokenizeCreditCardInput>;
+
+impl TryFrom<&types::TokenizationRouterData> for BraintreeTokenRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::TokenizationRouterData) -> Result<Self, Self::Error> {
+        match item.request.payment_method_data.clone() {
+            api::PaymentMethodData::Card(card) => Ok(Self::new(
+                TOKENIZE_CREDIT_CARD_MUTATION,
+                TokenizeCreditCardInput {
+                    credit_card: CreditCardInput {
+                        number: card.card_number,
+                        expiration_month: card.card_exp_month,
+                        expiration_year: card.card_exp_year,
+                        cvv: card.card_cvc,
+                        cardholder_name: card.card_holder_name,
+                    },
+                },
+            )),
+            _ => Err(errors::ConnectorError::NotImplemented(format!(
+                "Tokenization of payment method - {:?}",
+                item.request.payment_method_data
+            )))
+            .into_report(),
+        }
+    }
 }
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
-pub struct BraintreeApiVersion {
-    version: String,
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenizeCreditCardData {
+    tokenize_credit_card: TokenizedPaymentMethod,
 }
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
-pub struct BraintreeSessionRequest {
-    client_token: BraintreeApiVersion,
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenizedPaymentMethod {
+    payment_method: PaymentMethodDetails,
 }
 
-impl TryFrom<&types::PaymentsSessionRouterData> for BraintreeSessionRequest {
+pub type BraintreeTokenResponse = BraintreeGraphQlResponse<TokenizeCreditCardData>;
+
+impl<F, T>
+    TryFrom<types::ResponseRouterData<F, BraintreeTokenResponse, T, types::PaymentsResponseData>>
+    for types::RouterData<F, T, types::PaymentsResponseData>
+{
     type Error = error_stack::Report<errors::ConnectorError>;
-    fn try_from(_item: &types::PaymentsSessionRouterData) -> Result<Self, Self::Error> {
+    fn try_from(
+        item: types::ResponseRouterData<F, BraintreeTokenResponse, T, types::PaymentsResponseData>,
+    ) -> Result<Self, Self::Error> {
+        let response = item.response.into_data(item.http_code)?.map(|data| {
+            types::PaymentsResponseData::TokenizationResponse {
+                token: data.tokenize_credit_card.payment_method.id,
+            }
+        });
         Ok(Self {
-            client_token: BraintreeApiVersion {
-                version: "2".to_string(),
-            },
+            response,
+            ..item.data
         })
     }
 }
 
-#[derive(Debug, Serialize, Eq, PartialEq)]
+// Client token, used by the PayPal SDK and to authenticate cards with 3DS
+
+#[derive(Debug, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct TransactionBody {
-    amount: String,
-    device_data: DeviceData,
-    options: PaymentOptions,
-    #[serde(flatten)]
-    payment_method_data_type: PaymentMethodType,
-    #[serde(rename = "type")]
-    kind: String,
+pub struct CreateClientTokenInput {}
+
+pub type BraintreeClientTokenRequest = BraintreeGraphQlRequest<CreateClientTokenInput>;
+
+impl<F, T> TryFrom<&types::RouterData<F, T, types::PaymentsResponseData>>
+    for BraintreeClientTokenRequest
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        _item: &types::RouterData<F, T, types::PaymentsResponseData>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            CLIENT_TOKEN_MUTATION,
+            CreateClientTokenInput::default(),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateClientTokenData {
+    create_client_token: ClientToken,
 }
 
-#[derive(Debug, Serialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[serde(tag = "type")]
-pub enum PaymentMethodType {
-    CreditCard(Card),
-    PaymentMethodNonce(Nonce),
+pub struct ClientToken {
+    client_token: String,
+}
+
+pub type BraintreeClientTokenResponse = BraintreeGraphQlResponse<CreateClientTokenData>;
+
+impl
+    TryFrom<
+        types::ResponseRouterData<
+            api::Session,
+            BraintreeClientTokenResponse,
+            types::PaymentsSessionData,
+            types::PaymentsResponseData,
+        >,
+    > for types::PaymentsSessionRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<
+            api::Session,
+            BraintreeClientTokenResponse,
+            types::PaymentsSessionData,
+            types::PaymentsResponseData,
+        >,
+    ) -> Result<Self, Self::Error> {
+        let response = item.response.into_data(item.http_code)?.map(|data| {
+            types::PaymentsResponseData::SessionResponse {
+                session_token: types::api::SessionToken::Paypal(Box::new(
+                    payments::PaypalSessionTokenResponse {
+                        session_token: data.create_client_token.client_token,
+                    },
+                )),
+            }
+        });
+        Ok(Self {
+            response,
+            ..item.data
+        })
+    }
 }
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
-pub struct Nonce {
-    payment_method_nonce: String,
+/// Cards are authenticated with 3DS by the Braintree SDK on the customer's browser, which needs a
+/// client token. The payment is then made in the complete authorize flow with the nonce returned
+/// by the SDK, unless it is a recurring payment made with a vaulted payment method.
+pub fn is_client_token_required(item: &types::PaymentsAuthorizeRouterData) -> bool {
+    item.is_three_ds()
+        && item.request.connector_mandate_id().is_none()
+        && matches!(
+            item.request.payment_method_data,
+            api::PaymentMethodData::Card(_)
+        )
+}
+
+impl
+    TryFrom<
+        types::ResponseRouterData<
+            api::Authorize,
+            BraintreeClientTokenResponse,
+            types::PaymentsAuthorizeData,
+            types::PaymentsResponseData,
+        >,
+    > for types::PaymentsAuthorizeRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<
+            api::Authorize,
+            BraintreeClientTokenResponse,
+            types::PaymentsAuthorizeData,
+            types::PaymentsResponseData,
+        >,
+    ) -> Result<Self, Self::Error> {
+        let data = match item.response.into_data(item.http_code)? {
+            Ok(data) => data,
+            Err(error) => {
+                return Ok(Self {
+                    response: Err(error),
+                    ..item.data
+                })
+            }
+        };
+        let card = item.data.request.get_card()?;
+        let redirection_data = services::RedirectForm::Braintree {
+            client_token: data.create_client_token.client_token,
+            card_token: item.data.get_payment_method_token()?,
+            bin: card.card_number.peek().chars().take(6).collect(),
+            amount: utils::to_currency_base_unit(
+                item.data.request.amount,
+                item.data.request.currency,
+            )?,
+            complete_authorize_url: item
+                .data
+                .request
+                .complete_authorize_url
+                .clone()
+                .ok_or_else(utils::missing_field_err("complete_authorize_url"))?,
+        };
+        Ok(Self {
+            status: enums::AttemptStatus::AuthenticationPending,
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::NoResponseId,
+                redirection_data: Some(redirection_data),
+                mandate_reference: None,
+                connector_metadata: None,
+            }),
+            ..item.data
+        })
+    }
 }
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+// Payments
+
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Card {
-    credit_card: CardDetails,
+pub struct PaymentMethodInput {
+    payment_method_id: Secret<String>,
+    transaction: TransactionInput,
 }
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CardDetails {
-    number: Secret<String, common_utils::pii::CardNumber>,
-    expiration_month: Secret<String>,
-    expiration_year: Secret<String>,
-    cvv: Secret<String>,
+pub struct TransactionInput {
+    amount: String,
+    order_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vault_payment_method_after_transacting: Option<VaultPaymentMethodInput>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VaultPaymentMethodInput {
+    when: VaultPaymentMethodCriteria,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VaultPaymentMethodCriteria {
+    OnSuccessfulTransaction,
+}
+
+pub type BraintreePaymentsRequest = BraintreeGraphQlRequest<PaymentMethodInput>;
+
+impl BraintreePaymentsRequest {
+    /// Charges or authorizes the payment method identified by `payment_method_id`, which can be a
+    /// tokenized card, a nonce returned by one of the Braintree SDKs or a vaulted payment method.
+    fn payment(
+        payment_method_id: String,
+        auto_capture: bool,
+        vault: bool,
+        amount: String,
+        order_id: String,
+    ) -> Self {
+        let query = if auto_capture {
+            CHARGE_PAYMENT_METHOD_MUTATION
+        } else {
+            AUTHORIZE_PAYMENT_METHOD_MUTATION
+        };
+        Self::new(
+            query,
+            PaymentMethodInput {
+                payment_method_id: Secret::new(payment_method_id),
+                transaction: TransactionInput {
+                    amount,
+                    order_id,
+                    vault_payment_method_after_transacting: vault.then_some(
+                        VaultPaymentMethodInput {
+                            when: VaultPaymentMethodCriteria::OnSuccessfulTransaction,
+                        },
+                    ),
+                },
+            },
+        )
+    }
+}
+
+fn is_vaulting_required(
+    setup_future_usage: Option<enums::FutureUsage>,
+    setup_mandate_details: Option<&payments::MandateData>,
+) -> bool {
+    setup_mandate_details.is_some()
+        || matches!(setup_future_usage, Some(enums::FutureUsage::OffSession))
 }
 
 impl TryFrom<&types::PaymentsAuthorizeRouterData> for BraintreePaymentsRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
-        let submit_for_settlement = matches!(
-            item.request.capture_method,
-            Some(enums::CaptureMethod::Automatic) | None
-        );
-
-        let amount = utils::to_currency_base_unit(item.request.amount, item.request.currency)?;
-        let device_data = DeviceData {};
-        let options = PaymentOptions {
-            submit_for_settlement,
-        };
-        let kind = "sale".to_string();
-
-        let payment_method_data_type = match item.request.payment_method_data.clone() {
-            api::PaymentMethodData::Card(ccard) => Ok(PaymentMethodType::CreditCard(Card {
-                credit_card: CardDetails {
-                    number: ccard.card_number,
-                    expiration_month: ccard.card_exp_month,
-                    expiration_year: ccard.card_exp_year,
-                    cvv: ccard.card_cvc,
+        let payment_method_id = match item.request.connector_mandate_id() {
+            Some(vaulted_payment_method_id) => vaulted_payment_method_id,
+            None => match item.request.payment_method_data {
+                api::PaymentMethodData::Card(_) => item.get_payment_method_token()?,
+                api::PaymentMethodData::Wallet(ref wallet_data) => match wallet_data {
+                    payments::WalletData::PaypalSdk(wallet_data) => wallet_data.token.to_owned(),
+                    _ => Err(errors::ConnectorError::InvalidWallet)?,
                 },
-            })),
-            api::PaymentMethodData::Wallet(ref wallet_data) => {
-                Ok(PaymentMethodType::PaymentMethodNonce(Nonce {
-                    payment_method_nonce: match wallet_data {
-                        api_models::payments::WalletData::PaypalSdk(wallet_data) => {
-                            Ok(wallet_data.token.to_owned())
-                        }
-                        _ => Err(errors::ConnectorError::InvalidWallet),
-                    }?,
-                }))
-            }
-            _ => Err(errors::ConnectorError::NotImplemented(format!(
-                "Current Payment Method - {:?}",
-                item.request.payment_method_data
-            ))),
-        }?;
-        let braintree_transaction_body = TransactionBody {
-            amount,
-            device_data,
-            options,
-            payment_method_data_type,
-            kind,
+                _ => Err(errors::ConnectorError::NotImplemented(format!(
+                    "Current Payment Method - {:?}",
+                    item.request.payment_method_data
+                )))?,
+            },
         };
-        Ok(Self {
-            transaction: braintree_transaction_body,
-        })
+        let vault = item.request.connector_mandate_id().is_none()
+            && is_vaulting_required(
+                item.request.setup_future_usage,
+                item.request.setup_mandate_details.as_ref(),
+            );
+
+        Ok(Self::payment(
+            payment_method_id,
+            item.request.is_auto_capture()?,
+            vault,
+            utils::to_currency_base_unit(item.request.amount, item.request.currency)?,
+            item.payment_id.clone(),
+        ))
     }
 }
 
-pub struct BraintreeAuthType {
-    pub(super) auth_header: String,
-    pub(super) merchant_id: String,
+/// Form fields posted to the complete authorize URL once the Braintree SDK has authenticated the
+/// card, see [`services::RedirectForm::Braintree`]
+#[derive(Debug, Deserialize)]
+pub struct BraintreeRedirectionResponse {
+    nonce: Option<String>,
+    error_message: Option<String>,
 }
 
-impl TryFrom<&types::ConnectorAuthType> for BraintreeAuthType {
-    type Error = error_stack::Report<errors::ConnectorError>;
-    fn try_from(item: &types::ConnectorAuthType) -> Result<Self, Self::Error> {
-        if let types::ConnectorAuthType::SignatureKey {
-            api_key: public_key,
-            key1: merchant_id,
-            api_secret: private_key,
-        } = item
-        {
-            let auth_key = format!("{public_key}:{private_key}");
-            let auth_header = format!("Basic {}", consts::BASE64_ENGINE.encode(auth_key));
-            Ok(Self {
-                auth_header,
-                merchant_id: merchant_id.to_owned(),
-            })
-        } else {
-            Err(errors::ConnectorError::FailedToObtainAuthType)?
+impl BraintreeRedirectionResponse {
+    /// The nonce of the authenticated card, which the form posts blank when the authentication
+    /// failed
+    pub fn get_nonce(&self) -> Option<&str> {
+        self.nonce
+            .as_deref()
+            .map(str::trim)
+            .filter(|nonce| !nonce.is_empty())
+    }
+}
+
+/// Failed 3DS authentication of a card, handled as the response of the complete authorize flow
+/// without calling Braintree
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BraintreeAuthenticationFailure {
+    authentication_error: String,
+}
+
+impl From<BraintreeRedirectionResponse> for BraintreeAuthenticationFailure {
+    fn from(redirection_response: BraintreeRedirectionResponse) -> Self {
+        Self {
+            authentication_error: redirection_response
+                .error_message
+                .filter(|error_message| !error_message.trim().is_empty())
+                .unwrap_or_else(|| "3DS authentication failed".to_string()),
         }
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum BraintreeCompleteAuthorizeResponse {
+    AuthenticationFailure(BraintreeAuthenticationFailure),
+    Payment(BraintreePaymentsResponse),
+}
+
+impl TryFrom<&types::PaymentsCompleteAuthorizeRouterData> for BraintreePaymentsRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCompleteAuthorizeRouterData) -> Result<Self, Self::Error> {
+        let redirection_response: BraintreeRedirectionResponse = item
+            .request
+            .payload
+            .clone()
+            .ok_or_else(utils::missing_field_err("payload"))
+            .and_then(|payload| {
+                serde_json::from_value(payload)
+                    .into_report()
+                    .change_context(errors::ConnectorError::ParsingFailed)
+            })?;
+        // Failed authentications are handled without calling Braintree, see `get_flow_type`
+        let nonce = redirection_response
+            .get_nonce()
+            .map(ToOwned::to_owned)
+            .ok_or(errors::ConnectorError::MissingRequiredField {
+                field_name: "nonce",
+            })?;
+        let auto_capture = match item.request.capture_method {
+            Some(enums::CaptureMethod::Automatic) | None => true,
+            Some(enums::CaptureMethod::Manual) => false,
+            Some(_) => Err(errors::ConnectorError::CaptureMethodNotSupported)?,
+        };
+
+        Ok(Self::payment(
+            nonce,
+            auto_capture,
+            is_vaulting_required(
+                item.request.setup_future_usage,
+                item.request.setup_mandate_details.as_ref(),
+            ),
+            utils::to_currency_base_unit(item.request.amount, item.request.currency)?,
+            item.payment_id.clone(),
+        ))
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureTransactionInput {
+    transaction_id: String,
+    transaction: CaptureInput,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CaptureInput {
+    amount: String,
+}
+
+pub type BraintreeCaptureRequest = BraintreeGraphQlRequest<CaptureTransactionInput>;
+
+impl TryFrom<&types::PaymentsCaptureRouterData> for BraintreeCaptureRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCaptureRouterData) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            CAPTURE_TRANSACTION_MUTATION,
+            CaptureTransactionInput {
+                transaction_id: item.request.connector_transaction_id.clone(),
+                transaction: CaptureInput {
+                    amount: utils::to_currency_base_unit(
+                        item.request.amount_to_capture,
+                        item.request.currency,
+                    )?,
+                },
+            },
+        ))
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReverseTransactionInput {
+    transaction_id: String,
+}
+
+pub type BraintreeVoidRequest = BraintreeGraphQlRequest<ReverseTransactionInput>;
+
+impl TryFrom<&types::PaymentsCancelRouterData> for BraintreeVoidRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCancelRouterData) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            VOID_TRANSACTION_MUTATION,
+            ReverseTransactionInput {
+                transaction_id: item.request.connector_transaction_id.clone(),
+            },
+        ))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionSearchInput {
+    id: SearchValueInput,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchValueInput {
+    is: String,
+}
+
+pub type BraintreePSyncRequest = BraintreeGraphQlRequest<TransactionSearchInput>;
+
+impl TryFrom<&types::PaymentsSyncRouterData> for BraintreePSyncRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsSyncRouterData) -> Result<Self, Self::Error> {
+        let transaction_id = item
+            .request
+            .connector_transaction_id
+            .get_connector_transaction_id()
+            .change_context(errors::ConnectorError::MissingConnectorTransactionID)?;
+        // Searching by id matches both the global ids of the GraphQL API and the legacy ids of
+        // transactions made with the REST API
+        Ok(Self::new(
+            TRANSACTION_QUERY,
+            TransactionSearchInput {
+                id: SearchValueInput { is: transaction_id },
+            },
+        ))
+    }
+}
+
 #[derive(Debug, Default, Clone, Deserialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BraintreePaymentStatus {
-    Succeeded,
-    Failed,
     Authorized,
-    AuthorizedExpired,
-    ProcessorDeclined,
-    GatewayRejected,
-    Voided,
-    SubmittedForSettlement,
     #[default]
-    Settling,
+    Authorizing,
+    AuthorizationExpired,
+    Failed,
+    GatewayRejected,
+    ProcessorDeclined,
     Settled,
-    SettlementPending,
-    SettlementDeclined,
     SettlementConfirmed,
+    SettlementDeclined,
+    SettlementPending,
+    Settling,
+    SubmittedForSettlement,
+    Voided,
 }
 
 impl From<BraintreePaymentStatus> for enums::AttemptStatus {
     fn from(item: BraintreePaymentStatus) -> Self {
         match item {
-            BraintreePaymentStatus::Succeeded
-            | BraintreePaymentStatus::SubmittedForSettlement
-            | BraintreePaymentStatus::Settling => Self::Charged,
-            BraintreePaymentStatus::AuthorizedExpired => Self::AuthorizationFailed,
+            BraintreePaymentStatus::Settled
+            | BraintreePaymentStatus::SettlementConfirmed
+            | BraintreePaymentStatus::SettlementPending
+            | BraintreePaymentStatus::Settling
+            | BraintreePaymentStatus::SubmittedForSettlement => Self::Charged,
+            BraintreePaymentStatus::AuthorizationExpired => Self::AuthorizationFailed,
             BraintreePaymentStatus::Failed
             | BraintreePaymentStatus::GatewayRejected
             | BraintreePaymentStatus::ProcessorDeclined
             | BraintreePaymentStatus::SettlementDeclined => Self::Failure,
             BraintreePaymentStatus::Authorized => Self::Authorized,
             BraintreePaymentStatus::Voided => Self::Voided,
-            _ => Self::Pending,
+            BraintreePaymentStatus::Authorizing => Self::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentMethodDetails {
+    id: String,
+    usage: Option<PaymentMethodUsage>,
+}
+
+#[derive(Debug, Clone, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PaymentMethodUsage {
+    SingleUse,
+    MultiUse,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessorResponse {
+    legacy_code: Option<String>,
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionResponse {
+    id: String,
+    status: BraintreePaymentStatus,
+    payment_method: Option<PaymentMethodDetails>,
+    processor_response: Option<ProcessorResponse>,
+    gateway_rejection_reason: Option<String>,
+}
+
+impl TransactionResponse {
+    /// Builds the error of a transaction declined by the processor or rejected by the gateway
+    fn get_error_response(&self, http_code: u16) -> Option<types::ErrorResponse> {
+        match self.status {
+            BraintreePaymentStatus::ProcessorDeclined
+            | BraintreePaymentStatus::SettlementDeclined
+            | BraintreePaymentStatus::Failed => {
+                let processor_response = self.processor_response.as_ref();
+                let message = processor_response
+                    .and_then(|response| response.message.clone())
+                    .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string());
+                Some(types::ErrorResponse {
+                    code: processor_response
+                        .and_then(|response| response.legacy_code.clone())
+                        .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+                    message: message.clone(),
+                    reason: Some(message),
+                    status_code: http_code,
+                })
+            }
+            BraintreePaymentStatus::GatewayRejected => {
+                let reason = self
+                    .gateway_rejection_reason
+                    .clone()
+                    .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string());
+                Some(types::ErrorResponse {
+                    code: reason.clone(),
+                    message: format!("Transaction rejected by the gateway: {reason}"),
+                    reason: Some(reason),
+                    status_code: http_code,
+                })
+            }
+            _ => None,
         }
     }
+
+    fn into_payments_response(
+        self,
+        http_code: u16,
+    ) -> (
+        enums::AttemptStatus,
+        Result<types::PaymentsResponseData, types::ErrorResponse>,
+    ) {
+        let status = enums::AttemptStatus::from(self.status.clone());
+        let response = match self.get_error_response(http_code) {
+            Some(error) => Err(error),
+            None => {
+                // Only payment methods vaulted for later use can be used as mandates
+                let mandate_reference = self
+                    .payment_method
+                    .filter(|payment_method| {
+                        payment_method.usage == Some(PaymentMethodUsage::MultiUse)
+                    })
+                    .map(|payment_method| payment_method.id);
+                Ok(types::PaymentsResponseData::TransactionResponse {
+                    resource_id: types::ResponseId::ConnectorTransactionId(self.id),
+                    redirection_data: None,
+                    mandate_reference,
+                    connector_metadata: None,
+                })
+            }
+        };
+        (status, response)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaymentData {
+    payment: TransactionPayload,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionPayload {
+    transaction: TransactionResponse,
 }
 
+pub type BraintreePaymentsResponse = BraintreeGraphQlResponse<PaymentData>;
+
 impl<F, T>
     TryFrom<types::ResponseRouterData<F, BraintreePaymentsResponse, T, types::PaymentsResponseData>>
     for types::RouterData<F, T, types::PaymentsResponseData>
@@ -212,160 +718,379 @@ impl<F, T>
             types::PaymentsResponseData,
         >,
     ) -> Result<Self, Self::Error> {
-        Ok(Self {
-            status: enums::AttemptStatus::from(item.response.transaction.status),
-            response: Ok(types::PaymentsResponseData::TransactionResponse {
-                resource_id: types::ResponseId::ConnectorTransactionId(
-                    item.response.transaction.id,
-                ),
-                redirection_data: None,
-                mandate_reference: None,
-                connector_metadata: None,
+        match item.response.into_data(item.http_code)? {
+            Ok(data) => {
+                let (status, response) = data
+                    .payment
+                    .transaction
+                    .into_payments_response(item.http_code);
+                Ok(Self {
+                    status,
+                    response,
+                    ..item.data
+                })
+            }
+            Err(error) => Ok(Self {
+                response: Err(error),
+                ..item.data
             }),
-            ..item.data
-        })
+        }
     }
 }
 
 impl<F, T>
     TryFrom<
-        types::ResponseRouterData<F, BraintreeSessionTokenResponse, T, types::PaymentsResponseData>,
+        types::ResponseRouterData<
+            F,
+            BraintreeCompleteAuthorizeResponse,
+            T,
+            types::PaymentsResponseData,
+        >,
     > for types::RouterData<F, T, types::PaymentsResponseData>
 {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(
         item: types::ResponseRouterData<
             F,
-            BraintreeSessionTokenResponse,
+            BraintreeCompleteAuthorizeResponse,
             T,
             types::PaymentsResponseData,
         >,
     ) -> Result<Self, Self::Error> {
-        Ok(Self {
-            response: Ok(types::PaymentsResponseData::SessionResponse {
-                session_token: types::api::SessionToken::Paypal(Box::new(
-                    payments::PaypalSessionTokenResponse {
-                        session_token: item.response.client_token.value,
-                    },
-                )),
+        match item.response {
+            BraintreeCompleteAuthorizeResponse::AuthenticationFailure(failure) => Ok(Self {
+                status: enums::AttemptStatus::AuthenticationFailed,
+                response: Err(types::ErrorResponse {
+                    code: "THREE_D_SECURE".to_string(),
+                    message: failure.authentication_error,
+                    reason: None,
+                    status_code: item.http_code,
+                }),
+                ..item.data
             }),
-            ..item.data
-        })
+            BraintreeCompleteAuthorizeResponse::Payment(response) => {
+                Self::try_from(types::ResponseRouterData {
+                    response,
+                    data: item.data,
+                    http_code: item.http_code,
+                })
+            }
+        }
     }
 }
 
-#[derive(Default, Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct BraintreePaymentsResponse {
-    transaction: TransactionResponse,
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchData<T> {
+    search: T,
 }
 
-#[derive(Default, Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ClientToken {
-    pub value: String,
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionSearchResult {
+    transactions: SearchConnection<TransactionResponse>,
 }
 
-#[derive(Default, Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct BraintreeSessionTokenResponse {
-    pub client_token: ClientToken,
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchConnection<T> {
+    edges: Vec<SearchEdge<T>>,
 }
 
-#[derive(Default, Debug, Clone, Deserialize, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct TransactionResponse {
-    id: String,
-    currency_iso_code: String,
-    amount: String,
-    status: BraintreePaymentStatus,
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchEdge<T> {
+    node: T,
 }
 
-#[derive(Debug, Default, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ErrorResponse {
-    pub api_error_response: ApiErrorResponse,
+impl<T> SearchConnection<T> {
+    fn into_first_node(self) -> Option<T> {
+        self.edges.into_iter().next().map(|edge| edge.node)
+    }
 }
 
-#[derive(Default, Debug, Clone, Deserialize, Eq, PartialEq)]
-pub struct ApiErrorResponse {
-    pub message: String,
+pub type BraintreePSyncResponse = BraintreeGraphQlResponse<SearchData<TransactionSearchResult>>;
+
+impl<F, T>
+    TryFrom<types::ResponseRouterData<F, BraintreePSyncResponse, T, types::PaymentsResponseData>>
+    for types::RouterData<F, T, types::PaymentsResponseData>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<F, BraintreePSyncResponse, T, types::PaymentsResponseData>,
+    ) -> Result<Self, Self::Error> {
+        match item.response.into_data(item.http_code)? {
+            Ok(data) => {
+                let transaction = data
+                    .search
+                    .transactions
+                    .into_first_node()
+                    .ok_or(errors::ConnectorError::MissingConnectorTransactionID)?;
+                let (status, response) = transaction.into_payments_response(item.http_code);
+                Ok(Self {
+                    status,
+                    response,
+                    ..item.data
+                })
+            }
+            Err(error) => Ok(Self {
+                response: Err(error),
+                ..item.data
+            }),
+        }
+    }
 }
 
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct BraintreeRefundRequest {
-    transaction: Amount,
+// Refunds
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundTransactionInput {
+    transaction_id: String,
+    refund: RefundInput,
 }
 
-#[derive(Default, Debug, Serialize, Clone)]
-pub struct Amount {
-    amount: Option<String>,
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundInput {
+    amount: String,
+    order_id: String,
 }
 
+pub type BraintreeRefundRequest = BraintreeGraphQlRequest<RefundTransactionInput>;
+
 impl<F> TryFrom<&types::RefundsRouterData<F>> for BraintreeRefundRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
-    fn try_from(_item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
-        Ok(Self {
-            transaction: Amount { amount: None },
-        })
+    fn try_from(item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            REFUND_TRANSACTION_MUTATION,
+            RefundTransactionInput {
+                transaction_id: item.request.connector_transaction_id.clone(),
+                refund: RefundInput {
+                    amount: utils::to_currency_base_unit(
+                        item.request.refund_amount,
+                        item.request.currency,
+                    )?,
+                    order_id: item.request.refund_id.clone(),
+                },
+            },
+        ))
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Default, Deserialize, Clone)]
-pub enum RefundStatus {
-    Succeeded,
-    Failed,
-    #[default]
-    Processing,
+#[derive(Debug, Serialize)]
+pub struct RefundSearchInput {
+    id: SearchValueInput,
 }
 
-impl From<RefundStatus> for enums::RefundStatus {
-    fn from(item: RefundStatus) -> Self {
+pub type BraintreeRSyncRequest = BraintreeGraphQlRequest<RefundSearchInput>;
+
+impl TryFrom<&types::RefundSyncRouterData> for BraintreeRSyncRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::RefundSyncRouterData) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            REFUND_QUERY,
+            RefundSearchInput {
+                id: SearchValueInput {
+                    is: item.request.get_connector_refund_id()?,
+                },
+            },
+        ))
+    }
+}
+
+impl From<BraintreePaymentStatus> for enums::RefundStatus {
+    fn from(item: BraintreePaymentStatus) -> Self {
         match item {
-            RefundStatus::Succeeded => Self::Success,
-            RefundStatus::Failed => Self::Failure,
-            RefundStatus::Processing => Self::Pending,
+            BraintreePaymentStatus::Settled
+            | BraintreePaymentStatus::SettlementConfirmed
+            | BraintreePaymentStatus::Settling => Self::Success,
+            BraintreePaymentStatus::AuthorizationExpired
+            | BraintreePaymentStatus::Failed
+            | BraintreePaymentStatus::GatewayRejected
+            | BraintreePaymentStatus::ProcessorDeclined
+            | BraintreePaymentStatus::SettlementDeclined
+            | BraintreePaymentStatus::Voided => Self::Failure,
+            BraintreePaymentStatus::Authorized
+            | BraintreePaymentStatus::Authorizing
+            | BraintreePaymentStatus::SettlementPending
+            | BraintreePaymentStatus::SubmittedForSettlement => Self::Pending,
         }
     }
 }
 
-#[derive(Default, Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct RefundResponse {
     pub id: String,
-    pub status: RefundStatus,
+    pub status: BraintreePaymentStatus,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundTransactionData {
+    refund_transaction: RefundPayload,
 }
 
-impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
-    for types::RefundsRouterData<api::Execute>
+#[derive(Debug, Clone, Deserialize)]
+pub struct RefundPayload {
+    refund: RefundResponse,
+}
+
+pub type BraintreeRefundResponse = BraintreeGraphQlResponse<RefundTransactionData>;
+
+impl<F> TryFrom<types::RefundsResponseRouterData<F, BraintreeRefundResponse>>
+    for types::RefundsRouterData<F>
 {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(
-        item: types::RefundsResponseRouterData<api::Execute, RefundResponse>,
+        item: types::RefundsResponseRouterData<F, BraintreeRefundResponse>,
     ) -> Result<Self, Self::Error> {
+        let response =
+            item.response
+                .into_data(item.http_code)?
+                .map(|data| types::RefundsResponseData {
+                    connector_refund_id: data.refund_transaction.refund.id,
+                    refund_status: enums::RefundStatus::from(data.refund_transaction.refund.status),
+                });
         Ok(Self {
-            response: Ok(types::RefundsResponseData {
-                connector_refund_id: item.response.id,
-                refund_status: enums::RefundStatus::from(item.response.status),
-            }),
+            response,
             ..item.data
         })
     }
 }
 
-impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
+#[derive(Debug, Clone, Deserialize)]
+pub struct RefundSearchResult {
+    refunds: SearchConnection<RefundResponse>,
+}
+
+pub type BraintreeRSyncResponse = BraintreeGraphQlResponse<SearchData<RefundSearchResult>>;
+
+impl TryFrom<types::RefundsResponseRouterData<api::RSync, BraintreeRSyncResponse>>
     for types::RefundsRouterData<api::RSync>
 {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(
-        item: types::RefundsResponseRouterData<api::RSync, RefundResponse>,
+        item: types::RefundsResponseRouterData<api::RSync, BraintreeRSyncResponse>,
     ) -> Result<Self, Self::Error> {
+        let response = match item.response.into_data(item.http_code)? {
+            Ok(data) => {
+                let refund = data
+                    .search
+                    .refunds
+                    .into_first_node()
+                    .ok_or(errors::ConnectorError::MissingConnectorRefundID)?;
+                Ok(types::RefundsResponseData {
+                    connector_refund_id: refund.id,
+                    refund_status: enums::RefundStatus::from(refund.status),
+                })
+            }
+            Err(error) => Err(error),
+        };
         Ok(Self {
-            response: Ok(types::RefundsResponseData {
-                connector_refund_id: item.response.id,
-                refund_status: enums::RefundStatus::from(item.response.status),
-            }),
+            response,
             ..item.data
         })
     }
 }
+
+// Errors
+
+/// GraphQL responses carry either the requested data or the errors that prevented it from being
+/// resolved, usually along with a `200 OK` status code.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BraintreeGraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+impl<T> BraintreeGraphQlResponse<T> {
+    fn into_data(
+        self,
+        http_code: u16,
+    ) -> Result<Result<T, types::ErrorResponse>, error_stack::Report<errors::ConnectorError>> {
+        if let Some(error) = get_error_response(self.errors, http_code) {
+            return Ok(Err(error));
+        }
+        self.data
+            .map(Ok)
+            .ok_or(errors::ConnectorError::ResponseDeserializationFailed)
+            .into_report()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ErrorResponse {
+    pub errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQlError {
+    pub message: String,
+    pub extensions: Option<GraphQlErrorExtensions>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlErrorExtensions {
+    pub error_class: Option<String>,
+    pub legacy_code: Option<String>,
+}
+
+/// Combines the errors of a GraphQL response into one, keeping the code of the first of them.
+/// Validation errors carry the code of the REST API, other errors are identified by their class.
+pub fn get_error_response(
+    errors: Vec<GraphQlError>,
+    http_code: u16,
+) -> Option<types::ErrorResponse> {
+    let code = errors.first().map(|error| {
+        error
+            .extensions
+            .as_ref()
+            .and_then(|extensions| {
+                extensions
+                    .legacy_code
+                    .clone()
+                    .or_else(|| extensions.error_class.clone())
+            })
+            .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string())
+    })?;
+    let message = errors
+        .into_iter()
+        .map(|error| error.message)
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(types::ErrorResponse {
+        code,
+        message: message.clone(),
+        reason: Some(message),
+        status_code: http_code,
+    })
+}
+
+/// Maps the processor response code of a declined transaction, the rejection reason of a
+/// transaction rejected by the gateway or the code of a validation error to a connector agnostic
+/// category.
+pub fn get_error_category(error_code: &str) -> api::enums::ErrorCategory {
+    match error_code {
+        // Do Not Honor, Processor Declined, Declined - Call Issuer, Declined
+        "2000" | "2038" | "2044" | "2046" => api::enums::ErrorCategory::DoNotHonor,
+        "2001" => api::enums::ErrorCategory::InsufficientFunds,
+        // Limit Exceeded, Cardholder's Activity Limit Exceeded
+        "2002" | "2003" => api::enums::ErrorCategory::LimitExceeded,
+        "2004" => api::enums::ErrorCategory::ExpiredCard,
+        // Invalid Credit Card Number, Credit card number is invalid
+        "2005" | "81715" | "81716" => api::enums::ErrorCategory::InvalidCardNumber,
+        // Card Issuer Declined CVV, CVV verification failed
+        "2010" | "81736" | "CVV" | "AVS_AND_CVV" => api::enums::ErrorCategory::IncorrectCvc,
+        // Possible Lost Card, Possible Stolen Card, Call Issuer. Pick Up Card
+        "2012" | "2013" | "2047" => api::enums::ErrorCategory::LostOrStolenCard,
+        "2014" | "FRAUD" | "RISK_THRESHOLD" => api::enums::ErrorCategory::FraudSuspected,
+        // Transaction Not Allowed, Issuer or Cardholder has put a restriction on the card
+        "2015" | "2057" => api::enums::ErrorCategory::CardNotSupported,
+        // Cardholder Authentication Required
+        "2099" => api::enums::ErrorCategory::AuthenticationRequired,
+        "THREE_D_SECURE" => api::enums::ErrorCategory::AuthenticationFailed,
+        // Processor Network Unavailable - Try Again
+        "3000" => api::enums::ErrorCategory::IssuerUnavailable,
+        "VALIDATION" => api::enums::ErrorCategory::InvalidRequest,
+        _ => api::enums::ErrorCategory::Unknown,
+    }
+}
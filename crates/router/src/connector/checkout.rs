@@ -53,6 +53,10 @@ impl ConnectorCommon for Checkout {
         "checkout"
     }
 
+    fn get_error_category(&self, error_code: &str) -> api::enums::ErrorCategory {
+        checkout::get_error_category(error_code)
+    }
+
     fn common_get_content_type(&self) -> &'static str {
         "application/json"
     }
@@ -832,6 +836,32 @@ impl api::FileUpload for Checkout {
     }
 }
 
+impl api::ConnectorCapability for Checkout {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Checkout,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::MultipleCaptures,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+                api::ConnectorFlow::ThreeDs,
+            ],
+            payment_methods: vec![
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Card),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Wallet)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::ApplePay,
+                        api::enums::PaymentMethodType::GooglePay,
+                    ]),
+            ],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Checkout {
     fn get_webhook_source_verification_algorithm(
@@ -775,3 +775,25 @@ impl From<CheckoutTxnType> for api_models::enums::DisputeStage {
 pub struct CheckoutWebhookObjectResource {
     pub data: serde_json::Value,
 }
+
+/// Maps the error codes of a Checkout error, joined with ` & `, to a connector agnostic category.
+/// The first code that is mapped wins.
+pub fn get_error_category(error_codes: &str) -> api::enums::ErrorCategory {
+    error_codes
+        .split(" & ")
+        .map(|error_code| match error_code {
+            "card_expired" => api::enums::ErrorCategory::ExpiredCard,
+            "card_number_invalid" => api::enums::ErrorCategory::InvalidCardNumber,
+            "cvv_invalid" => api::enums::ErrorCategory::IncorrectCvc,
+            "card_not_supported" => api::enums::ErrorCategory::CardNotSupported,
+            "request_invalid" | "amount_invalid" | "currency_invalid" => {
+                api::enums::ErrorCategory::InvalidRequest
+            }
+            "processing_error" | "processing_key_required" => {
+                api::enums::ErrorCategory::ProcessingError
+            }
+            _ => api::enums::ErrorCategory::Unknown,
+        })
+        .find(|category| *category != api::enums::ErrorCategory::Unknown)
+        .unwrap_or_default()
+}
@@ -11,16 +11,17 @@ use url::Url;
 
 use crate::{
     configs::settings,
-    connector::utils::RefundsRequestData,
+    connector::utils::{self as conn_utils, RefundsRequestData},
     consts,
     core::errors::{self, CustomResult},
+    db::StorageInterface,
     headers,
     services::{self, ConnectorIntegration},
     types::{
         self,
         api::{self, ConnectorCommon, ConnectorCommonExt},
     },
-    utils::{self, BytesExt},
+    utils::{self, crypto, ByteSliceExt, BytesExt},
 };
 
 #[derive(Debug, Clone)]
@@ -703,26 +704,121 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Cybersource {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Cybersource,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Card,
+            )],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Cybersource {
-    fn get_webhook_object_reference_id(
+    fn get_webhook_source_verification_algorithm(
         &self,
         _request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Box<dyn crypto::VerifySignature + Send>, errors::ConnectorError> {
+        Ok(Box::new(crypto::HmacSha256))
+    }
+
+    fn get_webhook_source_verification_signature(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        let header = conn_utils::get_header_key_value("v-c-signature", request.headers)?;
+        let signature = cybersource::get_signature_header_value(header, "sig")
+            .ok_or(errors::ConnectorError::WebhookSignatureNotFound)
+            .into_report()?;
+        consts::BASE64_ENGINE
+            .decode(signature)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookSignatureNotFound)
+    }
+
+    fn get_webhook_source_verification_message(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+        _merchant_id: &str,
+        _secret: &[u8],
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        let header = conn_utils::get_header_key_value("v-c-signature", request.headers)?;
+        let timestamp = cybersource::get_signature_header_value(header, "t")
+            .ok_or(errors::ConnectorError::WebhookSignatureNotFound)
+            .into_report()?;
+        Ok([timestamp.as_bytes(), b".", request.body].concat())
+    }
+
+    async fn get_webhook_source_verification_merchant_secret(
+        &self,
+        db: &dyn StorageInterface,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        let key = format!("whsec_verification_{}_{}", self.id(), merchant_id);
+        let secret = db
+            .get_key(&key)
+            .await
+            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)?;
+        // The webhook security key is issued base64 encoded
+        consts::BASE64_ENGINE
+            .decode(secret)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)
+    }
+
+    fn get_webhook_object_reference_id(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api_models::webhooks::ObjectReferenceId, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("cybersource".to_string()).into())
+        let details: cybersource::CybersourceWebhookBody = request
+            .body
+            .parse_struct("CybersourceWebhookBody")
+            .change_context(errors::ConnectorError::WebhookReferenceIdNotFound)?;
+        let id = details.get_transaction_id().into_report()?;
+        Ok(match details.event_type {
+            cybersource::CybersourceWebhookEventType::RefundAccepted
+            | cybersource::CybersourceWebhookEventType::RefundRejected => {
+                api_models::webhooks::ObjectReferenceId::RefundId(
+                    api_models::webhooks::RefundIdType::ConnectorRefundId(id),
+                )
+            }
+            _ => api_models::webhooks::ObjectReferenceId::PaymentId(
+                api_models::payments::PaymentIdType::ConnectorTransactionId(id),
+            ),
+        })
     }
 
     fn get_webhook_event_type(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api::IncomingWebhookEvent, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("cybersource".to_string()).into())
+        let details: cybersource::CybersourceWebhookBody = request
+            .body
+            .parse_struct("CybersourceWebhookBody")
+            .change_context(errors::ConnectorError::WebhookEventTypeNotFound)?;
+        Ok(api::IncomingWebhookEvent::from(&details.event_type))
     }
 
     fn get_webhook_resource_object(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<serde_json::Value, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("cybersource".to_string()).into())
+        let details: cybersource::CybersourceWebhookBody = request
+            .body
+            .parse_struct("CybersourceWebhookBody")
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)?;
+        let resource = cybersource::CybersourceTransactionResponse::try_from(details)?;
+        utils::Encode::<cybersource::CybersourceTransactionResponse>::encode_to_value(&resource)
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)
     }
 }
@@ -56,6 +56,10 @@ pub struct Card {
 pub struct OrderInformationWithBill {
     amount_details: Amount,
     bill_to: BillTo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_items: Option<Vec<LineItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invoice_details: Option<InvoiceDetails>,
 }
 
 #[derive(Default, Debug, Serialize, Eq, PartialEq)]
@@ -69,6 +73,48 @@ pub struct OrderInformation {
 pub struct Amount {
     total_amount: String,
     currency: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tax_amount: Option<String>,
+}
+
+#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LineItem {
+    product_name: String,
+    quantity: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit_price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tax_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    discount_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product_sku: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commodity_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit_of_measure: Option<String>,
+}
+
+#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceDetails {
+    purchase_order_number: String,
+}
+
+impl From<&payments::OrderDetails> for LineItem {
+    fn from(item: &payments::OrderDetails) -> Self {
+        Self {
+            product_name: item.product_name.clone(),
+            quantity: item.quantity,
+            unit_price: item.unit_price.map(|amount| amount.to_string()),
+            tax_amount: item.tax_amount.map(|amount| amount.to_string()),
+            discount_amount: item.discount_amount.map(|amount| amount.to_string()),
+            product_sku: item.sku.clone(),
+            commodity_code: item.commodity_code.clone(),
+            unit_of_measure: item.unit_of_measure.clone(),
+        }
+    }
 }
 
 #[derive(Default, Debug, Serialize, Eq, PartialEq)]
@@ -125,12 +171,24 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for CybersourcePaymentsRequest
                     .ok_or_else(utils::missing_field_err("email"))?;
                 let bill_to = build_bill_to(item.get_billing()?, email, number_with_code)?;
 
+                let purchase_details = item.request.purchase_details.as_ref();
                 let order_information = OrderInformationWithBill {
                     amount_details: Amount {
                         total_amount: item.request.amount.to_string(),
                         currency: item.request.currency.to_string().to_uppercase(),
+                        tax_amount: purchase_details
+                            .and_then(|details| details.tax_amount)
+                            .map(|amount| amount.to_string()),
                     },
                     bill_to,
+                    line_items: purchase_details
+                        .and_then(|details| details.line_items.as_ref())
+                        .map(|line_items| line_items.iter().map(LineItem::from).collect()),
+                    invoice_details: purchase_details
+                        .and_then(|details| details.customer_reference.clone())
+                        .map(|purchase_order_number| InvoiceDetails {
+                            purchase_order_number,
+                        }),
                 };
 
                 let payment_information = PaymentInformation {
@@ -192,6 +250,7 @@ impl TryFrom<&types::RefundExecuteRouterData> for CybersourcePaymentsRequest {
                 amount_details: Amount {
                     total_amount: value.request.refund_amount.to_string(),
                     currency: value.request.currency.to_string(),
+                    tax_amount: None,
                 },
                 ..Default::default()
             },
@@ -225,7 +284,7 @@ impl TryFrom<&types::ConnectorAuthType> for CybersourceAuthType {
         }
     }
 }
-#[derive(Debug, Default, Clone, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CybersourcePaymentStatus {
     Authorized,
@@ -324,14 +383,14 @@ impl<F, T>
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CybersourceTransactionResponse {
     id: String,
     application_information: ApplicationInformation,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApplicationInformation {
     status: CybersourcePaymentStatus,
@@ -416,6 +475,7 @@ impl<F> TryFrom<&types::RefundsRouterData<F>> for CybersourceRefundRequest {
                 amount_details: Amount {
                     total_amount: item.request.refund_amount.to_string(),
                     currency: item.request.currency.to_string(),
+                    tax_amount: None,
                 },
             },
         })
@@ -458,3 +518,100 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, CybersourceTransaction
         })
     }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CybersourceWebhookBody {
+    pub event_type: CybersourceWebhookEventType,
+    pub payload: Vec<CybersourceWebhookPayload>,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum CybersourceWebhookEventType {
+    #[serde(rename = "payments.payments.accept")]
+    PaymentAccepted,
+    #[serde(rename = "payments.payments.review")]
+    PaymentReview,
+    #[serde(rename = "payments.payments.reject")]
+    PaymentRejected,
+    #[serde(rename = "payments.captures.accept")]
+    CaptureAccepted,
+    #[serde(rename = "payments.captures.reject")]
+    CaptureRejected,
+    #[serde(rename = "payments.refunds.accept")]
+    RefundAccepted,
+    #[serde(rename = "payments.refunds.reject")]
+    RefundRejected,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CybersourceWebhookPayload {
+    pub data: CybersourceWebhookData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CybersourceWebhookData {
+    pub id: String,
+}
+
+impl CybersourceWebhookBody {
+    pub fn get_transaction_id(&self) -> Result<String, errors::ConnectorError> {
+        self.payload
+            .first()
+            .map(|payload| payload.data.id.clone())
+            .ok_or(errors::ConnectorError::WebhookReferenceIdNotFound)
+    }
+}
+
+impl From<&CybersourceWebhookEventType> for api::IncomingWebhookEvent {
+    fn from(item: &CybersourceWebhookEventType) -> Self {
+        match item {
+            CybersourceWebhookEventType::PaymentAccepted
+            | CybersourceWebhookEventType::CaptureAccepted => Self::PaymentIntentSuccess,
+            CybersourceWebhookEventType::PaymentReview => Self::PaymentIntentProcessing,
+            CybersourceWebhookEventType::PaymentRejected
+            | CybersourceWebhookEventType::CaptureRejected => Self::PaymentIntentFailure,
+            CybersourceWebhookEventType::RefundAccepted => Self::RefundSuccess,
+            CybersourceWebhookEventType::RefundRejected => Self::RefundFailure,
+            CybersourceWebhookEventType::Unknown => Self::EventNotSupported,
+        }
+    }
+}
+
+/// Notifications only carry the event type and the transaction id, so the resource object is
+/// built in the shape of the transaction details returned by the sync flows
+impl TryFrom<CybersourceWebhookBody> for CybersourceTransactionResponse {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: CybersourceWebhookBody) -> Result<Self, Self::Error> {
+        let status = match item.event_type {
+            CybersourceWebhookEventType::PaymentAccepted => CybersourcePaymentStatus::Authorized,
+            CybersourceWebhookEventType::PaymentReview => {
+                CybersourcePaymentStatus::AuthorizedPendingReview
+            }
+            CybersourceWebhookEventType::CaptureAccepted
+            | CybersourceWebhookEventType::RefundAccepted => CybersourcePaymentStatus::Transmitted,
+            CybersourceWebhookEventType::PaymentRejected
+            | CybersourceWebhookEventType::CaptureRejected => CybersourcePaymentStatus::Declined,
+            CybersourceWebhookEventType::RefundRejected => CybersourcePaymentStatus::Failed,
+            CybersourceWebhookEventType::Unknown => {
+                Err(errors::ConnectorError::WebhookResourceObjectNotFound)?
+            }
+        };
+        Ok(Self {
+            id: item.get_transaction_id()?,
+            application_information: ApplicationInformation { status },
+        })
+    }
+}
+
+/// The signature is sent in the `v-c-signature` header as `t=<timestamp>;keyId=<key id>;sig=<base64>`
+pub fn get_signature_header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    header
+        .split(';')
+        .filter_map(|part| part.trim().split_once('='))
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| value)
+}
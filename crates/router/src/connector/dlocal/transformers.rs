@@ -507,3 +507,49 @@ fn get_doc_from_currency(country: String) -> Secret<String> {
     };
     Secret::new(doc.to_string())
 }
+
+/// dLocal notifies with the payment or refund object, refunds are told apart by the
+/// `payment_id` they belong to
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum DlocalWebhookBody {
+    Refund {
+        id: String,
+        payment_id: String,
+        status: RefundStatus,
+    },
+    Payment {
+        id: String,
+        status: DlocalPaymentStatus,
+    },
+}
+
+impl From<&DlocalWebhookBody> for api::IncomingWebhookEvent {
+    fn from(item: &DlocalWebhookBody) -> Self {
+        match item {
+            DlocalWebhookBody::Payment { status, .. } => match status {
+                DlocalPaymentStatus::Authorized
+                | DlocalPaymentStatus::Verified
+                | DlocalPaymentStatus::Paid => Self::PaymentIntentSuccess,
+                DlocalPaymentStatus::Pending => Self::PaymentIntentProcessing,
+                DlocalPaymentStatus::Cancelled | DlocalPaymentStatus::Rejected => {
+                    Self::PaymentIntentFailure
+                }
+            },
+            DlocalWebhookBody::Refund { status, .. } => match status {
+                RefundStatus::Success => Self::RefundSuccess,
+                RefundStatus::Rejected | RefundStatus::Cancelled => Self::RefundFailure,
+                RefundStatus::Pending => Self::EventNotSupported,
+            },
+        }
+    }
+}
+
+/// The signature is sent in the `Authorization` header as `V2-HMAC-SHA256, Signature: <hex>`
+pub fn get_signature_from_header(header: &str) -> Option<&str> {
+    header
+        .split(',')
+        .filter_map(|part| part.trim().strip_prefix("Signature:"))
+        .map(str::trim)
+        .next()
+}
@@ -550,3 +550,80 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, FiservSyncResponse>>
         })
     }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiservWebhookBody {
+    pub gateway_response: FiservWebhookGatewayResponse,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiservWebhookGatewayResponse {
+    pub transaction_type: FiservTransactionType,
+    #[serde(flatten)]
+    gateway_response: GatewayResponse,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FiservTransactionType {
+    Charge,
+    Capture,
+    Cancel,
+    Refund,
+    #[serde(other)]
+    Unknown,
+}
+
+impl FiservWebhookBody {
+    pub fn get_transaction_id(&self) -> String {
+        self.gateway_response
+            .gateway_response
+            .transaction_processing_details
+            .transaction_id
+            .clone()
+    }
+}
+
+impl From<&FiservWebhookBody> for api::IncomingWebhookEvent {
+    fn from(item: &FiservWebhookBody) -> Self {
+        let transaction_state = &item.gateway_response.gateway_response.transaction_state;
+        match item.gateway_response.transaction_type {
+            FiservTransactionType::Charge | FiservTransactionType::Capture => {
+                match transaction_state {
+                    FiservPaymentStatus::Captured
+                    | FiservPaymentStatus::Succeeded
+                    | FiservPaymentStatus::Authorized => Self::PaymentIntentSuccess,
+                    FiservPaymentStatus::Declined | FiservPaymentStatus::Failed => {
+                        Self::PaymentIntentFailure
+                    }
+                    FiservPaymentStatus::Processing => Self::PaymentIntentProcessing,
+                    FiservPaymentStatus::Voided => Self::EventNotSupported,
+                }
+            }
+            FiservTransactionType::Refund => {
+                match enums::RefundStatus::from(transaction_state.clone()) {
+                    enums::RefundStatus::Success => Self::RefundSuccess,
+                    enums::RefundStatus::Failure => Self::RefundFailure,
+                    _ => Self::EventNotSupported,
+                }
+            }
+            FiservTransactionType::Cancel | FiservTransactionType::Unknown => {
+                Self::EventNotSupported
+            }
+        }
+    }
+}
+
+/// The sync flows read the transaction inquiry response, which wraps the same gateway response
+/// in a list
+impl From<FiservWebhookBody> for FiservSyncResponse {
+    fn from(item: FiservWebhookBody) -> Self {
+        Self {
+            sync_responses: vec![FiservPaymentsResponse {
+                gateway_response: item.gateway_response.gateway_response,
+            }],
+        }
+    }
+}
@@ -1,43 +1,96 @@
+use error_stack::ResultExt;
 use masking::Secret;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    connector::utils::PaymentsAuthorizeRequestData,
+    connector::utils::{
+        self, AddressDetailsData, CardData, PaymentsAuthorizeRequestData, RouterData,
+    },
     core::errors,
-    types::{self, api, storage::enums},
+    types::{self, api, storage::enums, transformers::ForeignFrom},
 };
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[derive(Debug, Serialize)]
 pub struct FortePaymentsRequest {
-    amount: i64,
-    card: ForteCard,
+    action: ForteAction,
+    authorization_amount: f64,
+    billing_address: BillingAddress,
+    card: Card,
 }
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
-pub struct ForteCard {
-    name: Secret<String>,
-    number: Secret<String, common_utils::pii::CardNumber>,
-    expiry_month: Secret<String>,
-    expiry_year: Secret<String>,
-    cvc: Secret<String>,
-    complete: bool,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BillingAddress {
+    first_name: Secret<String>,
+    last_name: Secret<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Card {
+    card_type: ForteCardType,
+    name_on_card: Secret<String>,
+    account_number: Secret<String, common_utils::pii::CardNumber>,
+    expire_month: Secret<String>,
+    expire_year: Secret<String>,
+    card_verification_value: Secret<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForteCardType {
+    Visa,
+    #[serde(rename = "mast")]
+    MasterCard,
+    Amex,
+    #[serde(rename = "disc")]
+    Discover,
+}
+
+impl TryFrom<utils::CardIssuer> for ForteCardType {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(issuer: utils::CardIssuer) -> Result<Self, Self::Error> {
+        match issuer {
+            utils::CardIssuer::AmericanExpress => Ok(Self::Amex),
+            utils::CardIssuer::Master => Ok(Self::MasterCard),
+            utils::CardIssuer::Discover => Ok(Self::Discover),
+            utils::CardIssuer::Visa => Ok(Self::Visa),
+            _ => Err(errors::ConnectorError::NotSupported {
+                payment_method: api::enums::PaymentMethod::Card.to_string(),
+                connector: "Forte",
+                payment_experience: api::enums::PaymentExperience::RedirectToUrl.to_string(),
+            }
+            .into()),
+        }
+    }
 }
 
 impl TryFrom<&types::PaymentsAuthorizeRouterData> for FortePaymentsRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
-        match item.request.payment_method_data.clone() {
-            api::PaymentMethodData::Card(req_card) => {
-                let card = ForteCard {
-                    name: req_card.card_holder_name,
-                    number: req_card.card_number,
-                    expiry_month: req_card.card_exp_month,
-                    expiry_year: req_card.card_exp_year,
-                    cvc: req_card.card_cvc,
-                    complete: item.request.is_auto_capture()?,
+        match item.request.payment_method_data {
+            api::PaymentMethodData::Card(ref ccard) => {
+                let action = match item.request.is_auto_capture()? {
+                    true => ForteAction::Sale,
+                    false => ForteAction::Authorize,
+                };
+                let card = Card {
+                    card_type: ForteCardType::try_from(ccard.get_card_issuer()?)?,
+                    name_on_card: ccard.card_holder_name.clone(),
+                    account_number: ccard.card_number.clone(),
+                    expire_month: ccard.card_exp_month.clone(),
+                    expire_year: ccard.card_exp_year.clone(),
+                    card_verification_value: ccard.card_cvc.clone(),
                 };
+                let address = item.get_billing_address()?;
+                let billing_address = BillingAddress {
+                    first_name: address.get_first_name()?.to_owned(),
+                    last_name: address.get_last_name()?.to_owned(),
+                };
+                let authorization_amount =
+                    utils::to_currency_base_unit_asf64(item.request.amount, item.request.currency)?;
                 Ok(Self {
-                    amount: item.request.amount,
+                    action,
+                    authorization_amount,
+                    billing_address,
                     card,
                 })
             }
@@ -48,44 +101,141 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for FortePaymentsRequest {
 
 // Auth Struct
 pub struct ForteAuthType {
-    pub(super) api_key: String,
+    pub(super) api_access_id: String,
+    pub(super) organization_id: String,
+    pub(super) location_id: String,
+    pub(super) api_secret_key: String,
 }
 
 impl TryFrom<&types::ConnectorAuthType> for ForteAuthType {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(auth_type: &types::ConnectorAuthType) -> Result<Self, Self::Error> {
         match auth_type {
-            types::ConnectorAuthType::HeaderKey { api_key } => Ok(Self {
-                api_key: api_key.to_string(),
+            types::ConnectorAuthType::MultiAuthKey {
+                api_key,
+                key1,
+                api_secret,
+                key2,
+            } => Ok(Self {
+                api_access_id: api_key.to_string(),
+                organization_id: key1.to_string(),
+                location_id: key2.to_string(),
+                api_secret_key: api_secret.to_string(),
             }),
             _ => Err(errors::ConnectorError::FailedToObtainAuthType.into()),
         }
     }
 }
+
 // PaymentsResponse
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum FortePaymentStatus {
-    Succeeded,
+    Complete,
     Failed,
-    #[default]
-    Processing,
+    Authorized,
+    Ready,
+    Voided,
+    Settled,
+    Settling,
+    Declined,
+    Rejected,
+    Review,
 }
 
 impl From<FortePaymentStatus> for enums::AttemptStatus {
     fn from(item: FortePaymentStatus) -> Self {
         match item {
-            FortePaymentStatus::Succeeded => Self::Charged,
-            FortePaymentStatus::Failed => Self::Failure,
-            FortePaymentStatus::Processing => Self::Authorizing,
+            FortePaymentStatus::Complete
+            | FortePaymentStatus::Settled
+            | FortePaymentStatus::Settling => Self::Charged,
+            FortePaymentStatus::Authorized => Self::Authorized,
+            FortePaymentStatus::Ready | FortePaymentStatus::Review => Self::Pending,
+            FortePaymentStatus::Voided => Self::Voided,
+            FortePaymentStatus::Failed
+            | FortePaymentStatus::Declined
+            | FortePaymentStatus::Rejected => Self::Failure,
+        }
+    }
+}
+
+/// Outcome of a transaction request, `A` denoting an approval, `D` a decline and `E` an error
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ForteResponseType {
+    #[serde(rename = "A")]
+    Approved,
+    #[serde(rename = "D")]
+    Declined,
+    #[serde(rename = "E")]
+    Error,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ForteAction {
+    Sale,
+    Authorize,
+    Capture,
+    Void,
+    Reverse,
+    Verify,
+}
+
+impl ForeignFrom<(ForteResponseType, ForteAction)> for enums::AttemptStatus {
+    fn foreign_from((response_type, action): (ForteResponseType, ForteAction)) -> Self {
+        match response_type {
+            ForteResponseType::Approved => match action {
+                ForteAction::Sale | ForteAction::Capture => Self::Charged,
+                ForteAction::Authorize => Self::Authorized,
+                ForteAction::Void => Self::Voided,
+                ForteAction::Reverse | ForteAction::Verify => Self::Pending,
+            },
+            ForteResponseType::Declined | ForteResponseType::Error => match action {
+                ForteAction::Capture => Self::CaptureFailed,
+                ForteAction::Void => Self::VoidFailed,
+                _ => Self::AuthorizationFailed,
+            },
+            ForteResponseType::Unknown => Self::Pending,
         }
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize)]
+pub struct ResponseStatus {
+    pub environment: Option<String>,
+    pub response_type: Option<ForteResponseType>,
+    pub response_code: Option<String>,
+    pub response_desc: String,
+    pub authorization_code: Option<String>,
+}
+
+/// Details of the authorization, needed for capturing, voiding and refunding a payment
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForteMeta {
+    pub auth_id: String,
+}
+
+fn get_connector_metadata(
+    authorization_code: Option<String>,
+) -> Result<Option<serde_json::Value>, error_stack::Report<errors::ConnectorError>> {
+    authorization_code
+        .map(|auth_id| {
+            common_utils::ext_traits::Encode::<ForteMeta>::encode_to_value(&ForteMeta { auth_id })
+        })
+        .transpose()
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+}
+
+#[derive(Debug, Deserialize)]
 pub struct FortePaymentsResponse {
-    status: FortePaymentStatus,
-    id: String,
+    pub transaction_id: String,
+    pub location_id: String,
+    pub action: ForteAction,
+    pub authorization_amount: Option<f64>,
+    pub authorization_code: Option<String>,
+    pub response: ResponseStatus,
 }
 
 impl<F, T>
@@ -95,61 +245,174 @@ impl<F, T>
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(
         item: types::ResponseRouterData<F, FortePaymentsResponse, T, types::PaymentsResponseData>,
+    ) -> Result<Self, Self::Error> {
+        let response_type = item
+            .response
+            .response
+            .response_type
+            .unwrap_or(ForteResponseType::Unknown);
+        Ok(Self {
+            status: enums::AttemptStatus::foreign_from((response_type, item.response.action)),
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(
+                    item.response.transaction_id,
+                ),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: get_connector_metadata(item.response.authorization_code)?,
+            }),
+            ..item.data
+        })
+    }
+}
+
+// PaymentsSyncResponse, also sent as the `data` of webhooks
+#[derive(Debug, Deserialize)]
+pub struct FortePaymentsSyncResponse {
+    pub transaction_id: String,
+    pub location_id: String,
+    pub status: FortePaymentStatus,
+    pub action: ForteAction,
+    pub authorization_amount: Option<f64>,
+    pub authorization_code: Option<String>,
+}
+
+impl<F, T>
+    TryFrom<types::ResponseRouterData<F, FortePaymentsSyncResponse, T, types::PaymentsResponseData>>
+    for types::RouterData<F, T, types::PaymentsResponseData>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<
+            F,
+            FortePaymentsSyncResponse,
+            T,
+            types::PaymentsResponseData,
+        >,
     ) -> Result<Self, Self::Error> {
         Ok(Self {
             status: enums::AttemptStatus::from(item.response.status),
             response: Ok(types::PaymentsResponseData::TransactionResponse {
-                resource_id: types::ResponseId::ConnectorTransactionId(item.response.id),
+                resource_id: types::ResponseId::ConnectorTransactionId(
+                    item.response.transaction_id,
+                ),
                 redirection_data: None,
                 mandate_reference: None,
-                connector_metadata: None,
+                connector_metadata: get_connector_metadata(item.response.authorization_code)?,
             }),
             ..item.data
         })
     }
 }
 
+// Capture
+#[derive(Debug, Serialize)]
+pub struct ForteCaptureRequest {
+    action: ForteAction,
+    transaction_id: String,
+    authorization_code: String,
+}
+
+impl TryFrom<&types::PaymentsCaptureRouterData> for ForteCaptureRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCaptureRouterData) -> Result<Self, Self::Error> {
+        let metadata: ForteMeta = utils::to_connector_meta(item.request.connector_meta.clone())?;
+        Ok(Self {
+            action: ForteAction::Capture,
+            transaction_id: item.request.connector_transaction_id.to_string(),
+            authorization_code: metadata.auth_id,
+        })
+    }
+}
+
+// Void
+#[derive(Debug, Serialize)]
+pub struct ForteCancelRequest {
+    action: ForteAction,
+    authorization_code: String,
+}
+
+impl TryFrom<&types::PaymentsCancelRouterData> for ForteCancelRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCancelRouterData) -> Result<Self, Self::Error> {
+        let metadata: ForteMeta = utils::to_connector_meta(item.request.connector_meta.clone())?;
+        Ok(Self {
+            action: ForteAction::Void,
+            authorization_code: metadata.auth_id,
+        })
+    }
+}
+
 // REFUND :
 // Type definition for RefundRequest
-#[derive(Default, Debug, Serialize)]
+#[derive(Debug, Serialize)]
 pub struct ForteRefundRequest {
-    pub amount: i64,
+    action: ForteAction,
+    authorization_amount: f64,
+    original_transaction_id: String,
+    authorization_code: String,
 }
 
 impl<F> TryFrom<&types::RefundsRouterData<F>> for ForteRefundRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
+        let metadata: ForteMeta =
+            utils::to_connector_meta(item.request.connector_metadata.clone())?;
+        let authorization_amount =
+            utils::to_currency_base_unit_asf64(item.request.refund_amount, item.request.currency)?;
         Ok(Self {
-            amount: item.request.amount,
+            action: ForteAction::Reverse,
+            authorization_amount,
+            original_transaction_id: item.request.connector_transaction_id.to_string(),
+            authorization_code: metadata.auth_id,
         })
     }
 }
 
 // Type definition for Refund Response
-
-#[allow(dead_code)]
-#[derive(Debug, Serialize, Default, Deserialize, Clone)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RefundStatus {
-    Succeeded,
+    Complete,
+    Ready,
     Failed,
-    #[default]
-    Processing,
+    Declined,
+    Rejected,
+    Settled,
+    Settling,
+    Review,
 }
 
 impl From<RefundStatus> for enums::RefundStatus {
     fn from(item: RefundStatus) -> Self {
         match item {
-            RefundStatus::Succeeded => Self::Success,
-            RefundStatus::Failed => Self::Failure,
-            RefundStatus::Processing => Self::Pending,
+            RefundStatus::Complete | RefundStatus::Settled | RefundStatus::Settling => {
+                Self::Success
+            }
+            RefundStatus::Ready | RefundStatus::Review => Self::Pending,
+            RefundStatus::Failed | RefundStatus::Declined | RefundStatus::Rejected => Self::Failure,
+        }
+    }
+}
+
+impl From<ForteResponseType> for enums::RefundStatus {
+    fn from(item: ForteResponseType) -> Self {
+        match item {
+            ForteResponseType::Approved => Self::Pending,
+            ForteResponseType::Declined | ForteResponseType::Error => Self::Failure,
+            ForteResponseType::Unknown => Self::Pending,
         }
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Deserialize)]
 pub struct RefundResponse {
-    id: String,
-    status: RefundStatus,
+    pub transaction_id: String,
+    pub original_transaction_id: String,
+    pub action: ForteAction,
+    pub authorization_amount: Option<f64>,
+    pub authorization_code: Option<String>,
+    pub response: ResponseStatus,
 }
 
 impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
@@ -159,26 +422,38 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
     fn try_from(
         item: types::RefundsResponseRouterData<api::Execute, RefundResponse>,
     ) -> Result<Self, Self::Error> {
+        let response_type = item
+            .response
+            .response
+            .response_type
+            .unwrap_or(ForteResponseType::Unknown);
         Ok(Self {
             response: Ok(types::RefundsResponseData {
-                connector_refund_id: item.response.id.to_string(),
-                refund_status: enums::RefundStatus::from(item.response.status),
+                connector_refund_id: item.response.transaction_id,
+                // Approved reversals are only complete once they are settled
+                refund_status: enums::RefundStatus::from(response_type),
             }),
             ..item.data
         })
     }
 }
 
-impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
+#[derive(Debug, Deserialize)]
+pub struct RefundSyncResponse {
+    pub transaction_id: String,
+    pub status: RefundStatus,
+}
+
+impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundSyncResponse>>
     for types::RefundsRouterData<api::RSync>
 {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(
-        item: types::RefundsResponseRouterData<api::RSync, RefundResponse>,
+        item: types::RefundsResponseRouterData<api::RSync, RefundSyncResponse>,
     ) -> Result<Self, Self::Error> {
         Ok(Self {
             response: Ok(types::RefundsResponseData {
-                connector_refund_id: item.response.id.to_string(),
+                connector_refund_id: item.response.transaction_id,
                 refund_status: enums::RefundStatus::from(item.response.status),
             }),
             ..item.data
@@ -186,10 +461,69 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
     }
 }
 
-#[derive(Default, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize)]
 pub struct ForteErrorResponse {
-    pub status_code: u16,
-    pub code: String,
-    pub message: String,
-    pub reason: Option<String>,
+    pub response: ResponseStatus,
+}
+
+// Webhooks
+#[derive(Debug, Deserialize)]
+pub struct ForteWebhookBody {
+    pub event_id: String,
+    pub resource: ForteWebhookResource,
+    pub data: FortePaymentsSyncResponse,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ForteWebhookResource {
+    Transaction,
+    #[serde(other)]
+    Other,
+}
+
+impl ForteWebhookBody {
+    pub fn is_refund(&self) -> bool {
+        self.data.action == ForteAction::Reverse
+    }
+}
+
+impl From<&ForteWebhookBody> for api::IncomingWebhookEvent {
+    fn from(item: &ForteWebhookBody) -> Self {
+        if item.resource != ForteWebhookResource::Transaction {
+            return Self::EventNotSupported;
+        }
+        match (item.is_refund(), &item.data.status) {
+            (
+                true,
+                FortePaymentStatus::Complete
+                | FortePaymentStatus::Settled
+                | FortePaymentStatus::Settling,
+            ) => Self::RefundSuccess,
+            (
+                true,
+                FortePaymentStatus::Failed
+                | FortePaymentStatus::Declined
+                | FortePaymentStatus::Rejected,
+            ) => Self::RefundFailure,
+            (true, _) => Self::EventNotSupported,
+            (
+                false,
+                FortePaymentStatus::Complete
+                | FortePaymentStatus::Settled
+                | FortePaymentStatus::Settling
+                | FortePaymentStatus::Authorized,
+            ) => Self::PaymentIntentSuccess,
+            (
+                false,
+                FortePaymentStatus::Failed
+                | FortePaymentStatus::Declined
+                | FortePaymentStatus::Rejected,
+            ) => Self::PaymentIntentFailure,
+            (false, FortePaymentStatus::Ready | FortePaymentStatus::Review) => {
+                Self::PaymentIntentProcessing
+            }
+            (false, FortePaymentStatus::Voided) => Self::EventNotSupported,
+        }
+    }
 }
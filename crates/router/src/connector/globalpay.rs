@@ -794,6 +794,40 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Globalpay {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Globalpay,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+                api::ConnectorFlow::Mandates,
+                api::ConnectorFlow::ThreeDs,
+            ],
+            payment_methods: vec![
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Card),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Wallet)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::GooglePay,
+                        api::enums::PaymentMethodType::Paypal,
+                    ]),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::BankRedirect)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::Eps,
+                        api::enums::PaymentMethodType::Giropay,
+                        api::enums::PaymentMethodType::Ideal,
+                        api::enums::PaymentMethodType::Sofort,
+                    ])
+                    .with_currencies(vec![api::enums::Currency::EUR]),
+            ],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Globalpay {
     fn get_webhook_source_verification_algorithm(
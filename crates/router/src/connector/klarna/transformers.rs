@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     core::errors,
-    types::{self, storage::enums},
+    types::{self, api, storage::enums},
 };
 
 #[derive(Default, Debug, Serialize)]
@@ -166,6 +166,7 @@ pub enum KlarnaFraudStatus {
     Accepted,
     #[default]
     Pending,
+    Rejected,
 }
 
 impl From<KlarnaFraudStatus> for enums::AttemptStatus {
@@ -173,6 +174,75 @@ impl From<KlarnaFraudStatus> for enums::AttemptStatus {
         match item {
             KlarnaFraudStatus::Accepted => Self::Charged,
             KlarnaFraudStatus::Pending => Self::Authorizing,
+            KlarnaFraudStatus::Rejected => Self::Failure,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KlarnaOrderResponse {
+    order_id: String,
+    fraud_status: KlarnaFraudStatus,
+}
+
+impl TryFrom<types::PaymentsSyncResponseRouterData<KlarnaOrderResponse>>
+    for types::PaymentsSyncRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::PaymentsSyncResponseRouterData<KlarnaOrderResponse>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(item.response.order_id),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+            }),
+            status: item.response.fraud_status.into(),
+            ..item.data
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum KlarnaWebhookEventType {
+    FraudRiskAccepted,
+    FraudRiskRejected,
+    FraudRiskStopped,
+    #[serde(other)]
+    Unknown,
+}
+
+impl From<KlarnaWebhookEventType> for api::IncomingWebhookEvent {
+    fn from(event: KlarnaWebhookEventType) -> Self {
+        match event {
+            KlarnaWebhookEventType::FraudRiskAccepted => Self::PaymentIntentSuccess,
+            KlarnaWebhookEventType::FraudRiskRejected
+            | KlarnaWebhookEventType::FraudRiskStopped => Self::PaymentIntentFailure,
+            KlarnaWebhookEventType::Unknown => Self::EventNotSupported,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KlarnaWebhookBody {
+    pub order_id: String,
+    pub event_type: KlarnaWebhookEventType,
+}
+
+impl From<KlarnaWebhookBody> for KlarnaOrderResponse {
+    fn from(item: KlarnaWebhookBody) -> Self {
+        let fraud_status = match item.event_type {
+            KlarnaWebhookEventType::FraudRiskAccepted => KlarnaFraudStatus::Accepted,
+            KlarnaWebhookEventType::FraudRiskRejected
+            | KlarnaWebhookEventType::FraudRiskStopped => KlarnaFraudStatus::Rejected,
+            KlarnaWebhookEventType::Unknown => KlarnaFraudStatus::Pending,
+        };
+        Self {
+            order_id: item.order_id,
+            fraud_status,
         }
     }
 }
@@ -11,6 +11,7 @@ use crate::{
         errors::{self, CustomResult},
         payments,
     },
+    db::StorageInterface,
     headers,
     services::{self, ConnectorIntegration},
     types::{
@@ -442,27 +443,78 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Mollie {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Mollie,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+            ],
+            payment_methods: vec![
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Wallet)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::ApplePay,
+                        api::enums::PaymentMethodType::Paypal,
+                    ]),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::BankRedirect)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::Eps,
+                        api::enums::PaymentMethodType::Giropay,
+                        api::enums::PaymentMethodType::Ideal,
+                        api::enums::PaymentMethodType::Sofort,
+                    ])
+                    .with_currencies(vec![api::enums::Currency::EUR]),
+            ],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Mollie {
-    fn get_webhook_object_reference_id(
+    async fn verify_webhook_source(
         &self,
+        _db: &dyn StorageInterface,
         _request: &api::IncomingWebhookRequestDetails<'_>,
+        _merchant_id: &str,
+    ) -> CustomResult<bool, errors::ConnectorError> {
+        // The webhook carries nothing but the payment id, the payment is always fetched
+        // from Mollie to learn its new status
+        Ok(false)
+    }
+
+    fn get_webhook_object_reference_id(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api_models::webhooks::ObjectReferenceId, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: mollie::MollieWebhookBody = serde_urlencoded::from_bytes(request.body)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookReferenceIdNotFound)?;
+        Ok(api_models::webhooks::ObjectReferenceId::PaymentId(
+            api_models::payments::PaymentIdType::ConnectorTransactionId(details.id),
+        ))
     }
 
     fn get_webhook_event_type(
         &self,
         _request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api::IncomingWebhookEvent, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        // Mollie does not say what changed, the status is known only after the sync
+        Ok(api::IncomingWebhookEvent::PaymentIntentProcessing)
     }
 
     fn get_webhook_resource_object(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<serde_json::Value, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: mollie::MollieWebhookBody = serde_urlencoded::from_bytes(request.body)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)?;
+        serde_json::to_value(details)
+            .into_report()
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)
     }
 }
 
@@ -426,3 +426,9 @@ pub struct ErrorResponse {
     #[serde(rename = "_links")]
     pub links: Option<Links>,
 }
+
+/// Mollie only posts the id of the payment whose status changed, as a form encoded body
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MollieWebhookBody {
+    pub id: String,
+}
@@ -1,3 +1,4 @@
+use base64::Engine;
 use common_utils::pii::Email;
 use masking::ExposeInterface;
 use serde::{Deserialize, Serialize};
@@ -5,6 +6,7 @@ use url::Url;
 
 use crate::{
     connector::utils::{self, AddressDetailsData, CardData, RouterData},
+    consts,
     core::errors,
     pii::{self, Secret},
     services,
@@ -380,14 +382,27 @@ pub enum MultisafepayPaymentStatus {
     Declined,
     #[default]
     Initialized,
+    Uncleared,
+    Cancelled,
+    Void,
+    Expired,
+    Refunded,
+    #[serde(rename = "partial_refunded")]
+    PartialRefunded,
 }
 
 impl From<MultisafepayPaymentStatus> for enums::AttemptStatus {
     fn from(item: MultisafepayPaymentStatus) -> Self {
         match item {
-            MultisafepayPaymentStatus::Completed => Self::Charged,
-            MultisafepayPaymentStatus::Declined => Self::Failure,
+            MultisafepayPaymentStatus::Completed
+            | MultisafepayPaymentStatus::Refunded
+            | MultisafepayPaymentStatus::PartialRefunded => Self::Charged,
+            MultisafepayPaymentStatus::Declined | MultisafepayPaymentStatus::Expired => {
+                Self::Failure
+            }
             MultisafepayPaymentStatus::Initialized => Self::AuthenticationPending,
+            MultisafepayPaymentStatus::Uncleared => Self::Pending,
+            MultisafepayPaymentStatus::Cancelled | MultisafepayPaymentStatus::Void => Self::Voided,
         }
     }
 }
@@ -586,3 +601,38 @@ pub struct MultisafepayErrorResponse {
     pub error_code: i32,
     pub error_info: String,
 }
+
+/// Body of a POST notification, which carries the order the same way the order API returns it
+#[derive(Debug, Deserialize)]
+pub struct MultisafepayWebhookBody {
+    pub order_id: String,
+    pub status: MultisafepayPaymentStatus,
+}
+
+impl From<MultisafepayPaymentStatus> for api::IncomingWebhookEvent {
+    fn from(status: MultisafepayPaymentStatus) -> Self {
+        match status {
+            MultisafepayPaymentStatus::Completed => Self::PaymentIntentSuccess,
+            MultisafepayPaymentStatus::Declined
+            | MultisafepayPaymentStatus::Cancelled
+            | MultisafepayPaymentStatus::Void
+            | MultisafepayPaymentStatus::Expired => Self::PaymentIntentFailure,
+            MultisafepayPaymentStatus::Initialized | MultisafepayPaymentStatus::Uncleared => {
+                Self::PaymentIntentProcessing
+            }
+            // Refunds are notified on the order, which does not identify the refund
+            MultisafepayPaymentStatus::Refunded | MultisafepayPaymentStatus::PartialRefunded => {
+                Self::EventNotSupported
+            }
+        }
+    }
+}
+
+/// The `Auth` header is the base64 encoding of `<timestamp>:<hex signature>`, where the
+/// signature is computed over `<timestamp>:<body>`
+pub fn get_webhook_auth(header: &str) -> Option<(String, String)> {
+    let decoded = consts::BASE64_ENGINE.decode(header).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (timestamp, signature) = decoded.split_once(':')?;
+    Some((timestamp.to_string(), signature.to_string()))
+}
@@ -2,12 +2,16 @@ mod transformers;
 
 use std::fmt::Debug;
 
-use error_stack::{IntoReport, ResultExt};
+use base64::Engine;
+use error_stack::ResultExt;
 use transformers as nexinets;
 
 use crate::{
     configs::settings,
+    connector::utils::{PaymentsAuthorizeRequestData, PaymentsSyncRequestData, RefundsRequestData},
+    consts,
     core::errors::{self, CustomResult},
+    db::StorageInterface,
     headers,
     services::{self, ConnectorIntegration},
     types::{
@@ -33,6 +37,20 @@ impl api::Refund for Nexinets {}
 impl api::RefundExecute for Nexinets {}
 impl api::RefundSync for Nexinets {}
 
+impl Nexinets {
+    fn order_transaction_url(
+        &self,
+        connectors: &settings::Connectors,
+        order_id: &str,
+        transaction_id: &str,
+    ) -> String {
+        format!(
+            "{}/orders/{order_id}/transactions/{transaction_id}",
+            self.base_url(connectors)
+        )
+    }
+}
+
 impl<Flow, Request, Response> ConnectorCommonExt<Flow, Request, Response> for Nexinets
 where
     Self: ConnectorIntegration<Flow, Request, Response>,
@@ -71,7 +89,9 @@ impl ConnectorCommon for Nexinets {
     ) -> CustomResult<Vec<(String, String)>, errors::ConnectorError> {
         let auth = nexinets::NexinetsAuthType::try_from(auth_type)
             .change_context(errors::ConnectorError::FailedToObtainAuthType)?;
-        Ok(vec![(headers::AUTHORIZATION.to_string(), auth.api_key)])
+        let raw_basic_token = format!("{}:{}", auth.merchant_id, auth.api_key);
+        let basic_token = format!("Basic {}", consts::BASE64_ENGINE.encode(raw_basic_token));
+        Ok(vec![(headers::AUTHORIZATION.to_string(), basic_token)])
     }
 
     fn build_error_response(
@@ -83,11 +103,20 @@ impl ConnectorCommon for Nexinets {
             .parse_struct("NexinetsErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
 
+        let (code, message, reason) = match response.errors.first() {
+            Some(error) => (
+                error.code.to_string(),
+                error.message.clone(),
+                error.field.clone(),
+            ),
+            None => (response.code.to_string(), response.message, None),
+        };
+
         Ok(ErrorResponse {
-            status_code: res.status_code,
-            code: response.code,
-            message: response.message,
-            reason: response.reason,
+            status_code: response.status,
+            code,
+            message,
+            reason,
         })
     }
 }
@@ -136,10 +165,15 @@ impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::P
 
     fn get_url(
         &self,
-        _req: &types::PaymentsAuthorizeRouterData,
-        _connectors: &settings::Connectors,
+        req: &types::PaymentsAuthorizeRouterData,
+        connectors: &settings::Connectors,
     ) -> CustomResult<String, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("get_url method".to_string()).into())
+        let operation = if req.request.is_auto_capture()? {
+            "debit"
+        } else {
+            "preauth"
+        };
+        Ok(format!("{}/orders/{operation}", self.base_url(connectors)))
     }
 
     fn get_request_body(
@@ -215,10 +249,15 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
 
     fn get_url(
         &self,
-        _req: &types::PaymentsSyncRouterData,
-        _connectors: &settings::Connectors,
+        req: &types::PaymentsSyncRouterData,
+        connectors: &settings::Connectors,
     ) -> CustomResult<String, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("get_url method".to_string()).into())
+        let order_id = nexinets::get_order_id(req.request.connector_meta.clone())?;
+        let transaction_id = req
+            .request
+            .get_connector_transaction_id()
+            .change_context(errors::ConnectorError::MissingConnectorTransactionID)?;
+        Ok(self.order_transaction_url(connectors, &order_id, &transaction_id))
     }
 
     fn build_request(
@@ -278,17 +317,31 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
 
     fn get_url(
         &self,
-        _req: &types::PaymentsCaptureRouterData,
-        _connectors: &settings::Connectors,
+        req: &types::PaymentsCaptureRouterData,
+        connectors: &settings::Connectors,
     ) -> CustomResult<String, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("get_url method".to_string()).into())
+        let order_id = nexinets::get_order_id(req.request.connector_meta.clone())?;
+        Ok(format!(
+            "{}/capture",
+            self.order_transaction_url(
+                connectors,
+                &order_id,
+                &req.request.connector_transaction_id
+            )
+        ))
     }
 
     fn get_request_body(
         &self,
-        _req: &types::PaymentsCaptureRouterData,
+        req: &types::PaymentsCaptureRouterData,
     ) -> CustomResult<Option<String>, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("get_request_body method".to_string()).into())
+        let req_obj = nexinets::NexinetsOrderTransactionRequest::try_from(req)?;
+        let nexinets_req =
+            utils::Encode::<nexinets::NexinetsOrderTransactionRequest>::encode_to_string_of_json(
+                &req_obj,
+            )
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(nexinets_req))
     }
 
     fn build_request(
@@ -304,6 +357,7 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
                 .headers(types::PaymentsCaptureType::get_headers(
                     self, req, connectors,
                 )?)
+                .body(types::PaymentsCaptureType::get_request_body(self, req)?)
                 .build(),
         ))
     }
@@ -336,6 +390,86 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
 impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsResponseData>
     for Nexinets
 {
+    fn get_headers(
+        &self,
+        req: &types::PaymentsCancelRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, String)>, errors::ConnectorError> {
+        self.build_headers(req, connectors)
+    }
+
+    fn get_content_type(&self) -> &'static str {
+        self.common_get_content_type()
+    }
+
+    fn get_url(
+        &self,
+        req: &types::PaymentsCancelRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        let order_id = nexinets::get_order_id(req.request.connector_meta.clone())?;
+        Ok(format!(
+            "{}/cancel",
+            self.order_transaction_url(
+                connectors,
+                &order_id,
+                &req.request.connector_transaction_id
+            )
+        ))
+    }
+
+    fn get_request_body(
+        &self,
+        req: &types::PaymentsCancelRouterData,
+    ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        let req_obj = nexinets::NexinetsOrderTransactionRequest::try_from(req)?;
+        let nexinets_req =
+            utils::Encode::<nexinets::NexinetsOrderTransactionRequest>::encode_to_string_of_json(
+                &req_obj,
+            )
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(nexinets_req))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::PaymentsCancelRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Post)
+                .url(&types::PaymentsVoidType::get_url(self, req, connectors)?)
+                .attach_default_headers()
+                .headers(types::PaymentsVoidType::get_headers(self, req, connectors)?)
+                .body(types::PaymentsVoidType::get_request_body(self, req)?)
+                .build(),
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        data: &types::PaymentsCancelRouterData,
+        res: Response,
+    ) -> CustomResult<types::PaymentsCancelRouterData, errors::ConnectorError> {
+        let response: nexinets::NexinetsPaymentsResponse = res
+            .response
+            .parse_struct("Nexinets PaymentsCancelResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        types::RouterData::try_from(types::ResponseRouterData {
+            response,
+            data: data.clone(),
+            http_code: res.status_code,
+        })
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+
+    fn get_error_response(
+        &self,
+        res: Response,
+    ) -> CustomResult<ErrorResponse, errors::ConnectorError> {
+        self.build_error_response(res)
+    }
 }
 
 impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsResponseData>
@@ -355,20 +489,30 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
 
     fn get_url(
         &self,
-        _req: &types::RefundsRouterData<api::Execute>,
-        _connectors: &settings::Connectors,
+        req: &types::RefundsRouterData<api::Execute>,
+        connectors: &settings::Connectors,
     ) -> CustomResult<String, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("get_url method".to_string()).into())
+        let order_id = nexinets::get_order_id(req.request.connector_metadata.clone())?;
+        Ok(format!(
+            "{}/refund",
+            self.order_transaction_url(
+                connectors,
+                &order_id,
+                &req.request.connector_transaction_id
+            )
+        ))
     }
 
     fn get_request_body(
         &self,
         req: &types::RefundsRouterData<api::Execute>,
     ) -> CustomResult<Option<String>, errors::ConnectorError> {
-        let req_obj = nexinets::NexinetsRefundRequest::try_from(req)?;
+        let req_obj = nexinets::NexinetsOrderTransactionRequest::try_from(req)?;
         let nexinets_req =
-            utils::Encode::<nexinets::NexinetsRefundRequest>::encode_to_string_of_json(&req_obj)
-                .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+            utils::Encode::<nexinets::NexinetsOrderTransactionRequest>::encode_to_string_of_json(
+                &req_obj,
+            )
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
         Ok(Some(nexinets_req))
     }
 
@@ -429,10 +573,17 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
 
     fn get_url(
         &self,
-        _req: &types::RefundSyncRouterData,
-        _connectors: &settings::Connectors,
+        req: &types::RefundSyncRouterData,
+        connectors: &settings::Connectors,
     ) -> CustomResult<String, errors::ConnectorError> {
-        Err(errors::ConnectorError::NotImplemented("get_url method".to_string()).into())
+        // Refunds are transactions of the payment's order, whose id is carried over from the
+        // payment attempt's connector metadata
+        let order_id = nexinets::get_order_id(req.request.connector_metadata.clone())?;
+        Ok(self.order_transaction_url(
+            connectors,
+            &order_id,
+            &req.request.get_connector_refund_id()?,
+        ))
     }
 
     fn build_request(
@@ -446,7 +597,6 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
                 .url(&types::RefundSyncType::get_url(self, req, connectors)?)
                 .attach_default_headers()
                 .headers(types::RefundSyncType::get_headers(self, req, connectors)?)
-                .body(types::RefundSyncType::get_request_body(self, req)?)
                 .build(),
         ))
     }
@@ -476,26 +626,76 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Nexinets {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Nexinets,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Card,
+            )],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Nexinets {
-    fn get_webhook_object_reference_id(
+    async fn verify_webhook_source(
         &self,
+        _db: &dyn StorageInterface,
         _request: &api::IncomingWebhookRequestDetails<'_>,
+        _merchant_id: &str,
+    ) -> CustomResult<bool, errors::ConnectorError> {
+        // Nexinets does not sign its notifications, so they are never trusted as is and only
+        // trigger a sync of the referenced payment or refund
+        Ok(false)
+    }
+
+    fn get_webhook_object_reference_id(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api::webhooks::ObjectReferenceId, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: nexinets::NexinetsWebhookBody = request
+            .body
+            .parse_struct("NexinetsWebhookBody")
+            .change_context(errors::ConnectorError::WebhookReferenceIdNotFound)?;
+
+        Ok(if details.is_refund() {
+            api_models::webhooks::ObjectReferenceId::RefundId(
+                api_models::webhooks::RefundIdType::ConnectorRefundId(details.transaction_id),
+            )
+        } else {
+            api_models::webhooks::ObjectReferenceId::PaymentId(
+                api_models::payments::PaymentIdType::ConnectorTransactionId(details.transaction_id),
+            )
+        })
     }
 
     fn get_webhook_event_type(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<api::IncomingWebhookEvent, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        let details: nexinets::NexinetsWebhookBody = request
+            .body
+            .parse_struct("NexinetsWebhookBody")
+            .change_context(errors::ConnectorError::WebhookEventTypeNotFound)?;
+        Ok(api::IncomingWebhookEvent::from(&details))
     }
 
     fn get_webhook_resource_object(
         &self,
-        _request: &api::IncomingWebhookRequestDetails<'_>,
+        request: &api::IncomingWebhookRequestDetails<'_>,
     ) -> CustomResult<serde_json::Value, errors::ConnectorError> {
-        Err(errors::ConnectorError::WebhooksNotImplemented).into_report()
+        request
+            .body
+            .parse_struct::<serde_json::Value>("NexinetsWebhookBody")
+            .change_context(errors::ConnectorError::WebhookResourceObjectNotFound)
     }
 }
@@ -1,26 +1,56 @@
+use error_stack::{IntoReport, ResultExt};
 use masking::Secret;
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 use crate::{
-    connector::utils::PaymentsAuthorizeRequestData,
+    connector::utils::{self, CardData, PaymentsAuthorizeRequestData, PaymentsCancelRequestData},
     core::errors,
+    services,
     types::{self, api, storage::enums},
 };
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct NexinetsPaymentsRequest {
-    amount: i64,
-    card: NexinetsCard,
+    initial_amount: i64,
+    currency: enums::Currency,
+    channel: NexinetsChannel,
+    product: NexinetsProduct,
+    merchant_order_id: String,
+    payment_instrument: NexinetsCard,
+    #[serde(rename = "async")]
+    nexinets_async: NexinetsAsyncDetails,
 }
 
-#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum NexinetsChannel {
+    Ecom,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NexinetsProduct {
+    Creditcard,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct NexinetsCard {
-    name: Secret<String>,
-    number: Secret<String, common_utils::pii::CardNumber>,
+    card_number: Secret<String, common_utils::pii::CardNumber>,
     expiry_month: Secret<String>,
     expiry_year: Secret<String>,
-    cvc: Secret<String>,
-    complete: bool,
+    verification: Secret<String>,
+    card_holder_name: Secret<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NexinetsAsyncDetails {
+    success_url: String,
+    failure_url: String,
+    cancel_url: String,
 }
 
 impl TryFrom<&types::PaymentsAuthorizeRouterData> for NexinetsPaymentsRequest {
@@ -28,17 +58,26 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for NexinetsPaymentsRequest {
     fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
         match item.request.payment_method_data.clone() {
             api::PaymentMethodData::Card(req_card) => {
-                let card = NexinetsCard {
-                    name: req_card.card_holder_name,
-                    number: req_card.card_number,
+                let return_url = item.request.get_return_url()?;
+                let payment_instrument = NexinetsCard {
+                    expiry_year: req_card.get_card_expiry_year_2_digit(),
+                    card_number: req_card.card_number,
                     expiry_month: req_card.card_exp_month,
-                    expiry_year: req_card.card_exp_year,
-                    cvc: req_card.card_cvc,
-                    complete: item.request.is_auto_capture()?,
+                    verification: req_card.card_cvc,
+                    card_holder_name: req_card.card_holder_name,
                 };
                 Ok(Self {
-                    amount: item.request.amount,
-                    card,
+                    initial_amount: item.request.amount,
+                    currency: item.request.currency,
+                    channel: NexinetsChannel::Ecom,
+                    product: NexinetsProduct::Creditcard,
+                    merchant_order_id: item.attempt_id.clone(),
+                    payment_instrument,
+                    nexinets_async: NexinetsAsyncDetails {
+                        success_url: return_url.clone(),
+                        failure_url: return_url.clone(),
+                        cancel_url: return_url,
+                    },
                 })
             }
             _ => Err(errors::ConnectorError::NotImplemented("Payment methods".to_string()).into()),
@@ -48,6 +87,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for NexinetsPaymentsRequest {
 
 // Auth Struct
 pub struct NexinetsAuthType {
+    pub(super) merchant_id: String,
     pub(super) api_key: String,
 }
 
@@ -55,38 +95,98 @@ impl TryFrom<&types::ConnectorAuthType> for NexinetsAuthType {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(auth_type: &types::ConnectorAuthType) -> Result<Self, Self::Error> {
         match auth_type {
-            types::ConnectorAuthType::HeaderKey { api_key } => Ok(Self {
+            types::ConnectorAuthType::BodyKey { api_key, key1 } => Ok(Self {
+                merchant_id: key1.to_string(),
                 api_key: api_key.to_string(),
             }),
             _ => Err(errors::ConnectorError::FailedToObtainAuthType.into()),
         }
     }
 }
+
 // PaymentsResponse
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum NexinetsPaymentStatus {
-    Succeeded,
-    Failed,
-    #[default]
-    Processing,
+    Success,
+    Pending,
+    Failure,
+    Expired,
+    Aborted,
 }
 
-impl From<NexinetsPaymentStatus> for enums::AttemptStatus {
-    fn from(item: NexinetsPaymentStatus) -> Self {
-        match item {
-            NexinetsPaymentStatus::Succeeded => Self::Charged,
-            NexinetsPaymentStatus::Failed => Self::Failure,
-            NexinetsPaymentStatus::Processing => Self::Authorizing,
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum NexinetsTransactionType {
+    Preauth,
+    Debit,
+    Capture,
+    Cancel,
+    Refund,
+}
+
+fn get_status(
+    status: &NexinetsPaymentStatus,
+    transaction_type: &NexinetsTransactionType,
+    is_redirection_required: bool,
+) -> enums::AttemptStatus {
+    match (status, transaction_type) {
+        (NexinetsPaymentStatus::Success, NexinetsTransactionType::Preauth) => {
+            enums::AttemptStatus::Authorized
+        }
+        (
+            NexinetsPaymentStatus::Success,
+            NexinetsTransactionType::Debit
+            | NexinetsTransactionType::Capture
+            | NexinetsTransactionType::Refund,
+        ) => enums::AttemptStatus::Charged,
+        (NexinetsPaymentStatus::Success, NexinetsTransactionType::Cancel) => {
+            enums::AttemptStatus::Voided
+        }
+        (
+            NexinetsPaymentStatus::Pending,
+            NexinetsTransactionType::Preauth | NexinetsTransactionType::Debit,
+        ) if is_redirection_required => enums::AttemptStatus::AuthenticationPending,
+        (NexinetsPaymentStatus::Pending, NexinetsTransactionType::Capture) => {
+            enums::AttemptStatus::CaptureInitiated
+        }
+        (NexinetsPaymentStatus::Pending, NexinetsTransactionType::Cancel) => {
+            enums::AttemptStatus::VoidInitiated
+        }
+        (NexinetsPaymentStatus::Pending, _) => enums::AttemptStatus::Pending,
+        (NexinetsPaymentStatus::Failure, NexinetsTransactionType::Preauth) => {
+            enums::AttemptStatus::AuthorizationFailed
+        }
+        (NexinetsPaymentStatus::Failure, NexinetsTransactionType::Capture) => {
+            enums::AttemptStatus::CaptureFailed
+        }
+        (NexinetsPaymentStatus::Failure, NexinetsTransactionType::Cancel) => {
+            enums::AttemptStatus::VoidFailed
+        }
+        (NexinetsPaymentStatus::Failure, _) => enums::AttemptStatus::Failure,
+        (NexinetsPaymentStatus::Expired | NexinetsPaymentStatus::Aborted, _) => {
+            enums::AttemptStatus::Failure
         }
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Nexinets addresses every follow-up operation by order and transaction, so the order id is
+/// stored in the payment attempt's connector metadata
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NexinetsPaymentsMetadata {
+    pub order_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct NexinetsPaymentsResponse {
-    status: NexinetsPaymentStatus,
-    id: String,
+    pub order_id: String,
+    pub transaction_id: String,
+    pub transaction_type: NexinetsTransactionType,
+    pub status: NexinetsPaymentStatus,
+    pub redirect_url: Option<Url>,
 }
 
 impl<F, T>
@@ -102,72 +202,111 @@ impl<F, T>
             types::PaymentsResponseData,
         >,
     ) -> Result<Self, Self::Error> {
+        let redirection_data = item
+            .response
+            .redirect_url
+            .map(|url| services::RedirectForm::from((url, services::Method::Get)));
+        let connector_metadata = serde_json::to_value(NexinetsPaymentsMetadata {
+            order_id: item.response.order_id,
+        })
+        .into_report()
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)?;
         Ok(Self {
-            status: enums::AttemptStatus::from(item.response.status),
+            status: get_status(
+                &item.response.status,
+                &item.response.transaction_type,
+                redirection_data.is_some(),
+            ),
             response: Ok(types::PaymentsResponseData::TransactionResponse {
-                resource_id: types::ResponseId::ConnectorTransactionId(item.response.id),
-                redirection_data: None,
+                resource_id: types::ResponseId::ConnectorTransactionId(
+                    item.response.transaction_id,
+                ),
+                redirection_data,
                 mandate_reference: None,
-                connector_metadata: None,
+                connector_metadata: Some(connector_metadata),
             }),
             ..item.data
         })
     }
 }
 
-// REFUND :
-// Type definition for RefundRequest
-#[derive(Default, Debug, Serialize)]
-pub struct NexinetsRefundRequest {
-    pub amount: i64,
+// Capture, cancel and refund share the same request body
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NexinetsOrderTransactionRequest {
+    initial_amount: i64,
+    currency: enums::Currency,
 }
 
-impl<F> TryFrom<&types::RefundsRouterData<F>> for NexinetsRefundRequest {
+impl TryFrom<&types::PaymentsCaptureRouterData> for NexinetsOrderTransactionRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
-    fn try_from(item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
+    fn try_from(item: &types::PaymentsCaptureRouterData) -> Result<Self, Self::Error> {
         Ok(Self {
-            amount: item.request.amount,
+            initial_amount: item.request.amount_to_capture,
+            currency: item.request.currency,
         })
     }
 }
 
-// Type definition for Refund Response
+impl TryFrom<&types::PaymentsCancelRouterData> for NexinetsOrderTransactionRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCancelRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            initial_amount: item.request.get_amount()?,
+            currency: item.request.get_currency()?,
+        })
+    }
+}
 
-#[allow(dead_code)]
-#[derive(Debug, Serialize, Default, Deserialize, Clone)]
-pub enum RefundStatus {
-    Succeeded,
-    Failed,
-    #[default]
-    Processing,
+impl<F> TryFrom<&types::RefundsRouterData<F>> for NexinetsOrderTransactionRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            initial_amount: item.request.refund_amount,
+            currency: item.request.currency,
+        })
+    }
+}
+
+pub fn get_order_id(
+    connector_meta: Option<serde_json::Value>,
+) -> Result<String, error_stack::Report<errors::ConnectorError>> {
+    let meta: NexinetsPaymentsMetadata = utils::to_connector_meta(connector_meta)?;
+    Ok(meta.order_id)
 }
 
-impl From<RefundStatus> for enums::RefundStatus {
-    fn from(item: RefundStatus) -> Self {
+// REFUND :
+
+impl From<NexinetsPaymentStatus> for enums::RefundStatus {
+    fn from(item: NexinetsPaymentStatus) -> Self {
         match item {
-            RefundStatus::Succeeded => Self::Success,
-            RefundStatus::Failed => Self::Failure,
-            RefundStatus::Processing => Self::Pending,
+            NexinetsPaymentStatus::Success => Self::Success,
+            NexinetsPaymentStatus::Pending => Self::Pending,
+            NexinetsPaymentStatus::Failure
+            | NexinetsPaymentStatus::Expired
+            | NexinetsPaymentStatus::Aborted => Self::Failure,
         }
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RefundResponse {
-    id: String,
-    status: RefundStatus,
+    pub order_id: String,
+    pub transaction_id: String,
+    pub status: NexinetsPaymentStatus,
 }
 
-impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
-    for types::RefundsRouterData<api::Execute>
+impl<F> TryFrom<types::RefundsResponseRouterData<F, RefundResponse>>
+    for types::RefundsRouterData<F>
 {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(
-        item: types::RefundsResponseRouterData<api::Execute, RefundResponse>,
+        item: types::RefundsResponseRouterData<F, RefundResponse>,
     ) -> Result<Self, Self::Error> {
         Ok(Self {
             response: Ok(types::RefundsResponseData {
-                connector_refund_id: item.response.id.to_string(),
+                connector_refund_id: item.response.transaction_id,
                 refund_status: enums::RefundStatus::from(item.response.status),
             }),
             ..item.data
@@ -175,27 +314,68 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
     }
 }
 
-impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
-    for types::RefundsRouterData<api::RSync>
-{
-    type Error = error_stack::Report<errors::ConnectorError>;
-    fn try_from(
-        item: types::RefundsResponseRouterData<api::RSync, RefundResponse>,
-    ) -> Result<Self, Self::Error> {
-        Ok(Self {
-            response: Ok(types::RefundsResponseData {
-                connector_refund_id: item.response.id.to_string(),
-                refund_status: enums::RefundStatus::from(item.response.status),
-            }),
-            ..item.data
-        })
-    }
+#[derive(Debug, Deserialize)]
+pub struct NexinetsErrorResponse {
+    pub status: u16,
+    pub code: u16,
+    pub message: String,
+    #[serde(default)]
+    pub errors: Vec<OrderErrorDetails>,
 }
 
-#[derive(Default, Debug, Serialize, Deserialize, PartialEq)]
-pub struct NexinetsErrorResponse {
-    pub status_code: u16,
-    pub code: String,
+#[derive(Debug, Deserialize)]
+pub struct OrderErrorDetails {
+    pub code: u16,
     pub message: String,
-    pub reason: Option<String>,
+    pub field: Option<String>,
+}
+
+// WEBHOOKS :
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NexinetsWebhookBody {
+    pub order_id: String,
+    pub transaction_id: String,
+    pub transaction_type: NexinetsTransactionType,
+    pub status: NexinetsPaymentStatus,
+}
+
+impl NexinetsWebhookBody {
+    pub fn is_refund(&self) -> bool {
+        self.transaction_type == NexinetsTransactionType::Refund
+    }
+}
+
+impl From<&NexinetsWebhookBody> for api::IncomingWebhookEvent {
+    fn from(item: &NexinetsWebhookBody) -> Self {
+        match (&item.transaction_type, &item.status) {
+            (NexinetsTransactionType::Refund, NexinetsPaymentStatus::Success) => {
+                Self::RefundSuccess
+            }
+            (
+                NexinetsTransactionType::Refund,
+                NexinetsPaymentStatus::Failure
+                | NexinetsPaymentStatus::Expired
+                | NexinetsPaymentStatus::Aborted,
+            ) => Self::RefundFailure,
+            (
+                NexinetsTransactionType::Preauth
+                | NexinetsTransactionType::Debit
+                | NexinetsTransactionType::Capture,
+                NexinetsPaymentStatus::Success,
+            ) => Self::PaymentIntentSuccess,
+            (
+                NexinetsTransactionType::Preauth | NexinetsTransactionType::Debit,
+                NexinetsPaymentStatus::Failure
+                | NexinetsPaymentStatus::Expired
+                | NexinetsPaymentStatus::Aborted,
+            ) => Self::PaymentIntentFailure,
+            (
+                NexinetsTransactionType::Preauth | NexinetsTransactionType::Debit,
+                NexinetsPaymentStatus::Pending,
+            ) => Self::PaymentIntentProcessing,
+            _ => Self::EventNotSupported,
+        }
+    }
 }
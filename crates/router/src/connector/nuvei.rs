@@ -828,6 +828,40 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
 
 impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponseData> for Nuvei {}
 
+impl api::ConnectorCapability for Nuvei {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Nuvei,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::Mandates,
+                api::ConnectorFlow::ThreeDs,
+            ],
+            payment_methods: vec![
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Card),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Wallet)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::ApplePay,
+                        api::enums::PaymentMethodType::GooglePay,
+                        api::enums::PaymentMethodType::Paypal,
+                    ]),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::BankRedirect)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::Eps,
+                        api::enums::PaymentMethodType::Giropay,
+                        api::enums::PaymentMethodType::Ideal,
+                        api::enums::PaymentMethodType::Sofort,
+                    ])
+                    .with_currencies(vec![api::enums::Currency::EUR]),
+            ],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Nuvei {
     fn get_webhook_source_verification_algorithm(
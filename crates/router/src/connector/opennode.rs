@@ -487,6 +487,22 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Opennode {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Opennode,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::PaymentSync,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Crypto,
+            )
+            .with_payment_method_types(vec![api::enums::PaymentMethodType::CryptoCurrency])],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Opennode {
     fn get_webhook_source_verification_algorithm(
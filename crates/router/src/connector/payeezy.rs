@@ -223,11 +223,13 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
         _req: &types::PaymentsSyncRouterData,
         _connectors: &settings::Connectors,
     ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        // Payeezy has no transaction lookup API and returns final statuses synchronously, the
+        // payments core keeps the stored status for connectors without the payment sync flow
         Err(errors::ConnectorError::FlowNotSupported {
-            flow: "Psync".to_owned(),
-            connector: "payeezy".to_owned(),
-        }
-        .into())
+            flow: "PSync".to_string(),
+            connector: "Payeezy".to_string(),
+        })
+        .into_report()
     }
 }
 
@@ -497,14 +499,35 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
         _req: &types::RefundSyncRouterData,
         _connectors: &settings::Connectors,
     ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        // Refunds are final once Payeezy responds, the refunds core keeps the stored status for
+        // connectors without the refund sync flow
         Err(errors::ConnectorError::FlowNotSupported {
-            flow: "Rsync".to_owned(),
-            connector: "payeezy".to_owned(),
+            flow: "RSync".to_string(),
+            connector: "Payeezy".to_string(),
+        })
+        .into_report()
+    }
+}
+
+impl api::ConnectorCapability for Payeezy {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Payeezy,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::Mandates,
+            ],
+            payment_methods: vec![api::PaymentMethodCapability::new(
+                api::enums::PaymentMethod::Card,
+            )],
         }
-        .into())
     }
 }
 
+// Payeezy does not send webhooks, every payment and refund status is final once it responds
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Payeezy {
     fn get_webhook_object_reference_id(
@@ -717,3 +717,59 @@ pub struct PaypalAccessTokenErrorResponse {
     pub error: String,
     pub error_description: String,
 }
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub enum PaypalWebhookEventType {
+    #[serde(rename = "PAYMENT.CAPTURE.COMPLETED")]
+    PaymentCaptureCompleted,
+    #[serde(rename = "PAYMENT.CAPTURE.PENDING")]
+    PaymentCapturePending,
+    #[serde(rename = "PAYMENT.CAPTURE.DENIED")]
+    PaymentCaptureDenied,
+    #[serde(rename = "PAYMENT.CAPTURE.DECLINED")]
+    PaymentCaptureDeclined,
+    #[serde(rename = "PAYMENT.CAPTURE.REFUNDED")]
+    PaymentCaptureRefunded,
+    #[serde(other)]
+    Unknown,
+}
+
+impl PaypalWebhookEventType {
+    pub fn is_refund_event(&self) -> bool {
+        *self == Self::PaymentCaptureRefunded
+    }
+}
+
+impl From<PaypalWebhookEventType> for api::IncomingWebhookEvent {
+    fn from(event: PaypalWebhookEventType) -> Self {
+        match event {
+            PaypalWebhookEventType::PaymentCaptureCompleted => Self::PaymentIntentSuccess,
+            PaypalWebhookEventType::PaymentCapturePending => Self::PaymentIntentProcessing,
+            PaypalWebhookEventType::PaymentCaptureDenied
+            | PaypalWebhookEventType::PaymentCaptureDeclined => Self::PaymentIntentFailure,
+            PaypalWebhookEventType::PaymentCaptureRefunded => Self::RefundSuccess,
+            PaypalWebhookEventType::Unknown => Self::EventNotSupported,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaypalWebhookResource {
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaypalWebhookEventTypeBody {
+    pub event_type: PaypalWebhookEventType,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaypalWebhookBody {
+    pub event_type: PaypalWebhookEventType,
+    pub resource: PaypalWebhookResource,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaypalWebhookResourceBody {
+    pub resource: serde_json::Value,
+}
@@ -597,3 +597,76 @@ pub struct PayuAccessTokenErrorResponse {
     pub error: String,
     pub error_description: String,
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayuWebhookOrder {
+    pub order_id: String,
+    pub status: OrderStatus,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayuWebhookRefund {
+    pub refund_id: String,
+    pub status: RefundStatus,
+}
+
+/// PayU sends an order notification when the order status changes and a refund notification
+/// when a refund is finalized or canceled
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PayuWebhookBody {
+    Order { order: PayuWebhookOrder },
+    Refund { refund: PayuWebhookRefund },
+}
+
+impl From<&PayuWebhookBody> for api::IncomingWebhookEvent {
+    fn from(item: &PayuWebhookBody) -> Self {
+        match item {
+            PayuWebhookBody::Order { order } => match order.status {
+                OrderStatus::Completed => Self::PaymentIntentSuccess,
+                OrderStatus::Canceled => Self::PaymentIntentFailure,
+                OrderStatus::Pending | OrderStatus::WaitingForConfirmation => {
+                    Self::PaymentIntentProcessing
+                }
+                OrderStatus::New => Self::EventNotSupported,
+            },
+            PayuWebhookBody::Refund { refund } => match refund.status {
+                RefundStatus::Finalized | RefundStatus::Completed => Self::RefundSuccess,
+                RefundStatus::Canceled => Self::RefundFailure,
+                RefundStatus::Pending => Self::EventNotSupported,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PayuWebhookResourceBody {
+    pub order: Option<serde_json::Value>,
+    pub refund: Option<serde_json::Value>,
+}
+
+impl PayuWebhookResourceBody {
+    /// Wraps an order notification into the shape of the order retrieve response, so that it
+    /// can be handled like a payment sync
+    pub fn into_resource_object(self) -> Option<serde_json::Value> {
+        match (self.order, self.refund) {
+            (Some(order), _) => Some(serde_json::json!({
+                "orders": [order],
+                "status": { "statusCode": PayuPaymentStatus::Success },
+            })),
+            (None, refund) => refund,
+        }
+    }
+}
+
+/// Parses the value of the `OpenPayu-Signature` header, which looks like
+/// `sender=checkout;signature=<hex>;algorithm=MD5;content=DOCUMENT`
+pub fn get_signature_from_header(header: &str) -> Option<&str> {
+    header
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .find(|(key, _)| *key == "signature")
+        .map(|(_, value)| value)
+}
@@ -56,6 +56,10 @@ impl ConnectorCommon for Shift4 {
         "shift4"
     }
 
+    fn get_error_category(&self, error_code: &str) -> api::enums::ErrorCategory {
+        shift4::get_error_category(error_code)
+    }
+
     fn common_get_content_type(&self) -> &'static str {
         "application/json"
     }
@@ -719,6 +723,33 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::ConnectorCapability for Shift4 {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Shift4,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+                api::ConnectorFlow::ThreeDs,
+            ],
+            payment_methods: vec![
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Card),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::BankRedirect)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::Eps,
+                        api::enums::PaymentMethodType::Giropay,
+                        api::enums::PaymentMethodType::Ideal,
+                        api::enums::PaymentMethodType::Sofort,
+                    ])
+                    .with_currencies(vec![api::enums::Currency::EUR]),
+            ],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Shift4 {
     fn get_webhook_object_reference_id(
@@ -599,3 +599,19 @@ pub struct ApiErrorResponse {
     pub code: Option<String>,
     pub message: String,
 }
+
+/// Maps the `code` of a Shift4 error to a connector agnostic category.
+pub fn get_error_category(error_code: &str) -> api::enums::ErrorCategory {
+    match error_code {
+        "insufficient_funds" => api::enums::ErrorCategory::InsufficientFunds,
+        "card_declined" => api::enums::ErrorCategory::DoNotHonor,
+        "expired_card" => api::enums::ErrorCategory::ExpiredCard,
+        "invalid_cvc" | "incorrect_cvc" => api::enums::ErrorCategory::IncorrectCvc,
+        "invalid_number" => api::enums::ErrorCategory::InvalidCardNumber,
+        "lost_or_stolen" => api::enums::ErrorCategory::LostOrStolenCard,
+        "suspected_fraud" | "blacklisted" => api::enums::ErrorCategory::FraudSuspected,
+        "limit_exceeded" => api::enums::ErrorCategory::LimitExceeded,
+        "processing_error" => api::enums::ErrorCategory::ProcessingError,
+        _ => api::enums::ErrorCategory::Unknown,
+    }
+}
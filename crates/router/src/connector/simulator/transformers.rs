@@ -0,0 +1,282 @@
+pub use api_models::simulator::{
+    SimulatorCaptureRequest, SimulatorCard, SimulatorDisputeResponse, SimulatorDisputeStatus,
+    SimulatorErrorResponse, SimulatorEvidenceRequest, SimulatorNextAction, SimulatorPaymentMethod,
+    SimulatorPaymentRequest, SimulatorPaymentResponse, SimulatorPaymentStatus,
+    SimulatorRefundRequest, SimulatorRefundResponse, SimulatorRefundStatus, SimulatorWebhook,
+    SimulatorWebhookEventType, SimulatorWebhookObject,
+};
+use error_stack::{IntoReport, ResultExt};
+
+use crate::{
+    connector::utils::{PaymentsAuthorizeRequestData, RouterData},
+    core::errors,
+    services,
+    types::{self, api, storage::enums, transformers::ForeignInto},
+};
+
+pub struct SimulatorAuthType {
+    pub(super) api_key: String,
+}
+
+impl TryFrom<&types::ConnectorAuthType> for SimulatorAuthType {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(auth_type: &types::ConnectorAuthType) -> Result<Self, Self::Error> {
+        if let types::ConnectorAuthType::HeaderKey { api_key } = auth_type {
+            Ok(Self {
+                api_key: api_key.to_string(),
+            })
+        } else {
+            Err(errors::ConnectorError::FailedToObtainAuthType.into())
+        }
+    }
+}
+
+fn get_payment_method(
+    payment_method_data: &api::PaymentMethodData,
+) -> Result<SimulatorPaymentMethod, error_stack::Report<errors::ConnectorError>> {
+    match payment_method_data {
+        api::PaymentMethodData::Card(card) => Ok(SimulatorPaymentMethod::Card(SimulatorCard {
+            number: card.card_number.clone(),
+            expiry_month: card.card_exp_month.clone(),
+            expiry_year: card.card_exp_year.clone(),
+            cvc: card.card_cvc.clone(),
+        })),
+        api::PaymentMethodData::Wallet(wallet_data) => {
+            let wallet_type = match wallet_data {
+                api_models::payments::WalletData::AliPay(_) => {
+                    api_models::enums::PaymentMethodType::AliPay
+                }
+                api_models::payments::WalletData::ApplePay(_) => {
+                    api_models::enums::PaymentMethodType::ApplePay
+                }
+                api_models::payments::WalletData::GooglePay(_) => {
+                    api_models::enums::PaymentMethodType::GooglePay
+                }
+                api_models::payments::WalletData::MbWay(_) => {
+                    api_models::enums::PaymentMethodType::MbWay
+                }
+                api_models::payments::WalletData::MobilePay(_) => {
+                    api_models::enums::PaymentMethodType::MobilePay
+                }
+                api_models::payments::WalletData::PaypalRedirect(_)
+                | api_models::payments::WalletData::PaypalSdk(_) => {
+                    api_models::enums::PaymentMethodType::Paypal
+                }
+                api_models::payments::WalletData::WeChatPayRedirect(_) => {
+                    api_models::enums::PaymentMethodType::WeChatPay
+                }
+            };
+            Ok(SimulatorPaymentMethod::Wallet { wallet_type })
+        }
+        api::PaymentMethodData::PayLater(_)
+        | api::PaymentMethodData::BankRedirect(_)
+        | api::PaymentMethodData::BankDebit(_)
+        | api::PaymentMethodData::Crypto(_) => Err(errors::ConnectorError::NotImplemented(
+            "Payment method".to_string(),
+        ))
+        .into_report(),
+    }
+}
+
+impl TryFrom<&types::PaymentsAuthorizeRouterData> for SimulatorPaymentRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
+        let mandate_id = item.request.connector_mandate_id();
+        let payment_method = match mandate_id {
+            Some(_) => None,
+            None => Some(get_payment_method(&item.request.payment_method_data)?),
+        };
+
+        Ok(Self {
+            amount: item.request.amount,
+            currency: item.request.currency.foreign_into(),
+            payment_method,
+            capture: item.request.is_auto_capture()?,
+            three_ds: item.is_three_ds(),
+            setup_mandate: item.request.setup_mandate_details.is_some(),
+            mandate_id,
+            return_url: item.request.router_return_url.clone(),
+        })
+    }
+}
+
+impl TryFrom<&types::VerifyRouterData> for SimulatorPaymentRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::VerifyRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            amount: 0,
+            currency: item.request.currency.foreign_into(),
+            payment_method: Some(get_payment_method(&item.request.payment_method_data)?),
+            capture: true,
+            three_ds: item.is_three_ds(),
+            setup_mandate: true,
+            mandate_id: None,
+            return_url: item.return_url.clone(),
+        })
+    }
+}
+
+impl TryFrom<&types::PaymentsCaptureRouterData> for SimulatorCaptureRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCaptureRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            amount: item.request.amount_to_capture,
+        })
+    }
+}
+
+impl<F> TryFrom<&types::RefundsRouterData<F>> for SimulatorRefundRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            payment_id: item.request.connector_transaction_id.clone(),
+            amount: item.request.refund_amount,
+            reason: item.request.reason.clone(),
+        })
+    }
+}
+
+impl TryFrom<&types::SubmitEvidenceRouterData> for SimulatorEvidenceRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::SubmitEvidenceRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uncategorized_text: item.request.uncategorized_text.clone(),
+        })
+    }
+}
+
+fn get_attempt_status(status: SimulatorPaymentStatus) -> enums::AttemptStatus {
+    match status {
+        SimulatorPaymentStatus::Succeeded => enums::AttemptStatus::Charged,
+        SimulatorPaymentStatus::RequiresCapture => enums::AttemptStatus::Authorized,
+        SimulatorPaymentStatus::RequiresCustomerAction => {
+            enums::AttemptStatus::AuthenticationPending
+        }
+        SimulatorPaymentStatus::Processing => enums::AttemptStatus::Pending,
+        SimulatorPaymentStatus::Failed => enums::AttemptStatus::Failure,
+        SimulatorPaymentStatus::Cancelled => enums::AttemptStatus::Voided,
+    }
+}
+
+impl<F, T>
+    TryFrom<types::ResponseRouterData<F, SimulatorPaymentResponse, T, types::PaymentsResponseData>>
+    for types::RouterData<F, T, types::PaymentsResponseData>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<
+            F,
+            SimulatorPaymentResponse,
+            T,
+            types::PaymentsResponseData,
+        >,
+    ) -> Result<Self, Self::Error> {
+        let status = get_attempt_status(item.response.status);
+        let response = match item.response.error {
+            Some(error) => Err(types::ErrorResponse {
+                code: error.code,
+                message: error.message.clone(),
+                reason: Some(error.message),
+                status_code: item.http_code,
+            }),
+            None => {
+                let redirection_data = match item.response.next_action {
+                    Some(SimulatorNextAction::RedirectToUrl { url }) => {
+                        let url = url::Url::parse(&url)
+                            .into_report()
+                            .change_context(errors::ConnectorError::ResponseHandlingFailed)?;
+                        Some(services::RedirectForm::from((url, services::Method::Get)))
+                    }
+                    None => None,
+                };
+                Ok(types::PaymentsResponseData::TransactionResponse {
+                    resource_id: types::ResponseId::ConnectorTransactionId(item.response.id),
+                    redirection_data,
+                    mandate_reference: item.response.mandate_id,
+                    connector_metadata: None,
+                })
+            }
+        };
+
+        Ok(Self {
+            status,
+            response,
+            ..item.data
+        })
+    }
+}
+
+fn get_refund_status(status: SimulatorRefundStatus) -> enums::RefundStatus {
+    match status {
+        SimulatorRefundStatus::Succeeded => enums::RefundStatus::Success,
+        SimulatorRefundStatus::Pending => enums::RefundStatus::Pending,
+        SimulatorRefundStatus::Failed => enums::RefundStatus::Failure,
+    }
+}
+
+impl<F> TryFrom<types::RefundsResponseRouterData<F, SimulatorRefundResponse>>
+    for types::RefundsRouterData<F>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::RefundsResponseRouterData<F, SimulatorRefundResponse>,
+    ) -> Result<Self, Self::Error> {
+        let response = match item.response.error {
+            Some(error) => Err(types::ErrorResponse {
+                code: error.code,
+                message: error.message.clone(),
+                reason: Some(error.message),
+                status_code: item.http_code,
+            }),
+            None => Ok(types::RefundsResponseData {
+                connector_refund_id: item.response.id,
+                refund_status: get_refund_status(item.response.status),
+            }),
+        };
+
+        Ok(Self {
+            response,
+            ..item.data
+        })
+    }
+}
+
+/// Minimal view of a webhook body, used before the signature of the webhook is known to be valid
+#[derive(Debug, serde::Deserialize)]
+pub struct SimulatorWebhookEvent {
+    pub event_type: SimulatorWebhookEventType,
+}
+
+impl From<SimulatorWebhookEventType> for api::IncomingWebhookEvent {
+    fn from(event_type: SimulatorWebhookEventType) -> Self {
+        match event_type {
+            SimulatorWebhookEventType::PaymentSucceeded => Self::PaymentIntentSuccess,
+            SimulatorWebhookEventType::PaymentFailed => Self::PaymentIntentFailure,
+            SimulatorWebhookEventType::RefundSucceeded => Self::RefundSuccess,
+            SimulatorWebhookEventType::RefundFailed => Self::RefundFailure,
+            SimulatorWebhookEventType::DisputeOpened => Self::DisputeOpened,
+        }
+    }
+}
+
+pub fn get_dispute_status(status: SimulatorDisputeStatus) -> api_models::enums::DisputeStatus {
+    match status {
+        SimulatorDisputeStatus::Opened => api_models::enums::DisputeStatus::DisputeOpened,
+        SimulatorDisputeStatus::Accepted => api_models::enums::DisputeStatus::DisputeAccepted,
+        SimulatorDisputeStatus::Challenged => api_models::enums::DisputeStatus::DisputeChallenged,
+        SimulatorDisputeStatus::Won => api_models::enums::DisputeStatus::DisputeWon,
+        SimulatorDisputeStatus::Lost => api_models::enums::DisputeStatus::DisputeLost,
+    }
+}
+
+/// Maps the `code` of a simulator error to a connector agnostic category.
+pub fn get_error_category(error_code: &str) -> api::enums::ErrorCategory {
+    match error_code {
+        "insufficient_funds" => api::enums::ErrorCategory::InsufficientFunds,
+        "card_declined" | "do_not_honor" => api::enums::ErrorCategory::DoNotHonor,
+        "expired_card" => api::enums::ErrorCategory::ExpiredCard,
+        "incorrect_cvc" => api::enums::ErrorCategory::IncorrectCvc,
+        "processing_error" => api::enums::ErrorCategory::ProcessingError,
+        _ => api::enums::ErrorCategory::Unknown,
+    }
+}
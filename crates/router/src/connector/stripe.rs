@@ -32,6 +32,10 @@ impl ConnectorCommon for Stripe {
         "stripe"
     }
 
+    fn get_error_category(&self, error_code: &str) -> api::enums::ErrorCategory {
+        stripe::get_error_category(error_code)
+    }
+
     fn common_get_content_type(&self) -> &'static str {
         "application/x-www-form-urlencoded"
     }
@@ -180,7 +184,8 @@ impl
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -295,7 +300,8 @@ impl
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -414,7 +420,8 @@ impl
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -522,7 +529,8 @@ impl
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -624,7 +632,8 @@ impl
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -747,7 +756,8 @@ impl
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -847,7 +857,8 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -937,7 +948,8 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -1070,7 +1082,8 @@ impl
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -1180,7 +1193,8 @@ impl
             status_code: res.status_code,
             code: response
                 .error
-                .code
+                .decline_code
+                .or(response.error.code)
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response
                 .error
@@ -1221,6 +1235,50 @@ fn get_signature_elements_from_header(
     Ok(security_header_kvs)
 }
 
+impl api::ConnectorCapability for Stripe {
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            connector: api::enums::Connector::Stripe,
+            flows: vec![
+                api::ConnectorFlow::Authorize,
+                api::ConnectorFlow::Capture,
+                api::ConnectorFlow::Void,
+                api::ConnectorFlow::PaymentSync,
+                api::ConnectorFlow::Refund,
+                api::ConnectorFlow::RefundSync,
+                api::ConnectorFlow::Mandates,
+                api::ConnectorFlow::ThreeDs,
+            ],
+            payment_methods: vec![
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Card),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::Wallet)
+                    .with_payment_method_types(vec![api::enums::PaymentMethodType::ApplePay]),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::PayLater)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::Affirm,
+                        api::enums::PaymentMethodType::AfterpayClearpay,
+                        api::enums::PaymentMethodType::Klarna,
+                    ]),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::BankRedirect)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::Eps,
+                        api::enums::PaymentMethodType::Giropay,
+                        api::enums::PaymentMethodType::Ideal,
+                        api::enums::PaymentMethodType::Sofort,
+                    ])
+                    .with_currencies(vec![api::enums::Currency::EUR]),
+                api::PaymentMethodCapability::new(api::enums::PaymentMethod::BankDebit)
+                    .with_payment_method_types(vec![
+                        api::enums::PaymentMethodType::Ach,
+                        api::enums::PaymentMethodType::Bacs,
+                        api::enums::PaymentMethodType::Becs,
+                        api::enums::PaymentMethodType::Sepa,
+                    ]),
+            ],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Stripe {
     fn get_webhook_source_verification_algorithm(
@@ -1023,6 +1023,7 @@ impl std::ops::Deref for PaymentSyncResponse {
 #[derive(Serialize, Deserialize)]
 pub struct LastPaymentError {
     code: String,
+    decline_code: Option<String>,
     message: String,
 }
 
@@ -1189,7 +1190,10 @@ impl<F, T>
                 .last_payment_error
                 .as_ref()
                 .map(|error| types::ErrorResponse {
-                    code: error.code.to_owned(),
+                    code: error
+                        .decline_code
+                        .clone()
+                        .unwrap_or_else(|| error.code.to_owned()),
                     message: error.message.to_owned(),
                     reason: None,
                     status_code: item.http_code,
@@ -1401,6 +1405,9 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
 #[derive(Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct ErrorDetails {
     pub code: Option<String>,
+    /// Reason given by the issuer for declining the card, more specific than the `card_declined`
+    /// code that comes with it
+    pub decline_code: Option<String>,
     #[serde(rename = "type")]
     pub error_type: Option<String>,
     pub message: Option<String>,
@@ -1799,3 +1806,35 @@ pub struct DisputeObj {
     pub dispute_id: String,
     pub status: String,
 }
+
+/// Maps the error code of a Stripe error to a connector agnostic category. The error code is the
+/// `decline_code` of a declined card when there is one, and the `code` of the error otherwise.
+pub fn get_error_category(error_code: &str) -> api_enums::ErrorCategory {
+    match error_code {
+        "insufficient_funds" => api_enums::ErrorCategory::InsufficientFunds,
+        "card_declined" | "do_not_honor" | "generic_decline" | "call_issuer" => {
+            api_enums::ErrorCategory::DoNotHonor
+        }
+        "expired_card" => api_enums::ErrorCategory::ExpiredCard,
+        "incorrect_cvc" | "invalid_cvc" => api_enums::ErrorCategory::IncorrectCvc,
+        "incorrect_number" | "invalid_number" => api_enums::ErrorCategory::InvalidCardNumber,
+        "lost_card" | "stolen_card" | "pickup_card" => api_enums::ErrorCategory::LostOrStolenCard,
+        "fraudulent" | "merchant_blacklist" => api_enums::ErrorCategory::FraudSuspected,
+        "authentication_required" => api_enums::ErrorCategory::AuthenticationRequired,
+        "payment_intent_authentication_failure" => api_enums::ErrorCategory::AuthenticationFailed,
+        "card_not_supported" | "currency_not_supported" | "transaction_not_allowed" => {
+            api_enums::ErrorCategory::CardNotSupported
+        }
+        "card_velocity_exceeded" | "withdrawal_count_limit_exceeded" => {
+            api_enums::ErrorCategory::LimitExceeded
+        }
+        "issuer_not_available" | "try_again_later" => api_enums::ErrorCategory::IssuerUnavailable,
+        "processing_error" => api_enums::ErrorCategory::ProcessingError,
+        "rate_limit" => api_enums::ErrorCategory::RateLimited,
+        "parameter_invalid_empty"
+        | "parameter_invalid_integer"
+        | "parameter_missing"
+        | "parameter_unknown" => api_enums::ErrorCategory::InvalidRequest,
+        _ => api_enums::ErrorCategory::Unknown,
+    }
+}
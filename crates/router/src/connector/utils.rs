@@ -591,6 +591,16 @@ pub fn to_currency_base_unit(
     Ok(format!("{amount:.2}"))
 }
 
+pub fn to_currency_base_unit_asf64(
+    amount: i64,
+    currency: storage_models::enums::Currency,
+) -> Result<f64, error_stack::Report<errors::ConnectorError>> {
+    to_currency_base_unit(amount, currency)?
+        .parse::<f64>()
+        .into_report()
+        .change_context(errors::ConnectorError::RequestEncodingFailed)
+}
+
 pub fn str_to_f32<S>(value: &str, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
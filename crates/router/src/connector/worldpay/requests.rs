@@ -107,6 +107,38 @@ pub struct Instruction {
     pub value: PaymentValue,
     pub narrative: InstructionNarrative,
     pub payment_instrument: PaymentInstrument,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level23_data: Option<Level23Data>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Level23Data {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sales_tax: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<Level23Item>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Level23Item {
+    pub description: String,
+    pub quantity: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_cost: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_discount_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commodity_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_of_measure: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
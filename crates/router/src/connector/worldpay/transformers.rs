@@ -75,6 +75,11 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for WorldpayPaymentsRequest {
                     item.request.payment_method_data.clone(),
                 )?,
                 debt_repayment: None,
+                level23_data: item
+                    .request
+                    .purchase_details
+                    .as_ref()
+                    .map(Level23Data::from),
             },
             merchant: Merchant {
                 entity: item.attempt_id.clone().replace('_', "-"),
@@ -87,6 +92,30 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for WorldpayPaymentsRequest {
     }
 }
 
+impl From<&api_models::payments::PurchaseDetails> for Level23Data {
+    fn from(purchase_details: &api_models::payments::PurchaseDetails) -> Self {
+        Self {
+            customer_reference: purchase_details.customer_reference.clone(),
+            sales_tax: purchase_details.tax_amount,
+            items: purchase_details
+                .line_items
+                .iter()
+                .flatten()
+                .map(|line_item| Level23Item {
+                    description: line_item.product_name.clone(),
+                    quantity: line_item.quantity,
+                    unit_cost: line_item.unit_price,
+                    item_discount_amount: line_item.discount_amount,
+                    tax_amount: line_item.tax_amount,
+                    product_code: line_item.sku.clone(),
+                    commodity_code: line_item.commodity_code.clone(),
+                    unit_of_measure: line_item.unit_of_measure.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
 pub struct WorldpayAuthType {
     pub(super) api_key: String,
 }
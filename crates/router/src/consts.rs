@@ -23,4 +23,20 @@ pub(crate) const BASE64_ENGINE_URL_SAFE: base64::engine::GeneralPurpose =
     base64::engine::general_purpose::URL_SAFE;
 
 pub(crate) const API_KEY_LENGTH: usize = 64;
-pub(crate) const PUB_SUB_CHANNEL: &str = "hyperswitch_invalidate";
+
+// Scheduler heartbeats are retained for a day (in seconds), so that stale heartbeats can be
+// reported with their age instead of simply being missing
+pub(crate) const SCHEDULER_HEARTBEAT_TTL: i64 = 24 * 60 * 60;
+
+// Payment link validity (in seconds)
+pub(crate) const DEFAULT_PAYMENT_LINK_EXPIRY: u32 = 15 * 60;
+pub(crate) const MIN_PAYMENT_LINK_EXPIRY: u32 = 60;
+pub(crate) const MAX_PAYMENT_LINK_EXPIRY: u32 = 90 * 24 * 60 * 60;
+
+// Payment intent session validity (in seconds)
+pub(crate) const DEFAULT_INTENT_FULFILLMENT_TIME: u32 = 15 * 60;
+pub(crate) const MIN_INTENT_FULFILLMENT_TIME: u32 = 60;
+pub(crate) const MAX_INTENT_FULFILLMENT_TIME: u32 = 90 * 24 * 60 * 60;
+
+// Longest customer reference accepted by connectors supporting Level 2 / Level 3 card data
+pub(crate) const MAX_CUSTOMER_REFERENCE_LENGTH: usize = 25;
@@ -1,15 +1,24 @@
 pub mod admin;
 pub mod api_keys;
+pub mod cache;
 pub mod cards_info;
 pub mod configs;
+pub mod connector_audit_log;
+pub mod connector_capabilities;
 pub mod customers;
 pub mod disputes;
 pub mod errors;
 pub mod files;
+pub mod fx;
+pub mod health_check;
+pub mod key_rotation;
 pub mod mandate;
 pub mod metrics;
+pub mod payment_link;
 pub mod payment_methods;
 pub mod payments;
 pub mod refunds;
+#[cfg(feature = "simulator")]
+pub mod simulator;
 pub mod utils;
 pub mod webhooks;
@@ -75,8 +75,7 @@ pub async fn create_merchant_account(
     let api_key = match api_keys::create_api_key(
         db,
         &state.conf.api_keys,
-        #[cfg(feature = "kms")]
-        &state.conf.kms,
+        &state.conf.secrets_management,
         api_key_request,
         req.merchant_id.clone(),
     )
@@ -129,6 +128,12 @@ pub async fn create_merchant_account(
             .attach_printable("Invalid routing algorithm given")?;
     }
 
+    helpers::validate_intent_fulfillment_time(
+        req.intent_fulfillment_time,
+        "intent_fulfillment_time",
+    )?;
+    let intent_fulfillment_time = req.intent_fulfillment_time.map(i64::from);
+
     let merchant_account = storage::MerchantAccountNew {
         merchant_id: req.merchant_id,
         merchant_name: req.merchant_name,
@@ -151,6 +156,7 @@ pub async fn create_merchant_account(
         locker_id: req.locker_id,
         metadata: req.metadata,
         primary_business_details,
+        intent_fulfillment_time,
     };
 
     let merchant_account = db
@@ -223,6 +229,12 @@ pub async fn merchant_account_update(
         })
         .transpose()?;
 
+    helpers::validate_intent_fulfillment_time(
+        req.intent_fulfillment_time,
+        "intent_fulfillment_time",
+    )?;
+    let intent_fulfillment_time = req.intent_fulfillment_time.map(i64::from);
+
     let updated_merchant_account = storage::MerchantAccountUpdate::Update {
         merchant_name: req.merchant_name,
 
@@ -258,6 +270,7 @@ pub async fn merchant_account_update(
         metadata: req.metadata,
         publishable_key: None,
         primary_business_details,
+        intent_fulfillment_time,
     };
 
     let response = db
@@ -570,10 +583,13 @@ pub async fn kv_for_merchant(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
+    let is_draining = merchant_account.kv_drain_started_at.is_some();
     let updated_merchant_account = match (enable, merchant_account.storage_scheme) {
-        (true, enums::MerchantStorageScheme::RedisKv)
-        | (false, enums::MerchantStorageScheme::PostgresOnly) => Ok(merchant_account),
-        (true, enums::MerchantStorageScheme::PostgresOnly) => {
+        (true, enums::MerchantStorageScheme::RedisKv) if !is_draining => Ok(merchant_account),
+        (false, enums::MerchantStorageScheme::RedisKv) if is_draining => Ok(merchant_account),
+        (false, enums::MerchantStorageScheme::PostgresOnly) => Ok(merchant_account),
+        // Cancels the drain if KV is being turned off
+        (true, _) => {
             db.update_merchant(
                 merchant_account,
                 merchant_account::MerchantAccountUpdate::StorageSchemeUpdate {
@@ -582,12 +598,13 @@ pub async fn kv_for_merchant(
             )
             .await
         }
+        // The merchant continues to be served through Redis until the drainer has drained all of
+        // its entries, and then switches it to Postgres. The account of a draining merchant is
+        // not cached, so that instances pick up the switch as soon as it is made.
         (false, enums::MerchantStorageScheme::RedisKv) => {
             db.update_merchant(
                 merchant_account,
-                merchant_account::MerchantAccountUpdate::StorageSchemeUpdate {
-                    storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
-                },
+                merchant_account::MerchantAccountUpdate::KvDrainStarted,
             )
             .await
         }
@@ -597,17 +614,10 @@ pub async fn kv_for_merchant(
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("failed to switch merchant_storage_scheme")
     })?;
-    let kv_status = matches!(
-        updated_merchant_account.storage_scheme,
-        enums::MerchantStorageScheme::RedisKv
-    );
 
-    Ok(service_api::ApplicationResponse::Json(
-        api_models::admin::ToggleKVResponse {
-            merchant_id: updated_merchant_account.merchant_id,
-            kv_enabled: kv_status,
-        },
-    ))
+    Ok(service_api::ApplicationResponse::Json(get_kv_status(
+        updated_merchant_account,
+    )))
 }
 
 pub async fn check_merchant_account_kv_status(
@@ -620,15 +630,26 @@ pub async fn check_merchant_account_kv_status(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let kv_status = matches!(
+    Ok(service_api::ApplicationResponse::Json(get_kv_status(
+        merchant_account,
+    )))
+}
+
+fn get_kv_status(merchant_account: MerchantAccount) -> api_models::admin::ToggleKVResponse {
+    let kv_enabled = matches!(
         merchant_account.storage_scheme,
         enums::MerchantStorageScheme::RedisKv
     );
+    let kv_status = match (kv_enabled, merchant_account.kv_drain_started_at) {
+        (true, Some(_)) => api_models::admin::KvStatus::Draining,
+        (true, None) => api_models::admin::KvStatus::Enabled,
+        (false, _) => api_models::admin::KvStatus::Disabled,
+    };
 
-    Ok(service_api::ApplicationResponse::Json(
-        api_models::admin::ToggleKVResponse {
-            merchant_id: merchant_account.merchant_id,
-            kv_enabled: kv_status,
-        },
-    ))
+    api_models::admin::ToggleKVResponse {
+        merchant_id: merchant_account.merchant_id,
+        kv_enabled,
+        kv_status,
+        kv_drain_started_at: merchant_account.kv_drain_started_at,
+    }
 }
@@ -1,11 +1,12 @@
 use common_utils::date_time;
 use error_stack::{report, IntoReport, ResultExt};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::SecretsManagementConfig;
 use masking::{PeekInterface, StrongSecret};
-use router_env::{instrument, tracing};
+use router_env::{instrument, logger, tracing};
+use time::PrimitiveDateTime;
 
 use crate::{
+    cache::CacheKind,
     configs::settings,
     consts,
     core::errors::{self, RouterResponse, StorageErrorExt},
@@ -16,39 +17,163 @@ use crate::{
     utils,
 };
 
-static HASH_KEY: tokio::sync::OnceCell<StrongSecret<[u8; PlaintextApiKey::HASH_KEY_LEN]>> =
-    tokio::sync::OnceCell::const_new();
+pub struct HashKey {
+    pub key_id: String,
+    pub key: StrongSecret<[u8; PlaintextApiKey::HASH_KEY_LEN]>,
+}
+
+pub struct PreviousHashKey {
+    pub hash_key: HashKey,
+    pub accepted_until: PrimitiveDateTime,
+}
 
-pub async fn get_hash_key(
+/// The current API key hashing key, along with the keys it has replaced.
+pub struct HashKeys {
+    pub current: HashKey,
+    pub previous: Vec<PreviousHashKey>,
+}
+
+impl HashKeys {
+    /// Previous hash keys whose grace period has not yet ended.
+    pub fn accepted_previous_keys(&self) -> impl Iterator<Item = &HashKey> {
+        let now = date_time::now();
+        self.previous
+            .iter()
+            .filter(move |previous_key| previous_key.accepted_until > now)
+            .map(|previous_key| &previous_key.hash_key)
+    }
+}
+
+static HASH_KEYS: tokio::sync::OnceCell<HashKeys> = tokio::sync::OnceCell::const_new();
+
+pub async fn get_hash_keys(
     api_key_config: &settings::ApiKeys,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
-) -> errors::RouterResult<&'static StrongSecret<[u8; PlaintextApiKey::HASH_KEY_LEN]>> {
-    HASH_KEY
+    secrets_management: &SecretsManagementConfig,
+) -> errors::RouterResult<&'static HashKeys> {
+    HASH_KEYS
         .get_or_try_init(|| async {
-            #[cfg(feature = "kms")]
-            let hash_key = kms::get_kms_client(kms_config)
-                .await
-                .decrypt(&api_key_config.kms_encrypted_hash_key)
-                .await
-                .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Failed to KMS decrypt API key hashing key")?;
-
-            #[cfg(not(feature = "kms"))]
-            let hash_key = &api_key_config.hash_key;
-
-            <[u8; PlaintextApiKey::HASH_KEY_LEN]>::try_from(
-                hex::decode(hash_key)
-                    .into_report()
-                    .change_context(errors::ApiErrorResponse::InternalServerError)
-                    .attach_printable("API key hash key has invalid hexadecimal data")?
-                    .as_slice(),
-            )
+            let current = HashKey {
+                key_id: api_key_config.hash_key_id.clone(),
+                key: decode_hash_key(&api_key_config.hash_key, secrets_management).await?,
+            };
+
+            let mut previous = Vec::with_capacity(api_key_config.previous_hash_keys.len());
+            for previous_key in &api_key_config.previous_hash_keys {
+                previous.push(PreviousHashKey {
+                    hash_key: HashKey {
+                        key_id: previous_key.key_id.clone(),
+                        key: decode_hash_key(&previous_key.hash_key, secrets_management)
+                            .await
+                            .attach_printable_lazy(|| {
+                                format!(
+                                    "Invalid previous API key hash key `{}`",
+                                    previous_key.key_id
+                                )
+                            })?,
+                    },
+                    accepted_until: previous_key.accepted_until,
+                });
+            }
+
+            Ok(HashKeys { current, previous })
+        })
+        .await
+}
+
+async fn decode_hash_key(
+    hash_key: &str,
+    secrets_management: &SecretsManagementConfig,
+) -> errors::RouterResult<StrongSecret<[u8; PlaintextApiKey::HASH_KEY_LEN]>> {
+    let hash_key = secrets_management
+        .get_secret(hash_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key hashing key")?;
+
+    <[u8; PlaintextApiKey::HASH_KEY_LEN]>::try_from(
+        hex::decode(hash_key)
             .into_report()
             .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("The API hashing key has incorrect length")
-            .map(StrongSecret::new)
-        })
+            .attach_printable("API key hash key has invalid hexadecimal data")?
+            .as_slice(),
+    )
+    .into_report()
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("The API hashing key has incorrect length")
+    .map(StrongSecret::new)
+}
+
+/// Finds the stored API key matching `api_key`, trying the current hash key first and then the
+/// previous hash keys which are still accepted. API keys found using a previous hash key are
+/// rehashed with the current hash key.
+#[instrument(skip_all)]
+pub async fn find_api_key(
+    store: &dyn StorageInterface,
+    hash_keys: &HashKeys,
+    api_key: &PlaintextApiKey,
+) -> errors::RouterResult<Option<storage::ApiKey>> {
+    let stored_api_key = store
+        .find_api_key_by_hash_optional(api_key.keyed_hash(hash_keys.current.key.peek()).into())
         .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?;
+    if stored_api_key.is_some() {
+        return Ok(stored_api_key);
+    }
+
+    for previous_key in hash_keys.accepted_previous_keys() {
+        let previous_hash = api_key.keyed_hash(previous_key.key.peek());
+        let stored_api_key = store
+            .find_api_key_by_hash_optional(previous_hash.0.clone().into())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to retrieve API key")?;
+
+        if let Some(stored_api_key) = stored_api_key {
+            // Failing to rehash the API key must not fail the request, since the previous hash
+            // key is still accepted
+            let stored_api_key =
+                match rehash_api_key(store, &hash_keys.current, api_key, &stored_api_key).await {
+                    Ok(rehashed_api_key) => {
+                        metrics::API_KEY_REHASHED.add(&metrics::CONTEXT, 1, &[]);
+                        if let Err(error) = store
+                            .invalidate_cache(CacheKind::ApiKey, &previous_hash.0)
+                            .await
+                        {
+                            logger::error!(?error, "Failed to invalidate previous API key hash");
+                        }
+                        rehashed_api_key
+                    }
+                    Err(error) => {
+                        logger::error!(?error, "Failed to rehash API key");
+                        stored_api_key
+                    }
+                };
+            return Ok(Some(stored_api_key));
+        }
+    }
+
+    Ok(None)
+}
+
+async fn rehash_api_key(
+    store: &dyn StorageInterface,
+    current_key: &HashKey,
+    api_key: &PlaintextApiKey,
+    stored_api_key: &storage::ApiKey,
+) -> errors::RouterResult<storage::ApiKey> {
+    store
+        .update_api_key(
+            stored_api_key.merchant_id.clone(),
+            stored_api_key.key_id.clone(),
+            storage::ApiKeyUpdate::HashUpdate {
+                hashed_api_key: api_key.keyed_hash(current_key.key.peek()).into(),
+                hash_key_id: current_key.key_id.clone(),
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update API key hash")
 }
 
 // Defining new types `PlaintextApiKey` and `HashedApiKey` in the hopes of reducing the possibility
@@ -119,27 +244,25 @@ impl PlaintextApiKey {
 pub async fn create_api_key(
     store: &dyn StorageInterface,
     api_key_config: &settings::ApiKeys,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
     api_key: api::CreateApiKeyRequest,
     merchant_id: String,
 ) -> RouterResponse<api::CreateApiKeyResponse> {
-    let hash_key = get_hash_key(
-        api_key_config,
-        #[cfg(feature = "kms")]
-        kms_config,
-    )
-    .await?;
+    let hash_keys = get_hash_keys(api_key_config, secrets_management).await?;
     let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
     let api_key = storage::ApiKeyNew {
         key_id: PlaintextApiKey::new_key_id(),
         merchant_id,
         name: api_key.name,
         description: api_key.description,
-        hashed_api_key: plaintext_api_key.keyed_hash(hash_key.peek()).into(),
+        hashed_api_key: plaintext_api_key
+            .keyed_hash(hash_keys.current.key.peek())
+            .into(),
         prefix: plaintext_api_key.prefix(),
         created_at: date_time::now(),
         expires_at: api_key.expiration.into(),
         last_used: None,
+        hash_key_id: hash_keys.current.key_id.clone(),
     };
 
     let api_key = store
@@ -264,13 +387,10 @@ mod tests {
         let settings = settings::Settings::new().expect("invalid settings");
 
         let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
-        let hash_key = get_hash_key(
-            &settings.api_keys,
-            #[cfg(feature = "kms")]
-            &settings.kms,
-        )
-        .await
-        .unwrap();
+        let hash_keys = get_hash_keys(&settings.api_keys, &settings.secrets_management)
+            .await
+            .unwrap();
+        let hash_key = &hash_keys.current.key;
         let hashed_api_key = plaintext_api_key.keyed_hash(hash_key.peek());
 
         assert_ne!(
@@ -281,4 +401,38 @@ mod tests {
         let new_hashed_api_key = plaintext_api_key.keyed_hash(hash_key.peek());
         assert_eq!(hashed_api_key, new_hashed_api_key)
     }
+
+    #[test]
+    fn test_previous_hash_keys_accepted_until_grace_period_ends() {
+        let hash_key = |key_id: &str, byte: u8| HashKey {
+            key_id: key_id.to_string(),
+            key: StrongSecret::new([byte; PlaintextApiKey::HASH_KEY_LEN]),
+        };
+        let now = date_time::now();
+        let hash_keys = HashKeys {
+            current: hash_key("3", 3),
+            previous: vec![
+                PreviousHashKey {
+                    hash_key: hash_key("2", 2),
+                    accepted_until: now + time::Duration::days(1),
+                },
+                PreviousHashKey {
+                    hash_key: hash_key("1", 1),
+                    accepted_until: now - time::Duration::days(1),
+                },
+            ],
+        };
+
+        let accepted_key_ids = hash_keys
+            .accepted_previous_keys()
+            .map(|hash_key| hash_key.key_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(accepted_key_ids, vec!["2"]);
+
+        let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
+        assert_ne!(
+            plaintext_api_key.keyed_hash(hash_keys.current.key.peek()),
+            plaintext_api_key.keyed_hash(hash_keys.previous[0].hash_key.key.peek())
+        );
+    }
 }
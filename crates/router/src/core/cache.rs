@@ -0,0 +1,26 @@
+use error_stack::ResultExt;
+
+use crate::{
+    cache::CacheKind,
+    core::errors::{self, RouterResponse},
+    db::StorageInterface,
+    services::ApplicationResponse,
+    types::api,
+};
+
+/// Invalidates a key in Redis and in every in-memory cache across all instances.
+pub async fn invalidate(
+    store: &dyn StorageInterface,
+    key: &str,
+) -> RouterResponse<api::CacheInvalidationResponse> {
+    let subscribers_notified = store
+        .invalidate_cache(CacheKind::All, key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to invalidate cache")?;
+
+    Ok(ApplicationResponse::Json(api::CacheInvalidationResponse {
+        key: key.to_string(),
+        subscribers_notified,
+    }))
+}
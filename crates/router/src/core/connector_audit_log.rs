@@ -0,0 +1,47 @@
+use error_stack::{IntoReport, ResultExt};
+
+use crate::{
+    core::errors::{self, RouterResponse},
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{api, transformers::ForeignInto},
+};
+
+const DEFAULT_LIMIT: i64 = 100;
+
+/// Lists the requests sent to connectors for the payment, along with the responses received.
+pub async fn retrieve_connector_audit_log(
+    state: &AppState,
+    merchant_id: &str,
+    payment_id: &str,
+    constraints: api::ConnectorAuditLogConstraints,
+) -> RouterResponse<api::ConnectorAuditLogResponse> {
+    common_utils::fp_utils::when(!state.conf.connector_audit_log.is_queryable(), || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Connector audit logs can only be queried when they are stored in Postgres"
+                .to_string(),
+        })
+        .into_report()
+    })?;
+
+    let audit_logs = state
+        .store
+        .find_connector_audit_logs_by_merchant_id_payment_id(
+            merchant_id,
+            payment_id,
+            constraints.limit.unwrap_or(DEFAULT_LIMIT),
+            constraints.offset,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve connector audit logs")?;
+
+    Ok(ApplicationResponse::Json(api::ConnectorAuditLogResponse {
+        merchant_id: merchant_id.to_owned(),
+        payment_id: payment_id.to_owned(),
+        events: audit_logs
+            .into_iter()
+            .map(ForeignInto::foreign_into)
+            .collect(),
+    }))
+}
@@ -0,0 +1,28 @@
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::errors::RouterResponse,
+    routes::AppState,
+    services::ApplicationResponse,
+    types::api::{self, enums as api_enums},
+};
+
+/// Lists the declared capabilities of every connector that payments can be routed to.
+pub async fn list_connector_capabilities(
+    state: &AppState,
+) -> RouterResponse<api::ConnectorCapabilitiesResponse> {
+    let connectors = api_enums::Connector::iter()
+        .filter_map(|connector| {
+            api::ConnectorData::get_connector_by_name(
+                &connector.to_string(),
+                api::GetToken::Connector,
+            )
+            .ok()
+        })
+        .map(|connector_data| connector_data.connector.get_capabilities())
+        .collect();
+
+    Ok(ApplicationResponse::Json(
+        api::ConnectorCapabilitiesResponse { connectors },
+    ))
+}
@@ -73,6 +73,10 @@ pub enum StorageError {
     DeserializationFailed,
     #[error("RedisError: {0:?}")]
     RedisError(error_stack::Report<RedisError>),
+    #[error("Failed to encrypt field")]
+    EncryptionError,
+    #[error("Failed to decrypt field")]
+    DecryptionError,
 }
 
 impl From<error_stack::Report<RedisError>> for StorageError {
@@ -211,6 +215,19 @@ pub enum ApiClientError {
     GatewayTimeoutReceived,
     #[error("Server responded with unexpected response")]
     UnexpectedServerResponse,
+
+    #[error("Circuit breaker of connector {0} is open")]
+    CircuitBreakerOpen(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuditLogError {
+    #[error("Failed to open the audit log sink")]
+    SinkCreationFailed,
+    #[error("Failed to serialize the audit event")]
+    EventSerializationFailed,
+    #[error("Failed to write the audit event to the sink")]
+    EventWriteFailed,
 }
 
 #[derive(Debug, thiserror::Error, PartialEq)]
@@ -251,6 +268,8 @@ pub enum ConnectorError {
     FailedToObtainCertificateKey,
     #[error("This step has not been implemented for: {0}")]
     NotImplemented(String),
+    #[error("{0} is temporarily unavailable")]
+    ConnectorUnavailable(String),
     #[error("{payment_method} is not supported by {connector}")]
     NotSupported {
         payment_method: String,
@@ -331,6 +350,26 @@ pub enum KmsError {
     Utf8DecodingFailed,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum FxError {
+    #[error("No exchange rate is available from {from} to {to}")]
+    RateNotFound { from: String, to: String },
+    #[error("The stored exchange rate could not be parsed")]
+    InvalidRate,
+    #[error("Converted amount is out of range")]
+    AmountOutOfRange,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum NetworkTokenizationError {
+    #[error("Failed to provision a network token for the card")]
+    ProvisioningFailed,
+    #[error("Failed to delete the network token")]
+    DeletionFailed,
+    #[error("The network token notification signature could not be verified")]
+    NotificationVerificationFailed,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ProcessTrackerError {
     #[error("An unexpected flow was specified")]
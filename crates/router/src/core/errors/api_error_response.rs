@@ -87,6 +87,8 @@ pub enum ApiErrorResponse {
     NotSupported { message: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "IR_20", message = "{flow} flow not supported by the {connector} connector")]
     FlowNotSupported { flow: String, connector: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_21", message = "The payment session has expired")]
+    PaymentSessionExpired,
     #[error(error_type = ErrorType::ConnectorError, code = "CE_00", message = "{code}: {message}", ignore = "status_code")]
     ExternalConnectorError {
         code: String,
@@ -109,6 +111,8 @@ pub enum ApiErrorResponse {
     RefundFailed { data: Option<serde_json::Value> },
     #[error(error_type = ErrorType::ProcessingError, code = "CE_07", message = "Verification failed while processing with connector. Retry operation")]
     VerificationFailed { data: Option<serde_json::Value> },
+    #[error(error_type = ErrorType::ProcessingError, code = "CE_08", message = "The {connector} connector is temporarily unavailable. Retry later")]
+    ConnectorUnavailable { connector: String },
 
     #[error(error_type = ErrorType::ServerNotAvailable, code = "HE_00", message = "Something went wrong")]
     InternalServerError,
@@ -164,6 +168,8 @@ pub enum ApiErrorResponse {
     FileNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_04", message = "File not available")]
     FileNotAvailable,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_04", message = "Payment link does not exist in our records")]
+    PaymentLinkNotFound { payment_link_id: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "HE_04", message = "Dispute status validation failed")]
     DisputeStatusValidationFailed { reason: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "HE_04", message = "Card with the provided iin does not exist")]
@@ -267,6 +273,7 @@ impl actix_web::ResponseError for ApiErrorResponse {
             | Self::AddressNotFound
             | Self::NotSupported { .. }
             | Self::FlowNotSupported { .. }
+            | Self::PaymentSessionExpired
             | Self::ApiKeyNotFound
             | Self::DisputeStatusValidationFailed { .. } => StatusCode::BAD_REQUEST, // 400
             Self::DuplicateMerchantAccount
@@ -280,10 +287,13 @@ impl actix_web::ResponseError for ApiErrorResponse {
             | Self::MissingFilePurpose
             | Self::MissingDisputeId
             | Self::FileNotFound
-            | Self::FileNotAvailable => StatusCode::BAD_REQUEST, // 400
-            Self::ReturnUrlUnavailable => StatusCode::SERVICE_UNAVAILABLE, // 503
-            Self::PaymentNotSucceeded => StatusCode::BAD_REQUEST,          // 400
-            Self::NotImplemented { .. } => StatusCode::NOT_IMPLEMENTED,    // 501
+            | Self::FileNotAvailable
+            | Self::PaymentLinkNotFound { .. } => StatusCode::BAD_REQUEST, // 400
+            Self::ReturnUrlUnavailable | Self::ConnectorUnavailable { .. } => {
+                StatusCode::SERVICE_UNAVAILABLE // 503
+            }
+            Self::PaymentNotSucceeded => StatusCode::BAD_REQUEST, // 400
+            Self::NotImplemented { .. } => StatusCode::NOT_IMPLEMENTED, // 501
         }
     }
 
@@ -403,6 +413,7 @@ impl common_utils::errors::ErrorSwitch<api_models::errors::types::ApiErrorRespon
             Self::VerificationFailed { data } => {
                 AER::BadRequest(ApiError::new("CE", 7, "Verification failed while processing with connector. Retry operation", Some(Extra { data: data.clone(), ..Default::default()})))
             }
+            Self::ConnectorUnavailable { connector } => AER::ConnectorError(ApiError::new("CE", 8, format!("The {connector} connector is temporarily unavailable. Retry later"), Some(Extra { connector: Some(connector.clone()), ..Default::default()})), StatusCode::SERVICE_UNAVAILABLE),
             Self::InternalServerError => {
                 AER::InternalServerError(ApiError::new("HE", 0, "Something went wrong", None))
             }
@@ -471,6 +482,9 @@ impl common_utils::errors::ErrorSwitch<api_models::errors::types::ApiErrorRespon
             Self::FlowNotSupported { flow, connector } => {
                 AER::BadRequest(ApiError::new("IR", 20, format!("{flow} flow not supported"), Some(Extra {connector: Some(connector.to_owned()), ..Default::default()}))) //FIXME: error message
             }
+            Self::PaymentSessionExpired => {
+                AER::BadRequest(ApiError::new("IR", 21, "The payment session has expired", None))
+            }
             Self::DisputeNotFound { .. } => {
                 AER::NotFound(ApiError::new("HE", 2, "Dispute does not exist in our records", None))
             }
@@ -480,6 +494,9 @@ impl common_utils::errors::ErrorSwitch<api_models::errors::types::ApiErrorRespon
             Self::FileNotAvailable => {
                 AER::NotFound(ApiError::new("HE", 2, "File not available", None))
             }
+            Self::PaymentLinkNotFound { .. } => {
+                AER::NotFound(ApiError::new("HE", 4, "Payment link does not exist in our records", None))
+            }
             Self::DisputeStatusValidationFailed { .. } => {
                 AER::BadRequest(ApiError::new("HE", 2, "Dispute status validation failed", None))
             }
@@ -113,6 +113,9 @@ impl ConnectorErrorExt for error_stack::Report<errors::ConnectorError> {
             errors::ConnectorError::FlowNotSupported{ flow, connector } => {
                 errors::ApiErrorResponse::FlowNotSupported { flow: flow.to_owned(), connector: connector.to_owned() }
             }
+            errors::ConnectorError::ConnectorUnavailable(connector) => {
+                errors::ApiErrorResponse::ConnectorUnavailable { connector: connector.to_owned() }
+            }
             _ => errors::ApiErrorResponse::InternalServerError,
         };
         self.change_context(error)
@@ -80,7 +80,6 @@ pub async fn validate_file_upload(
                     dispute_id: dispute_id.to_string(),
                 })?;
             let connector_data = api::ConnectorData::get_connector_by_name(
-                &state.conf.connectors,
                 &dispute.connector,
                 api::GetToken::Connector,
             )?;
@@ -202,7 +201,6 @@ pub async fn upload_and_get_provider_provider_file_id(
                 .await
                 .to_not_found_response(errors::ApiErrorResponse::DisputeNotFound { dispute_id })?;
             let connector_data = api::ConnectorData::get_connector_by_name(
-                &state.conf.connectors,
                 &dispute.connector,
                 api::GetToken::Connector,
             )?;
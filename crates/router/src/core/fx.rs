@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    configs::settings,
+    core::errors::{self, CustomResult, RouterResult},
+    types::{
+        storage::{self, enums},
+        transformers::ForeignFrom,
+    },
+};
+
+/// Exact decimal number, `mantissa * 10^-scale`, used to represent exchange rates without the
+/// rounding errors of binary floating point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rate {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Rate {
+    /// Number of decimal places kept when a rate has to be derived from another one
+    const DERIVED_RATE_SCALE: u32 = 12;
+
+    pub const ONE: Self = Self {
+        mantissa: 1,
+        scale: 0,
+    };
+
+    fn new(mantissa: i128, scale: u32) -> Self {
+        // Trailing zeros are dropped so that equal rates compare equal
+        let (mut mantissa, mut scale) = (mantissa, scale);
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Self { mantissa, scale }
+    }
+
+    /// Rate for the opposite direction, rounded half up to twelve decimal places.
+    pub fn inverse(&self) -> CustomResult<Self, errors::FxError> {
+        let numerator = pow10(self.scale + Self::DERIVED_RATE_SCALE)?;
+        Ok(Self::new(
+            divide_round_half_up(numerator, self.mantissa)?,
+            Self::DERIVED_RATE_SCALE,
+        ))
+    }
+
+    fn is_positive(&self) -> bool {
+        self.mantissa > 0
+    }
+}
+
+impl std::str::FromStr for Rate {
+    type Err = error_stack::Report<errors::FxError>;
+
+    fn from_str(rate: &str) -> Result<Self, Self::Err> {
+        let rate = rate.trim();
+        let (integer_part, fractional_part) = rate.split_once('.').unwrap_or((rate, ""));
+        let is_valid = !integer_part.is_empty()
+            && integer_part.bytes().all(|digit| digit.is_ascii_digit())
+            && fractional_part.bytes().all(|digit| digit.is_ascii_digit());
+        common_utils::fp_utils::when(!is_valid, || {
+            Err(errors::FxError::InvalidRate).into_report()
+        })?;
+
+        let mantissa = format!("{integer_part}{fractional_part}")
+            .parse::<i128>()
+            .into_report()
+            .change_context(errors::FxError::InvalidRate)?;
+        let scale = u32::try_from(fractional_part.len())
+            .into_report()
+            .change_context(errors::FxError::InvalidRate)?;
+        let rate = Self::new(mantissa, scale);
+
+        common_utils::fp_utils::when(!rate.is_positive(), || {
+            Err(errors::FxError::InvalidRate).into_report()
+        })?;
+        Ok(rate)
+    }
+}
+
+impl std::fmt::Display for Rate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = self.mantissa.to_string();
+        let scale = usize::try_from(self.scale).map_err(|_| std::fmt::Error)?;
+        if scale == 0 {
+            return write!(f, "{digits}");
+        }
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (integer_part, fractional_part) = digits.split_at(digits.len() - scale);
+        write!(f, "{integer_part}.{fractional_part}")
+    }
+}
+
+/// Exchange rate between two currencies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FxRate {
+    pub from: enums::Currency,
+    pub to: enums::Currency,
+    /// Number of units of `to` that a single unit of `from` buys
+    pub rate: Rate,
+}
+
+/// Source of the exchange rates applied when a payment is presented to the customer in one
+/// currency and settled with the connector in another.
+#[async_trait::async_trait]
+pub trait FxRateProvider: Send + Sync {
+    async fn get_rate(
+        &self,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<FxRate, errors::FxError>;
+}
+
+/// Serves the exchange rates configured in the `[fx]` section of the application configuration.
+#[derive(Debug, Clone, Default)]
+pub struct StaticFxRateProvider {
+    rates: HashMap<(enums::Currency, enums::Currency), Rate>,
+}
+
+impl StaticFxRateProvider {
+    pub fn new(config: &settings::FxSettings) -> Self {
+        let rates = config
+            .rates
+            .iter()
+            .filter_map(|(currency_pair, rate)| {
+                let (from, to) = currency_pair.split_once('_')?;
+                Some(((parse_currency(from)?, parse_currency(to)?), *rate))
+            })
+            .collect();
+
+        Self { rates }
+    }
+}
+
+#[async_trait::async_trait]
+impl FxRateProvider for StaticFxRateProvider {
+    async fn get_rate(
+        &self,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<FxRate, errors::FxError> {
+        lookup_rate(&self.rates, from, to)
+    }
+}
+
+/// In-memory exchange rates, to be used in tests.
+#[derive(Debug, Clone, Default)]
+pub struct MockFxRateProvider {
+    rates: HashMap<(enums::Currency, enums::Currency), Rate>,
+}
+
+impl MockFxRateProvider {
+    pub fn with_rate(mut self, from: enums::Currency, to: enums::Currency, rate: Rate) -> Self {
+        self.rates.insert((from, to), rate);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl FxRateProvider for MockFxRateProvider {
+    async fn get_rate(
+        &self,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<FxRate, errors::FxError> {
+        lookup_rate(&self.rates, from, to)
+    }
+}
+
+pub fn get_fx_rate_provider(config: &settings::FxSettings) -> Box<dyn FxRateProvider> {
+    Box::new(StaticFxRateProvider::new(config))
+}
+
+fn parse_currency(currency: &str) -> Option<enums::Currency> {
+    currency.to_uppercase().parse().ok()
+}
+
+fn lookup_rate(
+    rates: &HashMap<(enums::Currency, enums::Currency), Rate>,
+    from: enums::Currency,
+    to: enums::Currency,
+) -> CustomResult<FxRate, errors::FxError> {
+    if from == to {
+        return Ok(FxRate {
+            from,
+            to,
+            rate: Rate::ONE,
+        });
+    }
+
+    // Fall back to the inverse of the rate configured for the opposite direction
+    let rate = match (rates.get(&(from, to)), rates.get(&(to, from))) {
+        (Some(rate), _) => *rate,
+        (None, Some(inverse_rate)) => inverse_rate.inverse()?,
+        (None, None) => Err(errors::FxError::RateNotFound {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+        .into_report()?,
+    };
+    Ok(FxRate { from, to, rate })
+}
+
+/// Number of decimal places in the lowest denomination of `currency`.
+pub fn currency_exponent(currency: enums::Currency) -> u32 {
+    u32::from(
+        api_models::enums::Currency::foreign_from(currency).number_of_digits_after_decimal_point(),
+    )
+}
+
+fn pow10(exponent: u32) -> CustomResult<i128, errors::FxError> {
+    10_i128
+        .checked_pow(exponent)
+        .ok_or(errors::FxError::AmountOutOfRange)
+        .into_report()
+}
+
+/// Divides `numerator` by the positive `denominator`, rounding halves away from zero.
+fn divide_round_half_up(numerator: i128, denominator: i128) -> CustomResult<i128, errors::FxError> {
+    common_utils::fp_utils::when(denominator <= 0, || {
+        Err(errors::FxError::InvalidRate).into_report()
+    })?;
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    let rounding = match remainder.checked_mul(2) {
+        Some(twice_remainder) if twice_remainder.abs() >= denominator => numerator.signum(),
+        Some(_) => 0,
+        None => numerator.signum(),
+    };
+    Ok(quotient + rounding)
+}
+
+/// Converts an amount in the lowest denomination of `fx_rate.from` into the lowest denomination of
+/// `fx_rate.to`. The result is rounded to the nearest unit, with halves rounded away from zero.
+pub fn convert_amount(amount: i64, fx_rate: &FxRate) -> CustomResult<i64, errors::FxError> {
+    let to_minor_units = pow10(currency_exponent(fx_rate.to))?;
+    let numerator = i128::from(amount)
+        .checked_mul(fx_rate.rate.mantissa)
+        .and_then(|product| product.checked_mul(to_minor_units))
+        .ok_or(errors::FxError::AmountOutOfRange)
+        .into_report()?;
+    let denominator = pow10(fx_rate.rate.scale + currency_exponent(fx_rate.from))?;
+
+    let converted = divide_round_half_up(numerator, denominator)?;
+    i64::try_from(converted)
+        .into_report()
+        .change_context(errors::FxError::AmountOutOfRange)
+}
+
+/// Amount and currency with which a payment is settled with the connector, along with the
+/// exchange rate applied to arrive at the amount.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SettlementDetails {
+    pub amount: i64,
+    pub currency: enums::Currency,
+    pub fx_rate: String,
+}
+
+/// Fetches the rate from `presentment_currency` to `settlement_currency` and converts `amount`
+/// into the settlement currency.
+#[instrument(skip_all)]
+pub async fn get_settlement_details(
+    provider: &dyn FxRateProvider,
+    amount: i64,
+    presentment_currency: enums::Currency,
+    settlement_currency: enums::Currency,
+) -> RouterResult<SettlementDetails> {
+    let fx_rate = provider
+        .get_rate(presentment_currency, settlement_currency)
+        .await
+        .change_context(errors::ApiErrorResponse::NotSupported {
+            message: format!(
+                "Conversion from {presentment_currency} to {settlement_currency} is not supported"
+            ),
+        })?;
+
+    let settlement_amount = convert_amount(amount, &fx_rate)
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "amount",
+        })
+        .attach_printable("Failed to convert payment amount to settlement currency")?;
+
+    Ok(SettlementDetails {
+        amount: settlement_amount,
+        currency: settlement_currency,
+        fx_rate: fx_rate.rate.to_string(),
+    })
+}
+
+/// Converts an amount in the presentment currency of a payment attempt into its settlement
+/// currency, using the rate persisted on the attempt. Returns `None` if the attempt was not
+/// created with FX conversion.
+pub fn to_settlement_money(
+    payment_attempt: &storage::PaymentAttempt,
+    amount: i64,
+) -> RouterResult<Option<(i64, enums::Currency)>> {
+    let (presentment_currency, settlement_currency, rate) = match (
+        payment_attempt.currency,
+        payment_attempt.settlement_currency,
+        payment_attempt.fx_rate.as_ref(),
+    ) {
+        (Some(presentment_currency), Some(settlement_currency), Some(rate)) => {
+            (presentment_currency, settlement_currency, rate)
+        }
+        _ => return Ok(None),
+    };
+
+    let rate = rate
+        .parse::<Rate>()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Invalid FX rate stored on payment attempt: {rate}"))?;
+
+    let converted_amount = convert_amount(
+        amount,
+        &FxRate {
+            from: presentment_currency,
+            to: settlement_currency,
+            rate,
+        },
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to convert amount to settlement currency")?;
+
+    Ok(Some((converted_amount, settlement_currency)))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn rate(rate: &str) -> Rate {
+        rate.parse().unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn test_mock_provider_uses_inverse_rate() {
+        let provider = MockFxRateProvider::default().with_rate(
+            enums::Currency::USD,
+            enums::Currency::EUR,
+            rate("0.8"),
+        );
+
+        let inverse = provider
+            .get_rate(enums::Currency::EUR, enums::Currency::USD)
+            .await
+            .unwrap();
+        assert_eq!(inverse.rate, rate("1.25"));
+        assert_eq!(inverse.rate.to_string(), "1.25");
+
+        let missing = provider
+            .get_rate(enums::Currency::USD, enums::Currency::GBP)
+            .await;
+        assert!(missing.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn test_static_provider_reads_configured_rates() {
+        let config = settings::FxSettings {
+            rates: HashMap::from([("usd_jpy".to_string(), rate("130"))]),
+        };
+        let provider = get_fx_rate_provider(&config);
+
+        let settlement_details = get_settlement_details(
+            provider.as_ref(),
+            1050,
+            enums::Currency::USD,
+            enums::Currency::JPY,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            settlement_details,
+            SettlementDetails {
+                amount: 1365,
+                currency: enums::Currency::JPY,
+                fx_rate: "130".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_configured_rates_are_parsed_exactly() {
+        let config: settings::FxSettings =
+            serde_json::from_value(serde_json::json!({ "rates": { "usd_eur": "0.92" } })).unwrap();
+        assert_eq!(config.rates.get("usd_eur"), Some(&rate("0.92")));
+
+        let config = serde_json::from_value::<settings::FxSettings>(
+            serde_json::json!({ "rates": { "usd_eur": "-0.92" } }),
+        );
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn test_convert_amount_across_exponents() {
+        let fx_rate = FxRate {
+            from: enums::Currency::KWD,
+            to: enums::Currency::EUR,
+            rate: rate("3"),
+        };
+        assert_eq!(convert_amount(1500, &fx_rate).unwrap(), 450);
+
+        let fx_rate = FxRate {
+            from: enums::Currency::USD,
+            to: enums::Currency::KRW,
+            rate: rate("1318.57"),
+        };
+        assert_eq!(convert_amount(1999, &fx_rate).unwrap(), 26358);
+    }
+
+    #[test]
+    fn test_convert_amount_rounds_half_up() {
+        let fx_rate = FxRate {
+            from: enums::Currency::EUR,
+            to: enums::Currency::USD,
+            rate: rate("1.5"),
+        };
+        assert_eq!(convert_amount(1, &fx_rate).unwrap(), 2);
+        assert_eq!(convert_amount(3, &fx_rate).unwrap(), 5);
+
+        let fx_rate = FxRate {
+            from: enums::Currency::USD,
+            to: enums::Currency::EUR,
+            rate: rate("0.1"),
+        };
+        // 0.1 has no exact binary representation, 1005 * 0.1 rounds down as a float
+        assert_eq!(convert_amount(1005, &fx_rate).unwrap(), 101);
+    }
+
+    #[test]
+    fn test_rate_parsing() {
+        assert_eq!(rate("130.00"), rate("130"));
+        assert_eq!(rate("0.000125").to_string(), "0.000125");
+        assert!("-1.2".parse::<Rate>().is_err());
+        assert!("0".parse::<Rate>().is_err());
+        assert!("1e3".parse::<Rate>().is_err());
+    }
+
+    #[test]
+    fn test_currency_exponent() {
+        assert_eq!(currency_exponent(enums::Currency::JPY), 0);
+        assert_eq!(currency_exponent(enums::Currency::KRW), 0);
+        assert_eq!(currency_exponent(enums::Currency::USD), 2);
+        assert_eq!(currency_exponent(enums::Currency::HUF), 2);
+        assert_eq!(currency_exponent(enums::Currency::BHD), 3);
+        assert_eq!(currency_exponent(enums::Currency::OMR), 3);
+    }
+}
@@ -0,0 +1,200 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use futures::future::{self, BoxFuture, FutureExt};
+use tokio::time::Instant;
+
+use crate::{
+    configs::settings::LockerSetup,
+    logger,
+    routes::AppState,
+    scheduler::{utils as pt_utils, SchedulerFlow},
+    services,
+    types::api::{DependencyHealth, HealthStatus, ReadinessReport},
+};
+
+enum CheckOutcome {
+    Healthy,
+    Skipped(&'static str),
+}
+
+type CheckResult = Result<CheckOutcome, String>;
+
+fn error_message<C: error_stack::Context>(error: error_stack::Report<C>) -> String {
+    error.current_context().to_string()
+}
+
+/// Checks every dependency required for serving requests. The report is `unhealthy` if any of
+/// the checks fail or do not complete within the configured timeout.
+pub async fn readiness(state: &AppState) -> ReadinessReport {
+    let timeout = Duration::from_millis(state.conf.health_check.check_timeout);
+
+    let mut checks: Vec<(&'static str, BoxFuture<'_, CheckResult>)> = vec![
+        ("database_master", check_master_db(state).boxed()),
+        ("redis", check_redis(state).boxed()),
+        ("locker", check_locker(state).boxed()),
+        (
+            "scheduler_producer",
+            check_scheduler_heartbeat(state, SchedulerFlow::Producer).boxed(),
+        ),
+        (
+            "scheduler_consumer",
+            check_scheduler_heartbeat(state, SchedulerFlow::Consumer).boxed(),
+        ),
+    ];
+    #[cfg(feature = "olap")]
+    checks.push(("database_replica", check_replica_db(state).boxed()));
+    #[cfg(feature = "kv_store")]
+    checks.push(("drainer", check_drainer(state).boxed()));
+
+    let checks = future::join_all(
+        checks
+            .into_iter()
+            .map(|(name, check)| async move { (name, run_check(name, timeout, check).await) }),
+    )
+    .await
+    .into_iter()
+    .collect::<BTreeMap<_, _>>();
+
+    let status = if checks
+        .values()
+        .any(|check| check.status == HealthStatus::Unhealthy)
+    {
+        HealthStatus::Unhealthy
+    } else {
+        HealthStatus::Healthy
+    };
+
+    ReadinessReport { status, checks }
+}
+
+async fn run_check(
+    name: &'static str,
+    timeout: Duration,
+    check: BoxFuture<'_, CheckResult>,
+) -> DependencyHealth {
+    let start = Instant::now();
+    let result = tokio::time::timeout(timeout, check)
+        .await
+        .unwrap_or_else(|_| Err(format!("Check did not complete within {timeout:?}")));
+    let duration_ms = start.elapsed().as_millis();
+
+    let (status, message) = match result {
+        Ok(CheckOutcome::Healthy) => (HealthStatus::Healthy, None),
+        Ok(CheckOutcome::Skipped(reason)) => (HealthStatus::Skipped, Some(reason.to_owned())),
+        Err(reason) => {
+            logger::warn!(dependency = name, %reason, "Readiness check failed");
+            (HealthStatus::Unhealthy, Some(reason))
+        }
+    };
+
+    DependencyHealth {
+        status,
+        duration_ms,
+        message,
+    }
+}
+
+async fn check_master_db(state: &AppState) -> CheckResult {
+    state
+        .store
+        .health_check_master_db()
+        .await
+        .map_err(error_message)?;
+    Ok(CheckOutcome::Healthy)
+}
+
+#[cfg(feature = "olap")]
+async fn check_replica_db(state: &AppState) -> CheckResult {
+    state
+        .store
+        .health_check_replica_db()
+        .await
+        .map_err(error_message)?;
+    Ok(CheckOutcome::Healthy)
+}
+
+async fn check_redis(state: &AppState) -> CheckResult {
+    state
+        .store
+        .health_check_redis()
+        .await
+        .map_err(error_message)?;
+    Ok(CheckOutcome::Healthy)
+}
+
+async fn check_locker(state: &AppState) -> CheckResult {
+    let locker = &state.conf.locker;
+    if locker.mock_locker {
+        return Ok(CheckOutcome::Skipped("Mock locker is in use"));
+    }
+
+    let host = match locker.locker_setup {
+        LockerSetup::LegacyLocker => &locker.host,
+        LockerSetup::BasiliskLocker => &locker.basilisk_host,
+    };
+    let request = services::Request::new(services::Method::Get, &format!("{host}/health"));
+
+    match services::call_connector_api(state, request).await {
+        Ok(Ok(_)) => Ok(CheckOutcome::Healthy),
+        // The locker is reachable, even if it does not expose a health endpoint
+        Ok(Err(response)) if response.status_code < 500 => Ok(CheckOutcome::Healthy),
+        Ok(Err(response)) => Err(format!(
+            "Locker responded with status code {}",
+            response.status_code
+        )),
+        Err(error) => Err(error_message(error)),
+    }
+}
+
+async fn check_scheduler_heartbeat(state: &AppState, flow: SchedulerFlow) -> CheckResult {
+    let health_check = &state.conf.health_check;
+    let scheduler_settings = match state.conf.scheduler.as_ref() {
+        Some(scheduler_settings) if health_check.check_scheduler => scheduler_settings,
+        _ => return Ok(CheckOutcome::Skipped("Scheduler checks are disabled")),
+    };
+
+    let heartbeat_key = pt_utils::get_heartbeat_key(scheduler_settings, flow);
+    let last_heartbeat = state
+        .store
+        .find_scheduler_heartbeat(&heartbeat_key)
+        .await
+        .map_err(error_message)?
+        .ok_or_else(|| format!("No heartbeat recorded by the scheduler {flow}"))?;
+
+    let age = common_utils::date_time::now_unix_timestamp() - last_heartbeat;
+    if age > health_check.max_heartbeat_age {
+        Err(format!(
+            "Last heartbeat of the scheduler {flow} was {age} seconds ago"
+        ))
+    } else {
+        Ok(CheckOutcome::Healthy)
+    }
+}
+
+#[cfg(feature = "kv_store")]
+async fn check_drainer(state: &AppState) -> CheckResult {
+    let health_check = &state.conf.health_check;
+    if !health_check.check_drainer {
+        return Ok(CheckOutcome::Skipped("Drainer checks are disabled"));
+    }
+
+    let max_stream_length = health_check.max_drainer_stream_length;
+    let lagging_streams = state
+        .store
+        .get_drainer_stream_lengths()
+        .await
+        .map_err(error_message)?
+        .into_iter()
+        .filter(|(_, length)| *length > max_stream_length)
+        .map(|(stream_name, length)| format!("{stream_name} ({length} entries)"))
+        .collect::<Vec<_>>();
+
+    if lagging_streams.is_empty() {
+        Ok(CheckOutcome::Healthy)
+    } else {
+        Err(format!(
+            "Drainer streams exceeding {max_stream_length} entries: {}",
+            lagging_streams.join(", ")
+        ))
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use error_stack::ResultExt;
+
+#[cfg(feature = "basilisk")]
+use crate::core::payment_methods::vault;
+use crate::{
+    core::errors::{self, RouterResponse},
+    routes::AppState,
+    services::ApplicationResponse,
+    types::api,
+};
+
+/// Reports the number of records per key version for each key which supports rotation.
+pub async fn report(state: &AppState) -> RouterResponse<api::KeyRotationReport> {
+    let api_keys = state
+        .store
+        .count_api_keys_by_hash_key_id()
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to count API keys by hash key ID")?;
+
+    Ok(ApplicationResponse::Json(api::KeyRotationReport {
+        api_keys: key_version_report(
+            state.conf.api_keys.hash_key_id.clone(),
+            api_keys.into_iter().collect(),
+        ),
+        #[cfg(feature = "basilisk")]
+        locker_tokens: locker_tokens_report(state).await?,
+    }))
+}
+
+/// Counts the data stored in the locker per locker key pair, from the key ID recorded with every
+/// tokenization in the task deleting the data. Data is counted until its task has deleted it from
+/// the locker: this includes tasks which are pending as well as those which gave up on deleting
+/// it.
+#[cfg(feature = "basilisk")]
+async fn locker_tokens_report(state: &AppState) -> errors::RouterResult<api::KeyVersionReport> {
+    let keys = &state.conf.jwekey;
+    let counts = state
+        .store
+        .count_processes_by_runner_and_key_id_excluding_business_status_prefix(
+            vault::DELETE_TOKENIZE_DATA_RUNNER,
+            vault::DELETE_TOKENIZE_DATA_COMPLETED_PREFIX,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to count tokenized data not deleted from the locker")?;
+
+    let mut records_by_key_id = HashMap::new();
+    for (key_id, count) in counts {
+        // Data tokenized before locker keys were tracked always used the first key pair
+        let key_id = key_id.unwrap_or_else(|| keys.locker_key_identifier1.clone());
+        *records_by_key_id.entry(key_id).or_insert(0) += count;
+    }
+
+    Ok(key_version_report(
+        vault::get_key_id(keys).to_owned(),
+        records_by_key_id,
+    ))
+}
+
+fn key_version_report(
+    current_key_id: String,
+    records_by_key_id: HashMap<String, i64>,
+) -> api::KeyVersionReport {
+    let records_with_previous_keys = records_by_key_id
+        .iter()
+        .filter(|(key_id, _)| **key_id != current_key_id)
+        .map(|(_, count)| count)
+        .sum();
+
+    api::KeyVersionReport {
+        current_key_id,
+        records_by_key_id,
+        records_with_previous_keys,
+    }
+}
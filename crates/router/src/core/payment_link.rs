@@ -0,0 +1,762 @@
+use api_models::enums as api_enums;
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use super::errors::{self, RouterResponse, RouterResult, StorageErrorExt};
+use crate::{
+    consts,
+    core::{
+        fx,
+        payments::{self, helpers},
+    },
+    db::StorageInterface,
+    routes::AppState,
+    scheduler::utils as pt_utils,
+    services,
+    types::{
+        api,
+        storage::{self, enums as storage_enums, ProcessTrackerExt},
+        transformers::{ForeignFrom, ForeignInto},
+    },
+    utils::{self, OptionExt, ValueExt},
+};
+
+const PAYMENT_LINK_EXPIRY_RUNNER: &str = "PAYMENT_LINK_EXPIRY_WORKFLOW";
+const PAYMENT_LINK_EXPIRY_TASK: &str = "PAYMENT_LINK_EXPIRY";
+
+#[instrument(skip(state))]
+pub async fn create_payment_link(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    req: api::PaymentLinkCreateRequest,
+) -> RouterResponse<api::PaymentLinkResponse> {
+    validate_create_request(&req)?;
+    let expires_in = req
+        .expires_in
+        .unwrap_or(consts::DEFAULT_PAYMENT_LINK_EXPIRY);
+
+    let payment_request = api::PaymentsRequest {
+        amount: Some(req.amount.into()),
+        currency: Some(req.currency),
+        description: req.description.clone(),
+        return_url: req.return_url.clone(),
+        customer_id: req.customer_id,
+        allowed_payment_method_types: req.allowed_payment_method_types,
+        confirm: Some(false),
+        session_expiry: Some(expires_in),
+        ..Default::default()
+    };
+
+    let payment_response =
+        match payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _>(
+            state,
+            merchant_account.clone(),
+            payments::PaymentCreate,
+            payment_request,
+            services::AuthFlow::Merchant,
+            payments::CallConnectorAction::Trigger,
+        )
+        .await?
+        {
+            services::ApplicationResponse::Json(response) => Ok(response),
+            _ => Err(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Failed to get the payment create response in json"),
+        }?;
+
+    let payment_link_id = utils::generate_id(consts::ID_LENGTH, "plink");
+    let payment_link_new = storage::PaymentLinkNew {
+        link_to_pay: format!(
+            "{}/payment_link/pay/{payment_link_id}",
+            state.conf.server.base_url
+        ),
+        payment_link_id,
+        merchant_id: merchant_account.merchant_id,
+        payment_id: payment_response
+            .payment_id
+            .get_required_value("payment_id")?,
+        amount: req.amount,
+        currency: req.currency.foreign_into(),
+        description: req.description,
+        return_url: req.return_url.map(|url| url.to_string()),
+        status: storage_enums::PaymentLinkStatus::Active,
+        expires_at: common_utils::date_time::now()
+            .saturating_add(time::Duration::seconds(expires_in.into())),
+    };
+
+    let payment_link = state
+        .store
+        .insert_payment_link(payment_link_new)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert payment link")?;
+
+    add_payment_link_expiry_task(&*state.store, &payment_link).await?;
+
+    Ok(services::ApplicationResponse::Json(
+        api::PaymentLinkResponse::foreign_from(payment_link),
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_payment_link(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    payment_link_id: String,
+) -> RouterResponse<api::PaymentLinkResponse> {
+    let payment_link = state
+        .store
+        .find_payment_link_by_merchant_id_payment_link_id(
+            &merchant_account.merchant_id,
+            &payment_link_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound { payment_link_id })?;
+
+    Ok(services::ApplicationResponse::Json(
+        api::PaymentLinkResponse::foreign_from(payment_link),
+    ))
+}
+
+/// Renders the hosted checkout page for a payment link.
+#[instrument(skip(state))]
+pub async fn initiate_payment_link_checkout(
+    state: &AppState,
+    payment_link_id: String,
+) -> RouterResponse<()> {
+    let (payment_link, merchant_account, payment_intent) =
+        get_payment_link_details(state, payment_link_id).await?;
+
+    let page = match get_checkout_status(&payment_link, &payment_intent) {
+        CheckoutStatus::Open => checkout_page(
+            &payment_link,
+            &merchant_account,
+            is_card_allowed(&payment_intent)?,
+        ),
+        CheckoutStatus::Processed(status) => payment_status_page(&payment_link, status),
+        CheckoutStatus::Unavailable => link_unavailable_page(),
+    };
+
+    Ok(html_response(page))
+}
+
+/// Confirms the payment bound to a payment link with the card details submitted through the
+/// hosted checkout page.
+#[instrument(skip(state, form))]
+pub async fn complete_payment_link_checkout(
+    state: &AppState,
+    payment_link_id: String,
+    form: api::PaymentLinkCheckoutForm,
+) -> RouterResponse<()> {
+    let (payment_link, merchant_account, payment_intent) =
+        get_payment_link_details(state, payment_link_id).await?;
+
+    match get_checkout_status(&payment_link, &payment_intent) {
+        CheckoutStatus::Open => (),
+        CheckoutStatus::Processed(status) => {
+            return Ok(html_response(payment_status_page(&payment_link, status)))
+        }
+        CheckoutStatus::Unavailable => return Ok(html_response(link_unavailable_page())),
+    }
+
+    common_utils::fp_utils::when(!is_card_allowed(&payment_intent)?, || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Card payments are not allowed for this payment link".to_string(),
+        })
+        .into_report()
+    })?;
+
+    let payment_request = api::PaymentsRequest {
+        payment_id: Some(api::PaymentIdType::PaymentIntentId(
+            payment_link.payment_id.clone(),
+        )),
+        merchant_id: Some(merchant_account.merchant_id.clone()),
+        payment_method: Some(api_enums::PaymentMethod::Card),
+        payment_method_data: Some(api::PaymentMethodData::Card(form.into())),
+        confirm: Some(true),
+        ..Default::default()
+    };
+
+    let payment_response =
+        match payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _>(
+            state,
+            merchant_account.clone(),
+            payments::PaymentConfirm,
+            payment_request,
+            services::AuthFlow::Merchant,
+            payments::CallConnectorAction::Trigger,
+        )
+        .await?
+        {
+            services::ApplicationResponse::Json(response) => Ok(response),
+            _ => Err(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Failed to get the payment confirm response in json"),
+        }?;
+
+    // The customer has to authenticate the payment with the connector before it can complete
+    if let Some(redirect_to_url) = payment_response
+        .next_action
+        .as_ref()
+        .and_then(|next_action| next_action.redirect_to_url.as_ref())
+    {
+        let redirect_url = url::Url::parse(redirect_to_url)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to parse the redirection url")?;
+
+        return Ok(services::ApplicationResponse::Form(
+            services::RedirectForm::from((redirect_url, services::Method::Get)),
+        ));
+    }
+
+    let payment_link = match payment_response.status {
+        api_enums::IntentStatus::Succeeded
+        | api_enums::IntentStatus::Processing
+        | api_enums::IntentStatus::RequiresCapture => {
+            update_payment_link_status(
+                &*state.store,
+                payment_link,
+                storage_enums::PaymentLinkStatus::Completed,
+            )
+            .await?
+        }
+        _ => payment_link,
+    };
+
+    if payment_response.return_url.is_some() || merchant_account.return_url.is_some() {
+        let connector = payment_response.connector.clone().unwrap_or_default();
+        let redirection_response = helpers::get_handle_response_url(
+            payment_link.payment_id.clone(),
+            &merchant_account,
+            payment_response,
+            connector,
+        )?;
+
+        return Ok(services::ApplicationResponse::JsonForRedirection(
+            redirection_response,
+        ));
+    }
+
+    Ok(html_response(payment_status_page(
+        &payment_link,
+        payment_response.status.foreign_into(),
+    )))
+}
+
+/// Expires a payment link once its validity has elapsed, cancelling the payment bound to the
+/// link if the customer has not attempted it yet.
+pub async fn start_payment_link_expiry_workflow(
+    state: &AppState,
+    process: &storage::ProcessTracker,
+) -> Result<(), errors::ProcessTrackerError> {
+    let db = &*state.store;
+    let tracking_data: storage::PaymentLinkExpiryTrackingData = process
+        .tracking_data
+        .clone()
+        .parse_value("PaymentLinkExpiryTrackingData")?;
+
+    let payment_link = db
+        .find_payment_link_by_merchant_id_payment_link_id(
+            &tracking_data.merchant_id,
+            &tracking_data.payment_link_id,
+        )
+        .await?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&tracking_data.merchant_id)
+        .await?;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_link.payment_id,
+            &payment_link.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await?;
+
+    let payment_link_status = match payment_intent.status {
+        storage_enums::IntentStatus::RequiresPaymentMethod
+        | storage_enums::IntentStatus::RequiresConfirmation => {
+            db.update_payment_intent(
+                payment_intent,
+                storage::PaymentIntentUpdate::PGStatusUpdate {
+                    status: storage_enums::IntentStatus::Cancelled,
+                },
+                merchant_account.storage_scheme,
+            )
+            .await?;
+            storage_enums::PaymentLinkStatus::Expired
+        }
+        storage_enums::IntentStatus::Succeeded
+        | storage_enums::IntentStatus::Processing
+        | storage_enums::IntentStatus::RequiresCapture => {
+            storage_enums::PaymentLinkStatus::Completed
+        }
+        _ => storage_enums::PaymentLinkStatus::Expired,
+    };
+
+    if payment_link.status == storage_enums::PaymentLinkStatus::Active {
+        db.update_payment_link(
+            payment_link,
+            storage::PaymentLinkUpdate::StatusUpdate {
+                status: payment_link_status,
+            },
+        )
+        .await?;
+    }
+
+    let id = process.id.clone();
+    process
+        .clone()
+        .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+        .await
+}
+
+#[instrument(skip_all)]
+pub async fn add_payment_link_expiry_task(
+    db: &dyn StorageInterface,
+    payment_link: &storage::PaymentLink,
+) -> RouterResult<()> {
+    let tracking_data = storage::PaymentLinkExpiryTrackingData {
+        payment_link_id: payment_link.payment_link_id.clone(),
+        merchant_id: payment_link.merchant_id.clone(),
+    };
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        PAYMENT_LINK_EXPIRY_RUNNER,
+        PAYMENT_LINK_EXPIRY_TASK,
+        &payment_link.payment_link_id,
+        &payment_link.merchant_id,
+    );
+    let process_tracker_entry =
+        <storage::ProcessTracker as ProcessTrackerExt>::make_process_tracker_new(
+            process_tracker_id,
+            PAYMENT_LINK_EXPIRY_TASK,
+            PAYMENT_LINK_EXPIRY_RUNNER,
+            tracking_data,
+            payment_link.expires_at,
+        )
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting expiry task for payment link: {}",
+                payment_link.payment_link_id
+            )
+        })?;
+
+    Ok(())
+}
+
+fn validate_create_request(req: &api::PaymentLinkCreateRequest) -> RouterResult<()> {
+    use common_utils::fp_utils::when;
+
+    when(req.amount <= 0, || {
+        Err(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "amount",
+        })
+        .into_report()
+        .attach_printable("Amount of a payment link must be greater than zero")
+    })?;
+
+    when(
+        req.expires_in.map_or(false, |expires_in| {
+            !(consts::MIN_PAYMENT_LINK_EXPIRY..=consts::MAX_PAYMENT_LINK_EXPIRY)
+                .contains(&expires_in)
+        }),
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "expires_in must be between {} and {} seconds",
+                    consts::MIN_PAYMENT_LINK_EXPIRY,
+                    consts::MAX_PAYMENT_LINK_EXPIRY
+                ),
+            })
+            .into_report()
+        },
+    )
+}
+
+async fn get_payment_link_details(
+    state: &AppState,
+    payment_link_id: String,
+) -> RouterResult<(
+    storage::PaymentLink,
+    storage::MerchantAccount,
+    storage::PaymentIntent,
+)> {
+    let db = &*state.store;
+    let payment_link = db
+        .find_payment_link_by_payment_link_id(&payment_link_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound { payment_link_id })?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&payment_link.merchant_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_link.payment_id,
+            &payment_link.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    Ok((payment_link, merchant_account, payment_intent))
+}
+
+async fn update_payment_link_status(
+    db: &dyn StorageInterface,
+    payment_link: storage::PaymentLink,
+    status: storage_enums::PaymentLinkStatus,
+) -> RouterResult<storage::PaymentLink> {
+    db.update_payment_link(
+        payment_link,
+        storage::PaymentLinkUpdate::StatusUpdate { status },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to update payment link status")
+}
+
+#[derive(Debug, PartialEq)]
+enum CheckoutStatus {
+    /// The customer can pay through the link
+    Open,
+    /// The customer has already attempted the payment
+    Processed(storage_enums::IntentStatus),
+    /// The link has expired or the payment was cancelled
+    Unavailable,
+}
+
+fn get_checkout_status(
+    payment_link: &storage::PaymentLink,
+    payment_intent: &storage::PaymentIntent,
+) -> CheckoutStatus {
+    let is_expired = payment_link.status == storage_enums::PaymentLinkStatus::Expired
+        || common_utils::date_time::now() >= payment_link.expires_at;
+
+    match payment_intent.status {
+        storage_enums::IntentStatus::RequiresPaymentMethod
+        | storage_enums::IntentStatus::RequiresConfirmation
+            if !is_expired =>
+        {
+            CheckoutStatus::Open
+        }
+        status @ (storage_enums::IntentStatus::Succeeded
+        | storage_enums::IntentStatus::Processing
+        | storage_enums::IntentStatus::RequiresCapture
+        | storage_enums::IntentStatus::RequiresCustomerAction
+        | storage_enums::IntentStatus::RequiresMerchantAction
+        | storage_enums::IntentStatus::Failed) => CheckoutStatus::Processed(status),
+        _ => CheckoutStatus::Unavailable,
+    }
+}
+
+fn is_card_allowed(payment_intent: &storage::PaymentIntent) -> RouterResult<bool> {
+    let metadata: Option<api_models::payments::Metadata> = payment_intent
+        .metadata
+        .clone()
+        .map(|metadata| {
+            metadata
+                .parse_value("Metadata")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Unable to parse payment intent metadata")
+        })
+        .transpose()?;
+
+    Ok(metadata
+        .and_then(|metadata| metadata.allowed_payment_method_types)
+        .map_or(true, |payment_method_types| {
+            payment_method_types.iter().any(|payment_method_type| {
+                matches!(
+                    payment_method_type,
+                    api_enums::PaymentMethodType::Credit | api_enums::PaymentMethodType::Debit
+                )
+            })
+        }))
+}
+
+fn format_amount(amount: i64, currency: storage_enums::Currency) -> String {
+    let exponent = fx::currency_exponent(currency);
+    let divisor = 10_i64.pow(exponent);
+    let major_units = amount / divisor;
+    let minor_units = (amount % divisor).abs();
+
+    match usize::try_from(exponent) {
+        Ok(0) | Err(_) => format!("{major_units} {currency}"),
+        Ok(width) => format!("{major_units}.{minor_units:0width$} {currency}"),
+    }
+}
+
+fn html_response(page: maud::Markup) -> services::ApplicationResponse<()> {
+    services::ApplicationResponse::Form(services::RedirectForm::Html {
+        html_data: page.into_string(),
+    })
+}
+
+fn page_layout(title: &str, content: maud::Markup) -> maud::Markup {
+    maud::html! {
+        (maud::DOCTYPE)
+        html {
+            head {
+                meta charset="utf-8";
+                meta name="viewport" content="width=device-width, initial-scale=1";
+                title { (title) }
+                style {
+                    r#"
+                    body { background-color: #f5f7fa; font-family: Arial, Helvetica, Sans-Serif; margin: 0; padding: 20px; }
+                    .card { background-color: #ffffff; border-radius: 8px; box-shadow: 0 2px 8px rgba(0, 0, 0, 0.1); margin: 40px auto; max-width: 420px; padding: 24px; }
+                    .amount { font-size: 28px; font-weight: bold; margin: 8px 0; }
+                    .muted { color: #6b7280; font-size: 14px; }
+                    label { display: block; font-size: 14px; margin-top: 12px; }
+                    input { border: 1px solid #d1d5db; border-radius: 4px; box-sizing: border-box; font-size: 16px; margin-top: 4px; padding: 10px; width: 100%; }
+                    .row { display: flex; gap: 12px; }
+                    button { background-color: #006df9; border: none; border-radius: 4px; color: #ffffff; cursor: pointer; font-size: 16px; margin-top: 20px; padding: 12px; width: 100%; }
+                    "#
+                }
+            }
+            body {
+                div class="card" { (content) }
+            }
+        }
+    }
+}
+
+fn checkout_page(
+    payment_link: &storage::PaymentLink,
+    merchant_account: &storage::MerchantAccount,
+    is_card_allowed: bool,
+) -> maud::Markup {
+    let merchant_name = merchant_account
+        .merchant_name
+        .as_deref()
+        .unwrap_or(merchant_account.merchant_id.as_str());
+    let amount = format_amount(payment_link.amount, payment_link.currency);
+
+    page_layout(
+        &format!("Pay {merchant_name}"),
+        maud::html! {
+            div class="muted" { (merchant_name) }
+            div class="amount" { (amount) }
+            @if let Some(description) = &payment_link.description {
+                p { (description) }
+            }
+            @if is_card_allowed {
+                form action=(payment_link.link_to_pay) method="post" {
+                    label for="card_holder_name" { "Name on card" }
+                    input type="text" id="card_holder_name" name="card_holder_name" autocomplete="cc-name" required;
+                    label for="card_number" { "Card number" }
+                    input type="text" id="card_number" name="card_number" inputmode="numeric" autocomplete="cc-number" required;
+                    div class="row" {
+                        div {
+                            label for="card_exp_month" { "Expiry month" }
+                            input type="text" id="card_exp_month" name="card_exp_month" inputmode="numeric" placeholder="MM" autocomplete="cc-exp-month" required;
+                        }
+                        div {
+                            label for="card_exp_year" { "Expiry year" }
+                            input type="text" id="card_exp_year" name="card_exp_year" inputmode="numeric" placeholder="YYYY" autocomplete="cc-exp-year" required;
+                        }
+                        div {
+                            label for="card_cvc" { "CVC" }
+                            input type="password" id="card_cvc" name="card_cvc" inputmode="numeric" autocomplete="cc-csc" required;
+                        }
+                    }
+                    button type="submit" { "Pay " (amount) }
+                }
+            } @else {
+                p { "None of the payment methods allowed for this payment can be used on this page. Please contact the merchant to complete the payment." }
+            }
+            p class="muted" { "This link expires at " (payment_link.expires_at.to_string()) " UTC." }
+        },
+    )
+}
+
+fn payment_status_page(
+    payment_link: &storage::PaymentLink,
+    status: storage_enums::IntentStatus,
+) -> maud::Markup {
+    let (title, message) = match status {
+        storage_enums::IntentStatus::Succeeded => (
+            "Payment successful",
+            "Thank you! Your payment has been received.",
+        ),
+        storage_enums::IntentStatus::Processing
+        | storage_enums::IntentStatus::RequiresCapture
+        | storage_enums::IntentStatus::RequiresMerchantAction => {
+            ("Payment processing", "Your payment is being processed.")
+        }
+        storage_enums::IntentStatus::RequiresCustomerAction => (
+            "Payment pending",
+            "Your payment is awaiting authentication.",
+        ),
+        _ => (
+            "Payment failed",
+            "Your payment could not be completed. Please contact the merchant.",
+        ),
+    };
+
+    page_layout(
+        title,
+        maud::html! {
+            h2 { (title) }
+            div class="amount" { (format_amount(payment_link.amount, payment_link.currency)) }
+            p { (message) }
+            p class="muted" { "Payment reference: " (payment_link.payment_id) }
+        },
+    )
+}
+
+fn link_unavailable_page() -> maud::Markup {
+    page_layout(
+        "Payment link unavailable",
+        maud::html! {
+            h2 { "Payment link unavailable" }
+            p { "This payment link has expired or is no longer valid. Please contact the merchant for a new link." }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use common_utils::errors::ErrorSwitch;
+
+    use super::*;
+
+    fn get_payment_link(
+        status: storage_enums::PaymentLinkStatus,
+        expires_at: time::PrimitiveDateTime,
+    ) -> storage::PaymentLink {
+        let current_time = common_utils::date_time::now();
+        storage::PaymentLink {
+            payment_link_id: "plink_test".to_string(),
+            merchant_id: "merchant_test".to_string(),
+            payment_id: "pay_test".to_string(),
+            link_to_pay: "http://localhost:8080/payment_link/plink_test".to_string(),
+            amount: 6540,
+            currency: storage_enums::Currency::USD,
+            description: None,
+            return_url: None,
+            status,
+            expires_at,
+            created_at: current_time,
+            modified_at: current_time,
+        }
+    }
+
+    fn get_payment_intent(status: storage_enums::IntentStatus) -> storage::PaymentIntent {
+        let current_time = common_utils::date_time::now();
+        storage::PaymentIntent {
+            id: 1,
+            payment_id: "pay_test".to_string(),
+            merchant_id: "merchant_test".to_string(),
+            status,
+            amount: 6540,
+            currency: Some(storage_enums::Currency::USD),
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: current_time,
+            modified_at: current_time,
+            last_synced: None,
+            setup_future_usage: None,
+            off_session: None,
+            client_secret: None,
+            active_attempt_id: "pay_test_1".to_string(),
+            business_country: storage_enums::CountryCode::US,
+            business_label: "default".to_string(),
+            session_expiry: None,
+            attempt_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_payment_link_not_found_error_code() {
+        let error = errors::ApiErrorResponse::PaymentLinkNotFound {
+            payment_link_id: "plink_test".to_string(),
+        };
+        assert_eq!(error.error_code(), "HE_04");
+
+        let response: serde_json::Value =
+            serde_json::from_str(&error.switch().to_string()).unwrap();
+        assert_eq!(response["error"]["code"], "HE_04");
+    }
+
+    #[test]
+    fn test_checkout_unavailable_for_expired_link() {
+        let past = common_utils::date_time::now().saturating_sub(time::Duration::minutes(1));
+        let future = common_utils::date_time::now().saturating_add(time::Duration::minutes(15));
+        let payment_intent = get_payment_intent(storage_enums::IntentStatus::RequiresPaymentMethod);
+
+        assert_eq!(
+            get_checkout_status(
+                &get_payment_link(storage_enums::PaymentLinkStatus::Active, future),
+                &payment_intent,
+            ),
+            CheckoutStatus::Open
+        );
+        // Past the expiry time, before the expiry task has updated the link
+        assert_eq!(
+            get_checkout_status(
+                &get_payment_link(storage_enums::PaymentLinkStatus::Active, past),
+                &payment_intent,
+            ),
+            CheckoutStatus::Unavailable
+        );
+        assert_eq!(
+            get_checkout_status(
+                &get_payment_link(storage_enums::PaymentLinkStatus::Expired, future),
+                &payment_intent,
+            ),
+            CheckoutStatus::Unavailable
+        );
+        assert_eq!(
+            get_checkout_status(
+                &get_payment_link(storage_enums::PaymentLinkStatus::Expired, past),
+                &get_payment_intent(storage_enums::IntentStatus::Cancelled),
+            ),
+            CheckoutStatus::Unavailable
+        );
+    }
+
+    #[test]
+    fn test_checkout_shows_outcome_of_processed_payment_after_expiry() {
+        let past = common_utils::date_time::now().saturating_sub(time::Duration::minutes(1));
+        assert_eq!(
+            get_checkout_status(
+                &get_payment_link(storage_enums::PaymentLinkStatus::Expired, past),
+                &get_payment_intent(storage_enums::IntentStatus::Succeeded),
+            ),
+            CheckoutStatus::Processed(storage_enums::IntentStatus::Succeeded)
+        );
+    }
+
+    #[test]
+    fn test_format_amount_uses_currency_exponent() {
+        assert_eq!(
+            format_amount(6540, storage_enums::Currency::USD),
+            "65.40 USD"
+        );
+        assert_eq!(
+            format_amount(6540, storage_enums::Currency::JPY),
+            "6540 JPY"
+        );
+        assert_eq!(
+            format_amount(1005, storage_enums::Currency::KWD),
+            "1.005 KWD"
+        );
+    }
+}
@@ -1,3 +1,4 @@
 pub mod cards;
+pub mod network_tokenization;
 pub mod transformers;
 pub mod vault;
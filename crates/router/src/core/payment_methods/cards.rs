@@ -27,6 +27,7 @@ use crate::{
     core::{
         errors::{self, StorageErrorExt},
         payment_methods::{
+            network_tokenization,
             transformers::{self as payment_methods},
             vault,
         },
@@ -120,6 +121,7 @@ pub async fn update_customer_payment_method(
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+    network_tokenization::delete_network_token(state, &pm).await;
     if pm.payment_method == enums::PaymentMethod::Card {
         delete_card_from_locker(
             state,
@@ -253,8 +255,7 @@ pub async fn add_card_hs(
     let locker = &state.conf.locker;
     let jwekey = &state.conf.jwekey;
 
-    #[cfg(feature = "kms")]
-    let kms_config = &state.conf.kms;
+    let secrets_management = &state.conf.secrets_management;
 
     let db = &*state.store;
     let merchant_id = &merchant_account.merchant_id;
@@ -271,8 +272,7 @@ pub async fn add_card_hs(
         &card,
         &customer_id,
         merchant_id,
-        #[cfg(feature = "kms")]
-        kms_config,
+        secrets_management,
     )
     .await?;
 
@@ -285,15 +285,11 @@ pub async fn add_card_hs(
             .get_response_inner("JweBody")
             .change_context(errors::VaultError::FetchCardFailed)?;
 
-        let decrypted_payload = payment_methods::get_decrypted_response_payload(
-            jwekey,
-            jwe_body,
-            #[cfg(feature = "kms")]
-            kms_config,
-        )
-        .await
-        .change_context(errors::VaultError::SaveCardFailed)
-        .attach_printable("Error getting decrypted response payload")?;
+        let decrypted_payload =
+            payment_methods::get_decrypted_response_payload(jwekey, jwe_body, secrets_management)
+                .await
+                .change_context(errors::VaultError::SaveCardFailed)
+                .attach_printable("Error getting decrypted response payload")?;
         let stored_card_resp: payment_methods::StoreCardResp = decrypted_payload
             .parse_struct("StoreCardResp")
             .change_context(errors::VaultError::ResponseDeserializationFailed)?;
@@ -397,8 +393,7 @@ pub async fn get_card_from_hs_locker<'a>(
     let locker = &state.conf.locker;
     let jwekey = &state.conf.jwekey;
 
-    #[cfg(feature = "kms")]
-    let kms_config = &state.conf.kms;
+    let secrets_management = &state.conf.secrets_management;
 
     let request = payment_methods::mk_get_card_request_hs(
         jwekey,
@@ -406,8 +401,7 @@ pub async fn get_card_from_hs_locker<'a>(
         customer_id,
         merchant_id,
         card_reference,
-        #[cfg(feature = "kms")]
-        kms_config,
+        secrets_management,
     )
     .await
     .change_context(errors::VaultError::FetchCardFailed)
@@ -420,15 +414,11 @@ pub async fn get_card_from_hs_locker<'a>(
         let jwe_body: services::JweBody = response
             .get_response_inner("JweBody")
             .change_context(errors::VaultError::FetchCardFailed)?;
-        let decrypted_payload = payment_methods::get_decrypted_response_payload(
-            jwekey,
-            jwe_body,
-            #[cfg(feature = "kms")]
-            kms_config,
-        )
-        .await
-        .change_context(errors::VaultError::FetchCardFailed)
-        .attach_printable("Error getting decrypted response payload for get card")?;
+        let decrypted_payload =
+            payment_methods::get_decrypted_response_payload(jwekey, jwe_body, secrets_management)
+                .await
+                .change_context(errors::VaultError::FetchCardFailed)
+                .attach_printable("Error getting decrypted response payload for get card")?;
         let get_card_resp: payment_methods::RetrieveCardResp = decrypted_payload
             .parse_struct("RetrieveCardResp")
             .change_context(errors::VaultError::FetchCardFailed)?;
@@ -486,8 +476,7 @@ pub async fn delete_card_from_hs_locker<'a>(
     let locker = &state.conf.locker;
     let jwekey = &state.conf.jwekey;
 
-    #[cfg(feature = "kms")]
-    let kms_config = &state.conf.kms;
+    let secrets_management = &state.conf.secrets_management;
 
     let request = payment_methods::mk_delete_card_request_hs(
         jwekey,
@@ -495,8 +484,7 @@ pub async fn delete_card_from_hs_locker<'a>(
         customer_id,
         merchant_id,
         card_reference,
-        #[cfg(feature = "kms")]
-        kms_config,
+        secrets_management,
     )
     .await
     .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -508,15 +496,11 @@ pub async fn delete_card_from_hs_locker<'a>(
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed while executing call_connector_api for delete card");
         let jwe_body: services::JweBody = response.get_response_inner("JweBody")?;
-        let decrypted_payload = payment_methods::get_decrypted_response_payload(
-            jwekey,
-            jwe_body,
-            #[cfg(feature = "kms")]
-            kms_config,
-        )
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Error getting decrypted response payload for delete card")?;
+        let decrypted_payload =
+            payment_methods::get_decrypted_response_payload(jwekey, jwe_body, secrets_management)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Error getting decrypted response payload for delete card")?;
         let delete_card_resp: payment_methods::DeleteCardResp = decrypted_payload
             .parse_struct("DeleteCardResp")
             .change_context(errors::ApiErrorResponse::InternalServerError)?;
@@ -798,7 +782,12 @@ pub async fn list_payment_methods(
     let address = payment_intent
         .as_ref()
         .async_map(|pi| async {
-            helpers::get_address_by_id(db, pi.shipping_address_id.clone()).await
+            helpers::get_address_by_id(
+                db,
+                pi.shipping_address_id.clone(),
+                merchant_account.storage_scheme,
+            )
+            .await
         })
         .await
         .transpose()?
@@ -836,6 +825,13 @@ pub async fn list_payment_methods(
             None => continue,
         };
 
+        let capabilities = api::ConnectorData::get_connector_by_name(
+            &mca.connector_name,
+            api::GetToken::Connector,
+        )
+        .ok()
+        .map(|connector_data| connector_data.connector.get_capabilities());
+
         filter_payment_methods(
             payment_methods,
             &mut req,
@@ -845,6 +841,7 @@ pub async fn list_payment_methods(
             address.as_ref(),
             mca.connector_name,
             pm_config_mapping,
+            capabilities.as_ref(),
         )
         .await?;
     }
@@ -1051,6 +1048,7 @@ async fn filter_payment_methods(
     address: Option<&storage::Address>,
     connector: String,
     config: &settings::ConnectorFilters,
+    capabilities: Option<&api::ConnectorCapabilities>,
 ) -> errors::CustomResult<(), errors::ApiErrorResponse> {
     for payment_method in payment_methods.into_iter() {
         let parse_result = serde_json::from_value::<PaymentMethodsEnabled>(payment_method);
@@ -1140,6 +1138,19 @@ async fn filter_payment_methods(
                         &payment_method_object.payment_method_type,
                     );
 
+                    let filter7 = capabilities
+                        .map(|capabilities| {
+                            capabilities.supports_payment_method(
+                                payment_method,
+                                Some(payment_method_object.payment_method_type),
+                                payment_attempt
+                                    .and_then(|value| value.currency)
+                                    .map(|value| value.foreign_into()),
+                                address.and_then(|inner| inner.country),
+                            )
+                        })
+                        .unwrap_or(true);
+
                     let connector = connector.clone();
 
                     let response_pm_type = ResponsePaymentMethodIntermediate::new(
@@ -1148,7 +1159,7 @@ async fn filter_payment_methods(
                         payment_method,
                     );
 
-                    if filter && filter2 && filter3 && filter4 && filter5 && filter6 {
+                    if filter && filter2 && filter3 && filter4 && filter5 && filter6 && filter7 {
                         resp.push(response_pm_type);
                     }
                 }
@@ -1754,6 +1765,7 @@ impl BasiliskCardSupport {
             &*state.store,
             &lookup_key,
             enums::PaymentMethod::Card,
+            vault::get_key_id(&state.conf.jwekey),
         )
         .await?;
         scheduler_metrics::TOKENIZED_DATA_COUNT.add(&metrics::CONTEXT, 1, &[]);
@@ -1827,6 +1839,7 @@ pub async fn delete_payment_method(
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
 
+    network_tokenization::delete_network_token(state, &pm).await;
     if pm.payment_method == enums::PaymentMethod::Card {
         let response =
             delete_card_from_locker(state, &pm.customer_id, &pm.merchant_id, &payment_method_id)
@@ -0,0 +1,520 @@
+use base64::Engine;
+use common_utils::{
+    crypto::{self, VerifySignature},
+    ext_traits::BytesExt,
+    generate_id, pii,
+};
+use error_stack::{report, IntoReport, ResultExt};
+use external_services::secrets_management::SecretsManagementConfig;
+use masking::{PeekInterface, Secret};
+use rand::Rng;
+use router_env::{instrument, logger, tracing};
+
+use super::cards;
+use crate::{
+    configs::settings,
+    consts,
+    core::errors::{self, CustomResult, RouterResponse, RouterResult, StorageErrorExt},
+    headers,
+    routes::AppState,
+    services,
+    types::{
+        self, api,
+        storage::{self, enums},
+    },
+    utils::{self, OptionExt},
+};
+
+/// Network token issued for a card by the token service provider.
+#[derive(Clone, Debug)]
+pub struct NetworkToken {
+    /// Identifier of the token with the token service provider
+    pub token_ref_id: String,
+    pub token: Secret<String, pii::CardNumber>,
+    pub token_exp_month: Secret<String>,
+    pub token_exp_year: Secret<String>,
+}
+
+/// Token service provider (such as VTS or MDES) through which network tokens are provisioned
+/// for saved cards.
+#[async_trait::async_trait]
+pub trait NetworkTokenProvider: Send + Sync {
+    async fn provision_token(
+        &self,
+        card: &api::CardDetail,
+    ) -> CustomResult<NetworkToken, errors::NetworkTokenizationError>;
+
+    async fn delete_token(
+        &self,
+        token_ref_id: &str,
+    ) -> CustomResult<(), errors::NetworkTokenizationError>;
+
+    /// Generates the single use cryptogram (TAVV) that has to accompany the token in an
+    /// authorization.
+    async fn get_cryptogram(
+        &self,
+        token_ref_id: &str,
+    ) -> CustomResult<Secret<String>, errors::NetworkTokenizationError>;
+}
+
+/// BIN range from which the mock provider issues tokens.
+const MOCK_TOKEN_BIN: &str = "489537";
+
+/// Issues tokens locally without contacting a token service provider, to be used in development
+/// and tests.
+#[derive(Debug, Clone, Default)]
+pub struct MockNetworkTokenProvider;
+
+#[async_trait::async_trait]
+impl NetworkTokenProvider for MockNetworkTokenProvider {
+    async fn provision_token(
+        &self,
+        card: &api::CardDetail,
+    ) -> CustomResult<NetworkToken, errors::NetworkTokenizationError> {
+        let card_number = card.card_number.peek();
+        let last4_digits = card_number
+            .get(card_number.len().saturating_sub(4)..)
+            .ok_or(errors::NetworkTokenizationError::ProvisioningFailed)
+            .into_report()
+            .attach_printable("Invalid card number")?;
+        let token_digits: u32 = rand::thread_rng().gen_range(0..1_000_000);
+
+        Ok(NetworkToken {
+            token_ref_id: generate_id(consts::ID_LENGTH, "ntr"),
+            token: Secret::new(format!("{MOCK_TOKEN_BIN}{token_digits:06}{last4_digits}")),
+            token_exp_month: card.card_exp_month.clone(),
+            token_exp_year: card.card_exp_year.clone(),
+        })
+    }
+
+    async fn delete_token(
+        &self,
+        _token_ref_id: &str,
+    ) -> CustomResult<(), errors::NetworkTokenizationError> {
+        Ok(())
+    }
+
+    async fn get_cryptogram(
+        &self,
+        _token_ref_id: &str,
+    ) -> CustomResult<Secret<String>, errors::NetworkTokenizationError> {
+        let cryptogram: [u8; 20] = rand::thread_rng().gen();
+        Ok(Secret::new(consts::BASE64_ENGINE.encode(cryptogram)))
+    }
+}
+
+pub fn get_network_token_provider(
+    config: &settings::NetworkTokenizationSettings,
+) -> Box<dyn NetworkTokenProvider> {
+    match config.provider {
+        settings::NetworkTokenProviderKind::Mock => Box::new(MockNetworkTokenProvider),
+    }
+}
+
+/// Provisions a network token for a card saved for future usage. Failures are only logged, as
+/// the card number continues to be used when no token is available.
+#[instrument(skip_all)]
+pub async fn provision_network_token(
+    state: &AppState,
+    merchant_account: &storage::MerchantAccount,
+    customer_id: &str,
+    payment_method_id: &str,
+    card: &api::CardDetail,
+) {
+    if !state.conf.network_tokenization.enabled {
+        return;
+    }
+
+    if let Err(error) = add_network_token(
+        state,
+        merchant_account,
+        customer_id,
+        payment_method_id,
+        card,
+    )
+    .await
+    {
+        logger::error!(?error, "Failed to provision network token for saved card");
+    }
+}
+
+async fn add_network_token(
+    state: &AppState,
+    merchant_account: &storage::MerchantAccount,
+    customer_id: &str,
+    payment_method_id: &str,
+    card: &api::CardDetail,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let payment_method = db
+        .find_payment_method(payment_method_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+
+    if payment_method.network_token_ref_id.is_some() {
+        return Ok(());
+    }
+
+    let network_token = get_network_token_provider(&state.conf.network_tokenization)
+        .provision_token(card)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    // The token is as sensitive as the card number, so it is kept in the locker like the card
+    let token_card = api::CardDetail {
+        card_number: network_token.token,
+        card_exp_month: network_token.token_exp_month.clone(),
+        card_exp_year: network_token.token_exp_year.clone(),
+        card_holder_name: card.card_holder_name.clone(),
+    };
+    let (locker_response, _) = cards::add_card_to_locker(
+        state,
+        api::PaymentMethodCreate {
+            payment_method: api_models::enums::PaymentMethod::Card,
+            payment_method_type: None,
+            payment_method_issuer: None,
+            payment_method_issuer_code: None,
+            card: Some(token_card.clone()),
+            metadata: None,
+            customer_id: Some(customer_id.to_string()),
+            card_network: None,
+        },
+        token_card,
+        customer_id.to_string(),
+        merchant_account,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to store network token in the locker")?;
+
+    db.update_payment_method(
+        payment_method,
+        storage::PaymentMethodUpdate::NetworkTokenUpdate {
+            network_token_ref_id: network_token.token_ref_id,
+            network_token_locker_id: locker_response.payment_method_id,
+            network_token_exp_month: network_token.token_exp_month,
+            network_token_exp_year: network_token.token_exp_year,
+            network_token_status: enums::NetworkTokenStatus::Active,
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to store network token of the payment method")?;
+
+    Ok(())
+}
+
+/// Deletes the network token of a payment method that is being deleted, both with the token
+/// service provider and from the locker. Failures are only logged, the token is left to expire
+/// with the token service provider.
+#[instrument(skip_all)]
+pub async fn delete_network_token(state: &AppState, payment_method: &storage::PaymentMethod) {
+    if let Some(locker_id) = &payment_method.network_token_locker_id {
+        if let Err(error) = cards::delete_card_from_locker(
+            state,
+            &payment_method.customer_id,
+            &payment_method.merchant_id,
+            locker_id,
+        )
+        .await
+        {
+            logger::error!(?error, "Failed to delete network token from the locker");
+        }
+    }
+
+    let token_ref_id = match (
+        &payment_method.network_token_ref_id,
+        payment_method.network_token_status,
+    ) {
+        (Some(_), Some(enums::NetworkTokenStatus::Deleted)) | (None, _) => return,
+        (Some(token_ref_id), _) => token_ref_id,
+    };
+
+    if let Err(error) = get_network_token_provider(&state.conf.network_tokenization)
+        .delete_token(token_ref_id)
+        .await
+    {
+        logger::error!(?error, "Failed to delete network token");
+    }
+}
+
+/// Replaces the card number and expiry of a merchant initiated payment with those of the
+/// network token of the saved card, along with a cryptogram for the authorization. The card
+/// number is kept when the card has no active token or the connector does not accept network
+/// tokens.
+#[instrument(skip_all)]
+pub async fn use_network_token_for_recurring_payment(
+    state: &AppState,
+    merchant_account: &storage::MerchantAccount,
+    capabilities: &api::ConnectorCapabilities,
+    router_data: &mut types::PaymentsAuthorizeRouterData,
+) -> RouterResult<()> {
+    // Connector mandates are charged with the connector's reference instead of the card
+    let mandate_id = match &router_data.request.mandate_id {
+        Some(mandate_ids) if mandate_ids.connector_mandate_id.is_none() => {
+            mandate_ids.mandate_id.clone()
+        }
+        _ => return Ok(()),
+    };
+    if !state.conf.network_tokenization.enabled
+        || !capabilities.supports_flow(api::ConnectorFlow::NetworkTokens)
+    {
+        return Ok(());
+    }
+
+    let card = match &mut router_data.request.payment_method_data {
+        api::PaymentMethodData::Card(card) => card,
+        _ => return Ok(()),
+    };
+
+    let db = &*state.store;
+    let mandate = db
+        .find_mandate_by_merchant_id_mandate_id(&merchant_account.merchant_id, &mandate_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+    let payment_method = db
+        .find_payment_method(&mandate.payment_method_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+
+    let (token_ref_id, locker_id, token_exp_month, token_exp_year) = match (
+        payment_method.network_token_status,
+        payment_method.network_token_ref_id,
+        payment_method.network_token_locker_id,
+        payment_method.network_token_exp_month,
+        payment_method.network_token_exp_year,
+    ) {
+        (
+            Some(enums::NetworkTokenStatus::Active),
+            Some(token_ref_id),
+            Some(locker_id),
+            Some(token_exp_month),
+            Some(token_exp_year),
+        ) => (token_ref_id, locker_id, token_exp_month, token_exp_year),
+        _ => return Ok(()),
+    };
+
+    let token = cards::get_card_from_locker(
+        state,
+        &payment_method.customer_id,
+        &payment_method.merchant_id,
+        &locker_id,
+        merchant_account.locker_id.clone(),
+    )
+    .await
+    .attach_printable("Failed to fetch network token from the locker")?;
+    let cryptogram = get_network_token_provider(&state.conf.network_tokenization)
+        .get_cryptogram(&token_ref_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    card.card_number = token.card_number;
+    card.card_exp_month = token_exp_month;
+    card.card_exp_year = token_exp_year;
+    router_data.request.network_token_cryptogram = Some(cryptogram);
+
+    Ok(())
+}
+
+/// Applies a token lifecycle update (suspension, resumption, deletion or PAN update) sent by
+/// the token service provider to the payment method holding the token.
+#[instrument(skip_all)]
+pub async fn handle_network_token_notification(
+    state: &AppState,
+    request_headers: &actix_web::http::header::HeaderMap,
+    body: actix_web::web::Bytes,
+) -> RouterResponse<()> {
+    verify_notification_signature(
+        &state.conf.network_tokenization,
+        &state.conf.secrets_management,
+        request_headers,
+        &body,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::GenericUnauthorized {
+        message: "Network token notification signature verification failed".to_string(),
+    })?;
+
+    let notification: api::NetworkTokenNotification = body
+        .parse_struct("NetworkTokenNotification")
+        .change_context(errors::ApiErrorResponse::InvalidRequestData {
+            message: "Invalid network token notification".to_string(),
+        })?;
+
+    let db = &*state.store;
+    let payment_method = db
+        .find_payment_method_by_network_token_ref_id(&notification.network_token_ref_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+
+    if payment_method.network_token_status == Some(enums::NetworkTokenStatus::Deleted) {
+        logger::warn!(
+            event_type = ?notification.event_type,
+            "Ignoring notification for a deleted network token"
+        );
+        return Ok(services::ApplicationResponse::StatusOk);
+    }
+
+    let payment_method_update = match notification.event_type {
+        api::NetworkTokenEventType::Suspended => {
+            storage::PaymentMethodUpdate::NetworkTokenStatusUpdate {
+                network_token_status: enums::NetworkTokenStatus::Suspended,
+            }
+        }
+        api::NetworkTokenEventType::Resumed => {
+            storage::PaymentMethodUpdate::NetworkTokenStatusUpdate {
+                network_token_status: enums::NetworkTokenStatus::Active,
+            }
+        }
+        api::NetworkTokenEventType::Deleted => {
+            storage::PaymentMethodUpdate::NetworkTokenStatusUpdate {
+                network_token_status: enums::NetworkTokenStatus::Deleted,
+            }
+        }
+        api::NetworkTokenEventType::PanUpdated => {
+            storage::PaymentMethodUpdate::NetworkTokenUpdate {
+                network_token_ref_id: notification.network_token_ref_id,
+                network_token_locker_id: payment_method
+                    .network_token_locker_id
+                    .clone()
+                    .get_required_value("network_token_locker_id")?,
+                network_token_exp_month: notification
+                    .token_exp_month
+                    .get_required_value("token_exp_month")?,
+                network_token_exp_year: notification
+                    .token_exp_year
+                    .get_required_value("token_exp_year")?,
+                network_token_status: enums::NetworkTokenStatus::Active,
+            }
+        }
+    };
+
+    db.update_payment_method(payment_method, payment_method_update)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update network token of the payment method")?;
+
+    Ok(services::ApplicationResponse::StatusOk)
+}
+
+async fn verify_notification_signature(
+    config: &settings::NetworkTokenizationSettings,
+    secrets_management: &SecretsManagementConfig,
+    request_headers: &actix_web::http::header::HeaderMap,
+    body: &[u8],
+) -> CustomResult<(), errors::NetworkTokenizationError> {
+    let signature = request_headers
+        .get(headers::X_NETWORK_TOKEN_SIGNATURE)
+        .and_then(|header_value| header_value.to_str().ok())
+        .and_then(|signature| hex::decode(signature).ok())
+        .ok_or(errors::NetworkTokenizationError::NotificationVerificationFailed)
+        .into_report()
+        .attach_printable("Missing or malformed notification signature")?;
+
+    let notification_secret = secrets_management
+        .get_secret(&config.notification_secret)
+        .await
+        .change_context(errors::NetworkTokenizationError::NotificationVerificationFailed)
+        .attach_printable("Failed to retrieve the network token notification secret")?;
+
+    let is_verified = crypto::HmacSha256
+        .verify_signature(notification_secret.as_bytes(), &signature, body)
+        .change_context(errors::NetworkTokenizationError::NotificationVerificationFailed)?;
+
+    utils::when(!is_verified, || {
+        Err(report!(
+            errors::NetworkTokenizationError::NotificationVerificationFailed
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use common_utils::crypto::SignMessage;
+
+    use super::*;
+
+    fn test_card() -> api::CardDetail {
+        api::CardDetail {
+            card_number: Secret::new("4111111111111111".to_string()),
+            card_exp_month: Secret::new("10".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_holder_name: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_mock_provider_keeps_last4_digits_and_expiry() {
+        let network_token = MockNetworkTokenProvider
+            .provision_token(&test_card())
+            .await
+            .unwrap();
+
+        let token = network_token.token.peek();
+        assert_eq!(token.len(), 16);
+        assert!(token.starts_with(MOCK_TOKEN_BIN));
+        assert!(token.ends_with("1111"));
+        assert_eq!(network_token.token_exp_month.peek(), "10");
+        assert_eq!(network_token.token_exp_year.peek(), "2030");
+    }
+
+    #[actix_rt::test]
+    async fn test_mock_provider_generates_cryptogram() {
+        let cryptogram = MockNetworkTokenProvider
+            .get_cryptogram("ntr_123")
+            .await
+            .unwrap();
+
+        let decoded = consts::BASE64_ENGINE.decode(cryptogram.peek()).unwrap();
+        assert_eq!(decoded.len(), 20);
+    }
+
+    #[actix_rt::test]
+    async fn test_notification_signature_verification() {
+        let config = settings::NetworkTokenizationSettings {
+            enabled: true,
+            provider: settings::NetworkTokenProviderKind::Mock,
+            notification_secret: "secret".to_string(),
+        };
+        let body = br#"{"network_token_ref_id":"ntr_123","event_type":"suspended"}"#;
+        let signature = crypto::HmacSha256
+            .sign_message(config.notification_secret.as_bytes(), body)
+            .unwrap();
+
+        let mut request_headers = actix_web::http::header::HeaderMap::new();
+        request_headers.insert(
+            actix_web::http::header::HeaderName::from_static("x-network-token-signature"),
+            actix_web::http::header::HeaderValue::from_str(&hex::encode(signature)).unwrap(),
+        );
+        let secrets_management = SecretsManagementConfig::default();
+        assert!(verify_notification_signature(
+            &config,
+            &secrets_management,
+            &request_headers,
+            body
+        )
+        .await
+        .is_ok());
+
+        let tampered_body = br#"{"network_token_ref_id":"ntr_123","event_type":"resumed"}"#;
+        assert!(verify_notification_signature(
+            &config,
+            &secrets_management,
+            &request_headers,
+            tampered_body
+        )
+        .await
+        .is_err());
+
+        let request_headers = actix_web::http::header::HeaderMap::new();
+        assert!(verify_notification_signature(
+            &config,
+            &secrets_management,
+            &request_headers,
+            body
+        )
+        .await
+        .is_err());
+    }
+}
@@ -1,7 +1,6 @@
 use common_utils::ext_traits::StringExt;
 use error_stack::ResultExt;
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::SecretsManagementConfig;
 use josekit::jwe;
 use serde::{Deserialize, Serialize};
 
@@ -151,28 +150,19 @@ pub fn get_dotted_jws(jws: encryption::JwsBody) -> String {
 pub async fn get_decrypted_response_payload(
     jwekey: &settings::Jwekey,
     jwe_body: encryption::JweBody,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> CustomResult<String, errors::VaultError> {
-    #[cfg(feature = "kms")]
-    let public_key = kms::get_kms_client(kms_config)
-        .await
-        .decrypt(&jwekey.vault_encryption_key)
+    let public_key = secrets_management
+        .get_secret(&jwekey.vault_encryption_key)
         .await
         .change_context(errors::VaultError::SaveCardFailed)
         .attach_printable("Fails to get public key of vault")?;
-    #[cfg(feature = "kms")]
-    let private_key = kms::get_kms_client(kms_config)
-        .await
-        .decrypt(&jwekey.vault_private_key)
+    let private_key = secrets_management
+        .get_secret(&jwekey.vault_private_key)
         .await
         .change_context(errors::VaultError::SaveCardFailed)
         .attach_printable("Error getting private key for signing jws")?;
 
-    #[cfg(not(feature = "kms"))]
-    let public_key = jwekey.vault_encryption_key.to_owned();
-    #[cfg(not(feature = "kms"))]
-    let private_key = jwekey.vault_private_key.to_owned();
-
     let jwt = get_dotted_jwe(jwe_body);
     let alg = jwe::RSA_OAEP;
 
@@ -199,7 +189,7 @@ pub async fn get_decrypted_response_payload(
 pub async fn mk_basilisk_req(
     jwekey: &settings::Jwekey,
     jws: &str,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> CustomResult<encryption::JweBody, errors::VaultError> {
     let jws_payload: Vec<&str> = jws.split('.').collect();
 
@@ -216,17 +206,12 @@ pub async fn mk_basilisk_req(
     let payload = utils::Encode::<encryption::JwsBody>::encode_to_vec(&jws_body)
         .change_context(errors::VaultError::SaveCardFailed)?;
 
-    #[cfg(feature = "kms")]
-    let public_key = kms::get_kms_client(kms_config)
-        .await
-        .decrypt(&jwekey.vault_encryption_key)
+    let public_key = secrets_management
+        .get_secret(&jwekey.vault_encryption_key)
         .await
         .change_context(errors::VaultError::SaveCardFailed)
         .attach_printable("Fails to get encryption key of vault")?;
 
-    #[cfg(not(feature = "kms"))]
-    let public_key = jwekey.vault_encryption_key.to_owned();
-
     let jwe_encrypted = encryption::encrypt_jwe(&payload, public_key)
         .await
         .change_context(errors::VaultError::SaveCardFailed)
@@ -254,7 +239,7 @@ pub async fn mk_add_card_request_hs(
     card: &api::CardDetail,
     customer_id: &str,
     merchant_id: &str,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> CustomResult<services::Request, errors::VaultError> {
     let merchant_customer_id = if cfg!(feature = "sandbox") {
         format!("{customer_id}::{merchant_id}")
@@ -278,28 +263,17 @@ pub async fn mk_add_card_request_hs(
     let payload = utils::Encode::<StoreCardReq<'_>>::encode_to_vec(&store_card_req)
         .change_context(errors::VaultError::RequestEncodingFailed)?;
 
-    #[cfg(feature = "kms")]
-    let private_key = kms::get_kms_client(kms_config)
-        .await
-        .decrypt(&jwekey.vault_private_key)
+    let private_key = secrets_management
+        .get_secret(&jwekey.vault_private_key)
         .await
         .change_context(errors::VaultError::SaveCardFailed)
         .attach_printable("Error getting private key for signing jws")?;
 
-    #[cfg(not(feature = "kms"))]
-    let private_key = jwekey.vault_private_key.to_owned();
-
     let jws = encryption::jws_sign_payload(&payload, &locker.locker_signing_key_id, private_key)
         .await
         .change_context(errors::VaultError::RequestEncodingFailed)?;
 
-    let jwe_payload = mk_basilisk_req(
-        jwekey,
-        &jws,
-        #[cfg(feature = "kms")]
-        kms_config,
-    )
-    .await?;
+    let jwe_payload = mk_basilisk_req(jwekey, &jws, secrets_management).await?;
 
     let body = utils::Encode::<encryption::JweBody>::encode_to_value(&jwe_payload)
         .change_context(errors::VaultError::RequestEncodingFailed)?;
@@ -416,7 +390,7 @@ pub async fn mk_get_card_request_hs(
     customer_id: &str,
     merchant_id: &str,
     card_reference: &str,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> CustomResult<services::Request, errors::VaultError> {
     let merchant_customer_id = if cfg!(feature = "sandbox") {
         format!("{customer_id}::{merchant_id}")
@@ -431,28 +405,17 @@ pub async fn mk_get_card_request_hs(
     let payload = utils::Encode::<CardReqBody<'_>>::encode_to_vec(&card_req_body)
         .change_context(errors::VaultError::RequestEncodingFailed)?;
 
-    #[cfg(feature = "kms")]
-    let private_key = kms::get_kms_client(kms_config)
-        .await
-        .decrypt(&jwekey.vault_private_key)
+    let private_key = secrets_management
+        .get_secret(&jwekey.vault_private_key)
         .await
         .change_context(errors::VaultError::SaveCardFailed)
         .attach_printable("Error getting private key for signing jws")?;
 
-    #[cfg(not(feature = "kms"))]
-    let private_key = jwekey.vault_private_key.to_owned();
-
     let jws = encryption::jws_sign_payload(&payload, &locker.locker_signing_key_id, private_key)
         .await
         .change_context(errors::VaultError::RequestEncodingFailed)?;
 
-    let jwe_payload = mk_basilisk_req(
-        jwekey,
-        &jws,
-        #[cfg(feature = "kms")]
-        kms_config,
-    )
-    .await?;
+    let jwe_payload = mk_basilisk_req(jwekey, &jws, secrets_management).await?;
 
     let body = utils::Encode::<encryption::JweBody>::encode_to_value(&jwe_payload)
         .change_context(errors::VaultError::RequestEncodingFailed)?;
@@ -508,7 +471,7 @@ pub async fn mk_delete_card_request_hs(
     customer_id: &str,
     merchant_id: &str,
     card_reference: &str,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> CustomResult<services::Request, errors::VaultError> {
     let merchant_customer_id = if cfg!(feature = "sandbox") {
         format!("{customer_id}::{merchant_id}")
@@ -523,28 +486,17 @@ pub async fn mk_delete_card_request_hs(
     let payload = utils::Encode::<CardReqBody<'_>>::encode_to_vec(&card_req_body)
         .change_context(errors::VaultError::RequestEncodingFailed)?;
 
-    #[cfg(feature = "kms")]
-    let private_key = kms::get_kms_client(kms_config)
-        .await
-        .decrypt(&jwekey.vault_private_key)
+    let private_key = secrets_management
+        .get_secret(&jwekey.vault_private_key)
         .await
         .change_context(errors::VaultError::SaveCardFailed)
         .attach_printable("Error getting private key for signing jws")?;
 
-    #[cfg(not(feature = "kms"))]
-    let private_key = jwekey.vault_private_key.to_owned();
-
     let jws = encryption::jws_sign_payload(&payload, &locker.locker_signing_key_id, private_key)
         .await
         .change_context(errors::VaultError::RequestEncodingFailed)?;
 
-    let jwe_payload = mk_basilisk_req(
-        jwekey,
-        &jws,
-        #[cfg(feature = "kms")]
-        kms_config,
-    )
-    .await?;
+    let jwe_payload = mk_basilisk_req(jwekey, &jws, secrets_management).await?;
 
     let body = utils::Encode::<encryption::JweBody>::encode_to_value(&jwe_payload)
         .change_context(errors::VaultError::RequestEncodingFailed)?;
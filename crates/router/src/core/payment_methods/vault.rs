@@ -1,7 +1,9 @@
-use common_utils::generate_id_with_default_len;
+use common_utils::{date_time, generate_id_with_default_len};
+#[cfg(feature = "basilisk")]
+use error_stack::report;
 use error_stack::{IntoReport, ResultExt};
 #[cfg(feature = "basilisk")]
-use external_services::kms;
+use external_services::secrets_management::SecretsManagementConfig;
 #[cfg(feature = "basilisk")]
 use josekit::jwe;
 use masking::PeekInterface;
@@ -31,6 +33,11 @@ use crate::{
 const VAULT_SERVICE_NAME: &str = "CARD";
 #[cfg(feature = "basilisk")]
 const VAULT_VERSION: &str = "0";
+#[cfg(feature = "basilisk")]
+pub(crate) const DELETE_TOKENIZE_DATA_RUNNER: &str = "DELETE_TOKENIZE_DATA_WORKFLOW";
+/// Prefix of the business status of tasks which deleted their data from the locker
+#[cfg(feature = "basilisk")]
+pub(crate) const DELETE_TOKENIZE_DATA_COMPLETED_PREFIX: &str = "COMPLETED_BY_PT_";
 
 pub struct SupplementaryVaultData {
     pub customer_id: Option<String>,
@@ -366,7 +373,13 @@ impl Vault {
         let lookup_key = token_id.unwrap_or_else(|| generate_id_with_default_len("token"));
 
         let lookup_key = create_tokenize(state, value1, Some(value2), lookup_key).await?;
-        add_delete_tokenized_data_task(&*state.store, &lookup_key, pm).await?;
+        add_delete_tokenized_data_task(
+            &*state.store,
+            &lookup_key,
+            pm,
+            get_key_id(&state.conf.jwekey),
+        )
+        .await?;
         scheduler_metrics::TOKENIZED_DATA_COUNT.add(&metrics::CONTEXT, 1, &[]);
         Ok(lookup_key)
     }
@@ -393,21 +406,34 @@ impl Vault {
 }
 
 //------------------------------------------------TokenizeService------------------------------------------------
+/// Identifier of the locker key pair used for new requests to the locker.
 pub fn get_key_id(keys: &settings::Jwekey) -> &str {
-    let key_identifier = "1"; // [#46]: Fetch this value from redis or external sources
-    if key_identifier == "1" {
+    if keys.active_locker_key_identifier.is_empty() {
         &keys.locker_key_identifier1
     } else {
-        &keys.locker_key_identifier2
+        &keys.active_locker_key_identifier
     }
 }
 
+/// Whether data encrypted with the locker key pair `key_id` is accepted. The inactive key pair is
+/// accepted only until the end of the rotation grace period.
+pub fn is_locker_key_accepted(keys: &settings::Jwekey, key_id: &str) -> bool {
+    let is_known_key =
+        key_id == keys.locker_key_identifier1 || key_id == keys.locker_key_identifier2;
+    let in_grace_period = keys
+        .locker_key_grace_period_ends_at
+        .map(|ends_at| ends_at > date_time::now())
+        .unwrap_or(false);
+
+    key_id == get_key_id(keys) || (is_known_key && in_grace_period)
+}
+
 #[cfg(feature = "basilisk")]
 async fn get_locker_jwe_keys(
     keys: &settings::Jwekey,
-    kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
+    key_id: &str,
 ) -> CustomResult<(String, String), errors::EncryptionError> {
-    let key_id = get_key_id(keys);
     let (encryption_key, decryption_key) = if key_id == keys.locker_key_identifier1 {
         (&keys.locker_encryption_key1, &keys.locker_decryption_key1)
     } else if key_id == keys.locker_key_identifier2 {
@@ -416,20 +442,42 @@ async fn get_locker_jwe_keys(
         return Err(errors::EncryptionError.into());
     };
 
-    let public_key = kms::get_kms_client(kms_config)
-        .await
-        .decrypt(encryption_key)
+    let public_key = secrets_management
+        .get_secret(encryption_key)
         .await
         .change_context(errors::EncryptionError)?;
-    let private_key = kms::get_kms_client(kms_config)
-        .await
-        .decrypt(decryption_key)
+    let private_key = secrets_management
+        .get_secret(decryption_key)
         .await
         .change_context(errors::EncryptionError)?;
 
     Ok((public_key, private_key))
 }
 
+/// Decrypts a locker response with the private key of the key pair it was encrypted for, as long
+/// as that key pair is still accepted.
+#[cfg(feature = "basilisk")]
+async fn decrypt_locker_response(
+    state: &routes::AppState,
+    response: &api::TokenizePayloadEncrypted,
+) -> CustomResult<String, errors::EncryptionError> {
+    let keys = &state.conf.jwekey;
+    utils::when(!is_locker_key_accepted(keys, &response.key_id), || {
+        Err(report!(errors::EncryptionError)
+            .attach_printable("key_id mismatch, Error authenticating response"))
+    })?;
+
+    let (_public_key, private_key) =
+        get_locker_jwe_keys(keys, &state.conf.secrets_management, &response.key_id).await?;
+    services::decrypt_jwe(
+        &response.payload,
+        services::KeyIdCheck::SkipKeyIdCheck,
+        private_key,
+        jwe::RSA_OAEP_256,
+    )
+    .await
+}
+
 #[cfg(feature = "basilisk")]
 pub async fn create_tokenize(
     state: &routes::AppState,
@@ -449,10 +497,14 @@ pub async fn create_tokenize(
     )
     .change_context(errors::ApiErrorResponse::InternalServerError)?;
 
-    let (public_key, private_key) = get_locker_jwe_keys(&state.conf.jwekey, &state.conf.kms)
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Error getting Encryption key")?;
+    let (public_key, _private_key) = get_locker_jwe_keys(
+        &state.conf.jwekey,
+        &state.conf.secrets_management,
+        get_key_id(&state.conf.jwekey),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Error getting Encryption key")?;
     let encrypted_payload = services::encrypt_jwe(payload.as_bytes(), public_key)
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -481,19 +533,10 @@ pub async fn create_tokenize(
                 .parse_struct("TokenizePayloadEncrypted")
                 .change_context(errors::ApiErrorResponse::InternalServerError)
                 .attach_printable("Decoding Failed for TokenizePayloadEncrypted")?;
-            let alg = jwe::RSA_OAEP_256;
-            let decrypted_payload = services::decrypt_jwe(
-                &resp.payload,
-                services::KeyIdCheck::RequestResponseKeyId((
-                    get_key_id(&state.conf.jwekey),
-                    &resp.key_id,
-                )),
-                private_key,
-                alg,
-            )
-            .await
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Decrypt Jwe failed for TokenizePayloadEncrypted")?;
+            let decrypted_payload = decrypt_locker_response(state, &resp)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Decrypt Jwe failed for TokenizePayloadEncrypted")?;
             let get_response: api::GetTokenizePayloadResponse = decrypted_payload
                 .parse_struct("GetTokenizePayloadResponse")
                 .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -526,10 +569,14 @@ pub async fn get_tokenized_data(
     let payload = serde_json::to_string(&payload_to_be_encrypted)
         .map_err(|_x| errors::ApiErrorResponse::InternalServerError)?;
 
-    let (public_key, private_key) = get_locker_jwe_keys(&state.conf.jwekey, &state.conf.kms)
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Error getting Encryption key")?;
+    let (public_key, _private_key) = get_locker_jwe_keys(
+        &state.conf.jwekey,
+        &state.conf.secrets_management,
+        get_key_id(&state.conf.jwekey),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Error getting Encryption key")?;
     let encrypted_payload = services::encrypt_jwe(payload.as_bytes(), public_key)
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -556,19 +603,12 @@ pub async fn get_tokenized_data(
                 .parse_struct("TokenizePayloadEncrypted")
                 .change_context(errors::ApiErrorResponse::InternalServerError)
                 .attach_printable("Decoding Failed for TokenizePayloadEncrypted")?;
-            let alg = jwe::RSA_OAEP_256;
-            let decrypted_payload = services::decrypt_jwe(
-                &resp.payload,
-                services::KeyIdCheck::RequestResponseKeyId((
-                    get_key_id(&state.conf.jwekey),
-                    &resp.key_id,
-                )),
-                private_key,
-                alg,
-            )
-            .await
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("GetTokenizedApi: Decrypt Jwe failed for TokenizePayloadEncrypted")?;
+            let decrypted_payload = decrypt_locker_response(state, &resp)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable(
+                    "GetTokenizedApi: Decrypt Jwe failed for TokenizePayloadEncrypted",
+                )?;
             let get_response: api::TokenizePayloadRequest = decrypted_payload
                 .parse_struct("TokenizePayloadRequest")
                 .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -597,10 +637,14 @@ pub async fn delete_tokenized_data(
     let payload = serde_json::to_string(&payload_to_be_encrypted)
         .map_err(|_x| errors::ApiErrorResponse::InternalServerError)?;
 
-    let (public_key, _private_key) = get_locker_jwe_keys(&state.conf.jwekey, &state.conf.kms)
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Error getting Encryption key")?;
+    let (public_key, _private_key) = get_locker_jwe_keys(
+        &state.conf.jwekey,
+        &state.conf.secrets_management,
+        get_key_id(&state.conf.jwekey),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Error getting Encryption key")?;
     let encrypted_payload = services::encrypt_jwe(payload.as_bytes(), public_key)
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -643,12 +687,14 @@ pub async fn add_delete_tokenized_data_task(
     db: &dyn db::StorageInterface,
     lookup_key: &str,
     pm: enums::PaymentMethod,
+    key_id: &str,
 ) -> RouterResult<storage::ProcessTracker> {
-    let runner = "DELETE_TOKENIZE_DATA_WORKFLOW";
+    let runner = DELETE_TOKENIZE_DATA_RUNNER;
     let current_time = common_utils::date_time::now();
     let tracking_data = serde_json::to_value(storage::TokenizeCoreWorkflow {
         lookup_key: lookup_key.to_owned(),
         pm,
+        key_id: Some(key_id.to_owned()),
     })
     .into_report()
     .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -708,7 +754,7 @@ pub async fn start_tokenize_data_workflow(
                 let id = tokenize_tracker.id.clone();
                 tokenize_tracker
                     .clone()
-                    .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+                    .finish_with_status(db, format!("{DELETE_TOKENIZE_DATA_COMPLETED_PREFIX}{id}"))
                     .await?;
             } else {
                 logger::error!("Error: Deleting Card From Locker : {}", resp);
@@ -90,6 +90,7 @@ where
             &mut payment_data,
             customer_details,
             validate_result.merchant_id,
+            validate_result.storage_scheme,
         )
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -270,11 +271,8 @@ pub trait PaymentRedirectFlow: Sync {
                 field_name: "payment_id",
             })?;
 
-        let connector_data = api::ConnectorData::get_connector_by_name(
-            &state.conf.connectors,
-            &connector,
-            api::GetToken::Connector,
-        )?;
+        let connector_data =
+            api::ConnectorData::get_connector_by_name(&connector, api::GetToken::Connector)?;
 
         let flow_type = connector_data
             .connector
@@ -321,6 +319,7 @@ impl PaymentRedirectFlow for PaymentRedirectCompleteAuthorize {
             merchant_id: req.merchant_id.clone(),
             metadata: Some(Metadata {
                 order_details: None,
+                purchase_details: None,
                 data: masking::Secret::new("{}".into()),
                 payload: Some(req.json_payload.unwrap_or(serde_json::json!({})).into()),
                 allowed_payment_method_types: None,
@@ -472,6 +471,10 @@ where
         .construct_router_data(state, connector.connector.id(), merchant_account)
         .await?;
 
+    if matches!(call_connector_action, CallConnectorAction::Trigger) {
+        router_data.validate_connector_capabilities(&connector.connector.get_capabilities())?;
+    }
+
     let add_access_token_result = router_data
         .add_access_token(state, &connector, merchant_account)
         .await?;
@@ -1052,13 +1055,10 @@ pub fn decide_connector(
     routing_data: &mut storage::RoutingData,
 ) -> RouterResult<api::ConnectorCallType> {
     if let Some(ref connector_name) = routing_data.routed_through {
-        let connector_data = api::ConnectorData::get_connector_by_name(
-            &state.conf.connectors,
-            connector_name,
-            api::GetToken::Connector,
-        )
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Invalid connector name received in 'routed_through'")?;
+        let connector_data =
+            api::ConnectorData::get_connector_by_name(connector_name, api::GetToken::Connector)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Invalid connector name received in 'routed_through'")?;
 
         return Ok(api::ConnectorCallType::Single(connector_data));
     }
@@ -1068,14 +1068,12 @@ pub fn decide_connector(
             api::RoutingAlgorithm::Single(conn) => conn.to_string(),
         };
 
-        let connector_data = api::ConnectorData::get_connector_by_name(
-            &state.conf.connectors,
-            &connector_name,
-            api::GetToken::Connector,
-        )
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Invalid connector name received in routing algorithm")?;
+        let connector_data =
+            api::ConnectorData::get_connector_by_name(&connector_name, api::GetToken::Connector)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Invalid connector name received in routing algorithm")?;
 
+        ensure_connector_is_available(&connector_name)?;
         routing_data.routed_through = Some(connector_name);
         routing_data.algorithm = Some(routing_algorithm);
         return Ok(api::ConnectorCallType::Single(connector_data));
@@ -1086,14 +1084,12 @@ pub fn decide_connector(
             api::RoutingAlgorithm::Single(conn) => conn.to_string(),
         };
 
-        let connector_data = api::ConnectorData::get_connector_by_name(
-            &state.conf.connectors,
-            &connector_name,
-            api::GetToken::Connector,
-        )
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Invalid connector name received in routing algorithm")?;
+        let connector_data =
+            api::ConnectorData::get_connector_by_name(&connector_name, api::GetToken::Connector)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Invalid connector name received in routing algorithm")?;
 
+        ensure_connector_is_available(&connector_name)?;
         routing_data.routed_through = Some(connector_name);
         return Ok(api::ConnectorCallType::Single(connector_data));
     }
@@ -1109,15 +1105,26 @@ pub fn decide_connector(
         api::RoutingAlgorithm::Single(conn) => conn.to_string(),
     };
 
-    let connector_data = api::ConnectorData::get_connector_by_name(
-        &state.conf.connectors,
-        &connector_name,
-        api::GetToken::Connector,
-    )
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("Routing algorithm gave invalid connector")?;
+    let connector_data =
+        api::ConnectorData::get_connector_by_name(&connector_name, api::GetToken::Connector)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Routing algorithm gave invalid connector")?;
 
+    ensure_connector_is_available(&connector_name)?;
     routing_data.routed_through = Some(connector_name);
 
     Ok(api::ConnectorCallType::Single(connector_data))
 }
+
+/// Fails fast instead of routing the payment to a connector whose circuit breaker is open.
+fn ensure_connector_is_available(connector_name: &str) -> RouterResult<()> {
+    if services::api::circuit_breaker::is_available(connector_name) {
+        Ok(())
+    } else {
+        Err(errors::ApiErrorResponse::ConnectorUnavailable {
+            connector: connector_name.to_owned(),
+        })
+        .into_report()
+        .attach_printable("Circuit breaker of the routed connector is open")
+    }
+}
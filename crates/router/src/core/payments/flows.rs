@@ -7,16 +7,18 @@ pub mod session_flow;
 pub mod verfiy_flow;
 
 use async_trait::async_trait;
+use error_stack::report;
 
 use crate::{
     connector,
     core::{
-        errors::{ConnectorError, CustomResult, RouterResult},
+        errors::{self, ConnectorError, CustomResult, RouterResult},
         payments,
     },
     routes::AppState,
     services,
-    types::{self, api, storage},
+    types::{self, api, storage, transformers::ForeignInto},
+    utils,
 };
 
 #[async_trait]
@@ -68,6 +70,67 @@ pub trait Feature<F, T> {
     {
         Ok(None)
     }
+
+    /// Rejects the request before it reaches the connector if the connector has not declared
+    /// support for the flow or the payment method used.
+    fn validate_connector_capabilities(
+        &self,
+        _capabilities: &api::ConnectorCapabilities,
+    ) -> RouterResult<()> {
+        Ok(())
+    }
+}
+
+pub fn ensure_flow_supported(
+    capabilities: &api::ConnectorCapabilities,
+    flow: api::ConnectorFlow,
+) -> RouterResult<()> {
+    utils::when(!capabilities.supports_flow(flow), || {
+        Err(report!(errors::ApiErrorResponse::FlowNotSupported {
+            flow: flow.to_string(),
+            connector: capabilities.connector.to_string(),
+        }))
+    })
+}
+
+pub fn ensure_payment_method_supported<F, Req, Res>(
+    capabilities: &api::ConnectorCapabilities,
+    router_data: &types::RouterData<F, Req, Res>,
+    payment_method_type: Option<storage::enums::PaymentMethodType>,
+    currency: storage::enums::Currency,
+) -> RouterResult<()> {
+    let payment_method: api::enums::PaymentMethod = router_data.payment_method.foreign_into();
+    let payment_method_type: Option<api::enums::PaymentMethodType> =
+        payment_method_type.map(ForeignInto::foreign_into);
+    let country = router_data
+        .address
+        .billing
+        .as_ref()
+        .and_then(|billing| billing.address.as_ref())
+        .and_then(|address| address.country);
+
+    utils::when(
+        !capabilities.supports_payment_method(
+            payment_method,
+            payment_method_type,
+            Some(currency.foreign_into()),
+            country,
+        ),
+        || {
+            Err(report!(errors::ApiErrorResponse::NotSupported {
+                message: format!(
+                    "{} payments{} in {currency} are not supported by {}",
+                    payment_method_type
+                        .map(|pm_type| pm_type.to_string())
+                        .unwrap_or_else(|| payment_method.to_string()),
+                    country
+                        .map(|country| format!(" from {country}"))
+                        .unwrap_or_default(),
+                    capabilities.connector,
+                ),
+            }))
+        },
+    )
 }
 
 macro_rules! default_imp_for_complete_authorize{
@@ -90,7 +153,6 @@ default_imp_for_complete_authorize!(
     connector::Adyen,
     connector::Authorizedotnet,
     connector::Bluesnap,
-    connector::Braintree,
     connector::Checkout,
     connector::Coinbase,
     connector::Cybersource,
@@ -104,6 +166,7 @@ default_imp_for_complete_authorize!(
     connector::Payeezy,
     connector::Payu,
     connector::Rapyd,
+    connector::Simulator,
     connector::Stripe,
     connector::Trustpay,
     connector::Worldline,
@@ -132,7 +195,6 @@ default_imp_for_connector_redirect_response!(
     connector::Adyen,
     connector::Authorizedotnet,
     connector::Bluesnap,
-    connector::Braintree,
     connector::Coinbase,
     connector::Cybersource,
     connector::Dlocal,
@@ -146,6 +208,7 @@ default_imp_for_connector_redirect_response!(
     connector::Payu,
     connector::Rapyd,
     connector::Shift4,
+    connector::Simulator,
     connector::Worldline,
     connector::Worldpay
 );
@@ -183,6 +246,7 @@ default_imp_for_connector_request_id!(
     connector::Payu,
     connector::Rapyd,
     connector::Shift4,
+    connector::Simulator,
     connector::Stripe,
     connector::Trustpay,
     connector::Worldline,
@@ -276,6 +340,7 @@ default_imp_for_file_upload!(
     connector::Payu,
     connector::Rapyd,
     connector::Shift4,
+    connector::Simulator,
     connector::Trustpay,
     connector::Opennode,
     connector::Worldline,
@@ -3,7 +3,7 @@ use common_utils::{ext_traits::ValueExt, pii};
 use error_stack::{report, ResultExt};
 use masking::ExposeInterface;
 
-use super::{ConstructFlowSpecificData, Feature};
+use super::{self as flows, ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
         errors::{self, ConnectorErrorExt, RouterResult},
@@ -60,6 +60,19 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
         call_connector_action: payments::CallConnectorAction,
         merchant_account: &storage::MerchantAccount,
     ) -> RouterResult<Self> {
+        if matches!(
+            call_connector_action,
+            payments::CallConnectorAction::Trigger
+        ) {
+            payment_methods::network_tokenization::use_network_token_for_recurring_payment(
+                state,
+                merchant_account,
+                &connector.connector.get_capabilities(),
+                &mut self,
+            )
+            .await?;
+        }
+
         let resp = self
             .decide_flow(
                 state,
@@ -93,6 +106,29 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
     ) -> RouterResult<Option<String>> {
         add_payment_method_token(state, connector, tokenization_action, self).await
     }
+
+    fn validate_connector_capabilities(
+        &self,
+        capabilities: &api::ConnectorCapabilities,
+    ) -> RouterResult<()> {
+        flows::ensure_flow_supported(capabilities, api::ConnectorFlow::Authorize)?;
+        flows::ensure_payment_method_supported(
+            capabilities,
+            self,
+            self.request.payment_method_type,
+            self.request.currency,
+        )?;
+        if self.request.mandate_id.is_some() || self.request.setup_mandate_details.is_some() {
+            flows::ensure_flow_supported(capabilities, api::ConnectorFlow::Mandates)?;
+        }
+        if self.auth_type == storage_models::enums::AuthenticationType::ThreeDs
+            && self.request.enrolled_for_3ds
+            && self.payment_method == storage_models::enums::PaymentMethod::Card
+        {
+            flows::ensure_flow_supported(capabilities, api::ConnectorFlow::ThreeDs)?;
+        }
+        Ok(())
+    }
 }
 
 impl types::PaymentsAuthorizeRouterData {
@@ -267,6 +303,17 @@ where
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to add payment method in db")?;
         };
+
+        if let Some(card) = payment_method_create_request.card.as_ref() {
+            payment_methods::network_tokenization::provision_network_token(
+                state,
+                merchant_account,
+                &customer.customer_id,
+                &locker_response.0.payment_method_id,
+                card,
+            )
+            .await;
+        }
         Some(locker_response.0.payment_method_id)
     } else {
         None
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use super::{ConstructFlowSpecificData, Feature};
+use super::{self as flows, ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
         errors::{ConnectorErrorExt, RouterResult},
@@ -69,6 +69,13 @@ impl Feature<api::Void, types::PaymentsCancelData>
     ) -> RouterResult<types::AddAccessTokenResult> {
         access_token::add_access_token(state, connector, merchant_account, self).await
     }
+
+    fn validate_connector_capabilities(
+        &self,
+        capabilities: &api::ConnectorCapabilities,
+    ) -> RouterResult<()> {
+        flows::ensure_flow_supported(capabilities, api::ConnectorFlow::Void)
+    }
 }
 
 impl types::PaymentsCancelRouterData {
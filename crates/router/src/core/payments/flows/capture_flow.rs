@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use super::ConstructFlowSpecificData;
+use super::{self as flows, ConstructFlowSpecificData};
 use crate::{
     core::{
         errors::{ConnectorErrorExt, RouterResult},
@@ -62,6 +62,13 @@ impl Feature<api::Capture, types::PaymentsCaptureData>
     ) -> RouterResult<types::AddAccessTokenResult> {
         access_token::add_access_token(state, connector, merchant_account, self).await
     }
+
+    fn validate_connector_capabilities(
+        &self,
+        capabilities: &api::ConnectorCapabilities,
+    ) -> RouterResult<()> {
+        flows::ensure_flow_supported(capabilities, api::ConnectorFlow::Capture)
+    }
 }
 
 impl types::PaymentsCaptureRouterData {
@@ -74,6 +74,20 @@ impl types::PaymentsSyncRouterData {
         _confirm: Option<bool>,
         call_connector_action: payments::CallConnectorAction,
     ) -> RouterResult<Self> {
+        // Connectors without a payment sync flow return final statuses synchronously, so the
+        // status stored on the payment attempt is kept instead of calling the connector
+        let call_connector_action = match call_connector_action {
+            payments::CallConnectorAction::Trigger
+                if !connector
+                    .connector
+                    .get_capabilities()
+                    .supports_flow(api::ConnectorFlow::PaymentSync) =>
+            {
+                payments::CallConnectorAction::Avoid
+            }
+            call_connector_action => call_connector_action,
+        };
+
         let connector_integration: services::BoxedConnectorIntegration<
             '_,
             api::PSync,
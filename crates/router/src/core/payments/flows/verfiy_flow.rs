@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use super::{authorize_flow, ConstructFlowSpecificData, Feature};
+use super::{self as flows, authorize_flow, ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
         errors::{ConnectorErrorExt, RouterResult},
@@ -61,6 +61,14 @@ impl Feature<api::Verify, types::VerifyRequestData> for types::VerifyRouterData
     ) -> RouterResult<types::AddAccessTokenResult> {
         access_token::add_access_token(state, connector, merchant_account, self).await
     }
+
+    fn validate_connector_capabilities(
+        &self,
+        capabilities: &api::ConnectorCapabilities,
+    ) -> RouterResult<()> {
+        flows::ensure_flow_supported(capabilities, api::ConnectorFlow::Mandates)?;
+        flows::ensure_payment_method_supported(capabilities, self, None, self.request.currency)
+    }
 }
 
 impl types::VerifyRouterData {
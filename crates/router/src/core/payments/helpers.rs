@@ -46,12 +46,13 @@ pub async fn get_address_for_payment_request(
     address_id: Option<&str>,
     merchant_id: &str,
     customer_id: &Option<String>,
+    storage_scheme: storage_enums::MerchantStorageScheme,
 ) -> CustomResult<Option<storage::Address>, errors::ApiErrorResponse> {
     Ok(match req_address {
         Some(address) => {
             match address_id {
                 Some(id) => Some(
-                    db.update_address(id.to_owned(), address.foreign_into())
+                    db.update_address(id.to_owned(), address.foreign_into(), storage_scheme)
                         .await
                         .to_not_found_response(errors::ApiErrorResponse::AddressNotFound)?,
                 ),
@@ -64,17 +65,20 @@ pub async fn get_address_for_payment_request(
 
                     let address_details = address.address.clone().unwrap_or_default();
                     Some(
-                        db.insert_address(storage::AddressNew {
-                            phone_number: address.phone.as_ref().and_then(|a| a.number.clone()),
-                            country_code: address
-                                .phone
-                                .as_ref()
-                                .and_then(|a| a.country_code.clone()),
-                            customer_id: customer_id.to_string(),
-                            merchant_id: merchant_id.to_string(),
-
-                            ..address_details.foreign_into()
-                        })
+                        db.insert_address(
+                            storage::AddressNew {
+                                phone_number: address.phone.as_ref().and_then(|a| a.number.clone()),
+                                country_code: address
+                                    .phone
+                                    .as_ref()
+                                    .and_then(|a| a.country_code.clone()),
+                                customer_id: customer_id.to_string(),
+                                merchant_id: merchant_id.to_string(),
+
+                                ..address_details.foreign_into()
+                            },
+                            storage_scheme,
+                        )
                         .await
                         .map_err(|_| errors::ApiErrorResponse::InternalServerError)?,
                     )
@@ -82,7 +86,7 @@ pub async fn get_address_for_payment_request(
             }
         }
         None => match address_id {
-            Some(id) => Some(db.find_address(id).await)
+            Some(id) => Some(db.find_address(id, storage_scheme).await)
                 .transpose()
                 .to_not_found_response(errors::ApiErrorResponse::AddressNotFound)?,
             None => None,
@@ -93,10 +97,11 @@ pub async fn get_address_for_payment_request(
 pub async fn get_address_by_id(
     db: &dyn StorageInterface,
     address_id: Option<String>,
+    storage_scheme: storage_enums::MerchantStorageScheme,
 ) -> CustomResult<Option<storage::Address>, errors::ApiErrorResponse> {
     match address_id {
         None => Ok(None),
-        Some(address_id) => Ok(db.find_address(&address_id).await.ok()),
+        Some(address_id) => Ok(db.find_address(&address_id, storage_scheme).await.ok()),
     }
 }
 
@@ -595,12 +600,17 @@ pub async fn get_customer_from_details<F: Clone>(
     customer_id: Option<String>,
     merchant_id: &str,
     payment_data: &mut PaymentData<F>,
+    storage_scheme: storage_enums::MerchantStorageScheme,
 ) -> CustomResult<Option<storage::Customer>, errors::StorageError> {
     match customer_id {
         None => Ok(None),
         Some(c_id) => {
             let customer = db
-                .find_customer_optional_by_customer_id_merchant_id(&c_id, merchant_id)
+                .find_customer_optional_by_customer_id_merchant_id(
+                    &c_id,
+                    merchant_id,
+                    storage_scheme,
+                )
                 .await?;
             payment_data.email = payment_data
                 .email
@@ -628,6 +638,7 @@ pub async fn create_customer_if_not_exist<'a, F: Clone, R>(
     payment_data: &mut PaymentData<F>,
     req: Option<CustomerDetails>,
     merchant_id: &str,
+    storage_scheme: storage_enums::MerchantStorageScheme,
 ) -> CustomResult<(BoxedOperation<'a, F, R>, Option<storage::Customer>), errors::StorageError> {
     let req = req
         .get_required_value("customer")
@@ -635,7 +646,11 @@ pub async fn create_customer_if_not_exist<'a, F: Clone, R>(
     let optional_customer = match req.customer_id.as_ref() {
         Some(customer_id) => {
             let customer_data = db
-                .find_customer_optional_by_customer_id_merchant_id(customer_id, merchant_id)
+                .find_customer_optional_by_customer_id_merchant_id(
+                    customer_id,
+                    merchant_id,
+                    storage_scheme,
+                )
                 .await?;
             Some(match customer_data {
                 Some(c) => Ok(c),
@@ -651,14 +666,18 @@ pub async fn create_customer_if_not_exist<'a, F: Clone, R>(
                     };
 
                     metrics::CUSTOMER_CREATED.add(&metrics::CONTEXT, 1, &[]);
-                    db.insert_customer(new_customer).await
+                    db.insert_customer(new_customer, storage_scheme).await
                 }
             })
         }
         None => match &payment_data.payment_intent.customer_id {
             None => None,
             Some(customer_id) => db
-                .find_customer_optional_by_customer_id_merchant_id(customer_id, merchant_id)
+                .find_customer_optional_by_customer_id_merchant_id(
+                    customer_id,
+                    merchant_id,
+                    storage_scheme,
+                )
                 .await?
                 .map(Ok),
         },
@@ -895,6 +914,133 @@ pub(crate) fn validate_payment_method_fields_present(
     Ok(())
 }
 
+pub(crate) fn get_payment_intent_metadata(
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<Option<api_models::payments::Metadata>> {
+    payment_intent
+        .metadata
+        .clone()
+        .map(|metadata_value| {
+            metadata_value
+                .parse_value("metadata")
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "metadata",
+                })
+                .attach_printable("unable to parse metadata")
+        })
+        .transpose()
+}
+
+#[instrument(skip_all)]
+pub(crate) fn validate_purchase_details(
+    metadata: Option<&api_models::payments::Metadata>,
+    amount: Option<api::Amount>,
+) -> RouterResult<()> {
+    let purchase_details = match metadata.and_then(|metadata| metadata.purchase_details.as_ref()) {
+        Some(purchase_details) => purchase_details,
+        None => return Ok(()),
+    };
+
+    utils::when(
+        purchase_details
+            .customer_reference
+            .as_ref()
+            .map_or(false, |reference| {
+                reference.len() > consts::MAX_CUSTOMER_REFERENCE_LENGTH
+            }),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "purchase_details.customer_reference".to_string(),
+                expected_format: format!(
+                    "at most {} characters",
+                    consts::MAX_CUSTOMER_REFERENCE_LENGTH
+                ),
+            }))
+        },
+    )?;
+
+    let total_tax_amount = purchase_details.tax_amount.unwrap_or(0);
+    utils::when(total_tax_amount.is_negative(), || {
+        Err(report!(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "purchase_details.tax_amount",
+        }))
+    })?;
+
+    if let Some(api::Amount::Value(amount)) = amount {
+        utils::when(total_tax_amount > amount.get(), || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "purchase_details.tax_amount is greater than amount".to_string(),
+            }))
+        })?;
+    }
+
+    purchase_details
+        .line_items
+        .iter()
+        .flatten()
+        .try_for_each(|line_item| {
+            let has_negative_amount = [
+                line_item.unit_price,
+                line_item.tax_amount,
+                line_item.discount_amount,
+            ]
+            .into_iter()
+            .flatten()
+            .any(i64::is_negative);
+            utils::when(has_negative_amount, || {
+                Err(report!(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "purchase_details.line_items",
+                }))
+            })
+        })
+}
+
+pub(crate) fn validate_intent_fulfillment_time(
+    fulfillment_time: Option<u32>,
+    field_name: &str,
+) -> RouterResult<()> {
+    utils::when(
+        fulfillment_time.map_or(false, |fulfillment_time| {
+            !(consts::MIN_INTENT_FULFILLMENT_TIME..=consts::MAX_INTENT_FULFILLMENT_TIME)
+                .contains(&fulfillment_time)
+        }),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: field_name.to_string(),
+                expected_format: format!(
+                    "value between {} and {} seconds",
+                    consts::MIN_INTENT_FULFILLMENT_TIME,
+                    consts::MAX_INTENT_FULFILLMENT_TIME
+                ),
+            }))
+        },
+    )
+}
+
+/// Time until which a payment intent created now can be confirmed. The fulfillment time passed in
+/// the request takes precedence over the one configured for the merchant.
+pub(crate) fn get_session_expiry(
+    created_at: time::PrimitiveDateTime,
+    request_fulfillment_time: Option<u32>,
+    merchant_account: &storage::MerchantAccount,
+) -> time::PrimitiveDateTime {
+    let fulfillment_time = request_fulfillment_time
+        .map(i64::from)
+        .or(merchant_account.intent_fulfillment_time)
+        .unwrap_or_else(|| i64::from(consts::DEFAULT_INTENT_FULFILLMENT_TIME));
+    created_at.saturating_add(time::Duration::seconds(fulfillment_time))
+}
+
+#[instrument(skip_all)]
+pub(crate) fn validate_session_expiry(
+    session_expiry: Option<time::PrimitiveDateTime>,
+) -> RouterResult<()> {
+    utils::when(
+        session_expiry.map_or(false, |expiry| expiry < common_utils::date_time::now()),
+        || Err(report!(errors::ApiErrorResponse::PaymentSessionExpired)),
+    )
+}
+
 pub fn check_force_psync_precondition(
     status: &storage_enums::AttemptStatus,
     connector_transaction_id: &Option<String>,
@@ -1214,6 +1360,47 @@ pub(crate) fn validate_payment_status_against_not_allowed_statuses(
     })
 }
 
+/// Category of the error the connector failed the payment attempt with, if any.
+pub fn get_error_category(
+    payment_attempt: &storage::PaymentAttempt,
+) -> Option<api_enums::ErrorCategory> {
+    payment_attempt
+        .error_code
+        .as_ref()
+        .zip(payment_attempt.connector.as_ref())
+        .map(|(error_code, connector)| {
+            api::ConnectorData::get_error_category(connector, error_code)
+        })
+}
+
+/// A failed payment can be confirmed again only if the error it failed with is retryable.
+pub(crate) fn validate_failed_payment_is_retryable(
+    payment_attempt: &storage::PaymentAttempt,
+) -> Result<(), errors::ApiErrorResponse> {
+    let error_category = get_error_category(payment_attempt).unwrap_or_default();
+    fp_utils::when(!error_category.is_retryable(), || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "You cannot confirm this payment because it failed with the non-retryable error category {error_category}",
+            ),
+        })
+    })
+}
+
+/// A failed payment can be confirmed again only while it has fewer attempts than allowed.
+pub(crate) fn validate_payment_attempt_count(
+    payment_intent: &storage::PaymentIntent,
+    max_attempts: i16,
+) -> Result<(), errors::ApiErrorResponse> {
+    fp_utils::when(payment_intent.attempt_count >= max_attempts, || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "You cannot confirm this payment because it has reached the maximum of {max_attempts} attempts",
+            ),
+        })
+    })
+}
+
 pub(crate) fn validate_pm_or_token_given(
     payment_method: &Option<api_enums::PaymentMethod>,
     payment_method_data: &Option<api::PaymentMethodData>,
@@ -1359,6 +1546,80 @@ mod tests {
         let pi_cs = Some("2".to_string());
         assert!(authenticate_client_secret(req_cs.as_ref(), pi_cs.as_ref()).is_err())
     }
+
+    #[test]
+    fn test_validate_purchase_details() {
+        let make_metadata = |tax_amount: i64, unit_price: i64| api_models::payments::Metadata {
+            purchase_details: Some(api_models::payments::PurchaseDetails {
+                customer_reference: Some("PO-1".to_string()),
+                tax_amount: Some(tax_amount),
+                line_items: Some(vec![api_models::payments::OrderDetails {
+                    product_name: "paper".to_string(),
+                    quantity: 2,
+                    unit_price: Some(unit_price),
+                    ..Default::default()
+                }]),
+            }),
+            ..Default::default()
+        };
+        let amount = Some(api::Amount::from(1000));
+
+        assert!(validate_purchase_details(Some(&make_metadata(100, 450)), amount).is_ok());
+        assert!(validate_purchase_details(Some(&make_metadata(1500, 450)), amount).is_err());
+        assert!(validate_purchase_details(Some(&make_metadata(100, -450)), amount).is_err());
+        // A reduced amount on update has to be checked against the stored tax amount
+        assert!(validate_purchase_details(
+            Some(&make_metadata(500, 450)),
+            Some(api::Amount::from(400))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_error_category_retry_eligibility() {
+        let category = api::ConnectorData::get_error_category;
+
+        assert_eq!(
+            category("stripe", "insufficient_funds"),
+            api_enums::ErrorCategory::InsufficientFunds
+        );
+        assert_eq!(
+            category("adyen", "6"),
+            api_enums::ErrorCategory::ExpiredCard
+        );
+        assert_eq!(
+            category("checkout", "request_invalid & cvv_invalid"),
+            api_enums::ErrorCategory::InvalidRequest
+        );
+        assert_eq!(
+            category("stripe", "unmapped_code"),
+            api_enums::ErrorCategory::Unknown
+        );
+
+        assert!(category("simulator", "card_declined").is_retryable());
+        assert!(!category("stripe", "stolen_card").is_retryable());
+        assert!(!category("unknown_connector", "card_declined").is_retryable());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_stripe_fraudulent_decline_is_not_retryable() {
+        let response = types::Response {
+            response: bytes::Bytes::from_static(
+                br#"{"error":{"code":"card_declined","decline_code":"fraudulent","message":"Your card was declined.","type":"card_error"}}"#,
+            ),
+            status_code: 402,
+        };
+        let error = services::ConnectorIntegration::<
+            api::Authorize,
+            types::PaymentsAuthorizeData,
+            types::PaymentsResponseData,
+        >::get_error_response(&crate::connector::Stripe, response)
+        .unwrap();
+
+        assert_eq!(error.code, "fraudulent");
+        assert!(!api::ConnectorData::get_error_category("stripe", &error.code).is_retryable());
+    }
 }
 
 // This function will be removed after moving this functionality to server_wrap and using cache instead of config
@@ -104,6 +104,7 @@ pub trait Domain<F: Clone, R>: Send + Sync {
         payment_data: &mut PaymentData<F>,
         request: Option<CustomerDetails>,
         merchant_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<(BoxedOperation<'a, F, R>, Option<storage::Customer>), errors::StorageError>;
 
     #[allow(clippy::too_many_arguments)]
@@ -171,6 +172,7 @@ where
         payment_data: &mut PaymentData<F>,
         _request: Option<CustomerDetails>,
         merchant_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<
         (
             BoxedOperation<'a, F, api::PaymentsRetrieveRequest>,
@@ -185,6 +187,7 @@ where
                 payment_data.payment_intent.customer_id.clone(),
                 merchant_id,
                 payment_data,
+                storage_scheme,
             )
             .await?,
         ))
@@ -226,6 +229,7 @@ where
         payment_data: &mut PaymentData<F>,
         _request: Option<CustomerDetails>,
         merchant_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<
         (
             BoxedOperation<'a, F, api::PaymentsCaptureRequest>,
@@ -240,6 +244,7 @@ where
                 payment_data.payment_intent.customer_id.clone(),
                 merchant_id,
                 payment_data,
+                storage_scheme,
             )
             .await?,
         ))
@@ -280,6 +285,7 @@ where
         payment_data: &mut PaymentData<F>,
         _request: Option<CustomerDetails>,
         merchant_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<
         (
             BoxedOperation<'a, F, api::PaymentsCancelRequest>,
@@ -294,6 +300,7 @@ where
                 payment_data.payment_intent.customer_id.clone(),
                 merchant_id,
                 payment_data,
+                storage_scheme,
             )
             .await?,
         ))
@@ -5,8 +5,11 @@ use common_utils::ext_traits::{AsyncExt, Encode};
 use error_stack::ResultExt;
 use router_derive::PaymentOperation;
 use router_env::{instrument, tracing};
+use uuid::Uuid;
 
-use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, ValidateRequest};
+use super::{
+    BoxedOperation, Domain, GetTracker, Operation, PaymentCreate, UpdateTracker, ValidateRequest,
+};
 use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
@@ -64,13 +67,12 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         helpers::validate_payment_status_against_not_allowed_statuses(
             &payment_intent.status,
-            &[
-                storage_enums::IntentStatus::Failed,
-                storage_enums::IntentStatus::Succeeded,
-            ],
+            &[storage_enums::IntentStatus::Succeeded],
             "confirm",
         )?;
 
+        helpers::validate_session_expiry(payment_intent.session_expiry)?;
+
         let (token, payment_method, setup_mandate) = helpers::get_token_pm_type_mandate_details(
             state,
             request,
@@ -103,6 +105,49 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
+        if payment_intent.status == storage_enums::IntentStatus::Failed {
+            helpers::validate_payment_attempt_count(
+                &payment_intent,
+                state.conf.payment_retry.max_attempts,
+            )?;
+            helpers::validate_failed_payment_is_retryable(&payment_attempt)?;
+
+            // The failed attempt is left as is, the payment is retried with a new attempt
+            payment_attempt = db
+                .insert_payment_attempt(
+                    Self::make_retry_payment_attempt(&payment_attempt, request),
+                    storage_scheme,
+                )
+                .await
+                .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
+                    payment_id: payment_id.clone(),
+                })?;
+
+            db.insert_connector_response(
+                PaymentCreate::make_connector_response(&payment_attempt),
+                storage_scheme,
+            )
+            .await
+            .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
+                payment_id: payment_id.clone(),
+            })?;
+
+            let setup_future_usage = payment_intent.setup_future_usage;
+            let attempt_count = payment_intent.attempt_count.saturating_add(1);
+            payment_intent = db
+                .update_payment_intent(
+                    payment_intent,
+                    storage::PaymentIntentUpdate::PaymentAttemptUpdate {
+                        active_attempt_id: payment_attempt.attempt_id.clone(),
+                        attempt_count,
+                    },
+                    storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+            payment_intent.setup_future_usage = setup_future_usage;
+        }
+
         let token = token.or_else(|| payment_attempt.payment_token.clone());
 
         helpers::validate_pm_or_token_given(
@@ -127,6 +172,12 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         currency = payment_attempt.currency.get_required_value("currency")?;
         amount = payment_attempt.amount.into();
 
+        let intent_metadata = helpers::get_payment_intent_metadata(&payment_intent)?;
+        helpers::validate_purchase_details(
+            request.metadata.as_ref().or(intent_metadata.as_ref()),
+            Some(amount),
+        )?;
+
         helpers::validate_customer_id_mandatory_cases(
             request.shipping.is_some(),
             request.billing.is_some(),
@@ -143,6 +194,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             payment_intent.shipping_address_id.as_deref(),
             merchant_id,
             &payment_intent.customer_id,
+            storage_scheme,
         )
         .await?;
         let billing_address = helpers::get_address_for_payment_request(
@@ -151,6 +203,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             payment_intent.billing_address_id.as_deref(),
             merchant_id,
             &payment_intent.customer_id,
+            storage_scheme,
         )
         .await?;
 
@@ -237,6 +290,7 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentConfirm {
         payment_data: &mut PaymentData<F>,
         request: Option<CustomerDetails>,
         merchant_id: &str,
+        storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> CustomResult<
         (
             BoxedOperation<'a, F, api::PaymentsRequest>,
@@ -250,6 +304,7 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentConfirm {
             payment_data,
             request,
             merchant_id,
+            storage_scheme,
         )
         .await
     }
@@ -395,6 +450,45 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
     }
 }
 
+impl PaymentConfirm {
+    #[instrument(skip_all)]
+    fn make_retry_payment_attempt(
+        failed_attempt: &storage::PaymentAttempt,
+        request: &api::PaymentsRequest,
+    ) -> storage::PaymentAttemptNew {
+        let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
+
+        storage::PaymentAttemptNew {
+            payment_id: failed_attempt.payment_id.clone(),
+            merchant_id: failed_attempt.merchant_id.clone(),
+            attempt_id: Uuid::new_v4().simple().to_string(),
+            status: helpers::payment_attempt_status_fsm(
+                &request.payment_method_data,
+                request.confirm,
+            ),
+            amount: failed_attempt.amount,
+            currency: failed_attempt.currency,
+            payment_method: failed_attempt.payment_method,
+            capture_method: failed_attempt.capture_method,
+            capture_on: failed_attempt.capture_on,
+            confirm: true,
+            authentication_type: failed_attempt.authentication_type,
+            created_at,
+            modified_at,
+            last_synced,
+            mandate_id: failed_attempt.mandate_id.clone(),
+            payment_token: failed_attempt.payment_token.clone(),
+            payment_experience: failed_attempt.payment_experience.clone(),
+            payment_method_type: failed_attempt.payment_method_type.clone(),
+            business_sub_label: failed_attempt.business_sub_label.clone(),
+            settlement_amount: failed_attempt.settlement_amount,
+            settlement_currency: failed_attempt.settlement_currency,
+            fx_rate: failed_attempt.fx_rate.clone(),
+            ..storage::PaymentAttemptNew::default()
+        }
+    }
+}
+
 impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest> for PaymentConfirm {
     #[instrument(skip_all)]
     fn validate_request<'a, 'b>(
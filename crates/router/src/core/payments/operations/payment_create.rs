@@ -12,6 +12,7 @@ use crate::{
     consts,
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
+        fx,
         payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
         utils as core_utils,
     },
@@ -56,6 +57,26 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         let money @ (amount, currency) = payments_create_request_validation(request)?;
 
+        let settlement_currency: Option<enums::Currency> = request
+            .settlement_currency
+            .map(ForeignInto::foreign_into)
+            .filter(|settlement_currency| *settlement_currency != currency);
+        let settlement_details = match settlement_currency {
+            Some(settlement_currency) => {
+                let fx_rate_provider = fx::get_fx_rate_provider(&state.conf.fx);
+                Some(
+                    fx::get_settlement_details(
+                        fx_rate_provider.as_ref(),
+                        amount.into(),
+                        currency,
+                        settlement_currency,
+                    )
+                    .await?,
+                )
+            }
+            None => None,
+        };
+
         let payment_id = payment_id
             .get_payment_intent_id()
             .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
@@ -75,6 +96,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             None,
             merchant_id,
             &request.customer_id,
+            storage_scheme,
         )
         .await?;
 
@@ -84,6 +106,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             None,
             merchant_id,
             &request.customer_id,
+            storage_scheme,
         )
         .await?;
 
@@ -107,6 +130,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                     payment_method_type,
                     request,
                     browser_info,
+                    settlement_details,
                 )?,
                 storage_scheme,
             )
@@ -132,6 +156,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
                 payment_id: payment_id.clone(),
             })?;
+
         connector_response = db
             .insert_connector_response(
                 Self::make_connector_response(&payment_attempt),
@@ -228,6 +253,7 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentCreate {
         payment_data: &mut PaymentData<F>,
         request: Option<CustomerDetails>,
         merchant_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<
         (
             BoxedOperation<'a, F, api::PaymentsRequest>,
@@ -241,6 +267,7 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentCreate {
             payment_data,
             request,
             merchant_id,
+            storage_scheme,
         )
         .await
     }
@@ -385,6 +412,10 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest> for PaymentCreate
 
         helpers::validate_payment_method_fields_present(request)?;
 
+        helpers::validate_purchase_details(request.metadata.as_ref(), request.amount)?;
+
+        helpers::validate_intent_fulfillment_time(request.session_expiry, "session_expiry")?;
+
         let payment_id = core_utils::get_or_generate_id("payment_id", &given_payment_id, "pay")?;
 
         let mandate_type = helpers::validate_mandate(request)?;
@@ -427,6 +458,7 @@ impl PaymentCreate {
         payment_method: Option<enums::PaymentMethod>,
         request: &api::PaymentsRequest,
         browser_info: Option<serde_json::Value>,
+        settlement_details: Option<fx::SettlementDetails>,
     ) -> RouterResult<storage::PaymentAttemptNew> {
         let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
         let status =
@@ -462,6 +494,13 @@ impl PaymentCreate {
             payment_experience: request.payment_experience.map(ForeignInto::foreign_into),
             payment_method_type: request.payment_method_type.map(ForeignInto::foreign_into),
             payment_method_data: additional_pm_data,
+            settlement_amount: settlement_details
+                .as_ref()
+                .map(|settlement_details| settlement_details.amount),
+            settlement_currency: settlement_details
+                .as_ref()
+                .map(|settlement_details| settlement_details.currency),
+            fx_rate: settlement_details.map(|settlement_details| settlement_details.fx_rate),
             ..storage::PaymentAttemptNew::default()
         })
     }
@@ -524,6 +563,10 @@ impl PaymentCreate {
             business_country,
             business_label,
             active_attempt_id,
+            attempt_count: 1,
+            session_expiry: created_at.map(|created_at| {
+                helpers::get_session_expiry(created_at, request.session_expiry, merchant_account)
+            }),
             ..storage::PaymentIntentNew::default()
         })
     }
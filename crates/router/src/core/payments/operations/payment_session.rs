@@ -95,6 +95,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsSessionRequest>
             payment_intent.shipping_address_id.as_deref(),
             merchant_id,
             &payment_intent.customer_id,
+            storage_scheme,
         )
         .await?;
 
@@ -104,6 +105,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsSessionRequest>
             payment_intent.billing_address_id.as_deref(),
             merchant_id,
             &payment_intent.customer_id,
+            storage_scheme,
         )
         .await?;
 
@@ -253,6 +255,7 @@ where
         payment_data: &mut PaymentData<F>,
         request: Option<payments::CustomerDetails>,
         merchant_id: &str,
+        storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> errors::CustomResult<
         (
             BoxedOperation<'a, F, api::PaymentsSessionRequest>,
@@ -266,6 +269,7 @@ where
             payment_data,
             request,
             merchant_id,
+            storage_scheme,
         )
         .await
     }
@@ -290,7 +294,6 @@ where
         state: &AppState,
         request: &api::PaymentsSessionRequest,
     ) -> RouterResult<api::ConnectorChoice> {
-        let connectors = &state.conf.connectors;
         let db = &state.store;
 
         let connector_accounts = db
@@ -350,7 +353,6 @@ where
                 {
                     if connector_and_payment_method_type.1 == payment_method_type {
                         let connector_details = api::ConnectorData::get_connector_by_name(
-                            connectors,
                             connector_and_payment_method_type.0.as_str(),
                             api::GetToken::from(connector_and_payment_method_type.1),
                         )?;
@@ -364,7 +366,6 @@ where
 
             for connector_and_payment_method_type in connector_and_supporting_payment_method_type {
                 let connector_details = api::ConnectorData::get_connector_by_name(
-                    connectors,
                     connector_and_payment_method_type.0.as_str(),
                     api::GetToken::from(connector_and_payment_method_type.1),
                 )?;
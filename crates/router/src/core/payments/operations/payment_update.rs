@@ -10,6 +10,7 @@ use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, Valida
 use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
+        fx,
         payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
         utils as core_utils,
     },
@@ -111,6 +112,34 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .amount
             .unwrap_or_else(|| payment_attempt.amount.into());
 
+        let intent_metadata = helpers::get_payment_intent_metadata(&payment_intent)?;
+        helpers::validate_purchase_details(
+            request.metadata.as_ref().or(intent_metadata.as_ref()),
+            Some(amount),
+        )?;
+
+        // The settlement amount has to follow changes to the amount or the presentment currency.
+        // The rate agreed on creation is kept unless the presentment currency changes.
+        if let Some(settlement_currency) = payment_attempt.settlement_currency {
+            let amount: i64 = amount.into();
+            if payment_attempt.currency == Some(currency) {
+                payment_attempt.settlement_amount =
+                    fx::to_settlement_money(&payment_attempt, amount)?
+                        .map(|(settlement_amount, _)| settlement_amount);
+            } else {
+                let fx_rate_provider = fx::get_fx_rate_provider(&state.conf.fx);
+                let settlement_details = fx::get_settlement_details(
+                    fx_rate_provider.as_ref(),
+                    amount,
+                    currency,
+                    settlement_currency,
+                )
+                .await?;
+                payment_attempt.settlement_amount = Some(settlement_details.amount);
+                payment_attempt.fx_rate = Some(settlement_details.fx_rate);
+            }
+        }
+
         if request.confirm.unwrap_or(false) {
             helpers::validate_customer_id_mandatory_cases(
                 request.shipping.is_some(),
@@ -129,6 +158,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             payment_intent.shipping_address_id.as_deref(),
             merchant_id,
             &payment_intent.customer_id,
+            storage_scheme,
         )
         .await?;
         let billing_address = helpers::get_address_for_payment_request(
@@ -137,6 +167,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             payment_intent.billing_address_id.as_deref(),
             merchant_id,
             &payment_intent.customer_id,
+            storage_scheme,
         )
         .await?;
 
@@ -289,6 +320,7 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentUpdate {
         payment_data: &mut PaymentData<F>,
         request: Option<CustomerDetails>,
         merchant_id: &str,
+        storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> CustomResult<
         (
             BoxedOperation<'a, F, api::PaymentsRequest>,
@@ -302,6 +334,7 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest> for PaymentUpdate {
             payment_data,
             request,
             merchant_id,
+            storage_scheme,
         )
         .await
     }
@@ -381,6 +414,8 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
 
         let payment_method_type = payment_data.payment_attempt.payment_method_type.clone();
         let payment_experience = payment_data.payment_attempt.payment_experience.clone();
+        let settlement_amount = payment_data.payment_attempt.settlement_amount;
+        let fx_rate = payment_data.payment_attempt.fx_rate.clone();
         payment_data.payment_attempt = db
             .update_payment_attempt_with_attempt_id(
                 payment_data.payment_attempt,
@@ -395,6 +430,8 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     payment_experience,
                     payment_method_type,
                     business_sub_label,
+                    settlement_amount,
+                    fx_rate,
                 },
                 storage_scheme,
             )
@@ -9,6 +9,7 @@ use crate::{
     connector::Paypal,
     core::{
         errors::{self, RouterResponse, RouterResult},
+        fx,
         payments::{self, helpers},
     },
     routes::AppState,
@@ -22,18 +23,18 @@ use crate::{
 };
 
 #[instrument(skip_all)]
-pub async fn construct_payment_router_data<'a, F, T>(
-    state: &'a AppState,
+pub async fn construct_payment_router_data<F, T>(
+    state: &AppState,
     payment_data: PaymentData<F>,
     connector_id: &str,
     merchant_account: &storage::MerchantAccount,
 ) -> RouterResult<types::RouterData<F, T, types::PaymentsResponseData>>
 where
-    T: TryFrom<PaymentAdditionalData<'a, F>>,
+    T: TryFrom<PaymentAdditionalData<F>>,
     types::RouterData<F, T, types::PaymentsResponseData>: Feature<F, T>,
     F: Clone,
     error_stack::Report<errors::ApiErrorResponse>:
-        From<<T as TryFrom<PaymentAdditionalData<'a, F>>>::Error>,
+        From<<T as TryFrom<PaymentAdditionalData<F>>>::Error>,
 {
     let (merchant_connector_account, payment_method, router_data);
     let connector_label = helpers::get_connector_label(
@@ -76,11 +77,20 @@ where
             connector_metadata: None,
         });
 
+    // Payments created with FX conversion are processed by the connector in the settlement
+    // currency rather than the presentment currency
+    let mut connector_payment_data = payment_data.clone();
+    if let Some((amount, currency)) =
+        fx::to_settlement_money(&payment_data.payment_attempt, payment_data.amount.into())?
+    {
+        connector_payment_data.amount = amount.into();
+        connector_payment_data.currency = currency;
+    }
+
     let additional_data = PaymentAdditionalData {
         router_base_url: state.conf.server.base_url.clone(),
         connector_name: connector_id.to_string(),
-        payment_data: payment_data.clone(),
-        state,
+        payment_data: connector_payment_data,
     };
 
     router_data = types::RouterData {
@@ -252,6 +262,7 @@ where
         .get_required_value("currency")?
         .to_string();
     let mandate_id = payment_attempt.mandate_id.clone();
+    let error_category = helpers::get_error_category(&payment_attempt);
     let refunds_response = if refunds.is_empty() {
         None
     } else {
@@ -345,6 +356,7 @@ where
                         .set_payment_token(payment_attempt.payment_token)
                         .set_error_message(payment_attempt.error_message)
                         .set_error_code(payment_attempt.error_code)
+                        .set_error_category(error_category)
                         .set_shipping(address.shipping)
                         .set_billing(address.billing)
                         .set_next_action(next_action_response)
@@ -386,6 +398,14 @@ where
                             parsed_metadata
                                 .and_then(|metadata| metadata.allowed_payment_method_types),
                         )
+                        .set_settlement_amount(payment_attempt.settlement_amount)
+                        .set_settlement_currency(
+                            payment_attempt
+                                .settlement_currency
+                                .map(ForeignInto::foreign_into),
+                        )
+                        .set_fx_rate(payment_attempt.fx_rate)
+                        .set_expires_on(payment_intent.session_expiry)
                         .to_owned(),
                 )
             }
@@ -411,6 +431,7 @@ where
                 .map(ForeignInto::foreign_into),
             error_message: payment_attempt.error_message,
             error_code: payment_attempt.error_code,
+            error_category,
             payment_method_data: payment_method_data.map(api::PaymentMethodDataResponse::from),
             email: customer
                 .as_ref()
@@ -427,6 +448,12 @@ where
             cancellation_reason: payment_attempt.cancellation_reason,
             payment_token: payment_attempt.payment_token,
             metadata: payment_intent.metadata,
+            settlement_amount: payment_attempt.settlement_amount,
+            settlement_currency: payment_attempt
+                .settlement_currency
+                .map(ForeignInto::foreign_into),
+            fx_rate: payment_attempt.fx_rate,
+            expires_on: payment_intent.session_expiry,
             ..Default::default()
         }),
     })
@@ -457,19 +484,18 @@ impl ForeignFrom<(storage::PaymentIntent, storage::PaymentAttempt)> for api::Pay
 }
 
 #[derive(Clone)]
-pub struct PaymentAdditionalData<'a, F>
+pub struct PaymentAdditionalData<F>
 where
     F: Clone,
 {
     router_base_url: String,
     connector_name: String,
     payment_data: PaymentData<F>,
-    state: &'a AppState,
 }
-impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthorizeData {
+impl<F: Clone> TryFrom<PaymentAdditionalData<F>> for types::PaymentsAuthorizeData {
     type Error = error_stack::Report<errors::ApiErrorResponse>;
 
-    fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
+    fn try_from(additional_data: PaymentAdditionalData<F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data;
         let router_base_url = &additional_data.router_base_url;
         let connector_name = &additional_data.connector_name;
@@ -497,7 +523,15 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             .transpose()
             .unwrap_or_default();
 
-        let order_details = parsed_metadata.and_then(|data| data.order_details);
+        let (order_details, purchase_details) = parsed_metadata
+            .map(|data| (data.order_details, data.purchase_details))
+            .unwrap_or_default();
+        let order_details = order_details
+            .map(|details| to_settlement_order_details(&payment_data.payment_attempt, details))
+            .transpose()?;
+        let purchase_details = purchase_details
+            .map(|details| to_settlement_purchase_details(&payment_data.payment_attempt, details))
+            .transpose()?;
         let complete_authorize_url = Some(helpers::create_complete_authorize_url(
             router_base_url,
             attempt,
@@ -533,9 +567,11 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             email: payment_data.email,
             payment_experience: payment_data.payment_attempt.payment_experience,
             order_details,
+            purchase_details,
             session_token: None,
             enrolled_for_3ds: true,
             related_transaction_id: None,
+            network_token_cryptogram: None,
             payment_method_type: payment_data.payment_attempt.payment_method_type,
             router_return_url,
             webhook_url,
@@ -544,10 +580,10 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
     }
 }
 
-impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSyncData {
+impl<F: Clone> TryFrom<PaymentAdditionalData<F>> for types::PaymentsSyncData {
     type Error = errors::ApiErrorResponse;
 
-    fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
+    fn try_from(additional_data: PaymentAdditionalData<F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data;
         Ok(Self {
             connector_transaction_id: match payment_data.payment_attempt.connector_transaction_id {
@@ -581,20 +617,24 @@ impl api::ConnectorTransactionId for Paypal {
     }
 }
 
-impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCaptureData {
+impl<F: Clone> TryFrom<PaymentAdditionalData<F>> for types::PaymentsCaptureData {
     type Error = error_stack::Report<errors::ApiErrorResponse>;
 
-    fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
+    fn try_from(additional_data: PaymentAdditionalData<F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data;
         let connector = api::ConnectorData::get_connector_by_name(
-            &additional_data.state.conf.connectors,
             &additional_data.connector_name,
             api::GetToken::Connector,
         )?;
-        let amount_to_capture: i64 = payment_data
-            .payment_attempt
-            .amount_to_capture
-            .map_or(payment_data.amount.into(), |capture_amount| capture_amount);
+        // `amount` has already been converted into the settlement currency, while the amount to
+        // capture is stored in the presentment currency
+        let amount_to_capture: i64 = match payment_data.payment_attempt.amount_to_capture {
+            Some(capture_amount) => {
+                fx::to_settlement_money(&payment_data.payment_attempt, capture_amount)?
+                    .map_or(capture_amount, |(settlement_amount, _)| settlement_amount)
+            }
+            None => payment_data.amount.into(),
+        };
         Ok(Self {
             amount_to_capture,
             currency: payment_data.currency,
@@ -608,13 +648,12 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCaptureD
     }
 }
 
-impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCancelData {
+impl<F: Clone> TryFrom<PaymentAdditionalData<F>> for types::PaymentsCancelData {
     type Error = error_stack::Report<errors::ApiErrorResponse>;
 
-    fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
+    fn try_from(additional_data: PaymentAdditionalData<F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data;
         let connector = api::ConnectorData::get_connector_by_name(
-            &additional_data.state.conf.connectors,
             &additional_data.connector_name,
             api::GetToken::Connector,
         )?;
@@ -631,10 +670,55 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCancelDa
     }
 }
 
-impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSessionData {
+fn to_settlement_amount(
+    payment_attempt: &storage::PaymentAttempt,
+    amount: Option<i64>,
+) -> RouterResult<Option<i64>> {
+    amount
+        .map(|amount| {
+            fx::to_settlement_money(payment_attempt, amount).map(|settlement_money| {
+                settlement_money.map_or(amount, |(settlement_amount, _)| settlement_amount)
+            })
+        })
+        .transpose()
+}
+
+/// Line item amounts are sent to the connector in the same currency as the payment amount
+fn to_settlement_order_details(
+    payment_attempt: &storage::PaymentAttempt,
+    order_details: api_models::payments::OrderDetails,
+) -> RouterResult<api_models::payments::OrderDetails> {
+    Ok(api_models::payments::OrderDetails {
+        unit_price: to_settlement_amount(payment_attempt, order_details.unit_price)?,
+        tax_amount: to_settlement_amount(payment_attempt, order_details.tax_amount)?,
+        discount_amount: to_settlement_amount(payment_attempt, order_details.discount_amount)?,
+        ..order_details
+    })
+}
+
+fn to_settlement_purchase_details(
+    payment_attempt: &storage::PaymentAttempt,
+    purchase_details: api_models::payments::PurchaseDetails,
+) -> RouterResult<api_models::payments::PurchaseDetails> {
+    Ok(api_models::payments::PurchaseDetails {
+        tax_amount: to_settlement_amount(payment_attempt, purchase_details.tax_amount)?,
+        line_items: purchase_details
+            .line_items
+            .map(|line_items| {
+                line_items
+                    .into_iter()
+                    .map(|line_item| to_settlement_order_details(payment_attempt, line_item))
+                    .collect::<RouterResult<Vec<_>>>()
+            })
+            .transpose()?,
+        ..purchase_details
+    })
+}
+
+impl<F: Clone> TryFrom<PaymentAdditionalData<F>> for types::PaymentsSessionData {
     type Error = error_stack::Report<errors::ApiErrorResponse>;
 
-    fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
+    fn try_from(additional_data: PaymentAdditionalData<F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data;
         let parsed_metadata: Option<api_models::payments::Metadata> = payment_data
             .payment_intent
@@ -650,7 +734,10 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSessionD
             .transpose()
             .unwrap_or_default();
 
-        let order_details = parsed_metadata.and_then(|data| data.order_details);
+        let order_details = parsed_metadata
+            .and_then(|data| data.order_details)
+            .map(|details| to_settlement_order_details(&payment_data.payment_attempt, details))
+            .transpose()?;
 
         Ok(Self {
             amount: payment_data.amount.into(),
@@ -663,10 +750,10 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSessionD
     }
 }
 
-impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::VerifyRequestData {
+impl<F: Clone> TryFrom<PaymentAdditionalData<F>> for types::VerifyRequestData {
     type Error = error_stack::Report<errors::ApiErrorResponse>;
 
-    fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
+    fn try_from(additional_data: PaymentAdditionalData<F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data;
         Ok(Self {
             currency: payment_data.currency,
@@ -683,10 +770,10 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::VerifyRequestDat
     }
 }
 
-impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::CompleteAuthorizeData {
+impl<F: Clone> TryFrom<PaymentAdditionalData<F>> for types::CompleteAuthorizeData {
     type Error = error_stack::Report<errors::ApiErrorResponse>;
 
-    fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
+    fn try_from(additional_data: PaymentAdditionalData<F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data;
         let browser_info: Option<types::BrowserInformation> = payment_data
             .payment_attempt
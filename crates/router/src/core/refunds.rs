@@ -13,7 +13,7 @@ use crate::{
     },
     db, logger,
     routes::{metrics, AppState},
-    scheduler::{process_data, utils as process_tracker_utils, workflows::payment_sync},
+    scheduler::{process_data, utils as process_tracker_utils},
     services,
     types::{
         self,
@@ -135,13 +135,10 @@ pub async fn trigger_refund_to_gateway(
         )],
     );
 
-    let connector: api::ConnectorData = api::ConnectorData::get_connector_by_name(
-        &state.conf.connectors,
-        &routed_through,
-        api::GetToken::Connector,
-    )
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("Failed to get the connector")?;
+    let connector: api::ConnectorData =
+        api::ConnectorData::get_connector_by_name(&routed_through, api::GetToken::Connector)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get the connector")?;
 
     let currency = payment_attempt.currency.ok_or_else(|| {
         report!(errors::ApiErrorResponse::MissingRequiredField {
@@ -340,14 +337,16 @@ pub async fn sync_refund_with_gateway(
     refund: &storage::Refund,
     creds_identifier: Option<String>,
 ) -> RouterResult<storage::Refund> {
+    // Connectors without a refund sync flow only report refund updates through webhooks
+    if !supports_refund_sync(&refund.connector)? {
+        return Ok(refund.to_owned());
+    }
+
     let connector_id = refund.connector.to_string();
-    let connector: api::ConnectorData = api::ConnectorData::get_connector_by_name(
-        &state.conf.connectors,
-        &connector_id,
-        api::GetToken::Connector,
-    )
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("Failed to get the connector")?;
+    let connector: api::ConnectorData =
+        api::ConnectorData::get_connector_by_name(&connector_id, api::GetToken::Connector)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get the connector")?;
 
     let currency = payment_attempt.currency.get_required_value("currency")?;
 
@@ -558,6 +557,8 @@ pub async fn validate_and_create_refund(
                 .into_report()
                 .attach_printable("No connector populated in payment attempt")?;
 
+            validator::validate_connector_supports_refunds(&connector)?;
+
             refund_create_req = storage::RefundNew::default()
                 .set_refund_id(refund_id.to_string())
                 .set_internal_reference_id(utils::generate_id(consts::ID_LENGTH, "refid"))
@@ -691,7 +692,7 @@ pub async fn schedule_refund_execution(
                             Ok(refund)
                         }
                         api_models::refunds::RefundType::Instant => {
-                            trigger_refund_to_gateway(
+                            let refund = trigger_refund_to_gateway(
                                 state,
                                 &refund,
                                 merchant_account,
@@ -699,7 +700,19 @@ pub async fn schedule_refund_execution(
                                 payment_intent,
                                 creds_identifier,
                             )
-                            .await
+                            .await?;
+
+                            // Refunds left pending by the connector are polled by the scheduler
+                            if refund.refund_status == enums::RefundStatus::Pending
+                                && supports_refund_sync(&refund.connector)?
+                            {
+                                add_refund_sync_task(db, &refund, runner)
+                                    .await
+                                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                                    .attach_printable_lazy(|| format!("Failed while pushing refund sync task in scheduler: refund_id: {}", refund.refund_id))?;
+                            }
+
+                            Ok(refund)
                         }
                     }
                 }
@@ -773,7 +786,7 @@ pub async fn sync_refund_with_gateway_workflow(
                 .await?
         }
         _ => {
-            payment_sync::retry_sync_task(
+            retry_refund_sync_task(
                 &*state.store,
                 response.connector,
                 response.merchant_id,
@@ -894,6 +907,17 @@ pub fn refund_to_refund_core_workflow_model(
     }
 }
 
+fn supports_refund_sync(connector_name: &str) -> RouterResult<bool> {
+    let connector =
+        api::ConnectorData::get_connector_by_name(connector_name, api::GetToken::Connector)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get the connector")?;
+    Ok(connector
+        .connector
+        .get_capabilities()
+        .supports_flow(api::ConnectorFlow::RefundSync))
+}
+
 #[instrument(skip_all)]
 pub async fn add_refund_sync_task(
     db: &dyn db::StorageInterface,
@@ -3,10 +3,16 @@ use router_env::{instrument, tracing};
 use time::PrimitiveDateTime;
 
 use crate::{
-    core::errors::{self, CustomResult, RouterResult},
+    core::{
+        errors::{self, CustomResult, RouterResult},
+        payments::flows,
+    },
     db::StorageInterface,
     logger,
-    types::storage::{self, enums},
+    types::{
+        api,
+        storage::{self, enums},
+    },
     utils::{self, OptionExt},
 };
 
@@ -177,3 +183,12 @@ pub fn validate_for_valid_refunds(
         _ => Ok(()),
     }
 }
+
+pub fn validate_connector_supports_refunds(connector: &str) -> RouterResult<()> {
+    let connector_data =
+        api::ConnectorData::get_connector_by_name(connector, api::GetToken::Connector)?;
+    flows::ensure_flow_supported(
+        &connector_data.connector.get_capabilities(),
+        api::ConnectorFlow::Refund,
+    )
+}
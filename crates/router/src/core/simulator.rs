@@ -0,0 +1,774 @@
+//! Sandbox simulator, a payment processor built into the router that the `simulator` connector
+//! talks to, so that payments, refunds and disputes can be exercised end to end without any
+//! network access.
+//!
+//! Outcomes are deterministic and driven by the card number used for the payment:
+//!
+//! | Card number        | Outcome                                                         |
+//! |--------------------|-----------------------------------------------------------------|
+//! | `4000000000000002` | Declined with `card_declined`                                   |
+//! | `4000000000009995` | Declined with `insufficient_funds`                              |
+//! | `4000000000000069` | Declined with `expired_card`                                    |
+//! | `4000000000000127` | Declined with `incorrect_cvc`                                   |
+//! | `4000000000003220` | 3DS challenge, completed by following the redirect              |
+//! | `4000000000000077` | Processing, then succeeded through a `payment.succeeded` webhook |
+//! | `4000000000000341` | Processing, then failed through a `payment.failed` webhook      |
+//! | `4000000000000259` | Succeeded, then disputed through a `dispute.opened` webhook     |
+//! | `4000000000006975` | Succeeded, but responds with a `504` gateway timeout            |
+//!
+//! Any other card, as well as wallets and mandates, succeeds unless the amount (in minor units)
+//! is `9901` (declined with `do_not_honor`), `9902` (processing, then succeeded through a webhook)
+//! or `9903` (timeout). A 3DS challenge is also presented whenever 3DS is requested.
+//!
+//! Refunds of `9911` fail, refunds of `9912` are pending until a `refund.succeeded` webhook and
+//! any other refund succeeds right away.
+//!
+//! Requests are authenticated with an API key of the merchant, which is the API key of the
+//! merchant's `simulator` connector account, and objects are only visible to the merchant they
+//! were created by. Webhooks are sent a couple of seconds later to the merchant's `simulator`
+//! webhook endpoint on the router, signed with the merchant's `simulator` webhook secret in the
+//! `X-Simulator-Signature` header.
+
+use api_models::simulator::{
+    SimulatorAuthenticationParams, SimulatorCaptureRequest, SimulatorDisputeResponse,
+    SimulatorDisputeStatus, SimulatorError, SimulatorEvidenceRequest, SimulatorNextAction,
+    SimulatorPaymentMethod, SimulatorPaymentRequest, SimulatorPaymentResponse,
+    SimulatorPaymentStatus, SimulatorRefundRequest, SimulatorRefundResponse, SimulatorRefundStatus,
+    SimulatorWebhook, SimulatorWebhookEventType, SimulatorWebhookObject,
+    SIMULATOR_SIGNATURE_HEADER,
+};
+use common_utils::crypto::{self, SignMessage};
+use error_stack::{IntoReport, ResultExt};
+use masking::PeekInterface;
+use router_env::{instrument, tracing};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult},
+    headers, logger,
+    routes::AppState,
+    services::{self, ApplicationResponse},
+    types::storage,
+    utils::{self, ByteSliceExt, Encode},
+};
+
+/// Delay after which pending objects are resolved and their webhook is sent.
+const WEBHOOK_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+const EVIDENCE_DUE_IN_DAYS: i64 = 7;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct SimulatorPayment {
+    merchant_id: String,
+    capture: bool,
+    amount_refunded: i64,
+    return_url: Option<String>,
+    response: SimulatorPaymentResponse,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct SimulatorRefund {
+    merchant_id: String,
+    response: SimulatorRefundResponse,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct SimulatorDispute {
+    merchant_id: String,
+    response: SimulatorDisputeResponse,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PaymentOutcome {
+    Authorize,
+    Decline {
+        code: &'static str,
+        message: &'static str,
+    },
+    Challenge,
+    Pending {
+        final_status: SimulatorPaymentStatus,
+    },
+    AuthorizeThenDispute,
+    Timeout,
+}
+
+fn get_payment_outcome(request: &SimulatorPaymentRequest) -> PaymentOutcome {
+    let card_number = match &request.payment_method {
+        Some(SimulatorPaymentMethod::Card(card)) => Some(card.number.peek().as_str()),
+        Some(SimulatorPaymentMethod::Wallet { .. }) | None => None,
+    };
+
+    match (card_number, request.amount) {
+        (Some("4000000000000002"), _) => PaymentOutcome::Decline {
+            code: "card_declined",
+            message: "Your card was declined",
+        },
+        (Some("4000000000009995"), _) => PaymentOutcome::Decline {
+            code: "insufficient_funds",
+            message: "Your card has insufficient funds",
+        },
+        (Some("4000000000000069"), _) => PaymentOutcome::Decline {
+            code: "expired_card",
+            message: "Your card has expired",
+        },
+        (Some("4000000000000127"), _) => PaymentOutcome::Decline {
+            code: "incorrect_cvc",
+            message: "Your card's security code is incorrect",
+        },
+        (Some("4000000000003220"), _) => PaymentOutcome::Challenge,
+        (Some("4000000000000077"), _) => PaymentOutcome::Pending {
+            final_status: SimulatorPaymentStatus::Succeeded,
+        },
+        (Some("4000000000000341"), _) => PaymentOutcome::Pending {
+            final_status: SimulatorPaymentStatus::Failed,
+        },
+        (Some("4000000000000259"), _) => PaymentOutcome::AuthorizeThenDispute,
+        (Some("4000000000006975"), _) => PaymentOutcome::Timeout,
+        (_, 9901) => PaymentOutcome::Decline {
+            code: "do_not_honor",
+            message: "The card issuer declined the payment",
+        },
+        (_, 9902) => PaymentOutcome::Pending {
+            final_status: SimulatorPaymentStatus::Succeeded,
+        },
+        (_, 9903) => PaymentOutcome::Timeout,
+        _ if request.three_ds => PaymentOutcome::Challenge,
+        _ => PaymentOutcome::Authorize,
+    }
+}
+
+fn simulator_error(code: &str, message: &str) -> SimulatorError {
+    SimulatorError {
+        code: code.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Moves an authorized payment to its final status, capturing it when requested.
+fn authorize(payment: &mut SimulatorPayment) {
+    if payment.capture {
+        payment.response.status = SimulatorPaymentStatus::Succeeded;
+        payment.response.amount_captured = payment.response.amount;
+    } else {
+        payment.response.status = SimulatorPaymentStatus::RequiresCapture;
+    }
+}
+
+fn payment_key(payment_id: &str) -> String {
+    format!("payment_{payment_id}")
+}
+
+fn refund_key(refund_id: &str) -> String {
+    format!("refund_{refund_id}")
+}
+
+fn dispute_key(dispute_id: &str) -> String {
+    format!("dispute_{dispute_id}")
+}
+
+fn mandate_key(mandate_id: &str) -> String {
+    format!("mandate_{mandate_id}")
+}
+
+async fn find_object<T: DeserializeOwned>(
+    state: &AppState,
+    key: &str,
+    not_found_error: errors::ApiErrorResponse,
+) -> RouterResult<T> {
+    state
+        .store
+        .find_simulator_object(key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+        .ok_or(not_found_error)
+        .into_report()?
+        .as_slice()
+        .parse_struct("SimulatorObject")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+}
+
+async fn save_object<T: Serialize + std::fmt::Debug>(
+    state: &AppState,
+    key: &str,
+    object: &T,
+) -> RouterResult<()> {
+    let value = Encode::<T>::encode_to_vec(object)
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    state
+        .store
+        .insert_or_update_simulator_object(key, value)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to store simulator object")
+}
+
+async fn find_payment(state: &AppState, payment_id: &str) -> RouterResult<SimulatorPayment> {
+    find_object(
+        state,
+        &payment_key(payment_id),
+        errors::ApiErrorResponse::PaymentNotFound,
+    )
+    .await
+}
+
+/// Finds a payment created by the given merchant.
+async fn find_merchant_payment(
+    state: &AppState,
+    merchant_id: &str,
+    payment_id: &str,
+) -> RouterResult<SimulatorPayment> {
+    let payment = find_payment(state, payment_id).await?;
+    utils::when(payment.merchant_id != merchant_id, || {
+        Err(errors::ApiErrorResponse::PaymentNotFound)
+    })?;
+    Ok(payment)
+}
+
+#[instrument(skip_all)]
+pub async fn create_payment(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    request: SimulatorPaymentRequest,
+) -> RouterResponse<SimulatorPaymentResponse> {
+    match (&request.payment_method, &request.mandate_id) {
+        (None, None) => Err(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "payment_method",
+        })
+        .into_report()?,
+        (None, Some(mandate_id)) => {
+            state
+                .store
+                .find_simulator_object(&mandate_key(mandate_id))
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)?
+                .ok_or(errors::ApiErrorResponse::MandateNotFound)
+                .into_report()?;
+        }
+        (Some(_), _) => {}
+    }
+
+    let outcome = get_payment_outcome(&request);
+    let payment_id = utils::generate_id(consts::ID_LENGTH, "sim_pay");
+
+    let mut payment = SimulatorPayment {
+        merchant_id: merchant_account.merchant_id,
+        capture: request.capture,
+        amount_refunded: 0,
+        return_url: request.return_url,
+        response: SimulatorPaymentResponse {
+            id: payment_id.clone(),
+            status: SimulatorPaymentStatus::Processing,
+            amount: request.amount,
+            amount_captured: 0,
+            currency: request.currency,
+            mandate_id: request.mandate_id,
+            next_action: None,
+            error: None,
+        },
+    };
+
+    match outcome {
+        PaymentOutcome::Authorize
+        | PaymentOutcome::AuthorizeThenDispute
+        | PaymentOutcome::Timeout => authorize(&mut payment),
+        PaymentOutcome::Decline { code, message } => {
+            payment.response.status = SimulatorPaymentStatus::Failed;
+            payment.response.error = Some(simulator_error(code, message));
+        }
+        PaymentOutcome::Challenge => {
+            payment.response.status = SimulatorPaymentStatus::RequiresCustomerAction;
+            payment.response.next_action = Some(SimulatorNextAction::RedirectToUrl {
+                url: format!(
+                    "{}payments/{}/authenticate",
+                    state.conf.connectors.simulator.base_url, payment_id
+                ),
+            });
+        }
+        PaymentOutcome::Pending { .. } => {}
+    }
+
+    if request.setup_mandate && payment.response.status != SimulatorPaymentStatus::Failed {
+        let mandate_id = utils::generate_id(consts::ID_LENGTH, "sim_mandate");
+        save_object(state, &mandate_key(&mandate_id), &payment_id).await?;
+        payment.response.mandate_id = Some(mandate_id);
+    }
+
+    save_object(state, &payment_key(&payment_id), &payment).await?;
+
+    match outcome {
+        PaymentOutcome::Timeout => {
+            // The payment went through on the simulator's end, while the router only learns
+            // that the issuer did not respond in time
+            return Err(errors::ApiErrorResponse::ExternalConnectorError {
+                code: "gateway_timeout".to_string(),
+                message: "The issuer did not respond in time".to_string(),
+                connector: "simulator".to_string(),
+                status_code: 504,
+                reason: None,
+            })
+            .into_report();
+        }
+        PaymentOutcome::Pending { final_status } => {
+            let state = state.clone();
+            let payment_id = payment_id.clone();
+            schedule(async move { resolve_payment(&state, &payment_id, final_status).await });
+        }
+        PaymentOutcome::AuthorizeThenDispute => {
+            let state = state.clone();
+            let payment_id = payment_id.clone();
+            schedule(async move { open_dispute(&state, &payment_id).await });
+        }
+        PaymentOutcome::Authorize | PaymentOutcome::Decline { .. } | PaymentOutcome::Challenge => {}
+    }
+
+    Ok(ApplicationResponse::Json(payment.response))
+}
+
+#[instrument(skip_all)]
+pub async fn retrieve_payment(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    payment_id: String,
+) -> RouterResponse<SimulatorPaymentResponse> {
+    let payment = find_merchant_payment(state, &merchant_account.merchant_id, &payment_id).await?;
+    Ok(ApplicationResponse::Json(payment.response))
+}
+
+#[instrument(skip_all)]
+pub async fn capture_payment(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    payment_id: String,
+    request: SimulatorCaptureRequest,
+) -> RouterResponse<SimulatorPaymentResponse> {
+    let mut payment =
+        find_merchant_payment(state, &merchant_account.merchant_id, &payment_id).await?;
+
+    utils::when(
+        payment.response.status != SimulatorPaymentStatus::RequiresCapture,
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Only payments that require capture can be captured".to_string(),
+            })
+        },
+    )?;
+    utils::when(
+        request.amount <= 0 || request.amount > payment.response.amount,
+        || {
+            Err(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "amount",
+            })
+        },
+    )?;
+
+    payment.response.status = SimulatorPaymentStatus::Succeeded;
+    payment.response.amount_captured = request.amount;
+    save_object(state, &payment_key(&payment_id), &payment).await?;
+
+    Ok(ApplicationResponse::Json(payment.response))
+}
+
+#[instrument(skip_all)]
+pub async fn void_payment(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    payment_id: String,
+) -> RouterResponse<SimulatorPaymentResponse> {
+    let mut payment =
+        find_merchant_payment(state, &merchant_account.merchant_id, &payment_id).await?;
+
+    utils::when(
+        !matches!(
+            payment.response.status,
+            SimulatorPaymentStatus::RequiresCapture
+                | SimulatorPaymentStatus::RequiresCustomerAction
+        ),
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Only payments that are not captured yet can be voided".to_string(),
+            })
+        },
+    )?;
+
+    payment.response.status = SimulatorPaymentStatus::Cancelled;
+    payment.response.next_action = None;
+    save_object(state, &payment_key(&payment_id), &payment).await?;
+
+    Ok(ApplicationResponse::Json(payment.response))
+}
+
+/// Completes (or fails, with `outcome=failure`) the 3DS challenge of a payment and redirects the
+/// customer back to the return URL of the payment. Opened by the customer's browser, hence not
+/// authenticated with an API key.
+#[instrument(skip_all)]
+pub async fn authenticate_payment(
+    state: &AppState,
+    payment_id: String,
+    params: SimulatorAuthenticationParams,
+) -> RouterResponse<()> {
+    let mut payment = find_payment(state, &payment_id).await?;
+
+    utils::when(
+        payment.response.status != SimulatorPaymentStatus::RequiresCustomerAction,
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "The payment does not require authentication".to_string(),
+            })
+        },
+    )?;
+
+    if params.outcome.as_deref() == Some("failure") {
+        payment.response.status = SimulatorPaymentStatus::Failed;
+        payment.response.error = Some(simulator_error(
+            "authentication_failed",
+            "The customer failed the 3DS challenge",
+        ));
+    } else {
+        authorize(&mut payment);
+    }
+    payment.response.next_action = None;
+    save_object(state, &payment_key(&payment_id), &payment).await?;
+
+    let return_url = payment
+        .return_url
+        .ok_or(errors::ApiErrorResponse::ReturnUrlUnavailable)
+        .into_report()?;
+    let return_url = url::Url::parse(&return_url)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Invalid return url for the simulator payment")?;
+
+    Ok(ApplicationResponse::Form(services::RedirectForm::from((
+        return_url,
+        services::Method::Get,
+    ))))
+}
+
+#[instrument(skip_all)]
+pub async fn create_refund(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    request: SimulatorRefundRequest,
+) -> RouterResponse<SimulatorRefundResponse> {
+    let mut payment =
+        find_merchant_payment(state, &merchant_account.merchant_id, &request.payment_id).await?;
+
+    utils::when(
+        payment.response.status != SimulatorPaymentStatus::Succeeded,
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Only succeeded payments can be refunded".to_string(),
+            })
+        },
+    )?;
+    utils::when(
+        request.amount > payment.response.amount_captured - payment.amount_refunded,
+        || Err(errors::ApiErrorResponse::RefundAmountExceedsPaymentAmount),
+    )?;
+
+    let refund_id = utils::generate_id(consts::ID_LENGTH, "sim_ref");
+    let (status, error) = match request.amount {
+        9911 => (
+            SimulatorRefundStatus::Failed,
+            Some(simulator_error(
+                "refund_failed",
+                "The refund could not be processed",
+            )),
+        ),
+        9912 => (SimulatorRefundStatus::Pending, None),
+        _ => (SimulatorRefundStatus::Succeeded, None),
+    };
+
+    let refund = SimulatorRefund {
+        merchant_id: payment.merchant_id.clone(),
+        response: SimulatorRefundResponse {
+            id: refund_id.clone(),
+            payment_id: request.payment_id.clone(),
+            amount: request.amount,
+            currency: payment.response.currency,
+            status,
+            error,
+        },
+    };
+    save_object(state, &refund_key(&refund_id), &refund).await?;
+
+    if status != SimulatorRefundStatus::Failed {
+        payment.amount_refunded += request.amount;
+        save_object(state, &payment_key(&request.payment_id), &payment).await?;
+    }
+
+    if status == SimulatorRefundStatus::Pending {
+        let state = state.clone();
+        let refund_id = refund_id.clone();
+        schedule(async move { resolve_refund(&state, &refund_id).await });
+    }
+
+    Ok(ApplicationResponse::Json(refund.response))
+}
+
+#[instrument(skip_all)]
+pub async fn retrieve_refund(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    refund_id: String,
+) -> RouterResponse<SimulatorRefundResponse> {
+    let refund: SimulatorRefund = find_object(
+        state,
+        &refund_key(&refund_id),
+        errors::ApiErrorResponse::RefundNotFound,
+    )
+    .await?;
+    utils::when(refund.merchant_id != merchant_account.merchant_id, || {
+        Err(errors::ApiErrorResponse::RefundNotFound)
+    })?;
+
+    Ok(ApplicationResponse::Json(refund.response))
+}
+
+async fn update_dispute_status(
+    state: &AppState,
+    merchant_id: &str,
+    dispute_id: String,
+    status: SimulatorDisputeStatus,
+) -> RouterResponse<SimulatorDisputeResponse> {
+    let mut dispute: SimulatorDispute = find_object(
+        state,
+        &dispute_key(&dispute_id),
+        errors::ApiErrorResponse::DisputeNotFound {
+            dispute_id: dispute_id.clone(),
+        },
+    )
+    .await?;
+    utils::when(dispute.merchant_id != merchant_id, || {
+        Err(errors::ApiErrorResponse::DisputeNotFound {
+            dispute_id: dispute_id.clone(),
+        })
+    })?;
+
+    utils::when(
+        dispute.response.status != SimulatorDisputeStatus::Opened,
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Only opened disputes can be accepted or challenged".to_string(),
+            })
+        },
+    )?;
+
+    dispute.response.status = status;
+    save_object(state, &dispute_key(&dispute_id), &dispute).await?;
+
+    Ok(ApplicationResponse::Json(dispute.response))
+}
+
+#[instrument(skip_all)]
+pub async fn accept_dispute(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    dispute_id: String,
+) -> RouterResponse<SimulatorDisputeResponse> {
+    update_dispute_status(
+        state,
+        &merchant_account.merchant_id,
+        dispute_id,
+        SimulatorDisputeStatus::Accepted,
+    )
+    .await
+}
+
+#[instrument(skip_all)]
+pub async fn submit_evidence(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    dispute_id: String,
+    _request: SimulatorEvidenceRequest,
+) -> RouterResponse<SimulatorDisputeResponse> {
+    update_dispute_status(
+        state,
+        &merchant_account.merchant_id,
+        dispute_id,
+        SimulatorDisputeStatus::Challenged,
+    )
+    .await
+}
+
+/// Runs the given task in the background once [`WEBHOOK_DELAY`] has elapsed.
+fn schedule<F>(task: F)
+where
+    F: futures::Future<Output = RouterResult<()>> + Send + 'static,
+{
+    let delayed_task = async move {
+        tokio::time::sleep(WEBHOOK_DELAY).await;
+        if let Err(error) = task.await {
+            logger::error!(simulator_webhook_error=?error);
+        }
+    };
+
+    match actix::Arbiter::try_current() {
+        Some(arbiter) => {
+            arbiter.spawn(delayed_task);
+        }
+        None => {
+            tokio::spawn(delayed_task);
+        }
+    }
+}
+
+async fn resolve_payment(
+    state: &AppState,
+    payment_id: &str,
+    final_status: SimulatorPaymentStatus,
+) -> RouterResult<()> {
+    let mut payment = find_payment(state, payment_id).await?;
+
+    let event_type = if final_status == SimulatorPaymentStatus::Failed {
+        payment.response.status = SimulatorPaymentStatus::Failed;
+        payment.response.error = Some(simulator_error(
+            "processing_error",
+            "The payment could not be processed",
+        ));
+        SimulatorWebhookEventType::PaymentFailed
+    } else {
+        authorize(&mut payment);
+        SimulatorWebhookEventType::PaymentSucceeded
+    };
+    save_object(state, &payment_key(payment_id), &payment).await?;
+
+    send_webhook(
+        state,
+        &payment.merchant_id,
+        event_type,
+        SimulatorWebhookObject::Payment(payment.response),
+    )
+    .await
+}
+
+async fn resolve_refund(state: &AppState, refund_id: &str) -> RouterResult<()> {
+    let mut refund: SimulatorRefund = find_object(
+        state,
+        &refund_key(refund_id),
+        errors::ApiErrorResponse::RefundNotFound,
+    )
+    .await?;
+
+    refund.response.status = SimulatorRefundStatus::Succeeded;
+    save_object(state, &refund_key(refund_id), &refund).await?;
+
+    send_webhook(
+        state,
+        &refund.merchant_id,
+        SimulatorWebhookEventType::RefundSucceeded,
+        SimulatorWebhookObject::Refund(refund.response),
+    )
+    .await
+}
+
+async fn open_dispute(state: &AppState, payment_id: &str) -> RouterResult<()> {
+    let payment = find_payment(state, payment_id).await?;
+
+    let created_at = time::OffsetDateTime::now_utc();
+    let evidence_due_by = created_at + time::Duration::days(EVIDENCE_DUE_IN_DAYS);
+    let format_date = |date: time::OffsetDateTime| {
+        date.format(&time::format_description::well_known::Iso8601::DEFAULT)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+    };
+
+    let dispute_id = utils::generate_id(consts::ID_LENGTH, "sim_dp");
+    let dispute = SimulatorDispute {
+        merchant_id: payment.merchant_id,
+        response: SimulatorDisputeResponse {
+            id: dispute_id.clone(),
+            payment_id: payment_id.to_string(),
+            amount: payment.response.amount_captured,
+            currency: payment.response.currency,
+            status: SimulatorDisputeStatus::Opened,
+            reason: "fraudulent".to_string(),
+            reason_code: "10.4".to_string(),
+            evidence_due_by: format_date(evidence_due_by)?,
+            created_at: format_date(created_at)?,
+        },
+    };
+    save_object(state, &dispute_key(&dispute_id), &dispute).await?;
+
+    send_webhook(
+        state,
+        &dispute.merchant_id,
+        SimulatorWebhookEventType::DisputeOpened,
+        SimulatorWebhookObject::Dispute(dispute.response),
+    )
+    .await
+}
+
+/// Sends the webhook to the `simulator` webhook endpoint of the merchant on this router.
+async fn send_webhook(
+    state: &AppState,
+    merchant_id: &str,
+    event_type: SimulatorWebhookEventType,
+    data: SimulatorWebhookObject,
+) -> RouterResult<()> {
+    let webhook_url = format!(
+        "{}/webhooks/{}/simulator",
+        state.conf.server.base_url, merchant_id
+    );
+
+    let webhook = SimulatorWebhook {
+        id: utils::generate_id(consts::ID_LENGTH, "sim_evt"),
+        event_type,
+        data,
+    };
+    let body = Encode::<SimulatorWebhook>::encode_to_string_of_json(&webhook)
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let mut headers = vec![(
+        headers::CONTENT_TYPE.to_string(),
+        "application/json".to_string(),
+    )];
+
+    match state
+        .store
+        .get_key(&format!("whsec_verification_simulator_{merchant_id}"))
+        .await
+    {
+        Ok(secret) => {
+            let signature = crypto::HmacSha256
+                .sign_message(&secret, body.as_bytes())
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to sign simulator webhook")?;
+            headers.push((
+                SIMULATOR_SIGNATURE_HEADER.to_string(),
+                hex::encode(signature),
+            ));
+        }
+        Err(error) => {
+            logger::warn!(
+                ?error,
+                "Webhook secret not found, sending unsigned simulator webhook"
+            );
+        }
+    }
+
+    let request = services::RequestBuilder::new()
+        .method(services::Method::Post)
+        .url(&webhook_url)
+        .headers(headers)
+        .body(Some(body))
+        .build();
+
+    let response = services::call_connector_api(state, request)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to send simulator webhook")?;
+
+    if let Err(response) = response {
+        logger::warn!(
+            status_code = response.status_code,
+            event_type = %event_type,
+            "Simulator webhook was not accepted"
+        );
+    }
+
+    Ok(())
+}
@@ -5,7 +5,10 @@ use common_utils::errors::CustomResult;
 use error_stack::ResultExt;
 use router_env::{instrument, tracing};
 
-use super::payments::{helpers, PaymentAddress};
+use super::{
+    fx,
+    payments::{helpers, PaymentAddress},
+};
 use crate::{
     consts,
     core::errors::{self, RouterResult},
@@ -55,6 +58,20 @@ pub async fn construct_refund_router_data<'a, F>(
 
     let (amount, currency) = money;
 
+    // Refunds of payments created with FX conversion are processed by the connector in the
+    // settlement currency, using the exchange rate applied when the payment was created
+    let (amount, currency, refund_amount) = match (
+        fx::to_settlement_money(payment_attempt, amount)?,
+        fx::to_settlement_money(payment_attempt, refund.refund_amount)?,
+    ) {
+        (Some((settlement_amount, settlement_currency)), Some((settlement_refund_amount, _))) => (
+            settlement_amount,
+            settlement_currency,
+            settlement_refund_amount,
+        ),
+        _ => (amount, currency, refund.refund_amount),
+    };
+
     let payment_method_type = payment_attempt
         .payment_method
         .get_required_value("payment_method_type")?;
@@ -79,7 +96,7 @@ pub async fn construct_refund_router_data<'a, F>(
         request: types::RefundsData {
             refund_id: refund.refund_id.clone(),
             connector_transaction_id: refund.connector_transaction_id.clone(),
-            refund_amount: refund.refund_amount,
+            refund_amount,
             currency,
             amount,
             connector_metadata: payment_attempt.connector_metadata.clone(),
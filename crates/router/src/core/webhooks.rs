@@ -64,29 +64,8 @@ async fn payments_incoming_webhook_flow<W: api::OutgoingWebhookType>(
 
     match payments_response {
         services::ApplicationResponse::Json(payments_response) => {
-            let payment_id = payments_response
-                .payment_id
-                .clone()
-                .get_required_value("payment_id")
-                .change_context(errors::WebhooksFlowError::PaymentsCoreFailed)?;
-
-            let event_type: enums::EventType = payments_response
-                .status
-                .foreign_try_into()
-                .into_report()
-                .change_context(errors::WebhooksFlowError::PaymentsCoreFailed)?;
-
-            create_event_and_trigger_outgoing_webhook::<W>(
-                state,
-                merchant_account,
-                event_type,
-                enums::EventClass::Payments,
-                None,
-                payment_id,
-                enums::EventObjectType::PaymentDetails,
-                api::OutgoingWebhookContent::PaymentDetails(payments_response),
-            )
-            .await?;
+            trigger_payment_outgoing_webhook::<W>(state, merchant_account, payments_response)
+                .await?;
         }
 
         _ => Err(errors::WebhooksFlowError::PaymentsCoreFailed).into_report()?,
@@ -358,6 +337,38 @@ async fn disputes_incoming_webhook_flow<W: api::OutgoingWebhookType>(
     }
 }
 
+/// Notifies the merchant about a payment status change that was not caused by an incoming webhook
+#[instrument(skip_all)]
+pub async fn trigger_payment_outgoing_webhook<W: api::OutgoingWebhookType>(
+    state: AppState,
+    merchant_account: storage::MerchantAccount,
+    payments_response: api::PaymentsResponse,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let payment_id = payments_response
+        .payment_id
+        .clone()
+        .get_required_value("payment_id")
+        .change_context(errors::WebhooksFlowError::PaymentsCoreFailed)?;
+
+    let event_type: enums::EventType = payments_response
+        .status
+        .foreign_try_into()
+        .into_report()
+        .change_context(errors::WebhooksFlowError::PaymentsCoreFailed)?;
+
+    create_event_and_trigger_outgoing_webhook::<W>(
+        state,
+        merchant_account,
+        event_type,
+        enums::EventClass::Payments,
+        None,
+        payment_id,
+        enums::EventObjectType::PaymentDetails,
+        api::OutgoingWebhookContent::PaymentDetails(payments_response),
+    )
+    .await
+}
+
 #[allow(clippy::too_many_arguments)]
 #[instrument(skip_all)]
 async fn create_event_and_trigger_outgoing_webhook<W: api::OutgoingWebhookType>(
@@ -387,10 +398,6 @@ async fn create_event_and_trigger_outgoing_webhook<W: api::OutgoingWebhookType>(
         .change_context(errors::WebhooksFlowError::WebhookEventCreationFailed)?;
 
     if state.conf.webhooks.outgoing_enabled {
-        let arbiter = actix::Arbiter::try_current()
-            .ok_or(errors::WebhooksFlowError::ForkFlowFailed)
-            .into_report()?;
-
         let outgoing_webhook = api::OutgoingWebhook {
             merchant_id: merchant_account.merchant_id.clone(),
             event_id: event.event_id,
@@ -399,7 +406,7 @@ async fn create_event_and_trigger_outgoing_webhook<W: api::OutgoingWebhookType>(
             timestamp: event.created_at,
         };
 
-        arbiter.spawn(async move {
+        let webhook_delivery = async move {
             let result =
                 trigger_webhook_to_merchant::<W>(merchant_account, outgoing_webhook, state.store)
                     .await;
@@ -407,7 +414,17 @@ async fn create_event_and_trigger_outgoing_webhook<W: api::OutgoingWebhookType>(
             if let Err(e) = result {
                 logger::error!(?e);
             }
-        });
+        };
+
+        match actix::Arbiter::try_current() {
+            Some(arbiter) => {
+                arbiter.spawn(webhook_delivery);
+            }
+            // Events raised outside the actix runtime (e.g. by the scheduler)
+            None => {
+                tokio::spawn(webhook_delivery);
+            }
+        }
     }
 
     Ok(())
@@ -481,13 +498,10 @@ pub async fn webhooks_core<W: api::OutgoingWebhookType>(
     connector_name: &str,
     body: actix_web::web::Bytes,
 ) -> RouterResponse<serde_json::Value> {
-    let connector = api::ConnectorData::get_connector_by_name(
-        &state.conf.connectors,
-        connector_name,
-        api::GetToken::Connector,
-    )
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("Failed construction of ConnectorData")?;
+    let connector =
+        api::ConnectorData::get_connector_by_name(connector_name, api::GetToken::Connector)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed construction of ConnectorData")?;
 
     let connector = connector.connector;
     let mut request_details = api::IncomingWebhookRequestDetails {
@@ -3,25 +3,31 @@ pub mod api_keys;
 pub mod cache;
 pub mod cards_info;
 pub mod configs;
+pub mod connector_audit_log;
 pub mod connector_response;
 pub mod customers;
 pub mod dispute;
 pub mod ephemeral_key;
 pub mod events;
 pub mod file;
+pub mod health_check;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
 pub mod merchant_connector_account;
+pub mod merchant_key_store;
 pub mod payment_attempt;
 pub mod payment_intent;
+pub mod payment_link;
 pub mod payment_method;
+pub mod pii_encryption;
 pub mod process_tracker;
 pub mod queue;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod simulator;
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use futures::lock::Mutex;
 
@@ -41,13 +47,16 @@ pub trait StorageInterface:
     + dyn_clone::DynClone
     + address::AddressInterface
     + api_keys::ApiKeyInterface
+    + cache::CacheInterface
     + configs::ConfigInterface
+    + connector_audit_log::ConnectorAuditLogInterface
     + connector_response::ConnectorResponseInterface
     + customers::CustomerInterface
     + dispute::DisputeInterface
     + ephemeral_key::EphemeralKeyInterface
     + events::EventInterface
     + file::FileMetadataInterface
+    + health_check::HealthCheckInterface
     + locker_mock_up::LockerMockUpInterface
     + mandate::MandateInterface
     + merchant_account::MerchantAccountInterface
@@ -55,12 +64,14 @@ pub trait StorageInterface:
     + merchant_connector_account::MerchantConnectorAccountInterface
     + payment_attempt::PaymentAttemptInterface
     + payment_intent::PaymentIntentInterface
+    + payment_link::PaymentLinkInterface
     + payment_method::PaymentMethodInterface
     + process_tracker::ProcessTrackerInterface
     + queue::QueueInterface
     + refund::RefundInterface
     + reverse_lookup::ReverseLookupInterface
     + cards_info::CardsInfoInterface
+    + simulator::SimulatorInterface
     + 'static
 {
     async fn close(&mut self) {}
@@ -83,10 +94,13 @@ pub struct MockDb {
     merchant_connector_accounts: Arc<Mutex<Vec<storage::MerchantConnectorAccount>>>,
     payment_attempts: Arc<Mutex<Vec<storage::PaymentAttempt>>>,
     payment_intents: Arc<Mutex<Vec<storage::PaymentIntent>>>,
+    payment_links: Arc<Mutex<Vec<storage::PaymentLink>>>,
     customers: Arc<Mutex<Vec<storage::Customer>>>,
     refunds: Arc<Mutex<Vec<storage::Refund>>>,
     processes: Arc<Mutex<Vec<storage::ProcessTracker>>>,
     connector_response: Arc<Mutex<Vec<storage::ConnectorResponse>>>,
+    connector_audit_logs: Arc<Mutex<Vec<storage::ConnectorAuditLog>>>,
+    simulator_objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
     redis: Arc<redis_interface::RedisConnectionPool>,
 }
 
@@ -97,10 +111,13 @@ impl MockDb {
             merchant_connector_accounts: Default::default(),
             payment_attempts: Default::default(),
             payment_intents: Default::default(),
+            payment_links: Default::default(),
             customers: Default::default(),
             refunds: Default::default(),
             processes: Default::default(),
             connector_response: Default::default(),
+            connector_audit_logs: Default::default(),
+            simulator_objects: Default::default(),
             redis: Arc::new(crate::connection::redis_connection(redis).await),
         }
     }
@@ -1,10 +1,8 @@
-use error_stack::IntoReport;
-
-use super::{MockDb, Store};
+use super::MockDb;
 use crate::{
-    connection,
     core::errors::{self, CustomResult},
-    types::storage,
+    services::Store,
+    types::storage::{self, enums},
 };
 
 #[async_trait::async_trait]
@@ -13,16 +11,19 @@ pub trait AddressInterface {
         &self,
         address_id: String,
         address: storage::AddressUpdate,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Address, errors::StorageError>;
 
     async fn insert_address(
         &self,
         address: storage::AddressNew,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Address, errors::StorageError>;
 
     async fn find_address(
         &self,
         address_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Address, errors::StorageError>;
 
     async fn update_address_by_merchant_id_customer_id(
@@ -30,62 +31,397 @@ pub trait AddressInterface {
         customer_id: &str,
         merchant_id: &str,
         address: storage::AddressUpdate,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::Address>, errors::StorageError>;
 }
 
-#[async_trait::async_trait]
-impl AddressInterface for Store {
-    async fn find_address(
+impl Store {
+    /// Encrypts an update to an address with the data key of the merchant owning the address, which
+    /// is looked up as addresses are updated by their ID alone.
+    async fn encrypt_address_update(
         &self,
         address_id: &str,
-    ) -> CustomResult<storage::Address, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        storage::Address::find_by_address_id(&conn, address_id)
-            .await
-            .map_err(Into::into)
-            .into_report()
+        address: storage::AddressUpdate,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<storage::AddressUpdate, errors::StorageError> {
+        if !self.pii_encryption.enabled {
+            return Ok(address);
+        }
+
+        let merchant_id = self
+            .find_address(address_id, storage_scheme)
+            .await?
+            .merchant_id;
+        self.encrypt_pii(&merchant_id, address).await
     }
+}
 
-    async fn update_address(
-        &self,
-        address_id: String,
-        address: storage::AddressUpdate,
-    ) -> CustomResult<storage::Address, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::Address::update_by_address_id(&conn, address_id, address)
+#[cfg(not(feature = "kv_store"))]
+mod storage {
+    use error_stack::IntoReport;
+
+    use super::AddressInterface;
+    use crate::{
+        connection,
+        core::errors::{self, CustomResult},
+        services::Store,
+        types::storage::{self, enums},
+    };
+
+    #[async_trait::async_trait]
+    impl AddressInterface for Store {
+        async fn find_address(
+            &self,
+            address_id: &str,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Address, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            let address = storage::Address::find_by_address_id(&conn, address_id)
+                .await
+                .map_err(Into::into)
+                .into_report()?;
+            self.decrypt_pii(&address.merchant_id.clone(), address)
+                .await
+        }
+
+        async fn update_address(
+            &self,
+            address_id: String,
+            address: storage::AddressUpdate,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Address, errors::StorageError> {
+            let address = self
+                .encrypt_address_update(&address_id, address, storage_scheme)
+                .await?;
+            let conn = connection::pg_connection_write(self).await?;
+            let updated_address =
+                storage::Address::update_by_address_id(&conn, address_id, address)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()?;
+            self.decrypt_pii(&updated_address.merchant_id.clone(), updated_address)
+                .await
+        }
+
+        async fn insert_address(
+            &self,
+            address: storage::AddressNew,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Address, errors::StorageError> {
+            let merchant_id = address.merchant_id.clone();
+            let address = self.encrypt_pii(&merchant_id, address).await?;
+            let conn = connection::pg_connection_write(self).await?;
+            let created_address = address
+                .insert(&conn)
+                .await
+                .map_err(Into::into)
+                .into_report()?;
+            self.decrypt_pii(&merchant_id, created_address).await
+        }
+
+        async fn update_address_by_merchant_id_customer_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            address: storage::AddressUpdate,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage::Address>, errors::StorageError> {
+            let address = self.encrypt_pii(merchant_id, address).await?;
+            let conn = connection::pg_connection_write(self).await?;
+            let updated_addresses = storage::Address::update_by_merchant_id_customer_id(
+                &conn,
+                customer_id,
+                merchant_id,
+                address,
+            )
             .await
             .map_err(Into::into)
-            .into_report()
+            .into_report()?;
+            self.decrypt_pii(merchant_id, updated_addresses).await
+        }
     }
+}
 
-    async fn insert_address(
-        &self,
-        address: storage::AddressNew,
+#[cfg(feature = "kv_store")]
+mod storage {
+    use common_utils::date_time;
+    use error_stack::{IntoReport, ResultExt};
+    use redis_interface::HsetnxReply;
+
+    use super::AddressInterface;
+    use crate::{
+        connection,
+        core::errors::{self, CustomResult},
+        services::Store,
+        types::storage::{self, enums, kv},
+        utils::{self, db_utils, storage_partitioning::PartitionKey},
+    };
+
+    const ADDRESS_FIELD: &str = "address";
+
+    fn get_address_key(address_id: &str) -> String {
+        format!("add_{address_id}")
+    }
+
+    /// Finds an address without decrypting its PII, as it is stored in Redis and the database.
+    async fn find_encrypted_address(
+        store: &Store,
+        address_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Address, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        address
-            .insert(&conn)
-            .await
-            .map_err(Into::into)
-            .into_report()
+        let database_call = || async {
+            let conn = connection::pg_connection_read(store).await?;
+            storage::Address::find_by_address_id(&conn, address_id)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        };
+        match storage_scheme {
+            enums::MerchantStorageScheme::PostgresOnly => database_call().await,
+            enums::MerchantStorageScheme::RedisKv => {
+                db_utils::try_redis_get_else_try_database_get(
+                    store
+                        .redis_conn()
+                        .map_err(Into::<errors::StorageError>::into)?
+                        .get_hash_field_and_deserialize(
+                            &get_address_key(address_id),
+                            ADDRESS_FIELD,
+                            "Address",
+                        ),
+                    database_call,
+                )
+                .await
+            }
+        }
     }
 
-    async fn update_address_by_merchant_id_customer_id(
-        &self,
-        customer_id: &str,
-        merchant_id: &str,
-        address: storage::AddressUpdate,
-    ) -> CustomResult<Vec<storage::Address>, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::Address::update_by_merchant_id_customer_id(
-            &conn,
-            customer_id,
-            merchant_id,
-            address,
-        )
-        .await
-        .map_err(Into::into)
-        .into_report()
+    #[async_trait::async_trait]
+    impl AddressInterface for Store {
+        async fn find_address(
+            &self,
+            address_id: &str,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Address, errors::StorageError> {
+            let address = find_encrypted_address(self, address_id, storage_scheme).await?;
+            self.decrypt_pii(&address.merchant_id.clone(), address)
+                .await
+        }
+
+        async fn update_address(
+            &self,
+            address_id: String,
+            address: storage::AddressUpdate,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Address, errors::StorageError> {
+            let updated_address = match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let address = self
+                        .encrypt_address_update(&address_id, address, storage_scheme)
+                        .await?;
+                    let conn = connection::pg_connection_write(self).await?;
+                    storage::Address::update_by_address_id(&conn, address_id, address)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+                enums::MerchantStorageScheme::RedisKv => {
+                    let this = find_encrypted_address(self, &address_id, storage_scheme).await?;
+                    let address = self.encrypt_pii(&this.merchant_id, address).await?;
+                    let updated_address = address.clone().apply_changeset(this.clone());
+                    let redis_value = utils::Encode::<storage::Address>::encode_to_string_of_json(
+                        &updated_address,
+                    )
+                    .change_context(errors::StorageError::SerializationFailed)?;
+
+                    let updated_address = self
+                        .redis_conn()
+                        .map_err(Into::<errors::StorageError>::into)?
+                        .set_hash_fields(
+                            &get_address_key(&address_id),
+                            (ADDRESS_FIELD, &redis_value),
+                        )
+                        .await
+                        .map(|_| updated_address)
+                        .change_context(errors::StorageError::KVError)?;
+
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Update {
+                            updatable: kv::Updateable::AddressUpdate(Box::new(
+                                kv::AddressUpdateMems {
+                                    orig: this,
+                                    update_data: address,
+                                },
+                            )),
+                        },
+                    };
+                    self.push_to_drainer_stream::<storage::Address>(
+                        redis_entry,
+                        PartitionKey::MerchantIdCustomerId {
+                            merchant_id: &updated_address.merchant_id,
+                            customer_id: &updated_address.customer_id,
+                        },
+                    )
+                    .await?;
+                    Ok(updated_address)
+                }
+            }?;
+            self.decrypt_pii(&updated_address.merchant_id.clone(), updated_address)
+                .await
+        }
+
+        async fn insert_address(
+            &self,
+            address: storage::AddressNew,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Address, errors::StorageError> {
+            let merchant_id = address.merchant_id.clone();
+            let address = self.encrypt_pii(&merchant_id, address).await?;
+            let created_address = match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    address
+                        .insert(&conn)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+                enums::MerchantStorageScheme::RedisKv => {
+                    let key = get_address_key(&address.address_id);
+                    let created_address = storage::Address {
+                        id: Default::default(),
+                        address_id: address.address_id.clone(),
+                        city: address.city.clone(),
+                        country: address.country,
+                        line1: address.line1.clone(),
+                        line2: address.line2.clone(),
+                        line3: address.line3.clone(),
+                        state: address.state.clone(),
+                        zip: address.zip.clone(),
+                        first_name: address.first_name.clone(),
+                        last_name: address.last_name.clone(),
+                        phone_number: address.phone_number.clone(),
+                        country_code: address.country_code.clone(),
+                        created_at: date_time::now(),
+                        modified_at: date_time::now(),
+                        customer_id: address.customer_id.clone(),
+                        merchant_id: address.merchant_id.clone(),
+                    };
+
+                    match self
+                        .redis_conn()
+                        .map_err(Into::<errors::StorageError>::into)?
+                        .serialize_and_set_hash_field_if_not_exist(
+                            &key,
+                            ADDRESS_FIELD,
+                            &created_address,
+                        )
+                        .await
+                    {
+                        Ok(HsetnxReply::KeyNotSet) => Err(errors::StorageError::DuplicateValue {
+                            entity: "address",
+                            key: Some(created_address.address_id),
+                        })
+                        .into_report(),
+                        Ok(HsetnxReply::KeySet) => {
+                            let redis_entry = kv::TypedSql {
+                                op: kv::DBOperation::Insert {
+                                    insertable: kv::Insertable::Address(Box::new(address)),
+                                },
+                            };
+                            self.push_to_drainer_stream::<storage::Address>(
+                                redis_entry,
+                                PartitionKey::MerchantIdCustomerId {
+                                    merchant_id: &created_address.merchant_id,
+                                    customer_id: &created_address.customer_id,
+                                },
+                            )
+                            .await?;
+                            Ok(created_address)
+                        }
+                        Err(error) => Err(error.change_context(errors::StorageError::KVError)),
+                    }
+                }
+            }?;
+            self.decrypt_pii(&merchant_id, created_address).await
+        }
+
+        async fn update_address_by_merchant_id_customer_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            address: storage::AddressUpdate,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage::Address>, errors::StorageError> {
+            let address = self.encrypt_pii(merchant_id, address).await?;
+            let updated_addresses = match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    storage::Address::update_by_merchant_id_customer_id(
+                        &conn,
+                        customer_id,
+                        merchant_id,
+                        address,
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()?
+                }
+                enums::MerchantStorageScheme::RedisKv => {
+                    // Addresses are not indexed by customer in Redis, so the addresses of the
+                    // customer are listed from the database and read from Redis one by one
+                    let conn = connection::pg_connection_read(self).await?;
+                    let address_ids = storage::Address::find_by_merchant_id_customer_id(
+                        &conn,
+                        customer_id,
+                        merchant_id,
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()?
+                    .into_iter()
+                    .map(|address| address.address_id);
+
+                    let redis_conn = self
+                        .redis_conn()
+                        .map_err(Into::<errors::StorageError>::into)?;
+                    let mut updated_addresses = Vec::new();
+                    for address_id in address_ids {
+                        let this =
+                            find_encrypted_address(self, &address_id, storage_scheme).await?;
+                        let updated_address = address.clone().apply_changeset(this.clone());
+
+                        let redis_entry = kv::TypedSql {
+                            op: kv::DBOperation::Update {
+                                updatable: kv::Updateable::AddressUpdate(Box::new(
+                                    kv::AddressUpdateMems {
+                                        orig: this,
+                                        update_data: address.clone(),
+                                    },
+                                )),
+                            },
+                        };
+                        self.push_to_drainer_stream::<storage::Address>(
+                            redis_entry,
+                            PartitionKey::MerchantIdCustomerId {
+                                merchant_id,
+                                customer_id,
+                            },
+                        )
+                        .await?;
+
+                        // The update is applied to the database by the drainer, the Redis copy
+                        // is invalidated only once the update has been queued
+                        redis_conn
+                            .delete_key(&get_address_key(&address_id))
+                            .await
+                            .change_context(errors::StorageError::KVError)?;
+                        updated_addresses.push(updated_address);
+                    }
+                    updated_addresses
+                }
+            };
+            self.decrypt_pii(merchant_id, updated_addresses).await
+        }
     }
 }
 
@@ -94,6 +430,7 @@ impl AddressInterface for MockDb {
     async fn find_address(
         &self,
         _address_id: &str,
+        _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Address, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
@@ -103,6 +440,7 @@ impl AddressInterface for MockDb {
         &self,
         _address_id: String,
         _address: storage::AddressUpdate,
+        _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Address, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
@@ -111,6 +449,7 @@ impl AddressInterface for MockDb {
     async fn insert_address(
         &self,
         _address: storage::AddressNew,
+        _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Address, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
@@ -121,6 +460,7 @@ impl AddressInterface for MockDb {
         _customer_id: &str,
         _merchant_id: &str,
         _address: storage::AddressUpdate,
+        _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::Address>, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
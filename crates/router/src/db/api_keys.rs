@@ -1,6 +1,8 @@
 use error_stack::IntoReport;
 
 use super::{MockDb, Store};
+#[cfg(feature = "accounts_cache")]
+use crate::cache::{CacheKind, API_KEY_CACHE};
 use crate::{
     connection,
     core::errors::{self, CustomResult},
@@ -44,6 +46,10 @@ pub trait ApiKeyInterface {
         limit: Option<i64>,
         offset: Option<i64>,
     ) -> CustomResult<Vec<storage::ApiKey>, errors::StorageError>;
+
+    async fn count_api_keys_by_hash_key_id(
+        &self,
+    ) -> CustomResult<Vec<(String, i64)>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -67,10 +73,21 @@ impl ApiKeyInterface for Store {
         api_key: storage::ApiKeyUpdate,
     ) -> CustomResult<storage::ApiKey, errors::StorageError> {
         let conn = connection::pg_connection_write(self).await?;
-        storage::ApiKey::update_by_merchant_id_key_id(&conn, merchant_id, key_id, api_key)
-            .await
-            .map_err(Into::into)
-            .into_report()
+        let api_key =
+            storage::ApiKey::update_by_merchant_id_key_id(&conn, merchant_id, key_id, api_key)
+                .await
+                .map_err(Into::<errors::StorageError>::into)
+                .into_report()?;
+
+        #[cfg(feature = "accounts_cache")]
+        super::cache::redact(
+            self,
+            CacheKind::ApiKey,
+            &api_key.hashed_api_key.clone().into_inner(),
+        )
+        .await?;
+
+        Ok(api_key)
     }
 
     async fn revoke_api_key(
@@ -78,11 +95,28 @@ impl ApiKeyInterface for Store {
         merchant_id: &str,
         key_id: &str,
     ) -> CustomResult<bool, errors::StorageError> {
+        #[cfg(feature = "accounts_cache")]
+        let api_key = self
+            .find_api_key_by_merchant_id_key_id_optional(merchant_id, key_id)
+            .await?;
+
         let conn = connection::pg_connection_write(self).await?;
-        storage::ApiKey::revoke_by_merchant_id_key_id(&conn, merchant_id, key_id)
+        let revoked = storage::ApiKey::revoke_by_merchant_id_key_id(&conn, merchant_id, key_id)
             .await
-            .map_err(Into::into)
-            .into_report()
+            .map_err(Into::<errors::StorageError>::into)
+            .into_report()?;
+
+        #[cfg(feature = "accounts_cache")]
+        if let Some(api_key) = api_key {
+            super::cache::redact(
+                self,
+                CacheKind::ApiKey,
+                &api_key.hashed_api_key.into_inner(),
+            )
+            .await?;
+        }
+
+        Ok(revoked)
     }
 
     async fn find_api_key_by_merchant_id_key_id_optional(
@@ -101,11 +135,34 @@ impl ApiKeyInterface for Store {
         &self,
         hashed_api_key: storage::HashedApiKey,
     ) -> CustomResult<Option<storage::ApiKey>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        storage::ApiKey::find_optional_by_hashed_api_key(&conn, hashed_api_key)
-            .await
-            .map_err(Into::into)
-            .into_report()
+        let _key = hashed_api_key.clone().into_inner();
+        let find_call = || async {
+            let conn = connection::pg_connection_read(self).await?;
+            storage::ApiKey::find_optional_by_hashed_api_key(&conn, hashed_api_key)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        };
+
+        #[cfg(not(feature = "accounts_cache"))]
+        {
+            find_call().await
+        }
+
+        #[cfg(feature = "accounts_cache")]
+        {
+            if let Some(api_key) = API_KEY_CACHE.get_val::<storage::ApiKey>(&_key) {
+                return Ok(Some(api_key));
+            }
+
+            // Keys which are not found are not cached, so that keys can be used as soon as they
+            // are created
+            let api_key = find_call().await?;
+            if let Some(api_key) = &api_key {
+                API_KEY_CACHE.push(_key, api_key.clone()).await;
+            }
+            Ok(api_key)
+        }
     }
 
     async fn list_api_keys_by_merchant_id(
@@ -120,6 +177,16 @@ impl ApiKeyInterface for Store {
             .map_err(Into::into)
             .into_report()
     }
+
+    async fn count_api_keys_by_hash_key_id(
+        &self,
+    ) -> CustomResult<Vec<(String, i64)>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ApiKey::count_by_hash_key_id(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -177,4 +244,11 @@ impl ApiKeyInterface for MockDb {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
     }
+
+    async fn count_api_keys_by_hash_key_id(
+        &self,
+    ) -> CustomResult<Vec<(String, i64)>, errors::StorageError> {
+        // [#172]: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
 }
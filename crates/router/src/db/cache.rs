@@ -1,14 +1,47 @@
-use common_utils::ext_traits::AsyncExt;
+use common_utils::{consts, ext_traits::Encode};
 use error_stack::ResultExt;
 
-use super::Store;
+use super::{MockDb, Store};
 use crate::{
-    cache::{self, Cacheable},
-    consts,
+    cache::{self, CacheInvalidation, CacheKind, Cacheable},
     core::errors::{self, CustomResult},
     services::PubSubInterface,
 };
 
+#[async_trait::async_trait]
+pub trait CacheInterface {
+    /// Invalidates `key` in Redis and in the in-memory caches of every instance, returning the
+    /// number of instances which received the invalidation.
+    async fn invalidate_cache(
+        &self,
+        kind: CacheKind,
+        key: &str,
+    ) -> CustomResult<usize, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl CacheInterface for Store {
+    async fn invalidate_cache(
+        &self,
+        kind: CacheKind,
+        key: &str,
+    ) -> CustomResult<usize, errors::StorageError> {
+        redact(self, kind, key).await
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheInterface for MockDb {
+    async fn invalidate_cache(
+        &self,
+        _kind: CacheKind,
+        _key: &str,
+    ) -> CustomResult<usize, errors::StorageError> {
+        // [#172]: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
+
 pub async fn get_or_populate_redis<T, F, Fut>(
     store: &Store,
     key: &str,
@@ -63,42 +96,45 @@ where
     }
 }
 
-pub async fn redact_cache<T, F, Fut>(
+/// Runs `fun` and then invalidates `key` in Redis and, through the invalidation channel, in the
+/// in-memory cache of every instance.
+pub async fn publish_and_redact<T, F, Fut>(
     store: &Store,
+    kind: CacheKind,
     key: &str,
     fun: F,
-    in_memory: Option<&cache::Cache>,
 ) -> CustomResult<T, errors::StorageError>
 where
     F: FnOnce() -> Fut + Send,
     Fut: futures::Future<Output = CustomResult<T, errors::StorageError>> + Send,
 {
     let data = fun().await?;
-    in_memory.async_map(|cache| cache.invalidate(key)).await;
-    store
-        .redis_conn()
-        .map_err(Into::<errors::StorageError>::into)?
-        .delete_key(key)
-        .await
-        .change_context(errors::StorageError::KVError)?;
+    redact(store, kind, key).await?;
     Ok(data)
 }
 
-pub async fn publish_and_redact<T, F, Fut>(
+/// Invalidates `key` in Redis and in the in-memory cache of every instance, returning the number
+/// of instances which received the invalidation.
+pub async fn redact(
     store: &Store,
+    kind: CacheKind,
     key: &str,
-    fun: F,
-) -> CustomResult<T, errors::StorageError>
-where
-    F: FnOnce() -> Fut + Send,
-    Fut: futures::Future<Output = CustomResult<T, errors::StorageError>> + Send,
-{
-    let data = fun().await?;
-    store
+) -> CustomResult<usize, errors::StorageError> {
+    let redis_conn = store
         .redis_conn()
-        .map_err(Into::<errors::StorageError>::into)?
-        .publish(consts::PUB_SUB_CHANNEL, key)
+        .map_err(Into::<errors::StorageError>::into)?;
+    redis_conn
+        .delete_key(key)
         .await
         .change_context(errors::StorageError::KVError)?;
-    Ok(data)
+
+    let message = Encode::<CacheInvalidation>::encode_to_string_of_json(&CacheInvalidation {
+        kind,
+        key: key.to_string(),
+    })
+    .change_context(errors::StorageError::SerializationFailed)?;
+    redis_conn
+        .publish(consts::PUB_SUB_CHANNEL, &message)
+        .await
+        .change_context(errors::StorageError::KVError)
 }
@@ -2,10 +2,9 @@ use error_stack::IntoReport;
 
 use super::{cache, MockDb, Store};
 use crate::{
-    cache::CONFIG_CACHE,
-    connection, consts,
+    cache::{CacheKind, CONFIG_CACHE},
+    connection,
     core::errors::{self, CustomResult},
-    services::PubSubInterface,
     types::storage,
 };
 
@@ -78,7 +77,10 @@ impl ConfigInterface for Store {
         key: &str,
         config_update: storage::ConfigUpdate,
     ) -> CustomResult<storage::Config, errors::StorageError> {
-        cache::publish_and_redact(self, key, || self.update_config_by_key(key, config_update)).await
+        cache::publish_and_redact(self, CacheKind::Config, key, || {
+            self.update_config_by_key(key, config_update)
+        })
+        .await
     }
 
     async fn find_config_by_key_cached(
@@ -96,11 +98,7 @@ impl ConfigInterface for Store {
             .map_err(Into::into)
             .into_report()?;
 
-        self.redis_conn()
-            .map_err(Into::<errors::StorageError>::into)?
-            .publish(consts::PUB_SUB_CHANNEL, key)
-            .await
-            .map_err(Into::<errors::StorageError>::into)?;
+        cache::redact(self, CacheKind::Config, key).await?;
 
         Ok(deleted)
     }
@@ -0,0 +1,108 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait ConnectorAuditLogInterface {
+    async fn insert_connector_audit_log(
+        &self,
+        audit_log: storage::ConnectorAuditLogNew,
+    ) -> CustomResult<storage::ConnectorAuditLog, errors::StorageError>;
+
+    async fn find_connector_audit_logs_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+        limit: i64,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ConnectorAuditLog>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl ConnectorAuditLogInterface for Store {
+    async fn insert_connector_audit_log(
+        &self,
+        audit_log: storage::ConnectorAuditLogNew,
+    ) -> CustomResult<storage::ConnectorAuditLog, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        audit_log
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_connector_audit_logs_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+        limit: i64,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ConnectorAuditLog>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ConnectorAuditLog::find_by_merchant_id_payment_id(
+            &conn,
+            merchant_id,
+            payment_id,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl ConnectorAuditLogInterface for MockDb {
+    async fn insert_connector_audit_log(
+        &self,
+        audit_log: storage::ConnectorAuditLogNew,
+    ) -> CustomResult<storage::ConnectorAuditLog, errors::StorageError> {
+        let mut audit_logs = self.connector_audit_logs.lock().await;
+        let audit_log = storage::ConnectorAuditLog {
+            #[allow(clippy::as_conversions)]
+            id: audit_logs.len() as i32,
+            merchant_id: audit_log.merchant_id,
+            payment_id: audit_log.payment_id,
+            attempt_id: audit_log.attempt_id,
+            connector: audit_log.connector,
+            flow: audit_log.flow,
+            request_method: audit_log.request_method,
+            request_url: audit_log.request_url,
+            request_body: audit_log.request_body,
+            status_code: audit_log.status_code,
+            response_body: audit_log.response_body,
+            error_message: audit_log.error_message,
+            latency_ms: audit_log.latency_ms,
+            created_at: audit_log.created_at,
+        };
+        audit_logs.push(audit_log.clone());
+        Ok(audit_log)
+    }
+
+    async fn find_connector_audit_logs_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+        limit: i64,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ConnectorAuditLog>, errors::StorageError> {
+        let audit_logs = self.connector_audit_logs.lock().await;
+
+        Ok(audit_logs
+            .iter()
+            .filter(|audit_log| {
+                audit_log.merchant_id == merchant_id && audit_log.payment_id == payment_id
+            })
+            .skip(usize::try_from(offset.unwrap_or(0)).unwrap_or(0))
+            .take(usize::try_from(limit).unwrap_or(0))
+            .cloned()
+            .collect())
+    }
+}
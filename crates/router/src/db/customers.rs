@@ -1,13 +1,10 @@
-use error_stack::IntoReport;
-
-use super::{MockDb, Store};
+use super::MockDb;
 use crate::{
-    connection,
     core::{
         customers::REDACTED,
         errors::{self, CustomResult},
     },
-    types::storage,
+    types::storage::{self, enums},
 };
 
 #[async_trait::async_trait]
@@ -22,6 +19,7 @@ pub trait CustomerInterface {
         &self,
         customer_id: &str,
         merchant_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Option<storage::Customer>, errors::StorageError>;
 
     async fn update_customer_by_customer_id_merchant_id(
@@ -29,103 +27,427 @@ pub trait CustomerInterface {
         customer_id: String,
         merchant_id: String,
         customer: storage::CustomerUpdate,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Customer, errors::StorageError>;
 
     async fn find_customer_by_customer_id_merchant_id(
         &self,
         customer_id: &str,
         merchant_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Customer, errors::StorageError>;
 
     async fn insert_customer(
         &self,
         customer_data: storage::CustomerNew,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Customer, errors::StorageError>;
 }
 
-#[async_trait::async_trait]
-impl CustomerInterface for Store {
-    async fn find_customer_optional_by_customer_id_merchant_id(
-        &self,
-        customer_id: &str,
-        merchant_id: &str,
-    ) -> CustomResult<Option<storage::Customer>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        let maybe_customer = storage::Customer::find_optional_by_customer_id_merchant_id(
-            &conn,
-            customer_id,
-            merchant_id,
-        )
-        .await
-        .map_err(Into::into)
-        .into_report()?;
-        maybe_customer.map_or(Ok(None), |customer| {
-            // in the future, once #![feature(is_some_and)] is stable, we can make this more concise:
-            // `if customer.name.is_some_and(|ref name| name == REDACTED) ...`
-            match customer.name {
-                Some(ref name) if name == REDACTED => Err(errors::StorageError::CustomerRedacted)?,
-                _ => Ok(Some(customer)),
-            }
-        })
+fn ensure_not_redacted(
+    customer: storage::Customer,
+) -> CustomResult<storage::Customer, errors::StorageError> {
+    // in the future, once #![feature(is_some_and)] is stable, we can make this more concise:
+    // `if customer.name.is_some_and(|ref name| name == REDACTED) ...`
+    match customer.name {
+        Some(ref name) if name == REDACTED => Err(errors::StorageError::CustomerRedacted)?,
+        _ => Ok(customer),
     }
+}
 
-    async fn update_customer_by_customer_id_merchant_id(
-        &self,
-        customer_id: String,
-        merchant_id: String,
-        customer: storage::CustomerUpdate,
-    ) -> CustomResult<storage::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::Customer::update_by_customer_id_merchant_id(
-            &conn,
-            customer_id,
-            merchant_id,
-            customer,
-        )
-        .await
-        .map_err(Into::into)
-        .into_report()
-    }
+#[cfg(not(feature = "kv_store"))]
+mod storage {
+    use error_stack::IntoReport;
 
-    async fn find_customer_by_customer_id_merchant_id(
-        &self,
-        customer_id: &str,
-        merchant_id: &str,
-    ) -> CustomResult<storage::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        let customer =
-            storage::Customer::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+    use super::{ensure_not_redacted, CustomerInterface};
+    use crate::{
+        connection,
+        core::errors::{self, CustomResult},
+        services::Store,
+        types::storage::{self, enums},
+    };
+
+    #[async_trait::async_trait]
+    impl CustomerInterface for Store {
+        async fn find_customer_optional_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Option<storage::Customer>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            let maybe_customer = storage::Customer::find_optional_by_customer_id_merchant_id(
+                &conn,
+                customer_id,
+                merchant_id,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()?;
+            self.decrypt_pii(merchant_id, maybe_customer)
+                .await?
+                .map(ensure_not_redacted)
+                .transpose()
+        }
+
+        async fn update_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: String,
+            merchant_id: String,
+            customer: storage::CustomerUpdate,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Customer, errors::StorageError> {
+            let customer = self.encrypt_pii(&merchant_id, customer).await?;
+            let conn = connection::pg_connection_write(self).await?;
+            let updated_customer = storage::Customer::update_by_customer_id_merchant_id(
+                &conn,
+                customer_id,
+                merchant_id.clone(),
+                customer,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()?;
+            self.decrypt_pii(&merchant_id, updated_customer).await
+        }
+
+        async fn find_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Customer, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            let customer =
+                storage::Customer::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()?;
+            ensure_not_redacted(self.decrypt_pii(merchant_id, customer).await?)
+        }
+
+        async fn insert_customer(
+            &self,
+            customer_data: storage::CustomerNew,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Customer, errors::StorageError> {
+            let merchant_id = customer_data.merchant_id.clone();
+            let customer_data = self.encrypt_pii(&merchant_id, customer_data).await?;
+            let conn = connection::pg_connection_write(self).await?;
+            let customer = customer_data
+                .insert(&conn)
                 .await
                 .map_err(Into::into)
                 .into_report()?;
-        match customer.name {
-            Some(ref name) if name == REDACTED => Err(errors::StorageError::CustomerRedacted)?,
-            _ => Ok(customer),
+            self.decrypt_pii(&merchant_id, customer).await
+        }
+
+        async fn delete_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+        ) -> CustomResult<bool, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            storage::Customer::delete_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+                .await
+                .map_err(Into::into)
+                .into_report()
         }
     }
+}
 
-    async fn insert_customer(
-        &self,
-        customer_data: storage::CustomerNew,
-    ) -> CustomResult<storage::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        customer_data
-            .insert(&conn)
-            .await
-            .map_err(Into::into)
-            .into_report()
+#[cfg(feature = "kv_store")]
+mod storage {
+    use error_stack::{IntoReport, ResultExt};
+    use redis_interface::HsetnxReply;
+    use storage_models::errors::DatabaseError;
+
+    use super::{ensure_not_redacted, CustomerInterface};
+    use crate::{
+        connection,
+        core::errors::{self, CustomResult},
+        services::Store,
+        types::storage::{self, enums, kv},
+        utils::{self, db_utils, storage_partitioning::PartitionKey},
+    };
+
+    const CUSTOMER_FIELD: &str = "cust";
+
+    fn get_customer_key(merchant_id: &str, customer_id: &str) -> String {
+        format!("mid_{merchant_id}_cust_{customer_id}")
     }
 
-    async fn delete_customer_by_customer_id_merchant_id(
-        &self,
+    async fn find_customer_optional_ignoring_redaction(
+        store: &Store,
         customer_id: &str,
         merchant_id: &str,
-    ) -> CustomResult<bool, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::Customer::delete_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Option<storage::Customer>, errors::StorageError> {
+        let database_call = || async {
+            let conn = connection::pg_connection_read(store).await?;
+            storage::Customer::find_optional_by_customer_id_merchant_id(
+                &conn,
+                customer_id,
+                merchant_id,
+            )
             .await
             .map_err(Into::into)
             .into_report()
+        };
+        match storage_scheme {
+            enums::MerchantStorageScheme::PostgresOnly => database_call().await,
+            enums::MerchantStorageScheme::RedisKv => {
+                let key = get_customer_key(merchant_id, customer_id);
+                let redis_conn = store
+                    .redis_conn()
+                    .map_err(Into::<errors::StorageError>::into)?;
+                db_utils::try_redis_get_else_try_database_get(
+                    async {
+                        redis_conn
+                            .get_hash_field_and_deserialize(&key, CUSTOMER_FIELD, "Customer")
+                            .await
+                            .map(Some)
+                    },
+                    database_call,
+                )
+                .await
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl CustomerInterface for Store {
+        async fn find_customer_optional_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Option<storage::Customer>, errors::StorageError> {
+            let maybe_customer = find_customer_optional_ignoring_redaction(
+                self,
+                customer_id,
+                merchant_id,
+                storage_scheme,
+            )
+            .await?;
+            self.decrypt_pii(merchant_id, maybe_customer)
+                .await?
+                .map(ensure_not_redacted)
+                .transpose()
+        }
+
+        async fn update_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: String,
+            merchant_id: String,
+            customer: storage::CustomerUpdate,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Customer, errors::StorageError> {
+            let customer = self.encrypt_pii(&merchant_id, customer).await?;
+            let updated_customer = match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    storage::Customer::update_by_customer_id_merchant_id(
+                        &conn,
+                        customer_id,
+                        merchant_id.clone(),
+                        customer,
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+                }
+                enums::MerchantStorageScheme::RedisKv => {
+                    let this = find_customer_optional_ignoring_redaction(
+                        self,
+                        &customer_id,
+                        &merchant_id,
+                        storage_scheme,
+                    )
+                    .await?
+                    .ok_or_else(|| {
+                        errors::StorageError::DatabaseError(DatabaseError::NotFound.into())
+                    })
+                    .into_report()?;
+
+                    let key = get_customer_key(&merchant_id, &customer_id);
+                    let updated_customer = customer.clone().apply_changeset(this.clone());
+                    let redis_value = utils::Encode::<storage::Customer>::encode_to_string_of_json(
+                        &updated_customer,
+                    )
+                    .change_context(errors::StorageError::SerializationFailed)?;
+
+                    let updated_customer = self
+                        .redis_conn()
+                        .map_err(Into::<errors::StorageError>::into)?
+                        .set_hash_fields(&key, (CUSTOMER_FIELD, &redis_value))
+                        .await
+                        .map(|_| updated_customer)
+                        .change_context(errors::StorageError::KVError)?;
+
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Update {
+                            updatable: kv::Updateable::CustomerUpdate(kv::CustomerUpdateMems {
+                                orig: this,
+                                update_data: customer,
+                            }),
+                        },
+                    };
+                    self.push_to_drainer_stream::<storage::Customer>(
+                        redis_entry,
+                        PartitionKey::MerchantIdCustomerId {
+                            merchant_id: &merchant_id,
+                            customer_id: &customer_id,
+                        },
+                    )
+                    .await?;
+                    Ok(updated_customer)
+                }
+            }?;
+            self.decrypt_pii(&merchant_id, updated_customer).await
+        }
+
+        async fn find_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Customer, errors::StorageError> {
+            let database_call = || async {
+                let conn = connection::pg_connection_read(self).await?;
+                storage::Customer::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+            };
+            let customer = match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => database_call().await,
+                enums::MerchantStorageScheme::RedisKv => {
+                    let key = get_customer_key(merchant_id, customer_id);
+                    db_utils::try_redis_get_else_try_database_get(
+                        self.redis_conn()
+                            .map_err(Into::<errors::StorageError>::into)?
+                            .get_hash_field_and_deserialize(&key, CUSTOMER_FIELD, "Customer"),
+                        database_call,
+                    )
+                    .await
+                }
+            }?;
+            ensure_not_redacted(self.decrypt_pii(merchant_id, customer).await?)
+        }
+
+        async fn insert_customer(
+            &self,
+            customer_data: storage::CustomerNew,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage::Customer, errors::StorageError> {
+            let merchant_id = customer_data.merchant_id.clone();
+            let customer_data = self.encrypt_pii(&merchant_id, customer_data).await?;
+            let created_customer = match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    customer_data
+                        .insert(&conn)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+                enums::MerchantStorageScheme::RedisKv => {
+                    // Customers outlive the switch of a merchant to the KV scheme, so the database
+                    // has to be checked for customers which are not in Redis
+                    if find_customer_optional_ignoring_redaction(
+                        self,
+                        &customer_data.customer_id,
+                        &customer_data.merchant_id,
+                        storage_scheme,
+                    )
+                    .await?
+                    .is_some()
+                    {
+                        return Err(errors::StorageError::DuplicateValue {
+                            entity: "customer",
+                            key: Some(customer_data.customer_id),
+                        })
+                        .into_report();
+                    }
+
+                    let key =
+                        get_customer_key(&customer_data.merchant_id, &customer_data.customer_id);
+                    let created_customer = storage::Customer {
+                        id: Default::default(),
+                        customer_id: customer_data.customer_id.clone(),
+                        merchant_id: customer_data.merchant_id.clone(),
+                        name: customer_data.name.clone(),
+                        email: customer_data.email.clone(),
+                        phone: customer_data.phone.clone(),
+                        phone_country_code: customer_data.phone_country_code.clone(),
+                        description: customer_data.description.clone(),
+                        created_at: common_utils::date_time::now(),
+                        metadata: customer_data.metadata.clone(),
+                        modified_at: common_utils::date_time::now(),
+                    };
+
+                    match self
+                        .redis_conn()
+                        .map_err(Into::<errors::StorageError>::into)?
+                        .serialize_and_set_hash_field_if_not_exist(
+                            &key,
+                            CUSTOMER_FIELD,
+                            &created_customer,
+                        )
+                        .await
+                    {
+                        Ok(HsetnxReply::KeyNotSet) => Err(errors::StorageError::DuplicateValue {
+                            entity: "customer",
+                            key: Some(created_customer.customer_id),
+                        })
+                        .into_report(),
+                        Ok(HsetnxReply::KeySet) => {
+                            let redis_entry = kv::TypedSql {
+                                op: kv::DBOperation::Insert {
+                                    insertable: kv::Insertable::Customer(customer_data),
+                                },
+                            };
+                            self.push_to_drainer_stream::<storage::Customer>(
+                                redis_entry,
+                                PartitionKey::MerchantIdCustomerId {
+                                    merchant_id: &created_customer.merchant_id,
+                                    customer_id: &created_customer.customer_id,
+                                },
+                            )
+                            .await?;
+                            Ok(created_customer)
+                        }
+                        Err(error) => Err(error.change_context(errors::StorageError::KVError)),
+                    }
+                }
+            }?;
+            self.decrypt_pii(&merchant_id, created_customer).await
+        }
+
+        async fn delete_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+        ) -> CustomResult<bool, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            let deleted = storage::Customer::delete_by_customer_id_merchant_id(
+                &conn,
+                customer_id,
+                merchant_id,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()?;
+
+            self.redis_conn()
+                .map_err(Into::<errors::StorageError>::into)?
+                .delete_key(&get_customer_key(merchant_id, customer_id))
+                .await
+                .change_context(errors::StorageError::KVError)?;
+
+            Ok(deleted)
+        }
     }
 }
 
@@ -136,6 +458,7 @@ impl CustomerInterface for MockDb {
         &self,
         customer_id: &str,
         merchant_id: &str,
+        _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Option<storage::Customer>, errors::StorageError> {
         let customers = self.customers.lock().await;
 
@@ -152,6 +475,7 @@ impl CustomerInterface for MockDb {
         _customer_id: String,
         _merchant_id: String,
         _customer: storage::CustomerUpdate,
+        _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Customer, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
@@ -161,6 +485,7 @@ impl CustomerInterface for MockDb {
         &self,
         _customer_id: &str,
         _merchant_id: &str,
+        _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Customer, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
@@ -170,6 +495,7 @@ impl CustomerInterface for MockDb {
     async fn insert_customer(
         &self,
         customer_data: storage::CustomerNew,
+        _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::Customer, errors::StorageError> {
         let mut customers = self.customers.lock().await;
         let customer = storage::Customer {
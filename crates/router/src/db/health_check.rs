@@ -0,0 +1,174 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use error_stack::{IntoReport, ResultExt};
+use storage_models::errors as storage_errors;
+
+use super::{MockDb, Store};
+use crate::{
+    connection::PgPool,
+    consts,
+    core::errors::{self, CustomResult},
+};
+
+#[async_trait::async_trait]
+pub trait HealthCheckInterface {
+    async fn health_check_master_db(&self) -> CustomResult<(), errors::StorageError>;
+
+    #[cfg(feature = "olap")]
+    async fn health_check_replica_db(&self) -> CustomResult<(), errors::StorageError>;
+
+    async fn health_check_redis(&self) -> CustomResult<(), errors::StorageError>;
+
+    /// Records that the scheduler process owning `heartbeat_key` is alive at the current time.
+    async fn update_scheduler_heartbeat(
+        &self,
+        heartbeat_key: &str,
+    ) -> CustomResult<(), errors::StorageError>;
+
+    /// Unix timestamp (in seconds) of the last heartbeat recorded against `heartbeat_key`.
+    async fn find_scheduler_heartbeat(
+        &self,
+        heartbeat_key: &str,
+    ) -> CustomResult<Option<i64>, errors::StorageError>;
+
+    /// Number of pending entries in each of the drainer streams, keyed by stream name.
+    #[cfg(feature = "kv_store")]
+    async fn get_drainer_stream_lengths(
+        &self,
+    ) -> CustomResult<Vec<(String, usize)>, errors::StorageError>;
+}
+
+async fn health_check_pool(pool: &PgPool) -> CustomResult<(), errors::StorageError> {
+    let conn = pool
+        .get()
+        .await
+        .into_report()
+        .change_context(errors::StorageError::DatabaseConnectionError)?;
+
+    diesel::sql_query("SELECT 1")
+        .execute_async(&conn)
+        .await
+        .into_report()
+        .change_context(storage_errors::DatabaseError::Others)
+        .attach_printable("Failed to run health check query")
+        .map_err(Into::<errors::StorageError>::into)?;
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl HealthCheckInterface for Store {
+    async fn health_check_master_db(&self) -> CustomResult<(), errors::StorageError> {
+        health_check_pool(&self.master_pool).await
+    }
+
+    #[cfg(feature = "olap")]
+    async fn health_check_replica_db(&self) -> CustomResult<(), errors::StorageError> {
+        health_check_pool(&self.replica_pool).await
+    }
+
+    async fn health_check_redis(&self) -> CustomResult<(), errors::StorageError> {
+        self.redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .ping()
+            .await
+            .map_err(Into::<errors::StorageError>::into)?;
+        Ok(())
+    }
+
+    async fn update_scheduler_heartbeat(
+        &self,
+        heartbeat_key: &str,
+    ) -> CustomResult<(), errors::StorageError> {
+        let now = common_utils::date_time::now_unix_timestamp();
+        self.redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .set_key_with_expiry(heartbeat_key, now, consts::SCHEDULER_HEARTBEAT_TTL)
+            .await
+            .map_err(Into::<errors::StorageError>::into)?;
+        Ok(())
+    }
+
+    async fn find_scheduler_heartbeat(
+        &self,
+        heartbeat_key: &str,
+    ) -> CustomResult<Option<i64>, errors::StorageError> {
+        let heartbeat = self
+            .redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .get_key::<Option<i64>>(heartbeat_key)
+            .await
+            .map_err(Into::<errors::StorageError>::into)?;
+        Ok(heartbeat)
+    }
+
+    #[cfg(feature = "kv_store")]
+    async fn get_drainer_stream_lengths(
+        &self,
+    ) -> CustomResult<Vec<(String, usize)>, errors::StorageError> {
+        let redis_conn = self
+            .redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?;
+        let mut stream_lengths = Vec::with_capacity(self.config.drainer_num_partitions.into());
+        for partition in 0..self.config.drainer_num_partitions {
+            let stream_name = self.get_drainer_stream_name(&format!("shard_{partition}"));
+            let length = redis_conn
+                .stream_get_length(stream_name.as_str())
+                .await
+                .map_err(Into::<errors::StorageError>::into)?;
+            stream_lengths.push((stream_name, length));
+        }
+        Ok(stream_lengths)
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheckInterface for MockDb {
+    async fn health_check_master_db(&self) -> CustomResult<(), errors::StorageError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "olap")]
+    async fn health_check_replica_db(&self) -> CustomResult<(), errors::StorageError> {
+        Ok(())
+    }
+
+    async fn health_check_redis(&self) -> CustomResult<(), errors::StorageError> {
+        self.redis
+            .ping()
+            .await
+            .map_err(Into::<errors::StorageError>::into)?;
+        Ok(())
+    }
+
+    async fn update_scheduler_heartbeat(
+        &self,
+        heartbeat_key: &str,
+    ) -> CustomResult<(), errors::StorageError> {
+        let now = common_utils::date_time::now_unix_timestamp();
+        self.redis
+            .set_key_with_expiry(heartbeat_key, now, consts::SCHEDULER_HEARTBEAT_TTL)
+            .await
+            .map_err(Into::<errors::StorageError>::into)?;
+        Ok(())
+    }
+
+    async fn find_scheduler_heartbeat(
+        &self,
+        heartbeat_key: &str,
+    ) -> CustomResult<Option<i64>, errors::StorageError> {
+        let heartbeat = self
+            .redis
+            .get_key::<Option<i64>>(heartbeat_key)
+            .await
+            .map_err(Into::<errors::StorageError>::into)?;
+        Ok(heartbeat)
+    }
+
+    #[cfg(feature = "kv_store")]
+    async fn get_drainer_stream_lengths(
+        &self,
+    ) -> CustomResult<Vec<(String, usize)>, errors::StorageError> {
+        // [#172]: Implement function for `MockDb`
+        Ok(Vec::new())
+    }
+}
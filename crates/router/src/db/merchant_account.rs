@@ -1,6 +1,8 @@
 use error_stack::IntoReport;
 
 use super::{MockDb, Store};
+#[cfg(feature = "accounts_cache")]
+use crate::cache::{CacheKind, ACCOUNTS_CACHE};
 use crate::{
     connection,
     core::errors::{self, CustomResult},
@@ -75,7 +77,22 @@ impl MerchantAccountInterface for Store {
 
         #[cfg(feature = "accounts_cache")]
         {
-            super::cache::get_or_populate_redis(self, merchant_id, fetch_func).await
+            let merchant_account = super::cache::get_or_populate_in_memory(
+                self,
+                merchant_id,
+                fetch_func,
+                &ACCOUNTS_CACHE,
+            )
+            .await?;
+
+            // Accounts of merchants whose KV is being turned off are read from the database every
+            // time, so that writes stop going through Redis as soon as the drainer switches the
+            // merchant to Postgres
+            if merchant_account.kv_drain_started_at.is_some() {
+                fetch_func().await
+            } else {
+                Ok(merchant_account)
+            }
         }
     }
 
@@ -100,7 +117,8 @@ impl MerchantAccountInterface for Store {
 
         #[cfg(feature = "accounts_cache")]
         {
-            super::cache::redact_cache(self, &_merchant_id, update_func, None).await
+            super::cache::publish_and_redact(self, CacheKind::Accounts, &_merchant_id, update_func)
+                .await
         }
     }
 
@@ -128,7 +146,8 @@ impl MerchantAccountInterface for Store {
 
         #[cfg(feature = "accounts_cache")]
         {
-            super::cache::redact_cache(self, merchant_id, update_func, None).await
+            super::cache::publish_and_redact(self, CacheKind::Accounts, merchant_id, update_func)
+                .await
         }
     }
 
@@ -162,7 +181,8 @@ impl MerchantAccountInterface for Store {
 
         #[cfg(feature = "accounts_cache")]
         {
-            super::cache::redact_cache(self, merchant_id, delete_func, None).await
+            super::cache::publish_and_redact(self, CacheKind::Accounts, merchant_id, delete_func)
+                .await
         }
     }
 }
@@ -201,6 +221,8 @@ impl MerchantAccountInterface for MockDb {
             primary_business_details: merchant_account.primary_business_details,
             created_at: common_utils::date_time::now(),
             modified_at: common_utils::date_time::now(),
+            intent_fulfillment_time: merchant_account.intent_fulfillment_time,
+            kv_drain_started_at: None,
         };
         accounts.push(account.clone());
         Ok(account)
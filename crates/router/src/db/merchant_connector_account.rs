@@ -3,6 +3,8 @@ use error_stack::{IntoReport, ResultExt};
 use masking::ExposeInterface;
 
 use super::{MockDb, Store};
+#[cfg(feature = "accounts_cache")]
+use crate::cache::{CacheKind, ACCOUNTS_CACHE};
 use crate::{
     connection,
     core::errors::{self, CustomResult},
@@ -142,14 +144,17 @@ impl MerchantConnectorAccountInterface for Store {
         connector_label: &str,
     ) -> CustomResult<storage::MerchantConnectorAccount, errors::StorageError> {
         let conn = connection::pg_connection_read(self).await?;
-        storage::MerchantConnectorAccount::find_by_merchant_id_connector(
-            &conn,
-            merchant_id,
-            connector_label,
-        )
-        .await
-        .map_err(Into::into)
-        .into_report()
+        let merchant_connector_account =
+            storage::MerchantConnectorAccount::find_by_merchant_id_connector(
+                &conn,
+                merchant_id,
+                connector_label,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()?;
+        self.decrypt_pii(merchant_id, merchant_connector_account)
+            .await
     }
 
     async fn find_by_merchant_connector_account_merchant_id_merchant_connector_id(
@@ -168,23 +173,39 @@ impl MerchantConnectorAccountInterface for Store {
             .map_err(Into::into)
             .into_report()
         };
+        // The cached accounts hold the encrypted connector account details
         #[cfg(not(feature = "accounts_cache"))]
-        {
-            find_call().await
-        }
+        let merchant_connector_account = find_call().await?;
 
         #[cfg(feature = "accounts_cache")]
-        {
-            super::cache::get_or_populate_redis(self, merchant_connector_id, find_call).await
-        }
+        let merchant_connector_account = super::cache::get_or_populate_in_memory(
+            self,
+            merchant_connector_id,
+            find_call,
+            &ACCOUNTS_CACHE,
+        )
+        .await?;
+
+        self.decrypt_pii(merchant_id, merchant_connector_account)
+            .await
     }
 
     async fn insert_merchant_connector_account(
         &self,
         t: storage::MerchantConnectorAccountNew,
     ) -> CustomResult<storage::MerchantConnectorAccount, errors::StorageError> {
+        let merchant_id = t
+            .merchant_id
+            .clone()
+            .ok_or(errors::StorageError::ValueNotFound(
+                "merchant_id of the merchant connector account".to_string(),
+            ))
+            .into_report()?;
+        let t = self.encrypt_pii(&merchant_id, t).await?;
         let conn = connection::pg_connection_write(self).await?;
-        t.insert(&conn).await.map_err(Into::into).into_report()
+        let merchant_connector_account = t.insert(&conn).await.map_err(Into::into).into_report()?;
+        self.decrypt_pii(&merchant_id, merchant_connector_account)
+            .await
     }
 
     async fn find_merchant_connector_account_by_merchant_id_and_disabled_list(
@@ -193,10 +214,16 @@ impl MerchantConnectorAccountInterface for Store {
         get_disabled: bool,
     ) -> CustomResult<Vec<storage::MerchantConnectorAccount>, errors::StorageError> {
         let conn = connection::pg_connection_read(self).await?;
-        storage::MerchantConnectorAccount::find_by_merchant_id(&conn, merchant_id, get_disabled)
+        let merchant_connector_accounts = storage::MerchantConnectorAccount::find_by_merchant_id(
+            &conn,
+            merchant_id,
+            get_disabled,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()?;
+        self.decrypt_pii(merchant_id, merchant_connector_accounts)
             .await
-            .map_err(Into::into)
-            .into_report()
     }
 
     async fn update_merchant_connector_account(
@@ -205,6 +232,10 @@ impl MerchantConnectorAccountInterface for Store {
         merchant_connector_account: storage::MerchantConnectorAccountUpdate,
     ) -> CustomResult<storage::MerchantConnectorAccount, errors::StorageError> {
         let _merchant_connector_id = this.merchant_connector_id.clone();
+        let merchant_id = this.merchant_id.clone();
+        let merchant_connector_account = self
+            .encrypt_pii(&merchant_id, merchant_connector_account)
+            .await?;
         let update_call = || async {
             let conn = connection::pg_connection_write(self).await?;
             this.update(&conn, merchant_connector_account)
@@ -214,14 +245,19 @@ impl MerchantConnectorAccountInterface for Store {
         };
 
         #[cfg(feature = "accounts_cache")]
-        {
-            super::cache::redact_cache(self, &_merchant_connector_id, update_call, None).await
-        }
+        let updated_merchant_connector_account = super::cache::publish_and_redact(
+            self,
+            CacheKind::Accounts,
+            &_merchant_connector_id,
+            update_call,
+        )
+        .await?;
 
         #[cfg(not(feature = "accounts_cache"))]
-        {
-            update_call().await
-        }
+        let updated_merchant_connector_account = update_call().await?;
+
+        self.decrypt_pii(&merchant_id, updated_merchant_connector_account)
+            .await
     }
 
     async fn delete_merchant_connector_account_by_merchant_id_merchant_connector_id(
@@ -229,15 +265,33 @@ impl MerchantConnectorAccountInterface for Store {
         merchant_id: &str,
         merchant_connector_id: &str,
     ) -> CustomResult<bool, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::MerchantConnectorAccount::delete_by_merchant_id_merchant_connector_id(
-            &conn,
-            merchant_id,
-            merchant_connector_id,
-        )
-        .await
-        .map_err(Into::into)
-        .into_report()
+        let delete_call = || async {
+            let conn = connection::pg_connection_write(self).await?;
+            storage::MerchantConnectorAccount::delete_by_merchant_id_merchant_connector_id(
+                &conn,
+                merchant_id,
+                merchant_connector_id,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        };
+
+        #[cfg(feature = "accounts_cache")]
+        {
+            super::cache::publish_and_redact(
+                self,
+                CacheKind::Accounts,
+                merchant_connector_id,
+                delete_call,
+            )
+            .await
+        }
+
+        #[cfg(not(feature = "accounts_cache"))]
+        {
+            delete_call().await
+        }
     }
 }
 
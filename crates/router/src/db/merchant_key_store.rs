@@ -0,0 +1,106 @@
+use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, StrongSecret};
+use storage_models::merchant_key_store::{MerchantKeyStore, MerchantKeyStoreNew};
+
+use crate::{
+    cache::MERCHANT_KEY_CACHE,
+    connection,
+    core::errors::{self, CustomResult},
+    services::{self, Store},
+};
+
+/// Length (in bytes) of the AES-256-GCM data keys of merchants
+const DATA_KEY_LENGTH: usize = 32;
+
+impl Store {
+    /// Returns the data key with which the PII of the merchant is encrypted, generating and storing
+    /// one (encrypted with the master key) if the merchant does not have a data key yet.
+    pub(crate) async fn get_merchant_data_key(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<StrongSecret<Vec<u8>>, errors::StorageError> {
+        if let Some(data_key) = MERCHANT_KEY_CACHE.get_val::<StrongSecret<Vec<u8>>>(merchant_id) {
+            return Ok(data_key);
+        }
+
+        let master_key = self
+            .pii_encryption
+            .master_key
+            .as_ref()
+            .ok_or(errors::StorageError::EncryptionError)
+            .into_report()
+            .attach_printable("PII encryption master key is not configured")?;
+
+        let key_store = match self.find_merchant_key_store(merchant_id).await? {
+            Some(key_store) => key_store,
+            None => {
+                self.insert_merchant_key_store(merchant_id, master_key)
+                    .await?
+            }
+        };
+
+        let data_key = services::decrypt(key_store.key.peek().clone(), master_key.peek())
+            .change_context(errors::StorageError::DecryptionError)
+            .attach_printable("Failed to decrypt data key of merchant")
+            .and_then(|data_key| {
+                hex::decode(data_key)
+                    .into_report()
+                    .change_context(errors::StorageError::DecryptionError)
+                    .attach_printable("Data key of merchant has invalid hexadecimal data")
+            })
+            .map(StrongSecret::new)?;
+
+        MERCHANT_KEY_CACHE
+            .push(merchant_id.to_string(), data_key.clone())
+            .await;
+        Ok(data_key)
+    }
+
+    async fn find_merchant_key_store(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Option<MerchantKeyStore>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        MerchantKeyStore::find_optional_by_merchant_id(&conn, merchant_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn insert_merchant_key_store(
+        &self,
+        merchant_id: &str,
+        master_key: &StrongSecret<Vec<u8>>,
+    ) -> CustomResult<MerchantKeyStore, errors::StorageError> {
+        let data_key: [u8; DATA_KEY_LENGTH] = rand::random();
+        let key = services::encrypt(&hex::encode(data_key), master_key.peek())
+            .change_context(errors::StorageError::EncryptionError)
+            .attach_printable("Failed to encrypt data key of merchant")?;
+
+        let conn = connection::pg_connection_write(self).await?;
+        let inserted = MerchantKeyStoreNew {
+            merchant_id: merchant_id.to_string(),
+            key: StrongSecret::new(key),
+        }
+        .insert(&conn)
+        .await
+        .map_err(Into::<errors::StorageError>::into)
+        .into_report();
+
+        match inserted {
+            // Another request generated the data key of the merchant in the meantime
+            Err(error) if error.current_context().is_db_unique_violation() => {
+                let conn = connection::pg_connection_write(self).await?;
+                MerchantKeyStore::find_optional_by_merchant_id(&conn, merchant_id)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()?
+                    .ok_or(errors::StorageError::ValueNotFound(format!(
+                        "Data key of merchant {merchant_id}"
+                    )))
+                    .into_report()
+            }
+            inserted => inserted,
+        }
+    }
+}
@@ -265,6 +265,9 @@ impl PaymentAttemptInterface for MockDb {
             payment_method_data: payment_attempt.payment_method_data,
             business_sub_label: payment_attempt.business_sub_label,
             straight_through_algorithm: payment_attempt.straight_through_algorithm,
+            settlement_amount: payment_attempt.settlement_amount,
+            settlement_currency: payment_attempt.settlement_currency,
+            fx_rate: payment_attempt.fx_rate,
         };
         payment_attempts.push(payment_attempt.clone());
         Ok(payment_attempt)
@@ -400,6 +403,9 @@ mod storage {
                         straight_through_algorithm: payment_attempt
                             .straight_through_algorithm
                             .clone(),
+                        settlement_amount: payment_attempt.settlement_amount,
+                        settlement_currency: payment_attempt.settlement_currency,
+                        fx_rate: payment_attempt.fx_rate.clone(),
                     };
 
                     let field = format!("pa_{}", created_attempt.attempt_id);
@@ -415,22 +421,20 @@ mod storage {
                         })
                         .into_report(),
                         Ok(HsetnxReply::KeySet) => {
-                            let conn = connection::pg_connection_write(self).await?;
-
                             //Reverse lookup for attempt_id
-                            ReverseLookupNew {
-                                lookup_id: format!(
-                                    "{}_{}",
-                                    &created_attempt.merchant_id, &created_attempt.attempt_id,
-                                ),
-                                pk_id: key,
-                                sk_id: field,
-                                source: "payment_attempt".to_string(),
-                            }
-                            .insert(&conn)
-                            .await
-                            .map_err(Into::<errors::StorageError>::into)
-                            .into_report()?;
+                            self.insert_reverse_lookup(
+                                ReverseLookupNew {
+                                    lookup_id: format!(
+                                        "{}_{}",
+                                        &created_attempt.merchant_id, &created_attempt.attempt_id,
+                                    ),
+                                    pk_id: key,
+                                    sk_id: field,
+                                    source: "payment_attempt".to_string(),
+                                },
+                                storage_scheme,
+                            )
+                            .await?;
 
                             let redis_entry = kv::TypedSql {
                                 op: kv::DBOperation::Insert {
@@ -495,6 +499,7 @@ mod storage {
                                 this.merchant_id.as_str(),
                                 updated_attempt.attempt_id.as_str(),
                                 connector_transaction_id.as_str(),
+                                storage_scheme,
                             )
                             .await?;
                         }
@@ -506,6 +511,7 @@ mod storage {
                                     this.merchant_id.as_str(),
                                     updated_attempt.attempt_id.as_str(),
                                     connector_transaction_id.as_str(),
+                                    storage_scheme,
                                 )
                                 .await?;
                             }
@@ -703,18 +709,19 @@ mod storage {
         merchant_id: &str,
         updated_attempt_attempt_id: &str,
         connector_transaction_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<ReverseLookup, errors::StorageError> {
-        let conn = connection::pg_connection_write(store).await?;
         let field = format!("pa_{}", updated_attempt_attempt_id);
-        ReverseLookupNew {
-            lookup_id: format!("{}_{}", merchant_id, connector_transaction_id),
-            pk_id: key.to_owned(),
-            sk_id: field.clone(),
-            source: "payment_attempt".to_string(),
-        }
-        .insert(&conn)
-        .await
-        .map_err(Into::<errors::StorageError>::into)
-        .into_report()
+        store
+            .insert_reverse_lookup(
+                ReverseLookupNew {
+                    lookup_id: format!("{}_{}", merchant_id, connector_transaction_id),
+                    pk_id: key.to_owned(),
+                    sk_id: field.clone(),
+                    source: "payment_attempt".to_string(),
+                },
+                storage_scheme,
+            )
+            .await
     }
 }
@@ -28,6 +28,15 @@ pub trait PaymentIntentInterface {
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<types::PaymentIntent, errors::StorageError>;
 
+    /// Payment intents in one of `statuses` whose session expired before `expired_before`, oldest
+    /// expiry first
+    async fn find_expired_payment_intents(
+        &self,
+        expired_before: time::PrimitiveDateTime,
+        statuses: Vec<enums::IntentStatus>,
+        limit: i64,
+    ) -> CustomResult<Vec<types::PaymentIntent>, errors::StorageError>;
+
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -44,8 +53,6 @@ mod storage {
     use redis_interface::HsetnxReply;
 
     use super::PaymentIntentInterface;
-    #[cfg(feature = "olap")]
-    use crate::types::api;
     use crate::{
         connection,
         core::errors::{self, CustomResult},
@@ -53,6 +60,11 @@ mod storage {
         types::storage::{enums, kv, payment_intent::*},
         utils::{self, db_utils, storage_partitioning},
     };
+    #[cfg(feature = "olap")]
+    use crate::{logger, types::api};
+
+    /// Entity name of payment intents in `created_at` indexes
+    const PAYMENT_INTENT_INDEX: &str = "pi";
 
     #[async_trait::async_trait]
     impl PaymentIntentInterface for Store {
@@ -95,6 +107,8 @@ mod storage {
                         business_country: new.business_country,
                         business_label: new.business_label.clone(),
                         active_attempt_id: new.active_attempt_id.to_owned(),
+                        session_expiry: new.session_expiry,
+                        attempt_count: new.attempt_count,
                     };
 
                     match self
@@ -122,6 +136,15 @@ mod storage {
                                 },
                             )
                             .await?;
+                            self.add_to_created_at_index(
+                                &db_utils::get_created_at_index_key(
+                                    &created_intent.merchant_id,
+                                    PAYMENT_INTENT_INDEX,
+                                ),
+                                created_intent.created_at,
+                                &created_intent.payment_id,
+                            )
+                            .await?;
                             Ok(created_intent)
                         }
                         Err(error) => Err(error.change_context(errors::StorageError::KVError)),
@@ -216,6 +239,21 @@ mod storage {
             }
         }
 
+        async fn find_expired_payment_intents(
+            &self,
+            expired_before: time::PrimitiveDateTime,
+            statuses: Vec<enums::IntentStatus>,
+            limit: i64,
+        ) -> CustomResult<Vec<PaymentIntent>, errors::StorageError> {
+            // Intents of merchants on the KV scheme are drained to the database shortly after
+            // being written, which is soon enough for expiry
+            let conn = connection::pg_connection_read(self).await?;
+            PaymentIntent::find_expired(&conn, expired_before, statuses, limit)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
         #[cfg(feature = "olap")]
         async fn filter_payment_intent_by_constraints(
             &self,
@@ -232,7 +270,102 @@ mod storage {
                         .into_report()
                 }
 
-                enums::MerchantStorageScheme::RedisKv => Err(errors::StorageError::KVError.into()),
+                enums::MerchantStorageScheme::RedisKv => {
+                    // Pagination cursors are resolved to creation times, as objects which are
+                    // yet to be drained have no database ID to compare against
+                    let starting_after = match &pc.starting_after {
+                        Some(payment_id) => Some(
+                            self.find_payment_intent_by_payment_id_merchant_id(
+                                payment_id,
+                                merchant_id,
+                                storage_scheme,
+                            )
+                            .await?
+                            .created_at,
+                        ),
+                        None => None,
+                    };
+                    let ending_before = match &pc.ending_before {
+                        Some(payment_id) => Some(
+                            self.find_payment_intent_by_payment_id_merchant_id(
+                                payment_id,
+                                merchant_id,
+                                storage_scheme,
+                            )
+                            .await?
+                            .created_at,
+                        ),
+                        None => None,
+                    };
+                    let range = db_utils::CreatedAtRange {
+                        created: pc.created,
+                        created_lt: match (pc.created_lt, ending_before) {
+                            (Some(created_lt), Some(ending_before)) => {
+                                Some(created_lt.min(ending_before))
+                            }
+                            (created_lt, ending_before) => created_lt.or(ending_before),
+                        },
+                        created_gt: pc.created_gt.max(starting_after),
+                        created_lte: pc.created_lte,
+                        created_gte: pc.created_gte,
+                    };
+
+                    let payment_ids = self
+                        .get_from_created_at_index(
+                            &db_utils::get_created_at_index_key(merchant_id, PAYMENT_INTENT_INDEX),
+                            &range,
+                            pc.customer_id.is_none().then_some(pc.limit),
+                        )
+                        .await?;
+                    let kv_intents: Vec<PaymentIntent> =
+                        futures::future::join_all(payment_ids.iter().map(|payment_id| {
+                            self.find_payment_intent_by_payment_id_merchant_id(
+                                payment_id,
+                                merchant_id,
+                                storage_scheme,
+                            )
+                        }))
+                        .await
+                        .into_iter()
+                        .filter_map(|intent| {
+                            intent
+                                .map_err(|error| {
+                                    logger::error!(?error, "Failed to read indexed payment intent")
+                                })
+                                .ok()
+                        })
+                        .filter(|intent| {
+                            range.contains(intent.created_at)
+                                && (pc.customer_id.is_none()
+                                    || intent.customer_id == pc.customer_id)
+                        })
+                        .collect();
+
+                    let database_constraints = api::PaymentListConstraints {
+                        starting_after: None,
+                        ending_before: None,
+                        created_lt: range.created_lt,
+                        created_gt: range.created_gt,
+                        ..pc.clone()
+                    };
+                    let conn = connection::pg_connection_read(self).await?;
+                    let database_intents = PaymentIntent::filter_by_constraints(
+                        &conn,
+                        merchant_id,
+                        &database_constraints,
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()?;
+
+                    Ok(db_utils::merge_kv_and_database_results(
+                        kv_intents,
+                        database_intents,
+                        |intent| intent.payment_id.clone(),
+                        |intent| intent.created_at,
+                        usize::try_from(pc.limit).ok(),
+                    ))
+                }
             }
         }
     }
@@ -289,6 +422,19 @@ mod storage {
                 .into_report()
         }
 
+        async fn find_expired_payment_intents(
+            &self,
+            expired_before: time::PrimitiveDateTime,
+            statuses: Vec<enums::IntentStatus>,
+            limit: i64,
+        ) -> CustomResult<Vec<PaymentIntent>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            PaymentIntent::find_expired(&conn, expired_before, statuses, limit)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
         #[cfg(feature = "olap")]
         async fn filter_payment_intent_by_constraints(
             &self,
@@ -318,6 +464,28 @@ impl PaymentIntentInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn find_expired_payment_intents(
+        &self,
+        expired_before: time::PrimitiveDateTime,
+        statuses: Vec<enums::IntentStatus>,
+        limit: i64,
+    ) -> CustomResult<Vec<types::PaymentIntent>, errors::StorageError> {
+        let payment_intents = self.payment_intents.lock().await;
+        let mut expired: Vec<_> = payment_intents
+            .iter()
+            .filter(|payment_intent| {
+                payment_intent
+                    .session_expiry
+                    .map_or(false, |session_expiry| session_expiry <= expired_before)
+                    && statuses.contains(&payment_intent.status)
+            })
+            .cloned()
+            .collect();
+        expired.sort_by_key(|payment_intent| payment_intent.session_expiry);
+        expired.truncate(usize::try_from(limit).unwrap_or_default());
+        Ok(expired)
+    }
+
     #[allow(clippy::panic)]
     async fn insert_payment_intent(
         &self,
@@ -353,6 +521,8 @@ impl PaymentIntentInterface for MockDb {
             business_country: new.business_country,
             business_label: new.business_label,
             active_attempt_id: new.active_attempt_id.to_owned(),
+            session_expiry: new.session_expiry,
+            attempt_count: new.attempt_count,
         };
         payment_intents.push(payment_intent.clone());
         Ok(payment_intent)
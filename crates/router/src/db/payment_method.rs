@@ -20,6 +20,11 @@ pub trait PaymentMethodInterface {
         merchant_id: &str,
     ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError>;
 
+    async fn find_payment_method_by_network_token_ref_id(
+        &self,
+        network_token_ref_id: &str,
+    ) -> CustomResult<storage::PaymentMethod, errors::StorageError>;
+
     async fn insert_payment_method(
         &self,
         m: storage::PaymentMethodNew,
@@ -51,6 +56,17 @@ impl PaymentMethodInterface for Store {
             .into_report()
     }
 
+    async fn find_payment_method_by_network_token_ref_id(
+        &self,
+        network_token_ref_id: &str,
+    ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::PaymentMethod::find_by_network_token_ref_id(&conn, network_token_ref_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
     async fn insert_payment_method(
         &self,
         m: storage::PaymentMethodNew,
@@ -111,6 +127,14 @@ impl PaymentMethodInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn find_payment_method_by_network_token_ref_id(
+        &self,
+        _network_token_ref_id: &str,
+    ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+        // [#172]: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
     async fn insert_payment_method(
         &self,
         _m: storage::PaymentMethodNew,
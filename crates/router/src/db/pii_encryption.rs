@@ -0,0 +1,700 @@
+//! Application-level encryption of PII columns with the data keys of merchants.
+//!
+//! Values are encrypted with AES-256-GCM and stored in the same (text) columns as before, as
+//! [`ENCRYPTED_VALUE_PREFIX`] followed by the base64-encoded nonce, ciphertext and tag. Values
+//! without the prefix are plaintext written before encryption was enabled, and are returned as is
+//! when decrypting, so that encrypted and plaintext rows can coexist until the backfill job has
+//! encrypted every row.
+//!
+//! Every value written while encryption is enabled is encrypted, whatever it looks like, so that
+//! plaintext carrying the prefix is never stored as is. The prefix is trusted only as far as the
+//! value decrypts with the data key of the merchant: reading such a value fails otherwise, and the
+//! backfill job encrypts it as plaintext.
+//!
+//! The encryption is applied by the [`Store`] at the boundary of the database interfaces rather
+//! than by a diesel type in `masking`, as the data key depends on the merchant owning the row,
+//! which is not available when (de)serializing a single column. Redis and the drainer streams
+//! therefore hold the encrypted values as well.
+
+use base64::Engine;
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface, Secret, Strategy};
+
+use crate::{
+    connection, consts,
+    core::errors::{self, CustomResult},
+    logger,
+    services::{self, Store},
+    types::storage,
+};
+
+/// Prefix identifying encrypted values, versioned to allow changing the encryption scheme.
+pub const ENCRYPTED_VALUE_PREFIX: &str = "hs_enc_v1:";
+
+fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_VALUE_PREFIX)
+}
+
+fn encrypt_value(value: String, key: &[u8]) -> CustomResult<String, errors::StorageError> {
+    let encrypted =
+        services::encrypt(&value, key).change_context(errors::StorageError::EncryptionError)?;
+    Ok(format!(
+        "{ENCRYPTED_VALUE_PREFIX}{}",
+        consts::BASE64_ENGINE.encode(encrypted)
+    ))
+}
+
+fn decrypt_value(value: String, key: &[u8]) -> CustomResult<String, errors::StorageError> {
+    match value.strip_prefix(ENCRYPTED_VALUE_PREFIX) {
+        Some(encoded) => {
+            let encrypted = consts::BASE64_ENGINE
+                .decode(encoded)
+                .into_report()
+                .change_context(errors::StorageError::DecryptionError)
+                .attach_printable("Encrypted value has invalid base64 data")?;
+            services::decrypt(encrypted, key).change_context(errors::StorageError::DecryptionError)
+        }
+        None => Ok(value),
+    }
+}
+
+/// A single column holding PII.
+trait EncryptableField: Sized {
+    /// Whether the column holds a value carrying the prefix of encrypted values.
+    fn is_encrypted(&self) -> bool;
+
+    /// Whether the column holds a value which is not encrypted with `key`, including values
+    /// carrying the prefix which do not decrypt.
+    fn is_plaintext(&self, key: &[u8]) -> bool;
+
+    fn encrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError>;
+
+    fn decrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError>;
+
+    /// Encrypts the value if it is plaintext, leaving values encrypted with `key` unchanged.
+    fn encrypt_plaintext(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        if self.is_plaintext(key) {
+            self.encrypt_field(key)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+impl<S: Strategy<String>> EncryptableField for Secret<String, S> {
+    fn is_encrypted(&self) -> bool {
+        is_encrypted(self.peek())
+    }
+
+    fn is_plaintext(&self, key: &[u8]) -> bool {
+        !self.is_encrypted() || decrypt_value(self.peek().clone(), key).is_err()
+    }
+
+    fn encrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        encrypt_value(self.expose(), key).map(Secret::new)
+    }
+
+    fn decrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        decrypt_value(self.expose(), key).map(Secret::new)
+    }
+}
+
+/// JSON values are encrypted as a whole, and stored as a JSON string holding the encrypted value.
+impl EncryptableField for serde_json::Value {
+    fn is_encrypted(&self) -> bool {
+        matches!(self, Self::String(value) if is_encrypted(value))
+    }
+
+    fn is_plaintext(&self, key: &[u8]) -> bool {
+        !self.is_encrypted() || self.clone().decrypt_field(key).is_err()
+    }
+
+    fn encrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        let value = serde_json::to_string(&self)
+            .into_report()
+            .change_context(errors::StorageError::SerializationFailed)?;
+        encrypt_value(value, key).map(Self::String)
+    }
+
+    fn decrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        match self {
+            Self::String(value) if is_encrypted(&value) => {
+                serde_json::from_str(&decrypt_value(value, key)?)
+                    .into_report()
+                    .change_context(errors::StorageError::DeserializationFailed)
+            }
+            value => Ok(value),
+        }
+    }
+}
+
+impl EncryptableField for Secret<serde_json::Value> {
+    fn is_encrypted(&self) -> bool {
+        self.peek().is_encrypted()
+    }
+
+    fn is_plaintext(&self, key: &[u8]) -> bool {
+        self.peek().is_plaintext(key)
+    }
+
+    fn encrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        self.expose().encrypt_field(key).map(Secret::new)
+    }
+
+    fn decrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        self.expose().decrypt_field(key).map(Secret::new)
+    }
+}
+
+impl<T: EncryptableField> EncryptableField for Option<T> {
+    fn is_encrypted(&self) -> bool {
+        self.as_ref().map_or(false, EncryptableField::is_encrypted)
+    }
+
+    fn is_plaintext(&self, key: &[u8]) -> bool {
+        self.as_ref().map_or(false, |value| value.is_plaintext(key))
+    }
+
+    fn encrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        self.map(|value| value.encrypt_field(key)).transpose()
+    }
+
+    fn decrypt_field(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        self.map(|value| value.decrypt_field(key)).transpose()
+    }
+}
+
+/// Storage types holding PII columns.
+pub(crate) trait PiiEncryption: Sized {
+    /// Whether any of the PII columns holds an encrypted value.
+    fn contains_ciphertext(&self) -> bool;
+
+    fn encrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError>;
+
+    fn decrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError>;
+}
+
+/// Rows holding PII columns, which are encrypted by the backfill job.
+trait BackfillEncryption: Sized {
+    fn contains_plaintext(&self, key: &[u8]) -> bool;
+
+    /// Encrypts the columns holding plaintext, leaving those already encrypted with `key`
+    /// unchanged.
+    fn encrypt_plaintext(self, key: &[u8]) -> CustomResult<Self, errors::StorageError>;
+}
+
+macro_rules! impl_pii_encryption {
+    ($type:ty, $($field:ident),+) => {
+        impl BackfillEncryption for $type {
+            fn contains_plaintext(&self, key: &[u8]) -> bool {
+                $(self.$field.is_plaintext(key))||+
+            }
+
+            fn encrypt_plaintext(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+                Ok(Self {
+                    $($field: self.$field.encrypt_plaintext(key)?,)+
+                    ..self
+                })
+            }
+        }
+
+        impl PiiEncryption for $type {
+            fn contains_ciphertext(&self) -> bool {
+                $(self.$field.is_encrypted())||+
+            }
+
+            fn encrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+                Ok(Self {
+                    $($field: self.$field.encrypt_field(key)?,)+
+                    ..self
+                })
+            }
+
+            fn decrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+                Ok(Self {
+                    $($field: self.$field.decrypt_field(key)?,)+
+                    ..self
+                })
+            }
+        }
+    };
+}
+
+impl_pii_encryption!(storage::CustomerNew, email, phone);
+impl_pii_encryption!(storage::Customer, email, phone);
+impl_pii_encryption!(
+    storage::AddressNew,
+    line1,
+    line2,
+    line3,
+    state,
+    zip,
+    first_name,
+    last_name,
+    phone_number
+);
+impl_pii_encryption!(
+    storage::Address,
+    line1,
+    line2,
+    line3,
+    state,
+    zip,
+    first_name,
+    last_name,
+    phone_number
+);
+impl_pii_encryption!(
+    storage::MerchantConnectorAccountNew,
+    connector_account_details
+);
+impl_pii_encryption!(storage::MerchantConnectorAccount, connector_account_details);
+
+impl PiiEncryption for storage::CustomerUpdate {
+    fn contains_ciphertext(&self) -> bool {
+        match self {
+            Self::Update { email, phone, .. } => email.is_encrypted() || phone.is_encrypted(),
+        }
+    }
+
+    fn encrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        match self {
+            Self::Update {
+                name,
+                email,
+                phone,
+                description,
+                phone_country_code,
+                metadata,
+            } => Ok(Self::Update {
+                name,
+                email: email.encrypt_field(key)?,
+                phone: phone.encrypt_field(key)?,
+                description,
+                phone_country_code,
+                metadata,
+            }),
+        }
+    }
+
+    fn decrypt_pii(self, _key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        Ok(self)
+    }
+}
+
+impl PiiEncryption for storage::AddressUpdate {
+    fn contains_ciphertext(&self) -> bool {
+        match self {
+            Self::Update {
+                line1,
+                line2,
+                line3,
+                state,
+                zip,
+                first_name,
+                last_name,
+                phone_number,
+                ..
+            } => [
+                line1,
+                line2,
+                line3,
+                state,
+                zip,
+                first_name,
+                last_name,
+                phone_number,
+            ]
+            .iter()
+            .any(|field| field.is_encrypted()),
+        }
+    }
+
+    fn encrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        match self {
+            Self::Update {
+                city,
+                country,
+                line1,
+                line2,
+                line3,
+                state,
+                zip,
+                first_name,
+                last_name,
+                phone_number,
+                country_code,
+            } => Ok(Self::Update {
+                city,
+                country,
+                line1: line1.encrypt_field(key)?,
+                line2: line2.encrypt_field(key)?,
+                line3: line3.encrypt_field(key)?,
+                state: state.encrypt_field(key)?,
+                zip: zip.encrypt_field(key)?,
+                first_name: first_name.encrypt_field(key)?,
+                last_name: last_name.encrypt_field(key)?,
+                phone_number: phone_number.encrypt_field(key)?,
+                country_code,
+            }),
+        }
+    }
+
+    fn decrypt_pii(self, _key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        Ok(self)
+    }
+}
+
+impl PiiEncryption for storage::MerchantConnectorAccountUpdate {
+    fn contains_ciphertext(&self) -> bool {
+        match self {
+            Self::Update {
+                connector_account_details,
+                ..
+            } => connector_account_details.is_encrypted(),
+        }
+    }
+
+    fn encrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        match self {
+            Self::Update {
+                merchant_id,
+                connector_type,
+                connector_account_details,
+                test_mode,
+                disabled,
+                merchant_connector_id,
+                payment_methods_enabled,
+                metadata,
+                frm_configs,
+            } => Ok(Self::Update {
+                merchant_id,
+                connector_type,
+                connector_account_details: connector_account_details.encrypt_field(key)?,
+                test_mode,
+                disabled,
+                merchant_connector_id,
+                payment_methods_enabled,
+                metadata,
+                frm_configs,
+            }),
+        }
+    }
+
+    fn decrypt_pii(self, _key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        Ok(self)
+    }
+}
+
+impl<T: PiiEncryption> PiiEncryption for Option<T> {
+    fn contains_ciphertext(&self) -> bool {
+        self.as_ref()
+            .map_or(false, PiiEncryption::contains_ciphertext)
+    }
+
+    fn encrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        self.map(|value| value.encrypt_pii(key)).transpose()
+    }
+
+    fn decrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        self.map(|value| value.decrypt_pii(key)).transpose()
+    }
+}
+
+impl<T: PiiEncryption> PiiEncryption for Vec<T> {
+    fn contains_ciphertext(&self) -> bool {
+        self.iter().any(PiiEncryption::contains_ciphertext)
+    }
+
+    fn encrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        self.into_iter()
+            .map(|value| value.encrypt_pii(key))
+            .collect()
+    }
+
+    fn decrypt_pii(self, key: &[u8]) -> CustomResult<Self, errors::StorageError> {
+        self.into_iter()
+            .map(|value| value.decrypt_pii(key))
+            .collect()
+    }
+}
+
+impl Store {
+    /// Encrypts the PII of `value` with the data key of the merchant, when PII encryption is
+    /// enabled.
+    pub(crate) async fn encrypt_pii<T: PiiEncryption>(
+        &self,
+        merchant_id: &str,
+        value: T,
+    ) -> CustomResult<T, errors::StorageError> {
+        if !self.pii_encryption.enabled {
+            return Ok(value);
+        }
+
+        let data_key = self.get_merchant_data_key(merchant_id).await?;
+        value.encrypt_pii(data_key.peek())
+    }
+
+    /// Decrypts the PII of `value` with the data key of the merchant. Values which were never
+    /// encrypted are returned as is, without looking up the data key.
+    pub(crate) async fn decrypt_pii<T: PiiEncryption>(
+        &self,
+        merchant_id: &str,
+        value: T,
+    ) -> CustomResult<T, errors::StorageError> {
+        if !value.contains_ciphertext() {
+            return Ok(value);
+        }
+
+        let data_key = self.get_merchant_data_key(merchant_id).await?;
+        value.decrypt_pii(data_key.peek())
+    }
+}
+
+/// Number of rows of each table encrypted by the backfill job.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct BackfillSummary {
+    pub customers: usize,
+    pub addresses: usize,
+    pub merchant_connector_accounts: usize,
+}
+
+/// Encrypts the PII columns of rows written before PII encryption was enabled, iterating over
+/// each table in batches of `batch_size` rows.
+///
+/// The job must only be run once every instance of the application (and the drainer) has PII
+/// encryption enabled, and the time-to-live of the KV entries has elapsed since, as plaintext
+/// values written by older instances would otherwise be drained into the database after the job.
+pub async fn backfill(
+    store: &Store,
+    batch_size: u32,
+) -> CustomResult<BackfillSummary, errors::StorageError> {
+    if !store.pii_encryption.enabled {
+        return Err(errors::StorageError::EncryptionError)
+            .into_report()
+            .attach_printable("PII encryption must be enabled to backfill encrypted values");
+    }
+
+    let batch_size = i64::from(batch_size);
+    let mut summary = BackfillSummary::default();
+    let conn = connection::pg_connection_write(store).await?;
+
+    let mut last_id = 0;
+    loop {
+        let customers = storage::Customer::list_after_id(&conn, last_id, batch_size)
+            .await
+            .map_err(Into::<errors::StorageError>::into)
+            .into_report()?;
+        last_id = match customers.last() {
+            Some(customer) => customer.id,
+            None => break,
+        };
+
+        for customer in customers {
+            let data_key = store.get_merchant_data_key(&customer.merchant_id).await?;
+            if !customer.contains_plaintext(data_key.peek()) {
+                continue;
+            }
+            let customer = customer.encrypt_plaintext(data_key.peek())?;
+            storage::Customer::update_by_customer_id_merchant_id(
+                &conn,
+                customer.customer_id,
+                customer.merchant_id,
+                storage::CustomerUpdate::Update {
+                    name: None,
+                    email: customer.email,
+                    phone: customer.phone,
+                    description: None,
+                    phone_country_code: None,
+                    metadata: None,
+                },
+            )
+            .await
+            .map_err(Into::<errors::StorageError>::into)
+            .into_report()?;
+            summary.customers += 1;
+        }
+        logger::info!(last_id, "Encrypted PII of customers");
+    }
+
+    let mut last_id = 0;
+    loop {
+        let addresses = storage::Address::list_after_id(&conn, last_id, batch_size)
+            .await
+            .map_err(Into::<errors::StorageError>::into)
+            .into_report()?;
+        last_id = match addresses.last() {
+            Some(address) => address.id,
+            None => break,
+        };
+
+        for address in addresses {
+            let data_key = store.get_merchant_data_key(&address.merchant_id).await?;
+            if !address.contains_plaintext(data_key.peek()) {
+                continue;
+            }
+            let address = address.encrypt_plaintext(data_key.peek())?;
+            storage::Address::update_by_address_id(
+                &conn,
+                address.address_id,
+                storage::AddressUpdate::Update {
+                    city: None,
+                    country: None,
+                    line1: address.line1,
+                    line2: address.line2,
+                    line3: address.line3,
+                    state: address.state,
+                    zip: address.zip,
+                    first_name: address.first_name,
+                    last_name: address.last_name,
+                    phone_number: address.phone_number,
+                    country_code: None,
+                },
+            )
+            .await
+            .map_err(Into::<errors::StorageError>::into)
+            .into_report()?;
+            summary.addresses += 1;
+        }
+        logger::info!(last_id, "Encrypted PII of addresses");
+    }
+
+    let mut last_id = 0;
+    loop {
+        let merchant_connector_accounts =
+            storage::MerchantConnectorAccount::list_after_id(&conn, last_id, batch_size)
+                .await
+                .map_err(Into::<errors::StorageError>::into)
+                .into_report()?;
+        last_id = match merchant_connector_accounts.last() {
+            Some(merchant_connector_account) => merchant_connector_account.id,
+            None => break,
+        };
+
+        for merchant_connector_account in merchant_connector_accounts {
+            let data_key = store
+                .get_merchant_data_key(&merchant_connector_account.merchant_id)
+                .await?;
+            if !merchant_connector_account.contains_plaintext(data_key.peek()) {
+                continue;
+            }
+            let merchant_connector_account =
+                merchant_connector_account.encrypt_plaintext(data_key.peek())?;
+            let connector_account_details =
+                merchant_connector_account.connector_account_details.clone();
+            merchant_connector_account
+                .update(
+                    &conn,
+                    storage::MerchantConnectorAccountUpdate::Update {
+                        merchant_id: None,
+                        connector_type: None,
+                        connector_account_details: Some(Secret::new(connector_account_details)),
+                        test_mode: None,
+                        disabled: None,
+                        merchant_connector_id: None,
+                        payment_methods_enabled: None,
+                        metadata: None,
+                        frm_configs: None,
+                    },
+                )
+                .await
+                .map_err(Into::<errors::StorageError>::into)
+                .into_report()?;
+            summary.merchant_connector_accounts += 1;
+        }
+        logger::info!(last_id, "Encrypted connector account details");
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    #[test]
+    fn test_encrypt_decrypt_address() {
+        let address = storage::AddressNew {
+            line1: Some(Secret::new("1467 Harrison Street".to_string())),
+            zip: Some(Secret::new("94122".to_string())),
+            city: Some("San Francisco".to_string()),
+            ..Default::default()
+        };
+
+        let encrypted = address.clone().encrypt_pii(&KEY).unwrap();
+        assert!(encrypted.contains_ciphertext());
+        assert!(encrypted
+            .line1
+            .as_ref()
+            .unwrap()
+            .peek()
+            .starts_with(ENCRYPTED_VALUE_PREFIX));
+        assert_eq!(encrypted.city, address.city);
+        assert!(encrypted.line2.is_none());
+
+        // Values are encrypted on every write, even if they look encrypted already
+        let encrypted_twice = encrypted.clone().encrypt_pii(&KEY).unwrap();
+        assert_eq!(
+            encrypted_twice
+                .decrypt_pii(&KEY)
+                .unwrap()
+                .line1
+                .unwrap()
+                .peek(),
+            encrypted.line1.as_ref().unwrap().peek()
+        );
+
+        // The backfill leaves values encrypted with the key unchanged
+        assert!(!encrypted.contains_plaintext(&KEY));
+        let backfilled = encrypted.clone().encrypt_plaintext(&KEY).unwrap();
+        assert_eq!(
+            backfilled.line1.as_ref().unwrap().peek(),
+            encrypted.line1.as_ref().unwrap().peek()
+        );
+
+        let decrypted = encrypted.decrypt_pii(&KEY).unwrap();
+        assert_eq!(decrypted.line1.unwrap().peek(), "1467 Harrison Street");
+        assert_eq!(decrypted.zip.unwrap().peek(), "94122");
+    }
+
+    #[test]
+    fn test_decrypt_plaintext_value() {
+        let value = Secret::<String>::new("plaintext".to_string());
+        assert!(!value.is_encrypted());
+        assert_eq!(value.decrypt_field(&KEY).unwrap().peek(), "plaintext");
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_json_value() {
+        let value = serde_json::json!({ "auth_type": "HeaderKey", "api_key": "key" });
+
+        let encrypted = value.clone().encrypt_field(&KEY).unwrap();
+        assert!(encrypted.is_encrypted());
+        assert_eq!(encrypted.decrypt_field(&KEY).unwrap(), value);
+    }
+
+    #[test]
+    fn test_encrypt_plaintext_carrying_prefix() {
+        let forged = format!("{ENCRYPTED_VALUE_PREFIX}bm90IGVuY3J5cHRlZA==");
+        let value = Secret::<String>::new(forged.clone());
+        assert!(value.is_encrypted());
+        assert!(value.is_plaintext(&KEY));
+        assert!(value.clone().decrypt_field(&KEY).is_err());
+
+        let encrypted = value.encrypt_plaintext(&KEY).unwrap();
+        assert!(!encrypted.is_plaintext(&KEY));
+        assert_eq!(encrypted.decrypt_field(&KEY).unwrap().peek(), &forged);
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_key() {
+        let encrypted = Secret::<String>::new("plaintext".to_string())
+            .encrypt_field(&KEY)
+            .unwrap();
+        assert!(encrypted.decrypt_field(&[8; 32]).is_err());
+    }
+}
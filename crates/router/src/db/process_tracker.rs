@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use error_stack::IntoReport;
 use time::PrimitiveDateTime;
 
@@ -50,6 +52,12 @@ pub trait ProcessTrackerInterface {
         status: enums::ProcessTrackerStatus,
         limit: Option<i64>,
     ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    async fn count_processes_by_runner_and_key_id_excluding_business_status_prefix(
+        &self,
+        runner: &str,
+        business_status_prefix: &str,
+    ) -> CustomResult<Vec<(Option<String>, i64)>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -97,6 +105,22 @@ impl ProcessTrackerInterface for Store {
         .into_report()
     }
 
+    async fn count_processes_by_runner_and_key_id_excluding_business_status_prefix(
+        &self,
+        runner: &str,
+        business_status_prefix: &str,
+    ) -> CustomResult<Vec<(Option<String>, i64)>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::count_by_runner_and_key_id_excluding_business_status_prefix(
+            &conn,
+            runner,
+            business_status_prefix,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
     async fn insert_process(
         &self,
         new: storage::ProcessTrackerNew,
@@ -182,6 +206,32 @@ impl ProcessTrackerInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn count_processes_by_runner_and_key_id_excluding_business_status_prefix(
+        &self,
+        runner: &str,
+        business_status_prefix: &str,
+    ) -> CustomResult<Vec<(Option<String>, i64)>, errors::StorageError> {
+        let mut counts = HashMap::new();
+        self.processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                process.runner.as_deref() == Some(runner)
+                    && !process.business_status.starts_with(business_status_prefix)
+            })
+            .for_each(|process| {
+                let key_id = process
+                    .tracking_data
+                    .get("key_id")
+                    .and_then(|key_id| key_id.as_str())
+                    .map(ToOwned::to_owned);
+                *counts.entry(key_id).or_insert(0) += 1;
+            });
+
+        Ok(counts.into_iter().collect())
+    }
+
     async fn insert_process(
         &self,
         new: storage::ProcessTrackerNew,
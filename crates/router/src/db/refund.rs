@@ -220,6 +220,10 @@ mod storage {
         types::storage::{self as storage_types, enums, kv},
         utils::{self, db_utils, storage_partitioning::PartitionKey},
     };
+
+    /// Entity name of refunds in `created_at` indexes
+    const REFUND_INDEX: &str = "ref";
+
     #[async_trait::async_trait]
     impl RefundInterface for Store {
         async fn find_refund_by_internal_reference_id_merchant_id(
@@ -314,8 +318,6 @@ mod storage {
                         })
                         .into_report(),
                         Ok(HsetnxReply::KeySet) => {
-                            let conn = connection::pg_connection_write(self).await?;
-
                             let mut reverse_lookups = vec![
                                 storage_types::ReverseLookupNew {
                                     sk_id: field.clone(),
@@ -353,9 +355,10 @@ mod storage {
                                     source: "refund".to_string(),
                                 })
                             };
-                            storage_types::ReverseLookupNew::batch_insert(reverse_lookups, &conn)
-                                .await
-                                .change_context(errors::StorageError::KVError)?;
+                            for reverse_lookup in reverse_lookups {
+                                self.insert_reverse_lookup(reverse_lookup, storage_scheme)
+                                    .await?;
+                            }
 
                             let redis_entry = kv::TypedSql {
                                 op: kv::DBOperation::Insert {
@@ -370,6 +373,15 @@ mod storage {
                                 },
                             )
                             .await?;
+                            self.add_to_created_at_index(
+                                &db_utils::get_created_at_index_key(
+                                    &created_refund.merchant_id,
+                                    REFUND_INDEX,
+                                ),
+                                created_refund.created_at,
+                                &created_refund.refund_id,
+                            )
+                            .await?;
 
                             Ok(created_refund)
                         }
@@ -594,7 +606,64 @@ mod storage {
                         .into_report()
                 }
 
-                enums::MerchantStorageScheme::RedisKv => Err(errors::StorageError::KVError.into()),
+                enums::MerchantStorageScheme::RedisKv => {
+                    let range = db_utils::CreatedAtRange {
+                        created: refund_details.created,
+                        created_lt: refund_details.created_lt,
+                        created_gt: refund_details.created_gt,
+                        created_lte: refund_details.created_lte,
+                        created_gte: refund_details.created_gte,
+                    };
+                    // The limit only applies to refunds listed across payments
+                    let list_limit = refund_details.payment_id.is_none().then_some(limit);
+
+                    let refund_ids = self
+                        .get_from_created_at_index(
+                            &db_utils::get_created_at_index_key(merchant_id, REFUND_INDEX),
+                            &range,
+                            list_limit,
+                        )
+                        .await?;
+                    let kv_refunds: Vec<storage_types::Refund> =
+                        futures::future::join_all(refund_ids.iter().map(|refund_id| {
+                            self.find_refund_by_merchant_id_refund_id(
+                                merchant_id,
+                                refund_id,
+                                storage_scheme,
+                            )
+                        }))
+                        .await
+                        .into_iter()
+                        .filter_map(|refund| {
+                            refund
+                                .map_err(|error| {
+                                    logger::error!(?error, "Failed to read indexed refund")
+                                })
+                                .ok()
+                        })
+                        .filter(|refund| {
+                            range.contains(refund.created_at)
+                                && refund_details
+                                    .payment_id
+                                    .as_ref()
+                                    .map_or(true, |payment_id| &refund.payment_id == payment_id)
+                        })
+                        .collect();
+
+                    let conn = connection::pg_connection_read(self).await?;
+                    let database_refunds = <storage_models::refund::Refund as storage_types::RefundDbExt>::filter_by_constraints(&conn, merchant_id, refund_details, limit)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()?;
+
+                    Ok(db_utils::merge_kv_and_database_results(
+                        kv_refunds,
+                        database_refunds,
+                        |refund| refund.refund_id.clone(),
+                        |refund| refund.created_at,
+                        list_limit.and_then(|limit| usize::try_from(limit).ok()),
+                    ))
+                }
             }
         }
     }
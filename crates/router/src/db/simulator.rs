@@ -0,0 +1,75 @@
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::{
+    core::errors::{self, CustomResult},
+    services::logger,
+};
+
+/// Objects created by the sandbox simulator only need to outlive the tests using them.
+const SIMULATOR_OBJECT_TTL: i64 = 24 * 60 * 60;
+
+#[async_trait::async_trait]
+pub trait SimulatorInterface {
+    async fn find_simulator_object(
+        &self,
+        key: &str,
+    ) -> CustomResult<Option<Vec<u8>>, errors::StorageError>;
+
+    async fn insert_or_update_simulator_object(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+    ) -> CustomResult<(), errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl SimulatorInterface for Store {
+    async fn find_simulator_object(
+        &self,
+        key: &str,
+    ) -> CustomResult<Option<Vec<u8>>, errors::StorageError> {
+        self.redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .get_key::<Option<Vec<u8>>>(&format!("simulator_{key}"))
+            .await
+            .change_context(errors::StorageError::KVError)
+            .attach_printable("Failed to fetch simulator object")
+    }
+
+    async fn insert_or_update_simulator_object(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+    ) -> CustomResult<(), errors::StorageError> {
+        self.redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?
+            .set_key_with_expiry(&format!("simulator_{key}"), value, SIMULATOR_OBJECT_TTL)
+            .await
+            .map_err(|error| {
+                logger::error!(simulator_kv_error=?error);
+                errors::StorageError::KVError
+            })
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl SimulatorInterface for MockDb {
+    async fn find_simulator_object(
+        &self,
+        key: &str,
+    ) -> CustomResult<Option<Vec<u8>>, errors::StorageError> {
+        Ok(self.simulator_objects.lock().await.get(key).cloned())
+    }
+
+    async fn insert_or_update_simulator_object(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+    ) -> CustomResult<(), errors::StorageError> {
+        let mut objects = self.simulator_objects.lock().await;
+        objects.insert(key.to_string(), value);
+        Ok(())
+    }
+}
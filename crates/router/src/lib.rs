@@ -49,6 +49,7 @@ pub mod headers {
     pub const APIKEY: &str = "apikey";
     pub const X_CC_API_KEY: &str = "X-CC-Api-Key";
     pub const AUTHORIZATION: &str = "Authorization";
+    pub const BRAINTREE_VERSION: &str = "Braintree-Version";
     pub const CONTENT_TYPE: &str = "Content-Type";
     pub const DATE: &str = "Date";
     pub const NONCE: &str = "nonce";
@@ -57,11 +58,13 @@ pub mod headers {
     pub const X_API_KEY: &str = "X-API-KEY";
     pub const X_API_VERSION: &str = "X-ApiVersion";
     pub const X_MERCHANT_ID: &str = "X-Merchant-Id";
+    pub const X_NETWORK_TOKEN_SIGNATURE: &str = "X-Network-Token-Signature";
     pub const X_LOGIN: &str = "X-Login";
     pub const X_TRANS_KEY: &str = "X-Trans-Key";
     pub const X_VERSION: &str = "X-Version";
     pub const X_CC_VERSION: &str = "X-CC-Version";
     pub const X_DATE: &str = "X-Date";
+    pub const X_FORTE_AUTH_ORG_ID: &str = "X-Forte-Auth-Organization-Id";
 }
 
 pub mod pii {
@@ -101,6 +104,7 @@ pub fn mk_app(
             .service(routes::Payments::server(state.clone()))
             .service(routes::Customers::server(state.clone()))
             .service(routes::Configs::server(state.clone()))
+            .service(routes::Cache::server(state.clone()))
             .service(routes::Refunds::server(state.clone()))
             .service(routes::Payouts::server(state.clone()))
             .service(routes::MerchantConnectorAccount::server(state.clone()))
@@ -112,6 +116,7 @@ pub fn mk_app(
         server_app = server_app
             .service(routes::PaymentMethods::server(state.clone()))
             .service(routes::EphemeralKey::server(state.clone()))
+            .service(routes::PaymentLink::server(state.clone()))
             .service(routes::Webhooks::server(state.clone()));
     }
 
@@ -120,8 +125,15 @@ pub fn mk_app(
         server_app = server_app
             .service(routes::MerchantAccount::server(state.clone()))
             .service(routes::ApiKeys::server(state.clone()))
+            .service(routes::KeyRotation::server(state.clone()))
             .service(routes::Files::server(state.clone()))
-            .service(routes::Disputes::server(state.clone()));
+            .service(routes::Disputes::server(state.clone()))
+            .service(routes::Connectors::server(state.clone()));
+    }
+
+    #[cfg(feature = "simulator")]
+    {
+        server_app = server_app.service(routes::Simulator::server(state.clone()));
     }
 
     #[cfg(feature = "stripe")]
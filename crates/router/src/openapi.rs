@@ -58,6 +58,8 @@ Never share your secret api keys. Keep them guarded and secure.
         (name = "Customers", description = "Create and manage customers"),
         (name = "Payment Methods", description = "Create and manage payment methods of customers"),
         (name = "Disputes", description = "Manage disputes"),
+        (name = "Payment Link", description = "Create and manage hosted payment links"),
+        (name = "Connectors", description = "Discover what each connector supports"),
         // (name = "API Key", description = "Create and manage API Keys"),
     ),
     paths(
@@ -103,6 +105,9 @@ Never share your secret api keys. Keep them guarded and secure.
         // crate::routes::api_keys::api_key_list,
         crate::routes::disputes::retrieve_disputes_list,
         crate::routes::disputes::retrieve_dispute,
+        crate::routes::payment_link::payment_link_create,
+        crate::routes::payment_link::payment_link_retrieve,
+        crate::routes::connector_capabilities::connector_capabilities_list,
     ),
     components(schemas(
         crate::types::api::refunds::RefundRequest,
@@ -147,16 +152,25 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::CardNetwork,
         api_models::enums::DisputeStage,
         api_models::enums::DisputeStatus,
+        api_models::enums::PaymentLinkStatus,
+        api_models::enums::ErrorCategory,
         api_models::enums::CountryCode,
         api_models::admin::MerchantConnectorCreate,
         api_models::admin::PaymentMethodsEnabled,
         api_models::disputes::DisputeResponse,
+        api_models::connector_capabilities::ConnectorCapabilitiesResponse,
+        api_models::connector_capabilities::ConnectorCapabilities,
+        api_models::connector_capabilities::ConnectorFlow,
+        api_models::connector_capabilities::PaymentMethodCapability,
+        api_models::payment_link::PaymentLinkCreateRequest,
+        api_models::payment_link::PaymentLinkResponse,
         api_models::payments::AddressDetails,
         api_models::payments::Address,
         api_models::payments::BankRedirectData,
         api_models::payments::BankRedirectBilling,
         api_models::payments::BankRedirectBilling,
         api_models::payments::OrderDetails,
+        api_models::payments::PurchaseDetails,
         api_models::payments::NextActionType,
         api_models::payments::Metadata,
         api_models::payments::WalletData,
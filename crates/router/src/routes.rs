@@ -1,25 +1,34 @@
 pub mod admin;
 pub mod api_keys;
 pub mod app;
+pub mod cache;
 pub mod cards_info;
 pub mod configs;
+pub mod connector_audit_log;
+pub mod connector_capabilities;
 pub mod customers;
 pub mod disputes;
 pub mod ephemeral_key;
 pub mod files;
 pub mod health;
+pub mod key_rotation;
 pub mod mandates;
 pub mod metrics;
+pub mod payment_link;
 pub mod payment_methods;
 pub mod payments;
 pub mod payouts;
 pub mod refunds;
+#[cfg(feature = "simulator")]
+pub mod simulator;
 pub mod webhooks;
 
+#[cfg(feature = "simulator")]
+pub use self::app::Simulator;
 pub use self::app::{
-    ApiKeys, AppState, Cards, Configs, Customers, Disputes, EphemeralKey, Files, Health, Mandates,
-    MerchantAccount, MerchantConnectorAccount, PaymentMethods, Payments, Payouts, Refunds,
-    Webhooks,
+    ApiKeys, AppState, Cache, Cards, Configs, Connectors, Customers, Disputes, EphemeralKey, Files,
+    Health, KeyRotation, Mandates, MerchantAccount, MerchantConnectorAccount, PaymentLink,
+    PaymentMethods, Payments, Payouts, Refunds, Webhooks,
 };
 #[cfg(feature = "stripe")]
 pub use super::compatibility::stripe::StripeApis;
@@ -45,8 +45,7 @@ pub async fn api_key_create(
             api_keys::create_api_key(
                 &*state.store,
                 &state.conf.api_keys,
-                #[cfg(feature = "kms")]
-                &state.conf.kms,
+                &state.conf.secrets_management,
                 payload,
                 merchant_id.clone(),
             )
@@ -2,12 +2,17 @@ use actix_web::{web, Scope};
 use tokio::sync::oneshot;
 
 use super::health::*;
+#[cfg(feature = "simulator")]
+use super::simulator::*;
 #[cfg(feature = "olap")]
-use super::{admin::*, api_keys::*, disputes::*, files::*};
+use super::{
+    admin::*, api_keys::*, connector_audit_log::*, connector_capabilities::*, disputes::*,
+    files::*, key_rotation::*,
+};
 #[cfg(any(feature = "olap", feature = "oltp"))]
-use super::{configs::*, customers::*, mandates::*, payments::*, payouts::*, refunds::*};
+use super::{cache::*, configs::*, customers::*, mandates::*, payments::*, payouts::*, refunds::*};
 #[cfg(feature = "oltp")]
-use super::{ephemeral_key::*, payment_methods::*, webhooks::*};
+use super::{ephemeral_key::*, payment_link::*, payment_methods::*, webhooks::*};
 use crate::{
     configs::settings::Settings,
     db::{MockDb, StorageImpl, StorageInterface},
@@ -73,6 +78,7 @@ impl Health {
         web::scope("")
             .app_data(web::Data::new(state))
             .service(web::resource("/health").route(web::get().to(health)))
+            .service(web::resource("/health/ready").route(web::get().to(readiness)))
     }
 }
 
@@ -227,19 +233,26 @@ pub struct PaymentMethods;
 #[cfg(feature = "oltp")]
 impl PaymentMethods {
     pub fn server(state: AppState) -> Scope {
-        web::scope("/payment_methods")
+        let network_tokenization_enabled = state.conf.network_tokenization.enabled;
+        let mut route = web::scope("/payment_methods")
             .app_data(web::Data::new(state))
             .service(
                 web::resource("")
                     .route(web::post().to(create_payment_method_api))
                     .route(web::get().to(list_payment_method_api)), // TODO : added for sdk compatibility for now, need to deprecate this later
-            )
-            .service(
-                web::resource("/{payment_method_id}")
-                    .route(web::get().to(payment_method_retrieve_api))
-                    .route(web::post().to(payment_method_update_api))
-                    .route(web::delete().to(payment_method_delete_api)),
-            )
+            );
+        if network_tokenization_enabled {
+            route = route.service(
+                web::resource("/network_token/notifications")
+                    .route(web::post().to(network_token_notification)),
+            );
+        }
+        route.service(
+            web::resource("/{payment_method_id}")
+                .route(web::get().to(payment_method_retrieve_api))
+                .route(web::post().to(payment_method_update_api))
+                .route(web::delete().to(payment_method_delete_api)),
+        )
     }
 }
 
@@ -367,6 +380,28 @@ impl Configs {
     }
 }
 
+pub struct Cache;
+
+#[cfg(any(feature = "olap", feature = "oltp"))]
+impl Cache {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/cache")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/invalidate/{key}").route(web::post().to(invalidate)))
+    }
+}
+
+pub struct KeyRotation;
+
+#[cfg(feature = "olap")]
+impl KeyRotation {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/key_rotation")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/report").route(web::get().to(key_rotation_report)))
+    }
+}
+
 pub struct ApiKeys;
 
 #[cfg(feature = "olap")]
@@ -399,6 +434,64 @@ impl Disputes {
     }
 }
 
+pub struct Connectors;
+
+#[cfg(feature = "olap")]
+impl Connectors {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/connectors")
+            .app_data(web::Data::new(state))
+            .service(
+                web::resource("/capabilities").route(web::get().to(connector_capabilities_list)),
+            )
+            .service(
+                web::resource("/audit_log/{merchant_id}/{payment_id}")
+                    .route(web::get().to(connector_audit_log_retrieve)),
+            )
+    }
+}
+
+#[cfg(feature = "simulator")]
+pub struct Simulator;
+
+#[cfg(feature = "simulator")]
+impl Simulator {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/simulator")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/payments").route(web::post().to(simulator_payments_create)))
+            .service(
+                web::resource("/payments/{payment_id}")
+                    .route(web::get().to(simulator_payments_retrieve)),
+            )
+            .service(
+                web::resource("/payments/{payment_id}/capture")
+                    .route(web::post().to(simulator_payments_capture)),
+            )
+            .service(
+                web::resource("/payments/{payment_id}/void")
+                    .route(web::post().to(simulator_payments_void)),
+            )
+            .service(
+                web::resource("/payments/{payment_id}/authenticate")
+                    .route(web::get().to(simulator_payments_authenticate)),
+            )
+            .service(web::resource("/refunds").route(web::post().to(simulator_refunds_create)))
+            .service(
+                web::resource("/refunds/{refund_id}")
+                    .route(web::get().to(simulator_refunds_retrieve)),
+            )
+            .service(
+                web::resource("/disputes/{dispute_id}/accept")
+                    .route(web::post().to(simulator_disputes_accept)),
+            )
+            .service(
+                web::resource("/disputes/{dispute_id}/evidence")
+                    .route(web::post().to(simulator_disputes_evidence_submit)),
+            )
+    }
+}
+
 pub struct Cards;
 
 impl Cards {
@@ -424,3 +517,22 @@ impl Files {
             )
     }
 }
+
+pub struct PaymentLink;
+
+#[cfg(feature = "oltp")]
+impl PaymentLink {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/payment_link")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::post().to(payment_link_create)))
+            .service(
+                web::resource("/pay/{payment_link_id}")
+                    .route(web::get().to(payment_link_checkout))
+                    .route(web::post().to(payment_link_pay)),
+            )
+            .service(
+                web::resource("/{payment_link_id}").route(web::get().to(payment_link_retrieve)),
+            )
+    }
+}
@@ -0,0 +1,28 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::cache,
+    services::{api, authentication as auth},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::CacheInvalidate))]
+pub async fn invalidate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::CacheInvalidate;
+    let key = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        &key,
+        |state, _, key| cache::invalidate(&*state.store, key),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
@@ -0,0 +1,37 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::connector_audit_log,
+    services::{api, authentication as auth},
+    types::api as api_types,
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorAuditLogRetrieve))]
+pub async fn connector_audit_log_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    query: web::Query<api_types::ConnectorAuditLogConstraints>,
+) -> impl Responder {
+    let flow = Flow::ConnectorAuditLogRetrieve;
+    let (merchant_id, payment_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (&merchant_id, &payment_id, query.into_inner()),
+        |state, _, (merchant_id, payment_id, constraints)| {
+            connector_audit_log::retrieve_connector_audit_log(
+                state,
+                merchant_id,
+                payment_id,
+                constraints,
+            )
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
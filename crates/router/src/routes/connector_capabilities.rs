@@ -0,0 +1,39 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::connector_capabilities,
+    services::{api, authentication as auth},
+};
+
+/// Connectors - Capabilities
+///
+/// List the flows, payment methods, currencies and countries supported by each connector
+#[utoipa::path(
+    get,
+    path = "/connectors/capabilities",
+    responses(
+        (status = 200, description = "Connector capabilities listed", body = ConnectorCapabilitiesResponse),
+    ),
+    tag = "Connectors",
+    operation_id = "List connector capabilities",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorCapabilitiesList))]
+pub async fn connector_capabilities_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    let flow = Flow::ConnectorCapabilitiesList;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, _, _| connector_capabilities::list_connector_capabilities(state),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
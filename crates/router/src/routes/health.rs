@@ -1,6 +1,8 @@
-use router_env::{instrument, logger, tracing};
+use actix_web::web;
+use router_env::{instrument, logger, tracing, Flow};
 
-use crate::routes::metrics;
+use super::app::AppState;
+use crate::{core::health_check, routes::metrics, types::api::HealthStatus};
 
 /// .
 // #[logger::instrument(skip_all, name = "name1", level = "warn", fields( key1 = "val1" ))]
@@ -11,3 +13,17 @@ pub async fn health() -> impl actix_web::Responder {
     logger::info!("Health was called");
     actix_web::HttpResponse::Ok().body("health is good")
 }
+
+/// Checks the dependencies of the application, responding with `503 Service Unavailable` if any
+/// of them are unhealthy.
+#[instrument(skip_all, fields(flow = ?Flow::HealthReadiness))]
+pub async fn readiness(state: web::Data<AppState>) -> impl actix_web::Responder {
+    metrics::HEALTH_READINESS_METRIC.add(&metrics::CONTEXT, 1, &[]);
+    let report = health_check::readiness(state.get_ref()).await;
+    logger::info!(status = ?report.status, "Readiness was checked");
+
+    match report.status {
+        HealthStatus::Unhealthy => actix_web::HttpResponse::ServiceUnavailable().json(report),
+        HealthStatus::Healthy | HealthStatus::Skipped => actix_web::HttpResponse::Ok().json(report),
+    }
+}
@@ -0,0 +1,23 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::key_rotation,
+    services::{api, authentication as auth},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::KeyRotationReport))]
+pub async fn key_rotation_report(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    let flow = Flow::KeyRotationReport;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, _, _| key_rotation::report(state),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
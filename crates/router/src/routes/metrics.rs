@@ -4,7 +4,15 @@ metrics_context!(CONTEXT);
 global_meter!(GLOBAL_METER, "ROUTER_API");
 
 counter_metric!(HEALTH_METRIC, GLOBAL_METER); // No. of health API hits
+counter_metric!(HEALTH_READINESS_METRIC, GLOBAL_METER); // No. of readiness check API hits
 counter_metric!(KV_MISS, GLOBAL_METER); // No. of KV misses
+
+// In-memory Cache Metrics
+counter_metric!(CACHE_HIT, GLOBAL_METER);
+counter_metric!(CACHE_MISS, GLOBAL_METER);
+counter_metric!(CACHE_EVICTION_COUNT, GLOBAL_METER);
+counter_metric!(CACHE_INVALIDATIONS_RECEIVED, GLOBAL_METER);
+counter_metric!(CACHE_SUBSCRIBER_RECONNECTS, GLOBAL_METER);
 #[cfg(feature = "kms")]
 counter_metric!(AWS_KMS_FAILURES, GLOBAL_METER); // No. of AWS KMS API failures
 
@@ -41,6 +49,7 @@ counter_metric!(CUSTOMER_REDACTED, GLOBAL_METER);
 
 counter_metric!(API_KEY_CREATED, GLOBAL_METER);
 counter_metric!(API_KEY_REVOKED, GLOBAL_METER);
+counter_metric!(API_KEY_REHASHED, GLOBAL_METER);
 
 // Flow Specific Metrics
 
@@ -60,6 +69,9 @@ counter_metric!(REQUEST_TIMEOUT_COUNT, GLOBAL_METER);
 // Connector Level Metric
 counter_metric!(REQUEST_BUILD_FAILURE, GLOBAL_METER);
 counter_metric!(UNIMPLEMENTED_FLOW, GLOBAL_METER);
+counter_metric!(CIRCUIT_BREAKER_OPENED, GLOBAL_METER);
+counter_metric!(CIRCUIT_BREAKER_REJECTED_REQUESTS, GLOBAL_METER);
+counter_metric!(CONNECTOR_AUDIT_LOG_FAILURES, GLOBAL_METER);
 
 // Service Level
 counter_metric!(CARD_LOCKER_FAILURES, GLOBAL_METER);
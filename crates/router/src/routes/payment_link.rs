@@ -0,0 +1,118 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::payment_link,
+    services::{api, authentication as auth},
+    types::api as api_types,
+};
+
+/// Payment Link - Create
+///
+/// Create a shareable link to a hosted checkout page, through which a customer can pay the
+/// amount specified in the request until the link expires.
+#[utoipa::path(
+    post,
+    path = "/payment_link",
+    request_body = PaymentLinkCreateRequest,
+    responses(
+        (status = 200, description = "Payment link created", body = PaymentLinkResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Payment Link",
+    operation_id = "Create a Payment Link",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentLinkCreate))]
+pub async fn payment_link_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<api_types::PaymentLinkCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinkCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        payment_link::create_payment_link,
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payment Link - Retrieve
+///
+/// Retrieve a payment link by its ID.
+#[utoipa::path(
+    get,
+    path = "/payment_link/{payment_link_id}",
+    params(("payment_link_id" = String, Path, description = "The identifier for the payment link")),
+    responses(
+        (status = 200, description = "Payment link retrieved", body = PaymentLinkResponse),
+        (status = 404, description = "Payment link not found")
+    ),
+    tag = "Payment Link",
+    operation_id = "Retrieve a Payment Link",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentLinkRetrieve))]
+pub async fn payment_link_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinkRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        payment_link::retrieve_payment_link,
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::PaymentLinkCheckout))]
+pub async fn payment_link_checkout(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinkCheckout;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, _, payment_link_id| {
+            payment_link::initiate_payment_link_checkout(state, payment_link_id)
+        },
+        &auth::NoAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::PaymentLinkPay))]
+pub async fn payment_link_pay(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    form_payload: web::Form<api_types::PaymentLinkCheckoutForm>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinkPay;
+    let payment_link_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        form_payload.into_inner(),
+        |state, _, form| {
+            payment_link::complete_payment_link_checkout(state, payment_link_id.clone(), form)
+        },
+        &auth::NoAuth,
+    )
+    .await
+}
@@ -3,7 +3,7 @@ use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
-    core::payment_methods::cards,
+    core::payment_methods::{cards, network_tokenization},
     services::{api, authentication as auth},
     types::api::payment_methods::{self, PaymentMethodId},
 };
@@ -293,3 +293,23 @@ mod tests {
         assert!(de_query.is_err())
     }
 }
+
+#[instrument(skip_all, fields(flow = ?Flow::NetworkTokenNotification))]
+pub async fn network_token_notification(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Bytes,
+) -> HttpResponse {
+    let flow = Flow::NetworkTokenNotification;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        body,
+        |state, _, body| {
+            network_tokenization::handle_network_token_notification(state, req.headers(), body)
+        },
+        &auth::NoAuth,
+    )
+    .await
+}
@@ -0,0 +1,191 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::simulator as simulator_models;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::simulator,
+    services::{api, authentication as auth},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorPaymentsCreate))]
+pub async fn simulator_payments_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<simulator_models::SimulatorPaymentRequest>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorPaymentsCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, merchant_account, req| simulator::create_payment(state, merchant_account, req),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorPaymentsRetrieve))]
+pub async fn simulator_payments_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorPaymentsRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, merchant_account, payment_id| {
+            simulator::retrieve_payment(state, merchant_account, payment_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorPaymentsCapture))]
+pub async fn simulator_payments_capture(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<simulator_models::SimulatorCaptureRequest>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorPaymentsCapture;
+    let payment_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, merchant_account, req| {
+            simulator::capture_payment(state, merchant_account, payment_id.clone(), req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorPaymentsVoid))]
+pub async fn simulator_payments_void(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorPaymentsVoid;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, merchant_account, payment_id| {
+            simulator::void_payment(state, merchant_account, payment_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Page the customer is redirected to for the 3DS challenge of a simulator payment, not
+/// authenticated as it is opened by the customer's browser
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorPaymentsAuthenticate))]
+pub async fn simulator_payments_authenticate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query_payload: web::Query<simulator_models::SimulatorAuthenticationParams>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorPaymentsAuthenticate;
+    let payment_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query_payload.into_inner(),
+        |state, _, params| simulator::authenticate_payment(state, payment_id.clone(), params),
+        &auth::NoAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorRefundsCreate))]
+pub async fn simulator_refunds_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<simulator_models::SimulatorRefundRequest>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorRefundsCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, merchant_account, req| simulator::create_refund(state, merchant_account, req),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorRefundsRetrieve))]
+pub async fn simulator_refunds_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorRefundsRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, merchant_account, refund_id| {
+            simulator::retrieve_refund(state, merchant_account, refund_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorDisputesAccept))]
+pub async fn simulator_disputes_accept(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorDisputesAccept;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, merchant_account, dispute_id| {
+            simulator::accept_dispute(state, merchant_account, dispute_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::SimulatorDisputesEvidenceSubmit))]
+pub async fn simulator_disputes_evidence_submit(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<simulator_models::SimulatorEvidenceRequest>,
+) -> HttpResponse {
+    let flow = Flow::SimulatorDisputesEvidenceSubmit;
+    let dispute_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, merchant_account, req| {
+            simulator::submit_evidence(state, merchant_account, dispute_id.clone(), req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
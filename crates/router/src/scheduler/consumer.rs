@@ -24,7 +24,7 @@ use crate::{
     db::StorageInterface,
     logger,
     routes::AppState,
-    scheduler::utils as pt_utils,
+    scheduler::{utils as pt_utils, SchedulerFlow},
     types::storage::{self, enums, ProcessTrackerExt},
 };
 
@@ -67,6 +67,7 @@ pub async fn start_consumer(
         match rx.try_recv() {
             Err(mpsc::error::TryRecvError::Empty) => {
                 interval.tick().await;
+                pt_utils::update_heartbeat(state, &settings, SchedulerFlow::Consumer).await;
 
                 // A guard from env to disable the consumer
                 if settings.consumer.disabled {
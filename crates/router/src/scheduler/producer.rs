@@ -12,7 +12,7 @@ use crate::{
     db::StorageInterface,
     logger::{self, debug, error, warn},
     routes::AppState,
-    scheduler::{utils::*, SchedulerFlow},
+    scheduler::{utils::*, workflows::payment_intent_expiry, SchedulerFlow},
     types::storage::{self, enums::ProcessTrackerStatus},
 };
 
@@ -48,6 +48,7 @@ pub async fn start_producer(
         match rx.try_recv() {
             Err(mpsc::error::TryRecvError::Empty) => {
                 interval.tick().await;
+                update_heartbeat(state, &scheduler_settings, SchedulerFlow::Producer).await;
                 match run_producer_flow(state, &scheduler_settings).await {
                     Ok(_) => (),
                     Err(error) => {
@@ -89,6 +90,12 @@ pub async fn run_producer_flow(
 
         divide_and_append_tasks(state, SchedulerFlow::Producer, tasks, settings).await?;
 
+        payment_intent_expiry::expire_payment_intents(
+            state,
+            settings.producer.intent_expiry_batch_size,
+        )
+        .await?;
+
         Ok(())
     })
     .await?;
@@ -236,6 +236,23 @@ pub fn get_process_tracker_id<'a>(
     format!("{runner}_{task_name}_{txn_id}_{merchant_id}")
 }
 
+/// Key under which the given scheduler flow records its heartbeat, for example
+/// `SCHEDULER_STREAM_producer_heartbeat`
+pub fn get_heartbeat_key(settings: &SchedulerSettings, flow: SchedulerFlow) -> String {
+    format!("{}_{flow}_heartbeat", settings.stream)
+}
+
+pub(crate) async fn update_heartbeat(
+    state: &AppState,
+    settings: &SchedulerSettings,
+    flow: SchedulerFlow,
+) {
+    let heartbeat_key = get_heartbeat_key(settings, flow);
+    if let Err(error) = state.store.update_scheduler_heartbeat(&heartbeat_key).await {
+        logger::error!(?error, %heartbeat_key, "Failed to update scheduler heartbeat");
+    }
+}
+
 pub fn get_time_from_delta(delta: Option<i32>) -> Option<time::PrimitiveDateTime> {
     delta.map(|t| common_utils::date_time::now().saturating_add(time::Duration::seconds(t.into())))
 }
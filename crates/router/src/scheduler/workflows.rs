@@ -8,6 +8,8 @@ use crate::{
     types::storage,
     utils::{OptionExt, StringExt},
 };
+pub mod payment_intent_expiry;
+pub mod payment_link;
 pub mod payment_sync;
 pub mod refund_router;
 pub mod tokenized_data;
@@ -52,7 +54,9 @@ macro_rules! as_item {
 runners! {
     PaymentsSyncWorkflow,
     RefundWorkflowRouter,
-    DeleteTokenizeDataWorkflow
+    DeleteTokenizeDataWorkflow,
+    PaymentLinkExpiryWorkflow,
+    PaymentIntentExpiryWorkflow
 }
 
 pub type WorkflowSelectorFn =
@@ -0,0 +1,328 @@
+use error_stack::ResultExt;
+use router_env::{instrument, logger, tracing};
+
+use super::{PaymentIntentExpiryWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::{
+        payment_methods::vault,
+        payments::{self as payment_flows, operations},
+        webhooks,
+    },
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    scheduler::{consumer, utils as pt_utils},
+    services,
+    types::{
+        api,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+    utils::ValueExt,
+};
+
+const SESSION_EXPIRED_REASON: &str = "payment_session_expired";
+const PAYMENT_INTENT_EXPIRY_RUNNER: &str = "PAYMENT_INTENT_EXPIRY_WORKFLOW";
+const PAYMENT_INTENT_EXPIRY_TASK: &str = "PAYMENT_INTENT_EXPIRY";
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for PaymentIntentExpiryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let tracking_data: storage::PaymentIntentExpiryTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentIntentExpiryTrackingData")?;
+
+        expire_payment_intent(state, &tracking_data.payment_id, &tracking_data.merchant_id).await?;
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(&*state.store, format!("COMPLETED_BY_PT_{id}"))
+            .await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
+
+/// Finds payment intents whose session expired without the payment being completed and enqueues
+/// an expiry task for each of them. Runs as part of the producer loop, so it must not call the
+/// connector itself; the consumer picks up the tasks and cancels the payments.
+#[instrument(skip_all)]
+pub async fn expire_payment_intents(
+    state: &AppState,
+    batch_size: i64,
+) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+    let db: &dyn StorageInterface = &*state.store;
+    let expired_intents = db
+        .find_expired_payment_intents(
+            common_utils::date_time::now(),
+            ABANDONED_STATUSES.to_vec(),
+            batch_size,
+        )
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
+    logger::debug!("Found {} expired payment intents", expired_intents.len());
+
+    for payment_intent in expired_intents {
+        // Failures are retried on the next run of the producer, as the intent stays expired
+        if let Err(error) = add_payment_intent_expiry_task(db, &payment_intent).await {
+            logger::error!(
+                ?error,
+                payment_id = %payment_intent.payment_id,
+                "Failed to add expiry task for payment intent"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn add_payment_intent_expiry_task(
+    db: &dyn StorageInterface,
+    payment_intent: &storage::PaymentIntent,
+) -> Result<(), errors::ProcessTrackerError> {
+    let tracking_data = storage::PaymentIntentExpiryTrackingData {
+        payment_id: payment_intent.payment_id.clone(),
+        merchant_id: payment_intent.merchant_id.clone(),
+    };
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        PAYMENT_INTENT_EXPIRY_RUNNER,
+        PAYMENT_INTENT_EXPIRY_TASK,
+        &payment_intent.payment_id,
+        &payment_intent.merchant_id,
+    );
+    let process_tracker_entry =
+        <storage::ProcessTracker as ProcessTrackerExt>::make_process_tracker_new(
+            process_tracker_id,
+            PAYMENT_INTENT_EXPIRY_TASK,
+            PAYMENT_INTENT_EXPIRY_RUNNER,
+            tracking_data,
+            common_utils::date_time::now(),
+        )?;
+
+    match db.insert_process(process_tracker_entry).await {
+        Ok(_) => Ok(()),
+        // The intent stays expired until the consumer has processed its task, so it is selected
+        // again by every sweep in the meantime
+        Err(error) if error.current_context().is_db_unique_violation() => Ok(()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+async fn expire_payment_intent(
+    state: &AppState,
+    payment_id: &str,
+    merchant_id: &str,
+) -> Result<(), errors::ProcessTrackerError> {
+    let db: &dyn StorageInterface = &*state.store;
+    let merchant_account = db.find_merchant_account_by_merchant_id(merchant_id).await?;
+    let storage_scheme = merchant_account.storage_scheme;
+
+    // The intent listed by the sweep may be stale for merchants on the KV scheme
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(payment_id, merchant_id, storage_scheme)
+        .await?;
+    if !is_abandoned(payment_intent.status) {
+        return Ok(());
+    }
+
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &payment_intent.merchant_id,
+            &payment_intent.active_attempt_id,
+            storage_scheme,
+        )
+        .await?;
+    let payment_token = payment_attempt.payment_token.clone();
+
+    let cancelled = if payment_attempt.connector_transaction_id.is_some() {
+        // The customer may have completed the payment with the connector without being
+        // redirected back, so the latest status has to be fetched before cancelling
+        let (payment_data, _, _) = payment_flows::payments_operation_core::<api::PSync, _, _, _>(
+            state,
+            merchant_account.clone(),
+            operations::PaymentStatus,
+            api::PaymentsRetrieveRequest {
+                resource_id: api::PaymentIdType::PaymentIntentId(payment_id.to_string()),
+                merchant_id: Some(merchant_account.merchant_id.clone()),
+                force_sync: true,
+                ..Default::default()
+            },
+            payment_flows::CallConnectorAction::Trigger,
+        )
+        .await?;
+
+        match payment_data.payment_intent.status {
+            enums::IntentStatus::RequiresCapture => {
+                void_payment(state, &merchant_account, payment_id).await?;
+                true
+            }
+            // The connector still holds a pending transaction which cannot be voided, so the
+            // payment is only marked as expired on our side
+            status if is_abandoned(status) => {
+                mark_payment_expired(
+                    db,
+                    payment_data.payment_intent,
+                    payment_data.payment_attempt,
+                    storage_scheme,
+                )
+                .await?;
+                true
+            }
+            _ => false,
+        }
+    } else {
+        cancel_payment(db, payment_intent, payment_attempt, storage_scheme).await?;
+        true
+    };
+
+    if cancelled {
+        vault::Vault::delete_locker_payment_method_by_lookup_key(state, &payment_token).await;
+        notify_merchant(state, merchant_account, payment_id).await;
+    }
+
+    Ok(())
+}
+
+const ABANDONED_STATUSES: [enums::IntentStatus; 3] = [
+    enums::IntentStatus::RequiresPaymentMethod,
+    enums::IntentStatus::RequiresConfirmation,
+    enums::IntentStatus::RequiresCustomerAction,
+];
+
+fn is_abandoned(status: enums::IntentStatus) -> bool {
+    ABANDONED_STATUSES.contains(&status)
+}
+
+/// Cancels a payment which never reached the connector
+async fn cancel_payment(
+    db: &dyn StorageInterface,
+    payment_intent: storage::PaymentIntent,
+    payment_attempt: storage::PaymentAttempt,
+    storage_scheme: enums::MerchantStorageScheme,
+) -> Result<(), errors::ProcessTrackerError> {
+    db.update_payment_attempt_with_attempt_id(
+        payment_attempt,
+        storage::PaymentAttemptUpdate::VoidUpdate {
+            status: enums::AttemptStatus::Voided,
+            cancellation_reason: Some(SESSION_EXPIRED_REASON.to_string()),
+        },
+        storage_scheme,
+    )
+    .await?;
+
+    db.update_payment_intent(
+        payment_intent,
+        storage::PaymentIntentUpdate::PGStatusUpdate {
+            status: enums::IntentStatus::Cancelled,
+        },
+        storage_scheme,
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn mark_payment_expired(
+    db: &dyn StorageInterface,
+    payment_intent: storage::PaymentIntent,
+    payment_attempt: storage::PaymentAttempt,
+    storage_scheme: enums::MerchantStorageScheme,
+) -> Result<(), errors::ProcessTrackerError> {
+    db.update_payment_attempt_with_attempt_id(
+        payment_attempt,
+        storage::PaymentAttemptUpdate::ErrorUpdate {
+            connector: None,
+            status: enums::AttemptStatus::Failure,
+            error_code: Some(Some(SESSION_EXPIRED_REASON.to_string())),
+            error_message: Some(Some(
+                "Payment session expired before the customer completed the payment".to_string(),
+            )),
+        },
+        storage_scheme,
+    )
+    .await?;
+
+    db.update_payment_intent(
+        payment_intent,
+        storage::PaymentIntentUpdate::PGStatusUpdate {
+            status: enums::IntentStatus::Cancelled,
+        },
+        storage_scheme,
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn void_payment(
+    state: &AppState,
+    merchant_account: &storage::MerchantAccount,
+    payment_id: &str,
+) -> Result<(), errors::ProcessTrackerError> {
+    payment_flows::payments_core::<api::Void, api::PaymentsResponse, _, _, _>(
+        state,
+        merchant_account.clone(),
+        payment_flows::PaymentCancel,
+        api::PaymentsCancelRequest {
+            payment_id: payment_id.to_string(),
+            cancellation_reason: Some(SESSION_EXPIRED_REASON.to_string()),
+            merchant_connector_details: None,
+        },
+        services::AuthFlow::Merchant,
+        payment_flows::CallConnectorAction::Trigger,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Failures are only logged, the payment has already been cancelled at this point
+async fn notify_merchant(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    payment_id: &str,
+) {
+    let payments_response =
+        payment_flows::payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+            state,
+            merchant_account.clone(),
+            operations::PaymentStatus,
+            api::PaymentsRetrieveRequest {
+                resource_id: api::PaymentIdType::PaymentIntentId(payment_id.to_string()),
+                merchant_id: Some(merchant_account.merchant_id.clone()),
+                ..Default::default()
+            },
+            services::AuthFlow::Merchant,
+            payment_flows::CallConnectorAction::Trigger,
+        )
+        .await;
+
+    let result = match payments_response {
+        Ok(services::ApplicationResponse::Json(payments_response)) => {
+            webhooks::trigger_payment_outgoing_webhook::<api::OutgoingWebhook>(
+                state.clone(),
+                merchant_account,
+                payments_response,
+            )
+            .await
+        }
+        Ok(_) => Err(errors::WebhooksFlowError::PaymentsCoreFailed.into()),
+        Err(error) => Err(error.change_context(errors::WebhooksFlowError::PaymentsCoreFailed)),
+    };
+
+    if let Err(error) = result {
+        logger::error!(?error, "Failed to notify merchant about expired payment");
+    }
+}
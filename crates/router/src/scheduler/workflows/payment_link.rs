@@ -0,0 +1,22 @@
+use super::{PaymentLinkExpiryWorkflow, ProcessTrackerWorkflow};
+use crate::{core::payment_link, errors, routes::AppState, scheduler::consumer, types::storage};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for PaymentLinkExpiryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        payment_link::start_payment_link_expiry_workflow(state, &process).await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
@@ -1,4 +1,5 @@
 pub mod api;
+pub mod audit_log;
 pub mod authentication;
 pub mod encryption;
 pub mod logger;
@@ -6,17 +7,19 @@ pub mod logger;
 use std::sync::{atomic, Arc};
 
 use error_stack::{IntoReport, ResultExt};
-use redis_interface::{errors as redis_errors, PubsubInterface};
+use external_services::secrets_management::SecretsManagementConfig;
+use masking::StrongSecret;
+use redis_interface::{errors as redis_errors, ClientLike, PubsubInterface};
 use tokio::sync::oneshot;
 
 pub use self::{api::*, encryption::*};
 use crate::{
     async_spawn,
-    cache::CONFIG_CACHE,
+    cache::{self, CacheInvalidation},
     configs::settings,
     connection::{diesel_make_pg_pool, PgPool},
-    consts,
     core::errors,
+    routes::metrics,
 };
 
 #[async_trait::async_trait]
@@ -29,7 +32,7 @@ pub trait PubSubInterface {
     async fn publish(
         &self,
         channel: &str,
-        key: &str,
+        message: &str,
     ) -> errors::CustomResult<usize, redis_errors::RedisError>;
 
     async fn on_message(&self) -> errors::CustomResult<(), redis_errors::RedisError>;
@@ -53,28 +56,77 @@ impl PubSubInterface for redis_interface::RedisConnectionPool {
     async fn publish(
         &self,
         channel: &str,
-        key: &str,
+        message: &str,
     ) -> errors::CustomResult<usize, redis_errors::RedisError> {
         self.publisher
-            .publish(channel, key)
+            .publish(channel, message)
             .await
             .into_report()
-            .change_context(redis_errors::RedisError::SubscribeError)
+            .change_context(redis_errors::RedisError::PublishError)
     }
 
+    /// Invalidates the in-memory caches as messages are received on the invalidation channel,
+    /// returning when the connection of the subscriber is re-established, since the channel needs
+    /// to be subscribed to again and messages may have been missed in the meantime.
     #[inline]
     async fn on_message(&self) -> errors::CustomResult<(), redis_errors::RedisError> {
+        use tokio::sync::broadcast::error::RecvError;
+
         let mut rx = self.subscriber.on_message();
-        while let Ok(message) = rx.recv().await {
-            let key = message
-                .value
-                .as_string()
-                .ok_or::<redis_errors::RedisError>(redis_errors::RedisError::DeleteFailed)?;
-
-            self.delete_key(&key).await?;
-            CONFIG_CACHE.invalidate(&key).await;
+        let mut reconnect_rx = self.subscriber.on_reconnect();
+        loop {
+            tokio::select! {
+                message = rx.recv() => match message {
+                    Ok(message) => match message.value.as_string() {
+                        Some(message) => {
+                            CacheInvalidation::from_message(&message).invalidate().await;
+                            metrics::CACHE_INVALIDATIONS_RECEIVED.add(&metrics::CONTEXT, 1, &[]);
+                        }
+                        None => logger::warn!("Ignoring non-string cache invalidation message"),
+                    },
+                    Err(RecvError::Lagged(skipped)) => {
+                        logger::warn!(%skipped, "Cache invalidation messages were skipped");
+                        cache::invalidate_all();
+                    }
+                    Err(RecvError::Closed) => {
+                        return Err(redis_errors::RedisError::SubscribeError).into_report()
+                    }
+                },
+                _ = reconnect_rx.recv() => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Keeps the instance subscribed to the cache invalidation channel, subscribing again whenever
+/// the subscriber reconnects or fails. The in-memory caches are cleared every time, as
+/// invalidations published while the instance was not subscribed are lost.
+pub async fn subscribe_to_cache_invalidations(
+    redis_conn: Arc<redis_interface::RedisConnectionPool>,
+) {
+    const MAX_BACKOFF_SECS: u64 = 30;
+    let mut backoff_secs = 1;
+
+    loop {
+        let result = match redis_conn
+            .subscribe(common_utils::consts::PUB_SUB_CHANNEL)
+            .await
+        {
+            Ok(_) => redis_conn.on_message().await,
+            Err(error) => Err(error),
+        };
+
+        cache::invalidate_all();
+        metrics::CACHE_SUBSCRIBER_RECONNECTS.add(&metrics::CONTEXT, 1, &[]);
+
+        match result {
+            Ok(()) => backoff_secs = 1,
+            Err(error) => {
+                logger::error!(?error, "Cache invalidation subscriber failed");
+                tokio::time::sleep(std::time::Duration::from_secs(backoff_secs)).await;
+                backoff_secs = (backoff_secs * 2).min(MAX_BACKOFF_SECS);
+            }
         }
-        Ok(())
     }
 }
 
@@ -86,6 +138,7 @@ pub struct Store {
     pub redis_conn: Arc<redis_interface::RedisConnectionPool>,
     #[cfg(feature = "kv_store")]
     pub(crate) config: StoreConfig,
+    pub(crate) pii_encryption: PiiEncryptionConfig,
 }
 
 #[cfg(feature = "kv_store")]
@@ -93,6 +146,46 @@ pub struct Store {
 pub(crate) struct StoreConfig {
     pub(crate) drainer_stream_name: String,
     pub(crate) drainer_num_partitions: u8,
+    /// Duration (in seconds) for which entries are retained in the `created_at` indexes
+    pub(crate) kv_index_retention: u32,
+}
+
+#[derive(Clone)]
+pub(crate) struct PiiEncryptionConfig {
+    /// Whether PII columns are encrypted when written
+    pub(crate) enabled: bool,
+    /// Key encrypting the data keys of merchants, absent when no master key is configured
+    pub(crate) master_key: Option<StrongSecret<Vec<u8>>>,
+}
+
+impl PiiEncryptionConfig {
+    #[allow(clippy::expect_used)]
+    async fn new(
+        config: &settings::PiiEncryptionSettings,
+        secrets_management: &SecretsManagementConfig,
+    ) -> Self {
+        let master_key = if config.master_key.is_empty() {
+            None
+        } else {
+            Some(
+                secrets_management
+                    .get_secret(&config.master_key)
+                    .await
+                    .expect("Failed to retrieve PII encryption master key"),
+            )
+        };
+
+        Self {
+            enabled: config.enabled,
+            master_key: master_key.map(|master_key| {
+                hex::decode(master_key)
+                    .ok()
+                    .filter(|master_key| master_key.len() == 32)
+                    .map(StrongSecret::new)
+                    .expect("PII encryption master key must be a hex-encoded 32-byte key")
+            }),
+        }
+    }
 }
 
 impl Store {
@@ -105,12 +198,8 @@ impl Store {
         let redis_clone = redis_conn.clone();
 
         let subscriber_conn = redis_conn.clone();
-
-        redis_conn.subscribe(consts::PUB_SUB_CHANNEL).await.ok();
         async_spawn!({
-            if let Err(e) = subscriber_conn.on_message().await {
-                logger::error!(pubsub_err=?e);
-            }
+            subscribe_to_cache_invalidations(subscriber_conn).await;
         });
         async_spawn!({
             redis_clone.on_error(shut_down_signal).await;
@@ -120,16 +209,14 @@ impl Store {
             master_pool: diesel_make_pg_pool(
                 &config.master_database,
                 test_transaction,
-                #[cfg(feature = "kms")]
-                &config.kms,
+                &config.secrets_management,
             )
             .await,
             #[cfg(feature = "olap")]
             replica_pool: diesel_make_pg_pool(
                 &config.replica_database,
                 test_transaction,
-                #[cfg(feature = "kms")]
-                &config.kms,
+                &config.secrets_management,
             )
             .await,
             redis_conn,
@@ -137,7 +224,13 @@ impl Store {
             config: StoreConfig {
                 drainer_stream_name: config.drainer.stream_name.clone(),
                 drainer_num_partitions: config.drainer.num_partitions,
+                kv_index_retention: config.redis.default_hash_ttl,
             },
+            pii_encryption: PiiEncryptionConfig::new(
+                &config.pii_encryption,
+                &config.secrets_management,
+            )
+            .await,
         }
     }
 
@@ -171,15 +264,72 @@ impl Store {
     where
         T: crate::utils::storage_partitioning::KvStorePartition,
     {
+        let merchant_id = partition_key.merchant_id();
         let shard_key = T::shard_key(partition_key, self.config.drainer_num_partitions);
         let stream_name = self.get_drainer_stream_name(&shard_key);
+        let mut fields = redis_entry
+            .to_field_value_pairs()
+            .change_context(crate::core::errors::StorageError::KVError)?;
+        if let Some(merchant_id) = merchant_id {
+            fields.push(("merchant_id", merchant_id.to_string()));
+        }
         self.redis_conn
             .stream_append_entry(
                 &stream_name,
                 &redis_interface::RedisEntryId::AutoGeneratedID,
-                redis_entry
-                    .to_field_value_pairs()
-                    .change_context(crate::core::errors::StorageError::KVError)?,
+                fields,
+            )
+            .await
+            .change_context(crate::core::errors::StorageError::KVError)
+    }
+
+    /// Adds an object to the per-merchant index of objects sorted by their creation time, and
+    /// removes the entries which have outlived the objects in Redis, as those have been drained
+    /// to the database by now.
+    #[cfg(feature = "kv_store")]
+    pub(crate) async fn add_to_created_at_index(
+        &self,
+        index_key: &str,
+        created_at: time::PrimitiveDateTime,
+        member: &str,
+    ) -> crate::core::errors::CustomResult<(), crate::core::errors::StorageError> {
+        use crate::utils::db_utils::get_created_at_score;
+
+        self.redis_conn
+            .sorted_set_add_member(index_key, get_created_at_score(created_at), member)
+            .await
+            .change_context(crate::core::errors::StorageError::KVError)?;
+
+        let retained_since = common_utils::date_time::now()
+            - time::Duration::seconds(self.config.kv_index_retention.into());
+        self.redis_conn
+            .sorted_set_remove_members_by_score(
+                index_key,
+                f64::NEG_INFINITY,
+                get_created_at_score(retained_since),
+            )
+            .await
+            .change_context(crate::core::errors::StorageError::KVError)?;
+
+        Ok(())
+    }
+
+    /// Returns the members of a `created_at` index created within the given range, most recent
+    /// first.
+    #[cfg(feature = "kv_store")]
+    pub(crate) async fn get_from_created_at_index(
+        &self,
+        index_key: &str,
+        range: &crate::utils::db_utils::CreatedAtRange,
+        count: Option<i64>,
+    ) -> crate::core::errors::CustomResult<Vec<String>, crate::core::errors::StorageError> {
+        self.redis_conn()
+            .map_err(Into::<crate::core::errors::StorageError>::into)?
+            .sorted_set_get_members_by_score_rev(
+                index_key,
+                range.max_score(),
+                range.min_score(),
+                count,
             )
             .await
             .change_context(crate::core::errors::StorageError::KVError)
@@ -1,3 +1,4 @@
+pub(crate) mod circuit_breaker;
 mod client;
 pub(crate) mod request;
 
@@ -20,14 +21,14 @@ use serde_json::json;
 use self::request::{ContentType, HeaderExt, RequestBuilderExt};
 pub use self::request::{Method, Request, RequestBuilder};
 use crate::{
-    configs::settings::Connectors,
+    configs::settings::{ConnectorClientSettings, Connectors},
     core::{
         errors::{self, CustomResult},
         payments,
     },
     logger,
     routes::{app::AppStateInfo, metrics, AppState},
-    services::authentication as auth,
+    services::{audit_log, authentication as auth},
     types::{self, api, ErrorResponse},
 };
 
@@ -194,19 +195,16 @@ where
             Ok(router_data)
         }
         payments::CallConnectorAction::Trigger => {
+            let flow = std::any::type_name::<T>()
+                .split("::")
+                .last()
+                .unwrap_or_default();
             metrics::CONNECTOR_CALL_COUNT.add(
                 &metrics::CONTEXT,
                 1,
                 &[
                     metrics::request::add_attributes("connector", req.connector.to_string()),
-                    metrics::request::add_attributes(
-                        "flow",
-                        std::any::type_name::<T>()
-                            .split("::")
-                            .last()
-                            .unwrap_or_default()
-                            .to_string(),
-                    ),
+                    metrics::request::add_attributes("flow", flow.to_string()),
                 ],
             );
             match connector_integration
@@ -230,8 +228,28 @@ where
                 })? {
                 Some(request) => {
                     logger::debug!(connector_request=?request);
-                    let response = call_connector_api(state, request).await;
+                    let audited_request = state
+                        .conf
+                        .connector_audit_log
+                        .is_enabled()
+                        .then(|| audit_log::AuditedRequest::new(&request));
+                    let current_time = Instant::now();
+                    let response = send_connector_request(state, &req.connector, request).await;
                     logger::debug!(connector_response=?response);
+
+                    if let Some(audited_request) = audited_request {
+                        let event = audit_log::build_event(
+                            req,
+                            flow,
+                            audited_request,
+                            &response,
+                            current_time.elapsed(),
+                        );
+                        // Written in the background, so that a slow sink never delays the
+                        // response of the connector call
+                        let state = state.clone();
+                        tokio::spawn(async move { audit_log::emit(&state, event).await });
+                    }
                     match response {
                         Ok(body) => {
                             let response = match body {
@@ -269,8 +287,15 @@ where
                             };
                             Ok(response)
                         }
-                        Err(error) => Err(error
-                            .change_context(errors::ConnectorError::ProcessingStepFailed(None))),
+                        Err(error) => {
+                            let connector_error = match error.current_context() {
+                                errors::ApiClientError::CircuitBreakerOpen(connector) => {
+                                    errors::ConnectorError::ConnectorUnavailable(connector.clone())
+                                }
+                                _ => errors::ConnectorError::ProcessingStepFailed(None),
+                            };
+                            Err(error.change_context(connector_error))
+                        }
                     }
                 }
                 None => Ok(router_data),
@@ -286,7 +311,7 @@ pub async fn call_connector_api(
 ) -> CustomResult<Result<types::Response, types::Response>, errors::ApiClientError> {
     let current_time = Instant::now();
 
-    let response = send_request(state, request).await;
+    let response = send_request(state, request, None).await;
 
     let elapsed_time = current_time.elapsed();
     logger::info!(request_time=?elapsed_time);
@@ -294,22 +319,80 @@ pub async fn call_connector_api(
     handle_response(response).await
 }
 
+/// Sends a request to a connector using the client settings of the connector, failing fast
+/// without sending it while the circuit breaker of the connector is open.
+#[instrument(skip_all)]
+pub async fn send_connector_request(
+    state: &AppState,
+    connector: &str,
+    request: Request,
+) -> CustomResult<Result<types::Response, types::Response>, errors::ApiClientError> {
+    let client_settings = state.conf.connectors.get_client_settings(connector);
+    let permit = circuit_breaker::acquire(connector, &client_settings.circuit_breaker)?;
+    let current_time = Instant::now();
+
+    let response = send_request(state, request, Some((connector, &client_settings))).await;
+
+    let elapsed_time = current_time.elapsed();
+    logger::info!(request_time=?elapsed_time);
+
+    let response = handle_response(response).await;
+
+    // Errors raised before the request was sent say nothing about the health of the connector
+    let failed = match &response {
+        Ok(Ok(_)) => Some(false),
+        Ok(Err(body)) => Some(body.status_code >= 500),
+        Err(error) => matches!(
+            error.current_context(),
+            errors::ApiClientError::RequestNotSent(_)
+                | errors::ApiClientError::RequestTimeoutReceived
+                | errors::ApiClientError::ResponseDecodingFailed
+        )
+        .then_some(true),
+    };
+    if let Some(failed) = failed {
+        permit.record(failed);
+    }
+
+    response
+}
+
 #[instrument(skip_all)]
 async fn send_request(
     state: &AppState,
     request: Request,
+    connector_client_settings: Option<(&str, &ConnectorClientSettings)>,
 ) -> CustomResult<reqwest::Response, errors::ApiClientError> {
     logger::debug!(method=?request.method, headers=?request.headers, payload=?request.payload, ?request);
     let url = &request.url;
     let should_bypass_proxy = client::proxy_bypass_urls(&state.conf.locker).contains(url);
-    let client = client::create_client(
-        &state.conf.proxy,
-        should_bypass_proxy,
-        request.certificate,
-        request.certificate_key,
-    )?;
+    let client = match connector_client_settings {
+        Some((connector, client_settings)) => client::create_connector_client(
+            &state.conf.proxy,
+            should_bypass_proxy,
+            connector,
+            client_settings,
+            request.certificate,
+            request.certificate_key,
+        ),
+        None => client::create_client(
+            &state.conf.proxy,
+            should_bypass_proxy,
+            request.certificate,
+            request.certificate_key,
+        ),
+    }?;
+    let (request_timeout, connect_retries) = connector_client_settings.map_or(
+        (crate::consts::REQUEST_TIME_OUT, 0),
+        |(_, client_settings)| {
+            (
+                client_settings.request_timeout,
+                client_settings.connect_retries,
+            )
+        },
+    );
     let headers = request.headers.construct_header_map()?;
-    match request.method {
+    let request_builder = match request.method {
         Method::Get => client.get(url),
         Method::Post => {
             let client = client.post(url);
@@ -355,18 +438,44 @@ async fn send_request(
         Method::Delete => client.delete(url),
     }
     .add_headers(headers)
-    .timeout(Duration::from_secs(crate::consts::REQUEST_TIME_OUT))
-    .send()
-    .await
-    .map_err(|error| match error {
-        error if error.is_timeout() => {
-            metrics::REQUEST_BUILD_FAILURE.add(&metrics::CONTEXT, 1, &[]);
-            errors::ApiClientError::RequestTimeoutReceived
+    .timeout(Duration::from_secs(request_timeout));
+
+    send_with_connect_retries(request_builder, connect_retries)
+        .await
+        .map_err(|error| match error {
+            error if error.is_timeout() => {
+                metrics::REQUEST_BUILD_FAILURE.add(&metrics::CONTEXT, 1, &[]);
+                errors::ApiClientError::RequestTimeoutReceived
+            }
+            _ => errors::ApiClientError::RequestNotSent(error.to_string()),
+        })
+        .into_report()
+        .attach_printable("Unable to send request to connector")
+}
+
+// A request that failed to connect never reached the server, which makes it safe to send again
+async fn send_with_connect_retries(
+    mut request_builder: reqwest::RequestBuilder,
+    connect_retries: u8,
+) -> reqwest::Result<reqwest::Response> {
+    let mut attempt = 0;
+    loop {
+        // Requests with a streamed body, like multipart forms, cannot be cloned and are never retried
+        let retry = if attempt < connect_retries {
+            request_builder.try_clone()
+        } else {
+            None
+        };
+
+        match (request_builder.send().await, retry) {
+            (Err(error), Some(retry)) if error.is_connect() => {
+                attempt += 1;
+                logger::warn!(%error, attempt, "Failed to connect, retrying request");
+                request_builder = retry;
+            }
+            (response, _) => return response,
         }
-        _ => errors::ApiClientError::RequestNotSent(error.to_string()),
-    })
-    .into_report()
-    .attach_printable("Unable to send request to connector")
+    }
 }
 
 #[instrument(skip_all)]
@@ -476,6 +585,15 @@ pub enum RedirectForm {
     Html {
         html_data: String,
     },
+    /// Authenticates a card with 3DS using the Braintree SDK, then posts the resulting nonce to
+    /// the complete authorize URL
+    Braintree {
+        client_token: String,
+        card_token: String,
+        bin: String,
+        amount: String,
+        complete_authorize_url: String,
+    },
 }
 
 impl From<(url::Url, Method)> for RedirectForm {
@@ -747,6 +865,61 @@ pub fn build_redirection_form(form: &RedirectForm) -> maud::Markup {
         }
         },
         RedirectForm::Html { html_data } => PreEscaped(html_data.to_string()),
+        RedirectForm::Braintree {
+            client_token,
+            card_token,
+            bin,
+            amount,
+            complete_authorize_url,
+        } => maud::html! {
+        (maud::DOCTYPE)
+        html {
+            head {
+                meta name="viewport" content="width=device-width, initial-scale=1";
+                (PreEscaped(r#"<script src="https://js.braintreegateway.com/web/3.97.1/js/client.min.js"></script>"#))
+                (PreEscaped(r#"<script src="https://js.braintreegateway.com/web/3.97.1/js/three-d-secure.min.js"></script>"#))
+            }
+
+            body style="background-color: #ffffff; padding: 20px; font-family: Arial, Helvetica, Sans-Serif;" {
+
+                h3 style="text-align: center;" { "Please wait while we process your payment..." }
+
+                form action=(PreEscaped(complete_authorize_url)) method="POST" #payment_form {
+                    input type="hidden" name="nonce" #nonce;
+                    input type="hidden" name="error_message" #error_message;
+                }
+
+                (PreEscaped(format!(r#"
+                <script>
+                    var form = document.getElementById("payment_form");
+                    function submitAuthentication(nonce, errorMessage) {{
+                        document.getElementById("nonce").value = nonce || "";
+                        document.getElementById("error_message").value = errorMessage || "";
+                        form.submit();
+                    }}
+                    braintree.client.create({{ authorization: "{client_token}" }})
+                        .then(function (client) {{
+                            return braintree.threeDSecure.create({{ client: client, version: 2 }});
+                        }})
+                        .then(function (threeDSecure) {{
+                            return threeDSecure.verifyCard({{
+                                amount: "{amount}",
+                                nonce: "{card_token}",
+                                bin: "{bin}",
+                                onLookupComplete: function (data, next) {{ next(); }}
+                            }});
+                        }})
+                        .then(function (payload) {{
+                            submitAuthentication(payload.nonce, null);
+                        }})
+                        .catch(function (error) {{
+                            submitAuthentication(null, error.message);
+                        }});
+                </script>
+                "#)))
+            }
+        }
+        },
     }
 }
 
@@ -0,0 +1,347 @@
+//! Per-connector circuit breaker.
+//!
+//! The outcome of every request sent to a connector is counted over a fixed window. Once the
+//! failure rate within a window crosses the configured threshold the circuit is opened, and
+//! requests to that connector fail fast without being sent. When the open duration has elapsed, a
+//! limited number of probe requests are let through: the circuit is closed again once all of them
+//! succeed, and re-opened as soon as one of them fails.
+//!
+//! The state of the circuits is held in memory, so every router instance trips them independently.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use error_stack::IntoReport;
+use once_cell::sync::Lazy;
+
+use crate::{
+    configs::settings::CircuitBreakerSettings,
+    core::errors::{self, CustomResult},
+    logger,
+    routes::metrics,
+};
+
+static CIRCUITS: Lazy<Mutex<HashMap<String, Circuit>>> = Lazy::new(Default::default);
+
+#[derive(Debug, Clone, Copy)]
+enum Circuit {
+    Closed {
+        window_start: Instant,
+        requests: u32,
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    HalfOpen {
+        in_flight: u32,
+        successes: u32,
+    },
+}
+
+impl Circuit {
+    fn closed() -> Self {
+        Self::Closed {
+            window_start: Instant::now(),
+            requests: 0,
+            failures: 0,
+        }
+    }
+
+    fn open(connector: &str, settings: &CircuitBreakerSettings) -> Self {
+        metrics::CIRCUIT_BREAKER_OPENED.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::request::add_attributes(
+                "connector",
+                connector.to_owned(),
+            )],
+        );
+        Self::Open {
+            until: Instant::now() + Duration::from_secs(settings.open_duration),
+        }
+    }
+}
+
+fn circuits() -> MutexGuard<'static, HashMap<String, Circuit>> {
+    // The map is left consistent by every critical section, so a poisoned lock can be reused
+    CIRCUITS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Permission to send a single request to a connector. The outcome of the request must be passed
+/// to [`Permit::record`].
+#[must_use]
+#[derive(Debug)]
+pub struct Permit {
+    connector: String,
+    settings: CircuitBreakerSettings,
+    probe: bool,
+    recorded: bool,
+}
+
+/// Returns a [`Permit`] to send a request to the connector, or fails fast if its circuit is open.
+pub fn acquire(
+    connector: &str,
+    settings: &CircuitBreakerSettings,
+) -> CustomResult<Permit, errors::ApiClientError> {
+    let permit = |probe| Permit {
+        connector: connector.to_owned(),
+        settings: settings.clone(),
+        probe,
+        recorded: !settings.enabled,
+    };
+
+    if !settings.enabled {
+        return Ok(permit(false));
+    }
+
+    let mut circuits = circuits();
+    let circuit = circuits
+        .entry(connector.to_owned())
+        .or_insert_with(Circuit::closed);
+    let now = Instant::now();
+
+    let (allowed, next) = match *circuit {
+        Circuit::Closed { window_start, .. } => {
+            let window_elapsed =
+                now.duration_since(window_start) >= Duration::from_secs(settings.window);
+            (Some(false), window_elapsed.then(Circuit::closed))
+        }
+        Circuit::Open { until } if now >= until => {
+            logger::info!(
+                connector,
+                "Circuit breaker is half-open, sending probe request"
+            );
+            (
+                Some(true),
+                Some(Circuit::HalfOpen {
+                    in_flight: 1,
+                    successes: 0,
+                }),
+            )
+        }
+        Circuit::HalfOpen {
+            in_flight,
+            successes,
+        } if in_flight + successes < settings.half_open_probes => (
+            Some(true),
+            Some(Circuit::HalfOpen {
+                in_flight: in_flight + 1,
+                successes,
+            }),
+        ),
+        Circuit::Open { .. } | Circuit::HalfOpen { .. } => (None, None),
+    };
+
+    if let Some(next) = next {
+        *circuit = next;
+    }
+
+    match allowed {
+        Some(probe) => Ok(permit(probe)),
+        None => {
+            metrics::CIRCUIT_BREAKER_REJECTED_REQUESTS.add(
+                &metrics::CONTEXT,
+                1,
+                &[metrics::request::add_attributes(
+                    "connector",
+                    connector.to_owned(),
+                )],
+            );
+            Err(errors::ApiClientError::CircuitBreakerOpen(
+                connector.to_owned(),
+            ))
+            .into_report()
+        }
+    }
+}
+
+impl Permit {
+    /// Records the outcome of the request sent with this permit.
+    pub fn record(mut self, failed: bool) {
+        if self.recorded {
+            return;
+        }
+        self.recorded = true;
+
+        let mut circuits = circuits();
+        let circuit = circuits
+            .entry(self.connector.clone())
+            .or_insert_with(Circuit::closed);
+
+        let next = match *circuit {
+            // Outcomes of requests sent before the circuit changed state are ignored
+            Circuit::Closed {
+                window_start,
+                requests,
+                failures,
+            } if !self.probe => {
+                let requests = requests + 1;
+                let failures = failures + u32::from(failed);
+                let failure_rate_exceeded = u64::from(failures) * 100
+                    >= u64::from(self.settings.failure_rate_threshold) * u64::from(requests);
+
+                if requests >= self.settings.minimum_requests && failure_rate_exceeded {
+                    logger::warn!(
+                        connector = %self.connector,
+                        requests,
+                        failures,
+                        "Opening circuit breaker"
+                    );
+                    Circuit::open(&self.connector, &self.settings)
+                } else {
+                    Circuit::Closed {
+                        window_start,
+                        requests,
+                        failures,
+                    }
+                }
+            }
+            Circuit::HalfOpen { .. } if self.probe && failed => {
+                logger::warn!(connector = %self.connector, "Probe request failed, reopening circuit breaker");
+                Circuit::open(&self.connector, &self.settings)
+            }
+            Circuit::HalfOpen {
+                in_flight,
+                successes,
+            } if self.probe => {
+                let successes = successes + 1;
+                if successes >= self.settings.half_open_probes {
+                    logger::info!(connector = %self.connector, "Closing circuit breaker");
+                    Circuit::closed()
+                } else {
+                    Circuit::HalfOpen {
+                        in_flight: in_flight.saturating_sub(1),
+                        successes,
+                    }
+                }
+            }
+            unchanged => unchanged,
+        };
+
+        *circuit = next;
+    }
+}
+
+impl Drop for Permit {
+    // A probe that is dropped without an outcome, for instance because the request could not be
+    // built, must not hold on to its half-open slot
+    fn drop(&mut self) {
+        if self.recorded || !self.probe {
+            return;
+        }
+
+        if let Some(Circuit::HalfOpen { in_flight, .. }) = circuits().get_mut(&self.connector) {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+    }
+}
+
+/// Whether requests can currently be routed to the connector, that is its circuit is not open.
+pub fn is_available(connector: &str) -> bool {
+    match circuits().get(connector) {
+        Some(Circuit::Open { until }) => Instant::now() >= *until,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn settings() -> CircuitBreakerSettings {
+        CircuitBreakerSettings {
+            enabled: true,
+            failure_rate_threshold: 50,
+            minimum_requests: 4,
+            window: 60,
+            open_duration: 0,
+            half_open_probes: 2,
+        }
+    }
+
+    fn send(connector: &str, settings: &CircuitBreakerSettings, failed: bool) -> bool {
+        match acquire(connector, settings) {
+            Ok(permit) => {
+                permit.record(failed);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[test]
+    fn test_circuit_opens_once_failure_rate_is_exceeded() {
+        let connector = "circuit_breaker_test_opens";
+        let settings = CircuitBreakerSettings {
+            open_duration: 60,
+            ..settings()
+        };
+
+        assert!(send(connector, &settings, true));
+        assert!(send(connector, &settings, true));
+        assert!(send(connector, &settings, false));
+        assert!(is_available(connector));
+
+        assert!(send(connector, &settings, true));
+        assert!(!is_available(connector));
+        assert!(!send(connector, &settings, false));
+    }
+
+    #[test]
+    fn test_circuit_closes_after_successful_probes() {
+        let connector = "circuit_breaker_test_closes";
+        let settings = settings();
+
+        for _ in 0..4 {
+            send(connector, &settings, true);
+        }
+
+        let first_probe = acquire(connector, &settings).unwrap();
+        let second_probe = acquire(connector, &settings).unwrap();
+        assert!(acquire(connector, &settings).is_err());
+
+        first_probe.record(false);
+        second_probe.record(false);
+        assert!(matches!(
+            circuits().get(connector),
+            Some(Circuit::Closed { requests: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_failed_probe_reopens_circuit() {
+        let connector = "circuit_breaker_test_reopens";
+        let settings = settings();
+
+        for _ in 0..4 {
+            send(connector, &settings, true);
+        }
+
+        let probe = acquire(connector, &settings).unwrap();
+        probe.record(true);
+        assert!(matches!(
+            circuits().get(connector),
+            Some(Circuit::Open { .. })
+        ));
+    }
+
+    #[test]
+    fn test_disabled_circuit_never_opens() {
+        let connector = "circuit_breaker_test_disabled";
+        let settings = CircuitBreakerSettings {
+            enabled: false,
+            ..settings()
+        };
+
+        for _ in 0..10 {
+            assert!(send(connector, &settings, true));
+        }
+        assert!(is_available(connector));
+    }
+}
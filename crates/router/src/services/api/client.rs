@@ -1,15 +1,25 @@
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+    time::Duration,
+};
+
 use base64::Engine;
 use error_stack::{IntoReport, ResultExt};
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
 
 use crate::{
-    configs::settings::{Locker, Proxy},
+    configs::settings::{ConnectorClientSettings, Locker, Proxy},
     consts,
     core::errors::{self, CustomResult},
 };
 
 static NON_PROXIED_CLIENT: OnceCell<reqwest::Client> = OnceCell::new();
 static PROXIED_CLIENT: OnceCell<reqwest::Client> = OnceCell::new();
+// Connection pools are kept per connector, so that a connector holding on to its connections
+// does not exhaust the pool used for the others
+static CONNECTOR_CLIENTS: Lazy<RwLock<HashMap<(String, bool), reqwest::Client>>> =
+    Lazy::new(Default::default);
 
 fn get_client_builder(
     proxy_config: &Proxy,
@@ -44,6 +54,17 @@ fn get_client_builder(
     Ok(client_builder)
 }
 
+fn get_connector_client_builder(
+    proxy_config: &Proxy,
+    should_bypass_proxy: bool,
+    client_settings: &ConnectorClientSettings,
+) -> CustomResult<reqwest::ClientBuilder, errors::ApiClientError> {
+    Ok(get_client_builder(proxy_config, should_bypass_proxy)?
+        .connect_timeout(Duration::from_secs(client_settings.connect_timeout))
+        .pool_max_idle_per_host(client_settings.pool_max_idle_per_host)
+        .pool_idle_timeout(Duration::from_secs(client_settings.pool_idle_timeout)))
+}
+
 fn get_base_client(
     proxy_config: &Proxy,
     should_bypass_proxy: bool,
@@ -65,18 +86,41 @@ fn get_base_client(
     .clone())
 }
 
-// We may need to use outbound proxy to connect to external world.
-// Precedence will be the environment variables, followed by the config.
-pub(super) fn create_client(
+fn get_connector_base_client(
     proxy_config: &Proxy,
     should_bypass_proxy: bool,
+    connector: &str,
+    client_settings: &ConnectorClientSettings,
+) -> CustomResult<reqwest::Client, errors::ApiClientError> {
+    let key = (connector.to_owned(), should_bypass_proxy);
+    if let Some(client) = CONNECTOR_CLIENTS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+    {
+        return Ok(client.clone());
+    }
+
+    let client = get_connector_client_builder(proxy_config, should_bypass_proxy, client_settings)?
+        .build()
+        .into_report()
+        .change_context(errors::ApiClientError::ClientConstructionFailed)
+        .attach_printable_lazy(|| format!("Failed to construct client for {connector}"))?;
+
+    Ok(CONNECTOR_CLIENTS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(key)
+        .or_insert(client)
+        .clone())
+}
+
+fn get_client_identity(
     client_certificate: Option<String>,
     client_certificate_key: Option<String>,
-) -> CustomResult<reqwest::Client, errors::ApiClientError> {
+) -> CustomResult<Option<reqwest::Identity>, errors::ApiClientError> {
     match (client_certificate, client_certificate_key) {
         (Some(encoded_cert), Some(encoded_cert_key)) => {
-            let client_builder = get_client_builder(proxy_config, should_bypass_proxy)?;
-
             let decoded_cert = consts::BASE64_ENGINE
                 .decode(encoded_cert)
                 .into_report()
@@ -92,21 +136,59 @@ pub(super) fn create_client(
             let certificate_key = String::from_utf8(decoded_cert_key)
                 .into_report()
                 .change_context(errors::ApiClientError::CertificateDecodeFailed)?;
-            let identity = reqwest::Identity::from_pkcs8_pem(
-                certificate.as_bytes(),
-                certificate_key.as_bytes(),
-            )
+            reqwest::Identity::from_pkcs8_pem(certificate.as_bytes(), certificate_key.as_bytes())
+                .into_report()
+                .change_context(errors::ApiClientError::CertificateDecodeFailed)
+                .map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+// We may need to use outbound proxy to connect to external world.
+// Precedence will be the environment variables, followed by the config.
+pub(super) fn create_client(
+    proxy_config: &Proxy,
+    should_bypass_proxy: bool,
+    client_certificate: Option<String>,
+    client_certificate_key: Option<String>,
+) -> CustomResult<reqwest::Client, errors::ApiClientError> {
+    match get_client_identity(client_certificate, client_certificate_key)? {
+        Some(identity) => get_client_builder(proxy_config, should_bypass_proxy)?
+            .identity(identity)
+            .build()
             .into_report()
-            .change_context(errors::ApiClientError::CertificateDecodeFailed)?;
+            .change_context(errors::ApiClientError::ClientConstructionFailed)
+            .attach_printable("Failed to construct client with certificate and certificate key"),
+        None => get_base_client(proxy_config, should_bypass_proxy),
+    }
+}
 
-            client_builder
+/// Creates a client for requests to the connector, configured with its client settings.
+pub(super) fn create_connector_client(
+    proxy_config: &Proxy,
+    should_bypass_proxy: bool,
+    connector: &str,
+    client_settings: &ConnectorClientSettings,
+    client_certificate: Option<String>,
+    client_certificate_key: Option<String>,
+) -> CustomResult<reqwest::Client, errors::ApiClientError> {
+    match get_client_identity(client_certificate, client_certificate_key)? {
+        // Clients with a certificate are specific to the merchant, and are hence not cached
+        Some(identity) => {
+            get_connector_client_builder(proxy_config, should_bypass_proxy, client_settings)?
                 .identity(identity)
                 .build()
                 .into_report()
                 .change_context(errors::ApiClientError::ClientConstructionFailed)
                 .attach_printable("Failed to construct client with certificate and certificate key")
         }
-        _ => get_base_client(proxy_config, should_bypass_proxy),
+        None => get_connector_base_client(
+            proxy_config,
+            should_bypass_proxy,
+            connector,
+            client_settings,
+        ),
     }
 }
 
@@ -120,9 +202,11 @@ pub(super) fn proxy_bypass_urls(locker: &Locker) -> Vec<String> {
         format!("{locker_host}/card/addCard"),
         format!("{locker_host}/card/getCard"),
         format!("{locker_host}/card/deleteCard"),
+        format!("{locker_host}/health"),
         format!("{basilisk_host}/tokenize"),
         format!("{basilisk_host}/tokenize/get"),
         format!("{basilisk_host}/tokenize/delete"),
         format!("{basilisk_host}/tokenize/delete/token"),
+        format!("{basilisk_host}/health"),
     ]
 }
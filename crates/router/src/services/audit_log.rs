@@ -0,0 +1,614 @@
+//! Audit log of the requests sent to connectors and of the responses received.
+//!
+//! Sensitive values in request and response bodies are masked before an event leaves the
+//! process, and events are then written to the sink selected by the `connector_audit_log`
+//! configuration. Failing to write an event is logged and never fails the connector call.
+
+use std::{
+    fs,
+    io::Write,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use common_utils::pii;
+use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, Secret, WithoutType};
+use redis_interface::RedisEntryId;
+use serde_json::Value;
+
+use super::api::Request;
+use crate::{
+    configs::settings::ConnectorAuditLogConfig,
+    core::errors::{self, CustomResult},
+    logger,
+    routes::{metrics, AppState},
+    types::{self, api, transformers::ForeignInto},
+};
+
+static SINK: tokio::sync::OnceCell<Option<Box<dyn AuditLogSink>>> =
+    tokio::sync::OnceCell::const_new();
+
+/// Destination to which connector audit events are written.
+#[async_trait::async_trait]
+pub trait AuditLogSink: Send + Sync {
+    async fn emit(
+        &self,
+        state: &AppState,
+        event: &api::ConnectorAuditEvent,
+    ) -> CustomResult<(), errors::AuditLogError>;
+}
+
+struct PostgresSink;
+
+#[async_trait::async_trait]
+impl AuditLogSink for PostgresSink {
+    async fn emit(
+        &self,
+        state: &AppState,
+        event: &api::ConnectorAuditEvent,
+    ) -> CustomResult<(), errors::AuditLogError> {
+        state
+            .store
+            .insert_connector_audit_log(event.clone().foreign_into())
+            .await
+            .change_context(errors::AuditLogError::EventWriteFailed)
+            .map(|_| ())
+    }
+}
+
+struct FileSink {
+    file: Arc<Mutex<fs::File>>,
+}
+
+#[async_trait::async_trait]
+impl AuditLogSink for FileSink {
+    async fn emit(
+        &self,
+        _state: &AppState,
+        event: &api::ConnectorAuditEvent,
+    ) -> CustomResult<(), errors::AuditLogError> {
+        let mut line = serde_json::to_vec(event)
+            .into_report()
+            .change_context(errors::AuditLogError::EventSerializationFailed)?;
+        line.push(b'\n');
+
+        let file = Arc::clone(&self.file);
+        tokio::task::spawn_blocking(move || {
+            // Each event is written with a single call, so that lines are never interleaved
+            file.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .write_all(&line)
+        })
+        .await
+        .into_report()
+        .change_context(errors::AuditLogError::EventWriteFailed)?
+        .into_report()
+        .change_context(errors::AuditLogError::EventWriteFailed)
+    }
+}
+
+struct RedisStreamSink {
+    stream: String,
+}
+
+#[async_trait::async_trait]
+impl AuditLogSink for RedisStreamSink {
+    async fn emit(
+        &self,
+        state: &AppState,
+        event: &api::ConnectorAuditEvent,
+    ) -> CustomResult<(), errors::AuditLogError> {
+        let event = serde_json::to_string(event)
+            .into_report()
+            .change_context(errors::AuditLogError::EventSerializationFailed)?;
+
+        state
+            .store
+            .stream_append_entry(
+                &self.stream,
+                &RedisEntryId::AutoGeneratedID,
+                vec![("event", event)],
+            )
+            .await
+            .change_context(errors::AuditLogError::EventWriteFailed)
+    }
+}
+
+async fn get_sink(
+    config: &ConnectorAuditLogConfig,
+) -> CustomResult<Option<&'static dyn AuditLogSink>, errors::AuditLogError> {
+    SINK.get_or_try_init(|| async {
+        let sink: Option<Box<dyn AuditLogSink>> = match config {
+            ConnectorAuditLogConfig::Disabled => None,
+            ConnectorAuditLogConfig::Postgres => Some(Box::new(PostgresSink)),
+            ConnectorAuditLogConfig::File { path } => Some(Box::new(FileSink {
+                file: Arc::new(Mutex::new(
+                    fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)
+                        .into_report()
+                        .change_context(errors::AuditLogError::SinkCreationFailed)
+                        .attach_printable_lazy(|| {
+                            format!("Failed to open audit log file {}", path.display())
+                        })?,
+                )),
+            })),
+            ConnectorAuditLogConfig::RedisStream { stream } => Some(Box::new(RedisStreamSink {
+                stream: stream.clone(),
+            })),
+        };
+        Ok::<_, error_stack::Report<errors::AuditLogError>>(sink)
+    })
+    .await
+    .map(Option::as_deref)
+}
+
+/// Writes the event to the configured sink.
+pub async fn emit(state: &AppState, event: api::ConnectorAuditEvent) {
+    let result = match get_sink(&state.conf.connector_audit_log).await {
+        Ok(Some(sink)) => sink.emit(state, &event).await,
+        Ok(None) => Ok(()),
+        Err(error) => Err(error),
+    };
+
+    if let Err(error) = result {
+        metrics::CONNECTOR_AUDIT_LOG_FAILURES.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::request::add_attributes(
+                "connector",
+                event.connector,
+            )],
+        );
+        logger::error!(?error, "Failed to write connector audit event");
+    }
+}
+
+/// Details of a request sent to a connector, captured before the request is consumed.
+#[derive(Debug)]
+pub struct AuditedRequest {
+    method: String,
+    url: String,
+    body: Option<Value>,
+}
+
+impl AuditedRequest {
+    pub fn new(request: &Request) -> Self {
+        Self {
+            method: request.method.to_string(),
+            url: mask_url(&request.url),
+            // Multipart bodies are streamed and cannot be inspected, hence they are not recorded
+            body: request
+                .payload
+                .as_ref()
+                .and_then(|payload| mask_body(payload.peek().as_bytes())),
+        }
+    }
+}
+
+/// Builds the audit event of a request sent to the connector, from the outcome of the call.
+pub fn build_event<T, Req, Resp>(
+    router_data: &types::RouterData<T, Req, Resp>,
+    flow: &str,
+    request: AuditedRequest,
+    response: &CustomResult<Result<types::Response, types::Response>, errors::ApiClientError>,
+    latency: Duration,
+) -> api::ConnectorAuditEvent {
+    let (status_code, response_body, error_message) = match response {
+        Ok(Ok(body) | Err(body)) => (Some(body.status_code), mask_body(&body.response), None),
+        Err(error) => (None, None, Some(error.current_context().to_string())),
+    };
+
+    api::ConnectorAuditEvent {
+        merchant_id: router_data.merchant_id.clone(),
+        payment_id: router_data.payment_id.clone(),
+        attempt_id: router_data.attempt_id.clone(),
+        connector: router_data.connector.clone(),
+        flow: flow.to_owned(),
+        request_method: request.method,
+        request_url: request.url,
+        request_body: request.body,
+        status_code,
+        response_body,
+        error_message,
+        latency_ms: u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+        created_at: common_utils::date_time::now(),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SensitiveValue {
+    CardNumber,
+    Email,
+    IpAddress,
+    Other,
+}
+
+// Matched against keys stripped of separators and lowercased, so that `card_number`,
+// `cardNumber` and `card-number` are all recognised. Every value nested within a sensitive key is
+// masked, such as the fields of a billing address. A bare `code` fragment would also mask error,
+// currency and country codes, hence card codes are matched as `cardcode` instead.
+const CARD_NUMBER_KEYS: &[&str] = &["number", "cardnumber", "pan", "accountnumber", "ccnumber"];
+// Matched exactly, as fragments they would also mask fields such as `authorization_amount`,
+// `connector_name` or `document_type`. The innermost segment of a form key is matched as well,
+// so that `shipping[name]` is recognised.
+const SENSITIVE_KEYS: &[&str] = &["authorization", "name", "document"];
+const SENSITIVE_KEY_FRAGMENTS: &[&str] = &[
+    "cvc",
+    "cvv",
+    "cvn",
+    "cardcode",
+    "securitycode",
+    "verification",
+    "expiry",
+    "expiration",
+    "expire",
+    "expmonth",
+    "expyear",
+    "password",
+    "secret",
+    "token",
+    "apikey",
+    "transactionkey",
+    "merchantauthentication",
+    "signature",
+    "cryptogram",
+    "iban",
+    "holder",
+    "nameoncard",
+    "firstname",
+    "lastname",
+    "fullname",
+    "phone",
+    "address",
+    "billto",
+    "shipto",
+    "line1",
+    "line2",
+    "line3",
+    "street",
+    "zip",
+    "postal",
+    "birth",
+    "dob",
+    "ssn",
+];
+
+impl SensitiveValue {
+    fn of_key(key: &str) -> Option<Self> {
+        let normalize = |key: &str| {
+            key.chars()
+                .filter(|character| character.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        };
+        let innermost_segment = normalize(key.rsplit('[').next().unwrap_or(key));
+        let key = normalize(key);
+
+        if CARD_NUMBER_KEYS.contains(&key.as_str()) || key.ends_with("cardnumber") {
+            Some(Self::CardNumber)
+        } else if key.contains("email") {
+            Some(Self::Email)
+        } else if key == "ip" || key.contains("ipaddress") {
+            Some(Self::IpAddress)
+        } else if SENSITIVE_KEYS.contains(&key.as_str())
+            || SENSITIVE_KEYS.contains(&innermost_segment.as_str())
+            || SENSITIVE_KEY_FRAGMENTS
+                .iter()
+                .any(|fragment| key.contains(fragment))
+        {
+            Some(Self::Other)
+        } else {
+            None
+        }
+    }
+
+    fn mask(self, value: String) -> String {
+        match self {
+            Self::CardNumber => format!("{:?}", Secret::<_, pii::CardNumber>::new(value)),
+            Self::Email => format!("{:?}", Secret::<_, pii::Email>::new(value)),
+            Self::IpAddress => format!("{:?}", Secret::<_, pii::IpAddress>::new(value)),
+            Self::Other => format!("{:?}", Secret::<_, WithoutType>::new(value)),
+        }
+    }
+}
+
+fn mask_value(key: Option<&str>, value: &mut Value) {
+    let sensitive_value = key.and_then(SensitiveValue::of_key);
+    match value {
+        Value::Object(fields) => fields.iter_mut().for_each(|(key, value)| {
+            if sensitive_value.is_some() {
+                mask_nested_value(value)
+            } else {
+                mask_value(Some(key.as_str()), value)
+            }
+        }),
+        Value::Array(items) => items.iter_mut().for_each(|item| mask_value(key, item)),
+        Value::String(_) | Value::Number(_) | Value::Bool(_) => {
+            if let Some(sensitive_value) = sensitive_value {
+                mask_scalar(sensitive_value, value);
+            }
+        }
+        Value::Null => {}
+    }
+}
+
+fn mask_nested_value(value: &mut Value) {
+    match value {
+        Value::Object(fields) => fields.values_mut().for_each(mask_nested_value),
+        Value::Array(items) => items.iter_mut().for_each(mask_nested_value),
+        Value::String(_) | Value::Number(_) | Value::Bool(_) => {
+            mask_scalar(SensitiveValue::Other, value)
+        }
+        Value::Null => {}
+    }
+}
+
+fn mask_scalar(sensitive_value: SensitiveValue, value: &mut Value) {
+    let plaintext = match value {
+        Value::String(string) => std::mem::take(string),
+        other => other.to_string(),
+    };
+    *value = Value::String(sensitive_value.mask(plaintext));
+}
+
+fn mask_pairs(pairs: Vec<(String, String)>) -> Vec<(String, String)> {
+    pairs
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match SensitiveValue::of_key(&key) {
+                Some(sensitive_value) => sensitive_value.mask(value),
+                None => value,
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+/// Masks the sensitive values of a JSON or form URL encoded body. Bodies in any other format
+/// cannot be inspected, and are replaced entirely.
+fn mask_body(body: &[u8]) -> Option<Value> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+
+    if let Ok(mut value) = serde_json::from_slice::<Value>(body) {
+        mask_value(None, &mut value);
+        return Some(value);
+    }
+
+    let is_form = body.contains(&b'=') && !body.starts_with(b"<");
+    match serde_urlencoded::from_bytes::<Vec<(String, String)>>(body) {
+        Ok(pairs) if is_form => Some(Value::Object(
+            mask_pairs(pairs)
+                .into_iter()
+                .map(|(key, value)| (key, Value::String(value)))
+                .collect(),
+        )),
+        _ => Some(Value::String(format!(
+            "*** unparsed body of {} bytes ***",
+            body.len()
+        ))),
+    }
+}
+
+fn mask_url(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(mut url) if url.query().is_some() => {
+            let pairs = mask_pairs(url.query_pairs().into_owned().collect());
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+            url.to_string()
+        }
+        _ => url.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_json_body_is_masked() {
+        let body = serde_json::json!({
+            "amount": 1000,
+            "source": {
+                "number": "4242424242424242",
+                "cvc": "123",
+                "exp_month": 12,
+            },
+            "customer": { "email": "john.doe@example.com" },
+            "metadata": [{ "password": "hunter2" }],
+        });
+
+        let masked = mask_body(body.to_string().as_bytes()).unwrap();
+
+        assert_eq!(masked["amount"], 1000);
+        assert_eq!(masked["source"]["number"], "424242**********");
+        assert_eq!(masked["source"]["cvc"], "*** ***");
+        assert_eq!(masked["source"]["exp_month"], "*** ***");
+        assert_eq!(masked["customer"]["email"], "********@example.com");
+        assert_eq!(masked["metadata"][0]["password"], "*** ***");
+    }
+
+    #[test]
+    fn test_authorizedotnet_request_body_is_masked() {
+        let body = br#"{
+            "createTransactionRequest": {
+                "merchantAuthentication": { "name": "5KP3u95bQpv", "transactionKey": "346HZ32z3fP4hTG2" },
+                "transactionRequest": {
+                    "transactionType": "authCaptureTransaction",
+                    "amount": 1000,
+                    "currencyCode": "USD",
+                    "payment": {
+                        "creditCard": {
+                            "cardNumber": "4111111111111111",
+                            "expirationDate": "2030-10",
+                            "cardCode": "123"
+                        }
+                    },
+                    "billTo": { "firstName": "John", "address": "14 Main Street", "zip": "44628" },
+                    "authorizationIndicatorType": null
+                }
+            }
+        }"#;
+
+        let masked = mask_body(body).unwrap();
+        let authentication = &masked["createTransactionRequest"]["merchantAuthentication"];
+        let transaction = &masked["createTransactionRequest"]["transactionRequest"];
+
+        assert_eq!(authentication["name"], "*** ***");
+        assert_eq!(authentication["transactionKey"], "*** ***");
+        assert_eq!(transaction["amount"], 1000);
+        assert_eq!(transaction["currencyCode"], "USD");
+        assert_eq!(
+            transaction["payment"]["creditCard"]["cardNumber"],
+            "411111**********"
+        );
+        assert_eq!(
+            transaction["payment"]["creditCard"]["expirationDate"],
+            "*** ***"
+        );
+        assert_eq!(transaction["payment"]["creditCard"]["cardCode"], "*** ***");
+        assert_eq!(transaction["billTo"]["firstName"], "*** ***");
+        assert_eq!(transaction["billTo"]["address"], "*** ***");
+        assert_eq!(transaction["billTo"]["zip"], "*** ***");
+    }
+
+    #[test]
+    fn test_forte_request_body_is_masked() {
+        let body = br#"{
+            "action": "authorize",
+            "authorization_amount": 10.0,
+            "billing_address": {
+                "first_name": "John",
+                "last_name": "Doe",
+                "physical_address": { "street_line1": "14 Main Street", "locality": "Dover" }
+            },
+            "card": {
+                "card_type": "visa",
+                "name_on_card": "John Doe",
+                "account_number": "4111111111111111",
+                "expire_month": "10",
+                "expire_year": "2030",
+                "card_verification_value": "123"
+            }
+        }"#;
+
+        let masked = mask_body(body).unwrap();
+
+        assert_eq!(masked["action"], "authorize");
+        assert_eq!(masked["authorization_amount"], 10.0);
+        assert_eq!(masked["billing_address"]["first_name"], "*** ***");
+        assert_eq!(masked["billing_address"]["last_name"], "*** ***");
+        assert_eq!(
+            masked["billing_address"]["physical_address"]["street_line1"],
+            "*** ***"
+        );
+        assert_eq!(
+            masked["billing_address"]["physical_address"]["locality"],
+            "*** ***"
+        );
+        assert_eq!(masked["card"]["card_type"], "visa");
+        assert_eq!(masked["card"]["name_on_card"], "*** ***");
+        assert_eq!(masked["card"]["account_number"], "411111**********");
+        assert_eq!(masked["card"]["expire_month"], "*** ***");
+        assert_eq!(masked["card"]["expire_year"], "*** ***");
+        assert_eq!(masked["card"]["card_verification_value"], "*** ***");
+    }
+
+    #[test]
+    fn test_dlocal_request_body_is_masked() {
+        let body = br#"{
+            "amount": 1000,
+            "currency": "BRL",
+            "country": "BR",
+            "payment_method_id": "CARD",
+            "payment_method_flow": "DIRECT",
+            "payer": {
+                "name": "John Doe",
+                "email": "john.doe@example.com",
+                "document": "71575743221"
+            },
+            "card": {
+                "holder_name": "John Doe",
+                "number": "4111111111111111",
+                "cvv": "123",
+                "expiration_month": "10",
+                "expiration_year": "2030",
+                "capture": "true"
+            },
+            "order_id": "pay_123"
+        }"#;
+
+        let masked = mask_body(body).unwrap();
+
+        assert_eq!(masked["amount"], 1000);
+        assert_eq!(masked["payment_method_id"], "CARD");
+        assert_eq!(masked["order_id"], "pay_123");
+        assert_eq!(masked["payer"]["name"], "*** ***");
+        assert_eq!(masked["payer"]["email"], "********@example.com");
+        assert_eq!(masked["payer"]["document"], "*** ***");
+        assert_eq!(masked["card"]["holder_name"], "*** ***");
+        assert_eq!(masked["card"]["number"], "411111**********");
+        assert_eq!(masked["card"]["cvv"], "*** ***");
+        assert_eq!(masked["card"]["expiration_month"], "*** ***");
+        assert_eq!(masked["card"]["capture"], "true");
+    }
+
+    #[test]
+    fn test_rapyd_request_body_is_masked() {
+        let body = br#"{
+            "amount": 10,
+            "payment_method": {
+                "type": "in_amex_card",
+                "fields": { "name": "John Doe", "number": "4111111111111111", "cvv": "123" }
+            }
+        }"#;
+
+        let masked = mask_body(body).unwrap();
+
+        assert_eq!(masked["payment_method"]["type"], "in_amex_card");
+        assert_eq!(masked["payment_method"]["fields"]["name"], "*** ***");
+        assert_eq!(
+            masked["payment_method"]["fields"]["number"],
+            "411111**********"
+        );
+    }
+
+    #[test]
+    fn test_form_body_is_masked() {
+        let masked = mask_body(
+            b"amount=1000&card%5Bnumber%5D=4242424242424242&card%5Bcvc%5D=123&shipping%5Bname%5D=John+Doe",
+        )
+        .unwrap();
+
+        assert_eq!(masked["amount"], "1000");
+        assert_eq!(masked["card[number]"], "424242**********");
+        assert_eq!(masked["card[cvc]"], "*** ***");
+        assert_eq!(masked["shipping[name]"], "*** ***");
+    }
+
+    #[test]
+    fn test_unparsed_body_is_replaced() {
+        let masked = mask_body(b"<card><number>4242424242424242</number></card>").unwrap();
+
+        assert_eq!(masked, "*** unparsed body of 46 bytes ***");
+        assert_eq!(mask_body(b"  "), None);
+    }
+
+    #[test]
+    fn test_url_query_is_masked() {
+        assert_eq!(
+            mask_url("https://example.com/payments?api_key=secret&id=pay_123"),
+            "https://example.com/payments?api_key=***+***&id=pay_123"
+        );
+        assert_eq!(
+            mask_url("https://example.com/payments/pay_123"),
+            "https://example.com/payments/pay_123"
+        );
+    }
+}
@@ -3,8 +3,7 @@ use api_models::{payment_methods::PaymentMethodListRequest, payments::PaymentsRe
 use async_trait::async_trait;
 use common_utils::date_time;
 use error_stack::{report, IntoReport, ResultExt};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::SecretsManagementConfig;
 use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use masking::{PeekInterface, StrongSecret};
 
@@ -72,23 +71,13 @@ where
         }
 
         let api_key = api_keys::PlaintextApiKey::from(api_key);
-        let hash_key = {
+        let hash_keys = {
             let config = state.conf();
-            api_keys::get_hash_key(
-                &config.api_keys,
-                #[cfg(feature = "kms")]
-                &config.kms,
-            )
-            .await?
+            api_keys::get_hash_keys(&config.api_keys, &config.secrets_management).await?
         };
-        let hashed_api_key = api_key.keyed_hash(hash_key.peek());
 
-        let stored_api_key = state
-            .store()
-            .find_api_key_by_hash_optional(hashed_api_key.into())
-            .await
-            .change_context(errors::ApiErrorResponse::InternalServerError) // If retrieve failed
-            .attach_printable("Failed to retrieve API key")?
+        let stored_api_key = api_keys::find_api_key(&*state.store(), hash_keys, &api_key)
+            .await?
             .ok_or(report!(errors::ApiErrorResponse::Unauthorized)) // If retrieve returned `None`
             .attach_printable("Merchant not authenticated")?;
 
@@ -120,20 +109,15 @@ static ADMIN_API_KEY: tokio::sync::OnceCell<StrongSecret<String>> =
 
 pub async fn get_admin_api_key(
     secrets: &settings::Secrets,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> RouterResult<&'static StrongSecret<String>> {
     ADMIN_API_KEY
         .get_or_try_init(|| async {
-            #[cfg(feature = "kms")]
-            let admin_api_key = kms::get_kms_client(kms_config)
-                .await
-                .decrypt(&secrets.kms_encrypted_admin_api_key)
+            let admin_api_key = secrets_management
+                .get_secret(&secrets.admin_api_key)
                 .await
                 .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Failed to KMS decrypt admin API key")?;
-
-            #[cfg(not(feature = "kms"))]
-            let admin_api_key = secrets.admin_api_key.clone();
+                .attach_printable("Failed to retrieve admin API key")?;
 
             Ok(StrongSecret::new(admin_api_key))
         })
@@ -157,12 +141,7 @@ where
             get_api_key(request_headers).change_context(errors::ApiErrorResponse::Unauthorized)?;
         let conf = state.conf();
 
-        let admin_api_key = get_admin_api_key(
-            &conf.secrets,
-            #[cfg(feature = "kms")]
-            &conf.kms,
-        )
-        .await?;
+        let admin_api_key = get_admin_api_key(&conf.secrets, &conf.secrets_management).await?;
 
         if request_admin_api_key != admin_api_key.peek() {
             Err(report!(errors::ApiErrorResponse::Unauthorized)
@@ -396,20 +375,15 @@ static JWT_SECRET: tokio::sync::OnceCell<StrongSecret<String>> = tokio::sync::On
 
 pub async fn get_jwt_secret(
     secrets: &settings::Secrets,
-    #[cfg(feature = "kms")] kms_config: &kms::KmsConfig,
+    secrets_management: &SecretsManagementConfig,
 ) -> RouterResult<&'static StrongSecret<String>> {
     JWT_SECRET
         .get_or_try_init(|| async {
-            #[cfg(feature = "kms")]
-            let jwt_secret = kms::get_kms_client(kms_config)
-                .await
-                .decrypt(&secrets.kms_encrypted_jwt_secret)
+            let jwt_secret = secrets_management
+                .get_secret(&secrets.jwt_secret)
                 .await
                 .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Failed to KMS decrypt JWT secret")?;
-
-            #[cfg(not(feature = "kms"))]
-            let jwt_secret = secrets.jwt_secret.clone();
+                .attach_printable("Failed to retrieve JWT secret")?;
 
             Ok(StrongSecret::new(jwt_secret))
         })
@@ -421,14 +395,10 @@ where
     T: serde::de::DeserializeOwned,
 {
     let conf = state.conf();
-    let secret = get_jwt_secret(
-        &conf.secrets,
-        #[cfg(feature = "kms")]
-        &conf.kms,
-    )
-    .await?
-    .peek()
-    .as_bytes();
+    let secret = get_jwt_secret(&conf.secrets, &conf.secrets_management)
+        .await?
+        .peek()
+        .as_bytes();
 
     let key = DecodingKey::from_secret(secret);
     decode::<T>(token, &key, &Validation::new(Algorithm::HS256))
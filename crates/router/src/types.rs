@@ -90,6 +90,8 @@ pub type PaymentsSessionType =
     dyn services::ConnectorIntegration<api::Session, PaymentsSessionData, PaymentsResponseData>;
 pub type PaymentsVoidType =
     dyn services::ConnectorIntegration<api::Void, PaymentsCancelData, PaymentsResponseData>;
+pub type PaymentsVerifyType =
+    dyn services::ConnectorIntegration<api::Verify, VerifyRequestData, PaymentsResponseData>;
 
 pub type TokenizationType = dyn services::ConnectorIntegration<
     api::PaymentMethodToken,
@@ -181,11 +183,14 @@ pub struct PaymentsAuthorizeData {
     pub setup_mandate_details: Option<payments::MandateData>,
     pub browser_info: Option<BrowserInformation>,
     pub order_details: Option<api_models::payments::OrderDetails>,
+    pub purchase_details: Option<api_models::payments::PurchaseDetails>,
     pub session_token: Option<String>,
     pub enrolled_for_3ds: bool,
     pub related_transaction_id: Option<String>,
     pub payment_experience: Option<storage_enums::PaymentExperience>,
     pub payment_method_type: Option<storage_enums::PaymentMethodType>,
+    /// Cryptogram of the network token sent in place of the card number
+    pub network_token_cryptogram: Option<masking::Secret<String>>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -481,6 +486,12 @@ pub enum ConnectorAuthType {
         key1: String,
         api_secret: String,
     },
+    MultiAuthKey {
+        api_key: String,
+        key1: String,
+        api_secret: String,
+        key2: String,
+    },
     #[default]
     NoKey,
 }
@@ -1,11 +1,17 @@
 pub mod admin;
 pub mod api_keys;
+pub mod cache;
 pub mod configs;
+pub mod connector_audit_log;
+pub mod connector_capabilities;
 pub mod customers;
 pub mod disputes;
 pub mod enums;
 pub mod files;
+pub mod health_check;
+pub mod key_rotation;
 pub mod mandates;
+pub mod payment_link;
 pub mod payment_methods;
 pub mod payments;
 pub mod refunds;
@@ -16,8 +22,9 @@ use std::{fmt::Debug, str::FromStr};
 use error_stack::{report, IntoReport, ResultExt};
 
 pub use self::{
-    admin::*, api_keys::*, configs::*, customers::*, disputes::*, files::*, payment_methods::*,
-    payments::*, refunds::*, webhooks::*,
+    admin::*, api_keys::*, cache::*, configs::*, connector_audit_log::*, connector_capabilities::*,
+    customers::*, disputes::*, files::*, health_check::*, key_rotation::*, payment_link::*,
+    payment_methods::*, payments::*, refunds::*, webhooks::*,
 };
 use super::ErrorResponse;
 use crate::{
@@ -81,6 +88,12 @@ pub trait ConnectorCommon {
             reason: None,
         })
     }
+
+    /// Maps an error code returned by the connector to a connector agnostic category. Connectors
+    /// without a mapping table categorize every error as unknown, which is not retryable.
+    fn get_error_category(&self, _error_code: &str) -> api_enums::ErrorCategory {
+        api_enums::ErrorCategory::Unknown
+    }
 }
 
 /// Extended trait for connector common to allow functions with generic type
@@ -110,6 +123,7 @@ pub trait Connector:
     + Dispute
     + FileUpload
     + ConnectorTransactionId
+    + ConnectorCapability
 {
 }
 
@@ -127,7 +141,8 @@ impl<
             + ConnectorAccessToken
             + Dispute
             + FileUpload
-            + ConnectorTransactionId,
+            + ConnectorTransactionId
+            + ConnectorCapability,
     > Connector for T
 {
 }
@@ -170,11 +185,10 @@ impl ConnectorCallType {
 
 impl ConnectorData {
     pub fn get_connector_by_name(
-        connectors: &Connectors,
         name: &str,
         connector_type: GetToken,
     ) -> CustomResult<Self, errors::ApiErrorResponse> {
-        let connector = Self::convert_connector(connectors, name)?;
+        let connector = Self::convert_connector(name)?;
         let connector_name = api_enums::Connector::from_str(name)
             .into_report()
             .change_context(errors::ConnectorError::InvalidConnectorName)
@@ -187,8 +201,14 @@ impl ConnectorData {
         })
     }
 
+    /// Maps an error code returned by the named connector to a connector agnostic category.
+    pub fn get_error_category(connector_name: &str, error_code: &str) -> api_enums::ErrorCategory {
+        Self::convert_connector(connector_name)
+            .map(|connector| connector.get_error_category(error_code))
+            .unwrap_or_default()
+    }
+
     fn convert_connector(
-        _connectors: &Connectors,
         connector_name: &str,
     ) -> CustomResult<BoxedConnector, errors::ApiErrorResponse> {
         match connector_name {
@@ -204,21 +224,22 @@ impl ConnectorData {
             "cybersource" => Ok(Box::new(&connector::Cybersource)),
             "dlocal" => Ok(Box::new(&connector::Dlocal)),
             "fiserv" => Ok(Box::new(&connector::Fiserv)),
-            // "forte" => Ok(Box::new(&connector::Forte)),
+            "forte" => Ok(Box::new(&connector::Forte)),
             "globalpay" => Ok(Box::new(&connector::Globalpay)),
             "klarna" => Ok(Box::new(&connector::Klarna)),
             "mollie" => Ok(Box::new(&connector::Mollie)),
             "nuvei" => Ok(Box::new(&connector::Nuvei)),
             "opennode" => Ok(Box::new(&connector::Opennode)),
-            // "payeezy" => Ok(Box::new(&connector::Payeezy)), As psync and rsync are not supported by this connector, it is added as template code for future usage
+            "payeezy" => Ok(Box::new(&connector::Payeezy)),
             "payu" => Ok(Box::new(&connector::Payu)),
             "rapyd" => Ok(Box::new(&connector::Rapyd)),
             "shift4" => Ok(Box::new(&connector::Shift4)),
+            "simulator" => Ok(Box::new(&connector::Simulator)),
             "stripe" => Ok(Box::new(&connector::Stripe)),
             "worldline" => Ok(Box::new(&connector::Worldline)),
             "worldpay" => Ok(Box::new(&connector::Worldpay)),
             "multisafepay" => Ok(Box::new(&connector::Multisafepay)),
-            // "nexinets" => Ok(Box::new(&connector::Nexinets)), added as template code for future use
+            "nexinets" => Ok(Box::new(&connector::Nexinets)),
             "paypal" => Ok(Box::new(&connector::Paypal)),
             "trustpay" => Ok(Box::new(&connector::Trustpay)),
             _ => Err(report!(errors::ConnectorError::InvalidConnectorName)
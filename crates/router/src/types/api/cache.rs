@@ -0,0 +1,6 @@
+#[derive(Clone, serde::Serialize, Debug)]
+pub struct CacheInvalidationResponse {
+    pub key: String,
+    /// Number of instances subscribed to the invalidation channel, which have invalidated the key
+    pub subscribers_notified: usize,
+}
@@ -0,0 +1,42 @@
+use time::PrimitiveDateTime;
+
+/// A request sent to a connector, along with the response received. Bodies and query parameters
+/// have their sensitive values masked.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct ConnectorAuditEvent {
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub flow: String,
+    pub request_method: String,
+    pub request_url: String,
+    pub request_body: Option<serde_json::Value>,
+    /// Absent when no response was received from the connector
+    pub status_code: Option<u16>,
+    pub response_body: Option<serde_json::Value>,
+    /// Reason for no response being received from the connector
+    pub error_message: Option<String>,
+    /// Time taken by the connector to respond, in milliseconds
+    pub latency_ms: u64,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The constraints that are applicable when listing the connector audit events of a payment.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorAuditLogConstraints {
+    /// The maximum number of events to include in the response, 100 by default.
+    pub limit: Option<i64>,
+    /// The number of events to skip when retrieving the events.
+    pub offset: Option<i64>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct ConnectorAuditLogResponse {
+    pub merchant_id: String,
+    pub payment_id: String,
+    /// Requests sent to connectors for the payment, in the order they were sent
+    pub events: Vec<ConnectorAuditEvent>,
+}
@@ -0,0 +1,13 @@
+pub use api_models::connector_capabilities::{
+    ConnectorCapabilities, ConnectorCapabilitiesResponse, ConnectorFlow, PaymentMethodCapability,
+};
+
+use super::ConnectorCommon;
+
+pub trait ConnectorCapability: ConnectorCommon {
+    /// The flows, payment methods, currencies and countries the connector supports.
+    ///
+    /// Requests are validated against this before being sent to the connector, so a flow or
+    /// payment method must only be declared here once it is implemented.
+    fn get_capabilities(&self) -> ConnectorCapabilities;
+}
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    Unhealthy,
+    /// The check does not apply to the current configuration
+    Skipped,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct DependencyHealth {
+    pub status: HealthStatus,
+    /// Time taken by the check, in milliseconds
+    pub duration_ms: u128,
+    /// Reason for the check failing or being skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct ReadinessReport {
+    /// `unhealthy` if any of the checks failed, `healthy` otherwise
+    pub status: HealthStatus,
+    /// Results of the individual checks, keyed by the dependency checked
+    pub checks: BTreeMap<&'static str, DependencyHealth>,
+}
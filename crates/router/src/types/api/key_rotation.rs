@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct KeyRotationReport {
+    pub api_keys: KeyVersionReport,
+    /// Temporary locker entries which are yet to be deleted
+    #[cfg(feature = "basilisk")]
+    pub locker_tokens: KeyVersionReport,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct KeyVersionReport {
+    pub current_key_id: String,
+    pub records_by_key_id: HashMap<String, i64>,
+    /// Number of records which still use a key other than the current one
+    pub records_with_previous_keys: i64,
+}
@@ -0,0 +1,3 @@
+pub use api_models::payment_link::{
+    PaymentLinkCheckoutForm, PaymentLinkCreateRequest, PaymentLinkResponse,
+};
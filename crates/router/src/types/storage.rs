@@ -2,6 +2,7 @@ pub mod address;
 pub mod api_keys;
 pub mod cards_info;
 pub mod configs;
+pub mod connector_audit_log;
 pub mod connector_response;
 pub mod customers;
 pub mod dispute;
@@ -15,6 +16,7 @@ pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod payment_attempt;
 pub mod payment_intent;
+pub mod payment_link;
 pub mod payment_method;
 pub mod process_tracker;
 pub mod reverse_lookup;
@@ -26,8 +28,9 @@ pub mod refund;
 pub mod kv;
 
 pub use self::{
-    address::*, api_keys::*, cards_info::*, configs::*, connector_response::*, customers::*,
-    dispute::*, events::*, file::*, locker_mock_up::*, mandate::*, merchant_account::*,
-    merchant_connector_account::*, payment_attempt::*, payment_intent::*, payment_method::*,
-    process_tracker::*, refund::*, reverse_lookup::*,
+    address::*, api_keys::*, cards_info::*, configs::*, connector_audit_log::*,
+    connector_response::*, customers::*, dispute::*, events::*, file::*, locker_mock_up::*,
+    mandate::*, merchant_account::*, merchant_connector_account::*, payment_attempt::*,
+    payment_intent::*, payment_link::*, payment_method::*, process_tracker::*, refund::*,
+    reverse_lookup::*,
 };
@@ -0,0 +1 @@
+pub use storage_models::connector_audit_log::{ConnectorAuditLog, ConnectorAuditLogNew};
@@ -5,7 +5,8 @@ use router_env::{instrument, tracing};
 pub use storage_models::{
     errors,
     payment_intent::{
-        PaymentIntent, PaymentIntentNew, PaymentIntentUpdate, PaymentIntentUpdateInternal,
+        PaymentIntent, PaymentIntentExpiryTrackingData, PaymentIntentNew, PaymentIntentUpdate,
+        PaymentIntentUpdateInternal,
     },
     schema::payment_intent::dsl,
 };
@@ -38,9 +39,11 @@ impl PaymentIntentDbExt for PaymentIntent {
 
         //[#350]: Replace this with Boxable Expression and pass it into generic filter
         // when https://github.com/rust-lang/rust/issues/52662 becomes stable
+        // Ordered by creation time, like the `created_at` index the list is merged with when the
+        // merchant uses the KV store
         let mut filter = <Self as HasTable>::table()
             .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
-            .order(dsl::modified_at.desc())
+            .order(dsl::created_at.desc())
             .into_boxed();
 
         if let Some(customer_id) = customer_id {
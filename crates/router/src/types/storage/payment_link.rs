@@ -0,0 +1,4 @@
+pub use storage_models::payment_link::{
+    PaymentLink, PaymentLinkExpiryTrackingData, PaymentLinkNew, PaymentLinkUpdate,
+    PaymentLinkUpdateInternal,
+};
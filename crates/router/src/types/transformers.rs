@@ -162,6 +162,7 @@ impl ForeignTryFrom<api_enums::IntentStatus> for storage_enums::EventType {
         match value {
             api_enums::IntentStatus::Succeeded => Ok(Self::PaymentSucceeded),
             api_enums::IntentStatus::Processing => Ok(Self::PaymentProcessing),
+            api_enums::IntentStatus::Cancelled => Ok(Self::PaymentCancelled),
             api_enums::IntentStatus::RequiresMerchantAction => Ok(Self::ActionRequired),
             _ => Err(errors::ValidationError::IncorrectValueProvided {
                 field_name: "intent_status",
@@ -457,6 +458,12 @@ impl ForeignFrom<storage_enums::DisputeStatus> for api_enums::DisputeStatus {
     }
 }
 
+impl ForeignFrom<storage_enums::PaymentLinkStatus> for api_enums::PaymentLinkStatus {
+    fn foreign_from(status: storage_enums::PaymentLinkStatus) -> Self {
+        frunk::labelled_convert_from(status)
+    }
+}
+
 impl ForeignFrom<api_types::FileUploadProvider> for storage_enums::FileUploadProvider {
     fn foreign_from(provider: api_types::FileUploadProvider) -> Self {
         frunk::labelled_convert_from(provider)
@@ -513,6 +520,23 @@ impl ForeignFrom<storage::Dispute> for api_models::disputes::DisputeResponse {
     }
 }
 
+impl ForeignFrom<storage::PaymentLink> for api_models::payment_link::PaymentLinkResponse {
+    fn foreign_from(payment_link: storage::PaymentLink) -> Self {
+        Self {
+            payment_link_id: payment_link.payment_link_id,
+            payment_id: payment_link.payment_id,
+            merchant_id: payment_link.merchant_id,
+            link: payment_link.link_to_pay,
+            amount: payment_link.amount,
+            currency: payment_link.currency.foreign_into(),
+            description: payment_link.description,
+            status: payment_link.status.foreign_into(),
+            created: payment_link.created_at,
+            expires_at: payment_link.expires_at,
+        }
+    }
+}
+
 impl ForeignFrom<storage_models::cards_info::CardInfo>
     for api_models::cards_info::CardInfoResponse
 {
@@ -572,3 +596,45 @@ impl ForeignTryFrom<storage_models::merchant_connector_account::MerchantConnecto
         })
     }
 }
+
+impl ForeignFrom<api_types::ConnectorAuditEvent> for storage::ConnectorAuditLogNew {
+    fn foreign_from(event: api_types::ConnectorAuditEvent) -> Self {
+        Self {
+            merchant_id: event.merchant_id,
+            payment_id: event.payment_id,
+            attempt_id: event.attempt_id,
+            connector: event.connector,
+            flow: event.flow,
+            request_method: event.request_method,
+            request_url: event.request_url,
+            request_body: event.request_body,
+            status_code: event.status_code.map(i32::from),
+            response_body: event.response_body,
+            error_message: event.error_message,
+            latency_ms: i64::try_from(event.latency_ms).unwrap_or(i64::MAX),
+            created_at: event.created_at,
+        }
+    }
+}
+
+impl ForeignFrom<storage::ConnectorAuditLog> for api_types::ConnectorAuditEvent {
+    fn foreign_from(audit_log: storage::ConnectorAuditLog) -> Self {
+        Self {
+            merchant_id: audit_log.merchant_id,
+            payment_id: audit_log.payment_id,
+            attempt_id: audit_log.attempt_id,
+            connector: audit_log.connector,
+            flow: audit_log.flow,
+            request_method: audit_log.request_method,
+            request_url: audit_log.request_url,
+            request_body: audit_log.request_body,
+            status_code: audit_log
+                .status_code
+                .and_then(|status_code| u16::try_from(status_code).ok()),
+            response_body: audit_log.response_body,
+            error_message: audit_log.error_message,
+            latency_ms: u64::try_from(audit_log.latency_ms).unwrap_or_default(),
+            created_at: audit_log.created_at,
+        }
+    }
+}
@@ -11,6 +11,94 @@ pub fn generate_hscan_pattern_for_refund(sk: &str) -> String {
         .join("_")
 }
 
+#[cfg(feature = "kv_store")]
+/// Score of an object in a `created_at` index, which is its creation time in milliseconds since
+/// the Unix epoch.
+pub fn get_created_at_score(created_at: time::PrimitiveDateTime) -> f64 {
+    #[allow(clippy::as_conversions)]
+    let score = (created_at.assume_utc().unix_timestamp_nanos() / 1_000_000) as f64;
+    score
+}
+
+#[cfg(feature = "kv_store")]
+/// Key of the per-merchant index of objects of an entity, sorted by their creation time.
+pub fn get_created_at_index_key(merchant_id: &str, entity: &str) -> String {
+    format!("mid_{merchant_id}_{entity}_created_at")
+}
+
+#[cfg(feature = "kv_store")]
+/// Constraints on the creation time of the objects being listed.
+#[derive(Clone, Debug, Default)]
+pub struct CreatedAtRange {
+    pub created: Option<time::PrimitiveDateTime>,
+    pub created_lt: Option<time::PrimitiveDateTime>,
+    pub created_gt: Option<time::PrimitiveDateTime>,
+    pub created_lte: Option<time::PrimitiveDateTime>,
+    pub created_gte: Option<time::PrimitiveDateTime>,
+}
+
+#[cfg(feature = "kv_store")]
+impl CreatedAtRange {
+    /// Lowest score to be read from the index. Bounds are inclusive on the index, as scores only
+    /// have millisecond precision, exclusive bounds are applied by [`Self::contains`].
+    pub fn min_score(&self) -> f64 {
+        [self.created, self.created_gt, self.created_gte]
+            .into_iter()
+            .flatten()
+            .map(get_created_at_score)
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    /// Highest score to be read from the index.
+    pub fn max_score(&self) -> f64 {
+        [self.created, self.created_lt, self.created_lte]
+            .into_iter()
+            .flatten()
+            .map(get_created_at_score)
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn contains(&self, created_at: time::PrimitiveDateTime) -> bool {
+        self.created.map_or(true, |created| created_at == created)
+            && self.created_lt.map_or(true, |lt| created_at < lt)
+            && self.created_gt.map_or(true, |gt| created_at > gt)
+            && self.created_lte.map_or(true, |lte| created_at <= lte)
+            && self.created_gte.map_or(true, |gte| created_at >= gte)
+    }
+}
+
+#[cfg(feature = "kv_store")]
+/// Merges the objects read through a `created_at` index with those read from the database. An
+/// object present in both is taken from Redis, as the database may not have its latest updates
+/// yet. The result is ordered by `order_by`, most recent first, and truncated to `limit`; it must
+/// be the key the index and the database query are ordered by, so that no object is left out.
+pub fn merge_kv_and_database_results<T, K, O>(
+    kv_results: Vec<T>,
+    database_results: Vec<T>,
+    id: impl Fn(&T) -> K,
+    order_by: impl Fn(&T) -> O,
+    limit: Option<usize>,
+) -> Vec<T>
+where
+    K: Eq + std::hash::Hash,
+    O: Ord,
+{
+    let kv_ids: std::collections::HashSet<K> = kv_results.iter().map(&id).collect();
+    let mut results: Vec<T> = kv_results
+        .into_iter()
+        .chain(
+            database_results
+                .into_iter()
+                .filter(|result| !kv_ids.contains(&id(result))),
+        )
+        .collect();
+    results.sort_by(|a, b| order_by(b).cmp(&order_by(a)));
+    if let Some(limit) = limit {
+        results.truncate(limit);
+    }
+    results
+}
+
 // The first argument should be a future while the second argument should be a closure that returns a future for a database call
 pub async fn try_redis_get_else_try_database_get<F, RFut, DFut, T>(
     redis_fut: RFut,
@@ -33,3 +121,49 @@ where
         },
     }
 }
+
+#[cfg(all(test, feature = "kv_store"))]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_created_at_range() {
+        let range = CreatedAtRange {
+            created_gt: Some(datetime!(2023-01-01 10:00:00)),
+            created_lte: Some(datetime!(2023-01-01 12:00:00)),
+            ..Default::default()
+        };
+
+        assert!(range.min_score() <= get_created_at_score(datetime!(2023-01-01 10:00:00)));
+        assert!(range.max_score() >= get_created_at_score(datetime!(2023-01-01 12:00:00)));
+        assert!(!range.contains(datetime!(2023-01-01 10:00:00)));
+        assert!(range.contains(datetime!(2023-01-01 11:00:00)));
+        assert!(range.contains(datetime!(2023-01-01 12:00:00)));
+        assert!(!range.contains(datetime!(2023-01-01 12:00:01)));
+
+        let unbounded = CreatedAtRange::default();
+        assert_eq!(unbounded.min_score(), f64::NEG_INFINITY);
+        assert_eq!(unbounded.max_score(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_merge_kv_and_database_results() {
+        let kv_results = vec![("pay_3", 3, "kv"), ("pay_2", 2, "kv")];
+        let database_results = vec![("pay_2", 2, "db"), ("pay_1", 1, "db"), ("pay_0", 0, "db")];
+
+        let merged = merge_kv_and_database_results(
+            kv_results,
+            database_results,
+            |result| result.0,
+            |result| result.1,
+            Some(3),
+        );
+
+        assert_eq!(
+            merged,
+            vec![("pay_3", 3, "kv"), ("pay_2", 2, "kv"), ("pay_1", 1, "db")]
+        );
+    }
+}
@@ -13,6 +13,26 @@ pub(crate) enum PartitionKey<'a> {
         merchant_id: &'a str,
         payment_id: &'a str,
     },
+    MerchantIdCustomerId {
+        merchant_id: &'a str,
+        customer_id: &'a str,
+    },
+    CombinationKey {
+        combination: &'a str,
+    },
+}
+
+impl<'a> PartitionKey<'a> {
+    /// Merchant whose data the stream entry belongs to, tracked so that the drainer can tell
+    /// when all the entries of a merchant for which KV was turned off have been drained. Reverse
+    /// lookups are always read through Redis, so they do not need to be tracked.
+    pub(crate) fn merchant_id(&self) -> Option<&'a str> {
+        match *self {
+            PartitionKey::MerchantIdPaymentId { merchant_id, .. }
+            | PartitionKey::MerchantIdCustomerId { merchant_id, .. } => Some(merchant_id),
+            PartitionKey::CombinationKey { .. } => None,
+        }
+    }
 }
 
 impl<'a> std::fmt::Display for PartitionKey<'a> {
@@ -22,6 +42,11 @@ impl<'a> std::fmt::Display for PartitionKey<'a> {
                 merchant_id,
                 payment_id,
             } => f.write_str(&format!("mid_{merchant_id}_pid_{payment_id}")),
+            PartitionKey::MerchantIdCustomerId {
+                merchant_id,
+                customer_id,
+            } => f.write_str(&format!("mid_{merchant_id}_cid_{customer_id}")),
+            PartitionKey::CombinationKey { combination } => f.write_str(combination),
         }
     }
 }
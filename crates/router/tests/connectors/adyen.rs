@@ -79,12 +79,14 @@ impl AdyenTest {
             capture_method: Some(capture_method),
             browser_info: None,
             order_details: None,
+            purchase_details: None,
             email: None,
             payment_experience: None,
             payment_method_type: None,
             session_token: None,
             enrolled_for_3ds: false,
             related_transaction_id: None,
+            network_token_cryptogram: None,
             router_return_url: Some(String::from("http://localhost:8080")),
             webhook_url: None,
             complete_authorize_url: None,
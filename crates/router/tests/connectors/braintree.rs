@@ -0,0 +1,338 @@
+use router::{
+    connector::Braintree,
+    core::payments,
+    services,
+    types::{
+        self,
+        api::{self, ConnectorCommon},
+        storage::enums,
+    },
+};
+
+use crate::{
+    connector_auth,
+    utils::{self, ConnectorActions, PaymentInfo},
+};
+
+#[derive(Clone, Copy)]
+struct BraintreeTest;
+impl ConnectorActions for BraintreeTest {}
+impl utils::Connector for BraintreeTest {
+    fn get_data(&self) -> types::api::ConnectorData {
+        types::api::ConnectorData {
+            connector: Box::new(&Braintree),
+            connector_name: types::Connector::Braintree,
+            get_token: types::api::GetToken::Connector,
+        }
+    }
+
+    fn get_auth_token(&self) -> types::ConnectorAuthType {
+        types::ConnectorAuthType::from(
+            connector_auth::ConnectorAuthentication::new()
+                .braintree
+                .expect("Missing connector authentication configuration"),
+        )
+    }
+
+    fn get_name(&self) -> String {
+        "braintree".to_string()
+    }
+}
+
+static CONNECTOR: BraintreeTest = BraintreeTest {};
+
+// Cards are tokenized before the payment is made, the sandbox provides nonces standing for
+// tokenized test cards
+fn get_payment_info(nonce: &str) -> Option<PaymentInfo> {
+    Some(PaymentInfo {
+        payment_method_token: Some(nonce.to_string()),
+        ..Default::default()
+    })
+}
+
+fn get_default_payment_info() -> Option<PaymentInfo> {
+    get_payment_info("fake-valid-nonce")
+}
+
+// Cards Positive Tests
+// Creates a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_only_authorize_payment() {
+    let response = CONNECTOR
+        .authorize_payment(None, get_default_payment_info())
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Authorized);
+}
+
+// Creates a payment using the automatic capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_make_payment() {
+    let authorize_response = CONNECTOR
+        .make_payment(None, get_default_payment_info())
+        .await
+        .unwrap();
+    assert_eq!(authorize_response.status, enums::AttemptStatus::Charged);
+}
+
+// Captures a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_capture_authorized_payment() {
+    let response = CONNECTOR
+        .authorize_and_capture_payment(None, None, get_default_payment_info())
+        .await;
+    assert_eq!(response.unwrap().status, enums::AttemptStatus::Charged);
+}
+
+// Partially captures a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_partially_capture_authorized_payment() {
+    let response = CONNECTOR
+        .authorize_and_capture_payment(
+            None,
+            Some(types::PaymentsCaptureData {
+                amount_to_capture: 50,
+                ..utils::PaymentCaptureType::default().0
+            }),
+            get_default_payment_info(),
+        )
+        .await;
+    assert_eq!(response.unwrap().status, enums::AttemptStatus::Charged);
+}
+
+// Synchronizes a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_sync_authorized_payment() {
+    let authorize_response = CONNECTOR
+        .authorize_payment(None, get_default_payment_info())
+        .await
+        .unwrap();
+    let txn_id = utils::get_connector_transaction_id(authorize_response.response);
+    let response = CONNECTOR
+        .psync_retry_till_status_matches(
+            enums::AttemptStatus::Authorized,
+            Some(types::PaymentsSyncData {
+                connector_transaction_id: router::types::ResponseId::ConnectorTransactionId(
+                    txn_id.unwrap(),
+                ),
+                ..Default::default()
+            }),
+            get_default_payment_info(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Authorized);
+}
+
+// Synchronizes a payment using the automatic capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_sync_auto_captured_payment() {
+    let authorize_response = CONNECTOR
+        .make_payment(None, get_default_payment_info())
+        .await
+        .unwrap();
+    assert_eq!(authorize_response.status, enums::AttemptStatus::Charged);
+    let txn_id = utils::get_connector_transaction_id(authorize_response.response);
+    assert_ne!(txn_id, None, "Empty connector transaction id");
+    let response = CONNECTOR
+        .psync_retry_till_status_matches(
+            enums::AttemptStatus::Charged,
+            Some(types::PaymentsSyncData {
+                connector_transaction_id: router::types::ResponseId::ConnectorTransactionId(
+                    txn_id.unwrap(),
+                ),
+                ..Default::default()
+            }),
+            get_default_payment_info(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Charged);
+}
+
+// Voids a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_void_authorized_payment() {
+    let response = CONNECTOR
+        .authorize_and_void_payment(
+            None,
+            Some(types::PaymentsCancelData {
+                connector_transaction_id: "".to_string(),
+                cancellation_reason: Some("requested_by_customer".to_string()),
+                ..Default::default()
+            }),
+            get_default_payment_info(),
+        )
+        .await;
+    assert_eq!(response.unwrap().status, enums::AttemptStatus::Voided);
+}
+
+// Refunds a payment using the automatic capture flow (Non 3DS).
+// Sandbox transactions are only settled once a day, and unsettled transactions cannot be refunded.
+#[ignore]
+#[actix_web::test]
+async fn should_refund_auto_captured_payment() {
+    let response = CONNECTOR
+        .make_payment_and_refund(None, None, get_default_payment_info())
+        .await
+        .unwrap();
+    assert_eq!(
+        response.response.unwrap().refund_status,
+        enums::RefundStatus::Pending,
+    );
+}
+
+// Synchronizes a refund using the automatic capture flow (Non 3DS).
+// Sandbox transactions are only settled once a day, and unsettled transactions cannot be refunded.
+#[ignore]
+#[actix_web::test]
+async fn should_sync_refund() {
+    let refund_response = CONNECTOR
+        .make_payment_and_refund(None, None, get_default_payment_info())
+        .await
+        .unwrap();
+    let response = CONNECTOR
+        .rsync_retry_till_status_matches(
+            enums::RefundStatus::Pending,
+            refund_response.response.unwrap().connector_refund_id,
+            None,
+            get_default_payment_info(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response.response.unwrap().refund_status,
+        enums::RefundStatus::Pending,
+    );
+}
+
+// Cards Negative scenerios
+// Creates a payment with a card declined by the processor.
+#[actix_web::test]
+async fn should_fail_payment_for_processor_decline() {
+    let response = CONNECTOR
+        .make_payment(None, get_payment_info("fake-processor-declined-visa-nonce"))
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Failure);
+    let error = response.response.unwrap_err();
+    assert_eq!(error.code, "2000");
+    assert_eq!(
+        Braintree.get_error_category(&error.code),
+        api::enums::ErrorCategory::DoNotHonor,
+    );
+}
+
+// Creates a payment declined for insufficient funds, the sandbox declines amounts between 2000.00
+// and 2999.99 with the processor response code matching the amount.
+#[actix_web::test]
+async fn should_fail_payment_for_insufficient_funds() {
+    let response = CONNECTOR
+        .make_payment(
+            Some(types::PaymentsAuthorizeData {
+                amount: 200100,
+                ..utils::PaymentAuthorizeType::default().0
+            }),
+            get_default_payment_info(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Failure);
+    let error = response.response.unwrap_err();
+    assert_eq!(error.code, "2001");
+    assert_eq!(
+        Braintree.get_error_category(&error.code),
+        api::enums::ErrorCategory::InsufficientFunds,
+    );
+}
+
+// Creates a payment with a payment method that does not exist.
+#[actix_web::test]
+async fn should_fail_payment_for_unknown_payment_method() {
+    let response = CONNECTOR
+        .make_payment(None, get_payment_info("fake-unknown-nonce"))
+        .await
+        .unwrap();
+    assert!(response.response.is_err());
+}
+
+// Completes a payment whose card failed 3DS authentication, the form of the Braintree SDK posts a
+// blank nonce along with the error, and Braintree is not called.
+#[test]
+fn should_fail_payment_for_failed_3ds_authentication() {
+    let payload = serde_json::json!({
+        "nonce": "",
+        "error_message": "Cardholder failed authentication",
+    });
+    let flow_type = services::ConnectorRedirectResponse::get_flow_type(
+        &Braintree,
+        "",
+        Some(payload.clone()),
+        services::PaymentAction::CompleteAuthorize,
+    )
+    .unwrap();
+    let response = match flow_type {
+        payments::CallConnectorAction::HandleResponse(response) => response,
+        _ => panic!("3DS authentication failure should be handled without calling Braintree"),
+    };
+
+    let request = CONNECTOR
+        .generate_data::<api::CompleteAuthorize, _, types::PaymentsResponseData>(
+            types::CompleteAuthorizeData {
+                payment_method_data: None,
+                amount: 100,
+                email: None,
+                currency: enums::Currency::USD,
+                confirm: true,
+                statement_descriptor_suffix: None,
+                capture_method: None,
+                setup_future_usage: None,
+                mandate_id: None,
+                off_session: None,
+                setup_mandate_details: None,
+                payload: Some(payload),
+                browser_info: None,
+                connector_transaction_id: None,
+                connector_meta: None,
+            },
+            None,
+        );
+    let response = services::ConnectorIntegration::<
+        api::CompleteAuthorize,
+        types::CompleteAuthorizeData,
+        types::PaymentsResponseData,
+    >::handle_response(
+        &Braintree,
+        &request,
+        types::Response {
+            response: response.into(),
+            status_code: 200,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(response.status, enums::AttemptStatus::AuthenticationFailed);
+    let error = response.response.unwrap_err();
+    assert_eq!(error.code, "THREE_D_SECURE");
+    assert_eq!(error.message, "Cardholder failed authentication");
+}
+
+// Captures a payment using invalid connector payment id.
+#[actix_web::test]
+async fn should_fail_capture_for_invalid_payment() {
+    let capture_response = CONNECTOR
+        .capture_payment("123456789".to_string(), None, get_default_payment_info())
+        .await
+        .unwrap();
+    assert!(capture_response.response.is_err());
+}
+
+// Refunds a payment which is not settled yet.
+#[actix_web::test]
+async fn should_fail_refund_for_unsettled_payment() {
+    let response = CONNECTOR
+        .make_payment_and_refund(None, None, get_default_payment_info())
+        .await
+        .unwrap();
+    assert_eq!(response.response.unwrap_err().code, "91506");
+}
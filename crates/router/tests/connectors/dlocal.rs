@@ -457,6 +457,7 @@ pub fn get_payment_info() -> PaymentInfo {
         auth_type: None,
         access_token: None,
         connector_meta_data: None,
+        payment_method_token: None,
     }
 }
 // Connector dependent test cases goes here
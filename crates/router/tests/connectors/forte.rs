@@ -14,7 +14,7 @@ impl utils::Connector for ForteTest {
         use router::connector::Forte;
         types::api::ConnectorData {
             connector: Box::new(&Forte),
-            connector_name: types::Connector::Dummy,
+            connector_name: types::Connector::Forte,
             get_token: types::api::GetToken::Connector,
         }
     }
@@ -35,11 +35,30 @@ impl utils::Connector for ForteTest {
 static CONNECTOR: ForteTest = ForteTest {};
 
 fn get_default_payment_info() -> Option<utils::PaymentInfo> {
-    None
+    Some(utils::PaymentInfo {
+        address: Some(types::PaymentAddress {
+            billing: Some(api::Address {
+                address: Some(api::AddressDetails {
+                    first_name: Some(Secret::new("John".to_string())),
+                    last_name: Some(Secret::new("Doe".to_string())),
+                    ..Default::default()
+                }),
+                phone: None,
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
 }
 
 fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
-    None
+    Some(types::PaymentsAuthorizeData {
+        payment_method_data: types::api::PaymentMethodData::Card(api::Card {
+            card_number: Secret::new(String::from("4111111111111111")),
+            ..utils::CCardType::default().0
+        }),
+        ..utils::PaymentAuthorizeType::default().0
+    })
 }
 
 // Cards Positive Tests
@@ -53,24 +72,37 @@ async fn should_only_authorize_payment() {
     assert_eq!(response.status, enums::AttemptStatus::Authorized);
 }
 
-// Captures a payment using the manual capture flow (Non 3DS).
-#[actix_web::test]
-async fn should_capture_authorized_payment() {
+// Forte expects the authorization code of the original transaction on every follow-up
+// operation, which is returned in the connector metadata of the payment
+async fn authorize_payment_with_metadata() -> (String, Option<serde_json::Value>) {
     let response = CONNECTOR
-        .authorize_and_capture_payment(payment_method_details(), None, get_default_payment_info())
+        .authorize_payment(payment_method_details(), get_default_payment_info())
         .await
-        .expect("Capture payment response");
+        .expect("Authorize payment response");
+    let txn_id = utils::get_connector_transaction_id(response.response.clone())
+        .expect("Missing connector transaction id");
+    (txn_id, utils::get_connector_metadata(response.response))
+}
+
+async fn make_payment_with_metadata() -> (String, Option<serde_json::Value>) {
+    let response = CONNECTOR
+        .make_payment(payment_method_details(), get_default_payment_info())
+        .await
+        .expect("Make payment response");
     assert_eq!(response.status, enums::AttemptStatus::Charged);
+    let txn_id = utils::get_connector_transaction_id(response.response.clone())
+        .expect("Missing connector transaction id");
+    (txn_id, utils::get_connector_metadata(response.response))
 }
 
-// Partially captures a payment using the manual capture flow (Non 3DS).
-#[actix_web::test]
-async fn should_partially_capture_authorized_payment() {
+async fn capture_payment(amount_to_capture: i64) -> (String, Option<serde_json::Value>) {
+    let (txn_id, connector_meta) = authorize_payment_with_metadata().await;
     let response = CONNECTOR
-        .authorize_and_capture_payment(
-            payment_method_details(),
+        .capture_payment(
+            txn_id.clone(),
             Some(types::PaymentsCaptureData {
-                amount_to_capture: 50,
+                amount_to_capture,
+                connector_meta: connector_meta.clone(),
                 ..utils::PaymentCaptureType::default().0
             }),
             get_default_payment_info(),
@@ -78,23 +110,49 @@ async fn should_partially_capture_authorized_payment() {
         .await
         .expect("Capture payment response");
     assert_eq!(response.status, enums::AttemptStatus::Charged);
+    (txn_id, connector_meta)
+}
+
+async fn refund_payment(
+    txn_id: String,
+    connector_metadata: Option<serde_json::Value>,
+    refund_amount: i64,
+) -> types::RefundExecuteRouterData {
+    CONNECTOR
+        .refund_payment(
+            txn_id,
+            Some(types::RefundsData {
+                refund_amount,
+                connector_metadata,
+                ..utils::PaymentRefundType::default().0
+            }),
+            get_default_payment_info(),
+        )
+        .await
+        .expect("Refund payment response")
+}
+
+// Captures a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_capture_authorized_payment() {
+    capture_payment(100).await;
+}
+
+// Partially captures a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+async fn should_partially_capture_authorized_payment() {
+    capture_payment(50).await;
 }
 
 // Synchronizes a payment using the manual capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_sync_authorized_payment() {
-    let authorize_response = CONNECTOR
-        .authorize_payment(payment_method_details(), get_default_payment_info())
-        .await
-        .expect("Authorize payment response");
-    let txn_id = utils::get_connector_transaction_id(authorize_response.response);
+    let (txn_id, _) = authorize_payment_with_metadata().await;
     let response = CONNECTOR
         .psync_retry_till_status_matches(
             enums::AttemptStatus::Authorized,
             Some(types::PaymentsSyncData {
-                connector_transaction_id: router::types::ResponseId::ConnectorTransactionId(
-                    txn_id.unwrap(),
-                ),
+                connector_transaction_id: router::types::ResponseId::ConnectorTransactionId(txn_id),
                 ..Default::default()
             }),
             get_default_payment_info(),
@@ -107,13 +165,14 @@ async fn should_sync_authorized_payment() {
 // Voids a payment using the manual capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_void_authorized_payment() {
+    let (txn_id, connector_meta) = authorize_payment_with_metadata().await;
     let response = CONNECTOR
-        .authorize_and_void_payment(
-            payment_method_details(),
+        .void_payment(
+            txn_id,
             Some(types::PaymentsCancelData {
-                connector_transaction_id: String::from(""),
+                connector_meta,
                 cancellation_reason: Some("requested_by_customer".to_string()),
-                ..Default::default()
+                ..utils::PaymentCancelType::default().0
             }),
             get_default_payment_info(),
         )
@@ -125,54 +184,30 @@ async fn should_void_authorized_payment() {
 // Refunds a payment using the manual capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_refund_manually_captured_payment() {
-    let response = CONNECTOR
-        .capture_payment_and_refund(
-            payment_method_details(),
-            None,
-            None,
-            get_default_payment_info(),
-        )
-        .await
-        .unwrap();
+    let (txn_id, connector_meta) = capture_payment(100).await;
+    let response = refund_payment(txn_id, connector_meta, 100).await;
     assert_eq!(
         response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        enums::RefundStatus::Pending,
     );
 }
 
 // Partially refunds a payment using the manual capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_partially_refund_manually_captured_payment() {
-    let response = CONNECTOR
-        .capture_payment_and_refund(
-            payment_method_details(),
-            None,
-            Some(types::RefundsData {
-                refund_amount: 50,
-                ..utils::PaymentRefundType::default().0
-            }),
-            get_default_payment_info(),
-        )
-        .await
-        .unwrap();
+    let (txn_id, connector_meta) = capture_payment(100).await;
+    let response = refund_payment(txn_id, connector_meta, 50).await;
     assert_eq!(
         response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        enums::RefundStatus::Pending,
     );
 }
 
 // Synchronizes a refund using the manual capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_sync_manually_captured_refund() {
-    let refund_response = CONNECTOR
-        .capture_payment_and_refund(
-            payment_method_details(),
-            None,
-            None,
-            get_default_payment_info(),
-        )
-        .await
-        .unwrap();
+    let (txn_id, connector_meta) = capture_payment(100).await;
+    let refund_response = refund_payment(txn_id, connector_meta, 100).await;
     let response = CONNECTOR
         .rsync_retry_till_status_matches(
             enums::RefundStatus::Success,
@@ -201,20 +236,12 @@ async fn should_make_payment() {
 // Synchronizes a payment using the automatic capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_sync_auto_captured_payment() {
-    let authorize_response = CONNECTOR
-        .make_payment(payment_method_details(), get_default_payment_info())
-        .await
-        .unwrap();
-    assert_eq!(authorize_response.status, enums::AttemptStatus::Charged);
-    let txn_id = utils::get_connector_transaction_id(authorize_response.response);
-    assert_ne!(txn_id, None, "Empty connector transaction id");
+    let (txn_id, _) = make_payment_with_metadata().await;
     let response = CONNECTOR
         .psync_retry_till_status_matches(
             enums::AttemptStatus::Charged,
             Some(types::PaymentsSyncData {
-                connector_transaction_id: router::types::ResponseId::ConnectorTransactionId(
-                    txn_id.unwrap(),
-                ),
+                connector_transaction_id: router::types::ResponseId::ConnectorTransactionId(txn_id),
                 capture_method: Some(enums::CaptureMethod::Automatic),
                 ..Default::default()
             }),
@@ -228,58 +255,43 @@ async fn should_sync_auto_captured_payment() {
 // Refunds a payment using the automatic capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_refund_auto_captured_payment() {
-    let response = CONNECTOR
-        .make_payment_and_refund(payment_method_details(), None, get_default_payment_info())
-        .await
-        .unwrap();
+    let (txn_id, connector_meta) = make_payment_with_metadata().await;
+    let response = refund_payment(txn_id, connector_meta, 100).await;
     assert_eq!(
         response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        enums::RefundStatus::Pending,
     );
 }
 
 // Partially refunds a payment using the automatic capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_partially_refund_succeeded_payment() {
-    let refund_response = CONNECTOR
-        .make_payment_and_refund(
-            payment_method_details(),
-            Some(types::RefundsData {
-                refund_amount: 50,
-                ..utils::PaymentRefundType::default().0
-            }),
-            get_default_payment_info(),
-        )
-        .await
-        .unwrap();
+    let (txn_id, connector_meta) = make_payment_with_metadata().await;
+    let response = refund_payment(txn_id, connector_meta, 50).await;
     assert_eq!(
-        refund_response.response.unwrap().refund_status,
-        enums::RefundStatus::Success,
+        response.response.unwrap().refund_status,
+        enums::RefundStatus::Pending,
     );
 }
 
 // Creates multiple refunds against a payment using the automatic capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_refund_succeeded_payment_multiple_times() {
-    CONNECTOR
-        .make_payment_and_multiple_refund(
-            payment_method_details(),
-            Some(types::RefundsData {
-                refund_amount: 50,
-                ..utils::PaymentRefundType::default().0
-            }),
-            get_default_payment_info(),
-        )
-        .await;
+    let (txn_id, connector_meta) = make_payment_with_metadata().await;
+    for _ in 0..2 {
+        let response = refund_payment(txn_id.clone(), connector_meta.clone(), 50).await;
+        assert_eq!(
+            response.response.unwrap().refund_status,
+            enums::RefundStatus::Pending,
+        );
+    }
 }
 
 // Synchronizes a refund using the automatic capture flow (Non 3DS).
 #[actix_web::test]
 async fn should_sync_refund() {
-    let refund_response = CONNECTOR
-        .make_payment_and_refund(payment_method_details(), None, get_default_payment_info())
-        .await
-        .unwrap();
+    let (txn_id, connector_meta) = make_payment_with_metadata().await;
+    let refund_response = refund_payment(txn_id, connector_meta, 100).await;
     let response = CONNECTOR
         .rsync_retry_till_status_matches(
             enums::RefundStatus::Success,
@@ -6,6 +6,7 @@ mod airwallex;
 mod authorizedotnet;
 mod bambora;
 mod bluesnap;
+mod braintree;
 mod checkout;
 mod coinbase;
 mod connector_auth;
@@ -24,10 +25,14 @@ mod payeezy;
 mod paypal;
 mod payu;
 mod rapyd;
+mod recorder;
 mod selenium;
 mod shift4;
+#[cfg(feature = "simulator")]
+mod simulator;
 mod stripe;
 mod trustpay;
 mod utils;
+mod webhooks;
 mod worldline;
 mod worldpay;
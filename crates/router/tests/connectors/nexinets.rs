@@ -14,7 +14,7 @@ impl utils::Connector for NexinetsTest {
         use router::connector::Nexinets;
         types::api::ConnectorData {
             connector: Box::new(&Nexinets),
-            connector_name: types::Connector::Dummy,
+            connector_name: types::Connector::Nexinets,
             get_token: types::api::GetToken::Connector,
         }
     }
@@ -39,7 +39,14 @@ fn get_default_payment_info() -> Option<utils::PaymentInfo> {
 }
 
 fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
-    None
+    Some(types::PaymentsAuthorizeData {
+        router_return_url: Some("https://google.com".to_string()),
+        payment_method_data: types::api::PaymentMethodData::Card(api::Card {
+            card_number: Secret::new(String::from("4012001038443335")),
+            ..utils::CCardType::default().0
+        }),
+        ..utils::PaymentAuthorizeType::default().0
+    })
 }
 
 // Cards Positive Tests
@@ -136,6 +136,28 @@ async fn should_refund_succeeded_payment() {
     }
 }
 
+#[actix_web::test]
+async fn should_sync_refund() {
+    let connector = Rapyd {};
+    let refund_response = connector
+        .make_payment_and_refund(None, None, None)
+        .await
+        .unwrap();
+    let response = connector
+        .rsync_retry_till_status_matches(
+            enums::RefundStatus::Success,
+            refund_response.response.unwrap().connector_refund_id,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response.response.unwrap().refund_status,
+        enums::RefundStatus::Success,
+    );
+}
+
 #[actix_web::test]
 async fn should_fail_payment_for_incorrect_card_number() {
     let response = Rapyd {}
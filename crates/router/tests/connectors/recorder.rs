@@ -0,0 +1,413 @@
+//! Recording and replaying of the HTTP traffic between the router and connectors.
+//!
+//! The mode is selected with the `CONNECTOR_HTTP_MODE` environment variable:
+//!
+//! - `live` (default): requests are sent to the connector sandbox.
+//! - `record`: requests are sent to the connector sandbox and every request/response pair is
+//!   written to `fixtures/recordings/<connector>/<test name>.json`, with secrets masked.
+//! - `replay`: every request is answered by a `wiremock` server serving the recorded response,
+//!   so that the transformers of a connector can be regression tested offline. The request must
+//!   match the recorded method, path and body, the body being masked before it is compared.
+//!   Replay uses the mock database, so no Postgres instance is needed either.
+//!
+//! ```bash
+//! CONNECTOR_HTTP_MODE=record cargo test --package router --test connectors -- shift4
+//! CONNECTOR_HTTP_MODE=replay cargo test --package router --test connectors -- shift4
+//! ```
+//!
+//! Recordings are keyed by the name of the thread running the test, which is the test name for
+//! `cargo test`. Requests made from within the router itself, such as those made by
+//! `execute_pretasks`, bypass the harness and are always sent to the connector. Only JSON and form
+//! encoded bodies are masked, other bodies (such as XML) must be reviewed before being committed.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use error_stack::{Report, ResultExt};
+use masking::{PeekInterface, Secret};
+use once_cell::sync::Lazy;
+use router::{
+    core::errors::ConnectorError,
+    db::StorageImpl,
+    routes, services,
+    types::{self, RouterData},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+const MODE_ENV: &str = "CONNECTOR_HTTP_MODE";
+
+/// Suffixes (compared case insensitively, ignoring punctuation) of the keys holding credentials,
+/// which are masked in both requests and responses.
+const CREDENTIAL_KEYS: [&str; 10] = [
+    "cvc",
+    "cvv",
+    "cvv2",
+    "securitycode",
+    "password",
+    "secret",
+    "apikey",
+    "privatekey",
+    "accesstoken",
+    "authorization",
+];
+
+/// Suffixes of the keys holding customer data, which are only masked in requests. Responses keep
+/// them since connectors tend to return identifiers under similar keys (`transaction_number`).
+const PERSONAL_KEYS: [&str; 4] = ["number", "pan", "iban", "email"];
+
+/// Request headers that are recorded as is, every other header is masked.
+const PLAIN_HEADERS: [&str; 5] = [
+    "accept",
+    "content-length",
+    "content-type",
+    "user-agent",
+    "via",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HttpMode {
+    Live,
+    Record,
+    Replay,
+}
+
+impl HttpMode {
+    pub fn current() -> Self {
+        match std::env::var(MODE_ENV).as_deref() {
+            Ok("live") | Err(_) => Self::Live,
+            Ok("record") => Self::Record,
+            Ok("replay") => Self::Replay,
+            Ok(other) => panic!("invalid {MODE_ENV} `{other}`, expected live, record or replay"),
+        }
+    }
+
+    pub fn storage(self) -> StorageImpl {
+        match self {
+            Self::Live | Self::Record => StorageImpl::PostgresqlTest,
+            Self::Replay => StorageImpl::Mock,
+        }
+    }
+}
+
+/// Waits for the connector to settle before a dependent request, recordings need no waiting.
+pub async fn wait(seconds: u64) {
+    if HttpMode::current() != HttpMode::Replay {
+        tokio::time::sleep(Duration::from_secs(seconds)).await;
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RecordedRequest {
+    method: services::Method,
+    path: String,
+    query: Option<String>,
+    headers: BTreeMap<String, String>,
+    body: Option<RecordedBody>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RecordedResponse {
+    status_code: u16,
+    body: RecordedBody,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RecordedBody {
+    Json(Value),
+    Text(String),
+}
+
+impl From<&services::Request> for RecordedRequest {
+    fn from(request: &services::Request) -> Self {
+        let url = url::Url::parse(&request.url).expect("connector built an invalid url");
+        let headers = request
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let name = name.to_ascii_lowercase();
+                let value = if PLAIN_HEADERS.contains(&name.as_str()) {
+                    value.clone()
+                } else {
+                    masked(value)
+                };
+                (name, value)
+            })
+            .collect();
+
+        Self {
+            method: request.method,
+            path: url.path().to_string(),
+            query: url.query().map(|query| mask_form(query, Masking::Request)),
+            headers,
+            body: request
+                .payload
+                .as_ref()
+                .map(|payload| RecordedBody::new(payload.peek(), Masking::Request)),
+        }
+    }
+}
+
+impl From<&types::Response> for RecordedResponse {
+    fn from(response: &types::Response) -> Self {
+        Self {
+            status_code: response.status_code,
+            body: RecordedBody::new(
+                &String::from_utf8_lossy(&response.response),
+                Masking::Response,
+            ),
+        }
+    }
+}
+
+impl RecordedBody {
+    fn new(body: &str, masking: Masking) -> Self {
+        match serde_json::from_str::<Value>(body) {
+            Ok(mut value) => {
+                mask_json(&mut value, masking);
+                Self::Json(value)
+            }
+            Err(_) if body.trim_start().starts_with('<') => Self::Text(body.to_string()),
+            Err(_) => Self::Text(mask_form(body, masking)),
+        }
+    }
+
+    fn into_raw(self) -> (String, &'static str) {
+        match self {
+            Self::Json(value) => (value.to_string(), "application/json"),
+            Self::Text(text) => (text, "text/plain"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Masking {
+    Request,
+    Response,
+}
+
+impl Masking {
+    fn is_sensitive(self, key: &str) -> bool {
+        let key = key
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let is_match = |suffix: &&str| key.ends_with(suffix);
+        match self {
+            Self::Request => {
+                CREDENTIAL_KEYS.iter().any(is_match) || PERSONAL_KEYS.iter().any(is_match)
+            }
+            Self::Response => CREDENTIAL_KEYS.iter().any(is_match),
+        }
+    }
+}
+
+fn masked(value: &str) -> String {
+    format!("{:?}", Secret::<String>::new(value.to_string()))
+}
+
+/// Masks the string values of sensitive keys. Other values are kept so that the masked body still
+/// deserializes into the types of the connector.
+fn mask_json(value: &mut Value, masking: Masking) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(string) if masking.is_sensitive(key) => *string = masked(string),
+                    _ => mask_json(value, masking),
+                }
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| mask_json(value, masking)),
+        _ => {}
+    }
+}
+
+fn mask_form(form: &str, masking: Masking) -> String {
+    url::form_urlencoded::parse(form.as_bytes())
+        .fold(
+            url::form_urlencoded::Serializer::new(String::new()),
+            |mut serializer, (key, value)| {
+                if masking.is_sensitive(&key) {
+                    serializer.append_pair(&key, &masked(&value));
+                } else {
+                    serializer.append_pair(&key, &value);
+                }
+                serializer
+            },
+        )
+        .finish()
+}
+
+/// Path of the recording of the current test for the given connector.
+fn fixture_path(connector: &str) -> PathBuf {
+    let thread = std::thread::current();
+    let test_name = thread
+        .name()
+        .and_then(|name| name.rsplit("::").next())
+        .filter(|name| *name != "main")
+        .expect("recordings are keyed by the test name, the test must run on its own thread");
+
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/connectors/fixtures/recordings")
+        .join(connector)
+        .join(format!("{test_name}.json"))
+}
+
+/// Interactions recorded so far in this run, the recording of a test is overwritten by its first
+/// request.
+static RECORDINGS: Lazy<Mutex<HashMap<PathBuf, Vec<Interaction>>>> = Lazy::new(Default::default);
+
+/// Index of the next interaction to replay for each recording.
+static REPLAY_POSITIONS: Lazy<Mutex<HashMap<PathBuf, usize>>> = Lazy::new(Default::default);
+
+fn record(fixture: &Path, interaction: Interaction) {
+    let mut recordings = RECORDINGS.lock().expect("recordings lock poisoned");
+    let interactions = recordings.entry(fixture.to_path_buf()).or_default();
+    interactions.push(interaction);
+
+    if let Some(directory) = fixture.parent() {
+        fs::create_dir_all(directory).expect("failed to create the recordings directory");
+    }
+    let contents =
+        serde_json::to_string_pretty(interactions).expect("failed to serialize the recording");
+    fs::write(fixture, contents + "\n").expect("failed to write the recording");
+}
+
+fn next_interaction(fixture: &Path) -> Interaction {
+    let contents = fs::read_to_string(fixture).unwrap_or_else(|error| {
+        panic!(
+            "failed to read {}: {error}, run the test with {MODE_ENV}=record first",
+            fixture.display()
+        )
+    });
+    let mut interactions: Vec<Interaction> =
+        serde_json::from_str(&contents).expect("failed to deserialize the recording");
+
+    let mut positions = REPLAY_POSITIONS.lock().expect("replay lock poisoned");
+    let position = positions.entry(fixture.to_path_buf()).or_default();
+    assert!(
+        *position < interactions.len(),
+        "{} has no recorded response left for the request, record it again",
+        fixture.display()
+    );
+    let interaction = interactions.swap_remove(*position);
+    *position += 1;
+    interaction
+}
+
+/// Matches the requests whose body is the recorded one once masked, as the recording only holds
+/// the masked body.
+struct MaskedBody(Option<RecordedBody>);
+
+impl wiremock::Match for MaskedBody {
+    fn matches(&self, request: &wiremock::Request) -> bool {
+        let body = (!request.body.is_empty())
+            .then(|| RecordedBody::new(&String::from_utf8_lossy(&request.body), Masking::Request));
+        body == self.0
+    }
+}
+
+/// Starts a mock server expecting exactly the recorded request, and answering with the recorded
+/// response. The expectation is verified when the server is dropped.
+async fn serve(interaction: Interaction) -> MockServer {
+    let server = MockServer::start().await;
+    let (body, content_type) = interaction.response.body.into_raw();
+    Mock::given(matchers::method(
+        interaction.request.method.to_string().as_str(),
+    ))
+    .and(matchers::path(interaction.request.path))
+    .and(MaskedBody(interaction.request.body))
+    .respond_with(
+        ResponseTemplate::new(interaction.response.status_code).set_body_raw(body, content_type),
+    )
+    .expect(1)
+    .mount(&server)
+    .await;
+    server
+}
+
+fn point_to(url: &str, server: &MockServer) -> String {
+    let mut url = url::Url::parse(url).expect("connector built an invalid url");
+    let server_url = url::Url::parse(&server.uri()).expect("invalid mock server url");
+    url.set_scheme(server_url.scheme())
+        .expect("failed to set the scheme of the mock server");
+    url.set_host(server_url.host_str())
+        .expect("failed to set the host of the mock server");
+    url.set_port(server_url.port())
+        .expect("failed to set the port of the mock server");
+    url.to_string()
+}
+
+/// Equivalent of `execute_connector_processing_step` that records or replays the HTTP exchange
+/// with the connector.
+pub async fn call_connector<
+    T: Debug + Clone + 'static,
+    Req: Debug + Clone + 'static,
+    Resp: Debug + Clone + 'static,
+>(
+    state: &routes::AppState,
+    integration: services::BoxedConnectorIntegration<'_, T, Req, Resp>,
+    request: &RouterData<T, Req, Resp>,
+    mode: HttpMode,
+) -> Result<RouterData<T, Req, Resp>, Report<ConnectorError>> {
+    let mut connector_request = match integration.build_request(request, &state.conf.connectors)? {
+        Some(connector_request) => connector_request,
+        None => return Ok(request.clone()),
+    };
+    let fixture = fixture_path(&request.connector);
+
+    let server = match mode {
+        HttpMode::Replay => {
+            let server = serve(next_interaction(&fixture)).await;
+            connector_request.url = point_to(&connector_request.url, &server);
+            Some(server)
+        }
+        HttpMode::Live | HttpMode::Record => None,
+    };
+    let recorded_request =
+        (mode == HttpMode::Record).then(|| RecordedRequest::from(&connector_request));
+
+    let response =
+        services::api::send_connector_request(state, &request.connector, connector_request)
+            .await
+            .change_context(ConnectorError::ProcessingStepFailed(None))?;
+    drop(server);
+
+    if let Some(recorded_request) = recorded_request {
+        let (Ok(body) | Err(body)) = &response;
+        record(
+            &fixture,
+            Interaction {
+                request: recorded_request,
+                response: RecordedResponse::from(body),
+            },
+        );
+    }
+
+    match response {
+        Ok(body) => integration.handle_response(request, body),
+        Err(body) => {
+            let mut router_data = request.clone();
+            router_data.response = Err(integration.get_error_response(body)?);
+            Ok(router_data)
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use std::{net::TcpStream, sync::Once, time::Duration};
+
+use masking::Secret;
+use router::{
+    configs::settings::Settings,
+    types::{self, api, storage::enums},
+};
+
+use crate::{
+    connector_auth,
+    utils::{self, ConnectorActions, PaymentInfo},
+};
+
+#[derive(Clone, Copy)]
+struct SimulatorTest;
+impl ConnectorActions for SimulatorTest {}
+impl utils::Connector for SimulatorTest {
+    fn get_data(&self) -> types::api::ConnectorData {
+        use router::connector::Simulator;
+        types::api::ConnectorData {
+            connector: Box::new(&Simulator),
+            connector_name: types::Connector::Simulator,
+            get_token: types::api::GetToken::Connector,
+        }
+    }
+
+    // An API key of a merchant on the router serving the simulator
+    fn get_auth_token(&self) -> types::ConnectorAuthType {
+        types::ConnectorAuthType::from(
+            connector_auth::ConnectorAuthentication::new()
+                .simulator
+                .expect("Missing connector authentication configuration"),
+        )
+    }
+
+    fn get_name(&self) -> String {
+        "simulator".to_string()
+    }
+
+    // Pending objects are resolved by the simulator after two seconds
+    fn get_request_interval(&self) -> u64 {
+        2
+    }
+}
+
+static CONNECTOR: SimulatorTest = SimulatorTest {};
+static SIMULATOR: Once = Once::new();
+
+/// Starts the router, which serves the simulator, on a dedicated thread so that it outlives the
+/// runtime of the test that happened to start it.
+fn start_simulator() {
+    SIMULATOR.call_once(|| {
+        std::thread::spawn(|| {
+            actix_web::rt::System::new().block_on(async {
+                let conf = Settings::new().expect("invalid settings");
+                let (server, _state) = router::start_server(conf)
+                    .await
+                    .expect("failed to create server");
+                server.await.expect("simulator server failed");
+            })
+        });
+
+        let address = {
+            let conf = Settings::new().expect("invalid settings");
+            format!("{}:{}", conf.server.host, conf.server.port)
+        };
+        for _ in 0..50 {
+            if TcpStream::connect(&address).is_ok() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        panic!("simulator server did not start");
+    });
+}
+
+fn payment_method_details(card_number: &str, amount: i64) -> Option<types::PaymentsAuthorizeData> {
+    Some(types::PaymentsAuthorizeData {
+        amount,
+        payment_method_data: types::api::PaymentMethodData::Card(api::Card {
+            card_number: Secret::new(card_number.to_string()),
+            ..utils::CCardType::default().0
+        }),
+        ..utils::PaymentAuthorizeType::default().0
+    })
+}
+
+// Creates a payment using the manual capture flow.
+#[actix_web::test]
+async fn should_only_authorize_payment() {
+    start_simulator();
+    let response = CONNECTOR.authorize_payment(None, None).await.unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Authorized);
+}
+
+// Creates a payment using the automatic capture flow.
+#[actix_web::test]
+async fn should_make_payment() {
+    start_simulator();
+    let response = CONNECTOR.make_payment(None, None).await.unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Charged);
+}
+
+// Partially captures a payment using the manual capture flow.
+#[actix_web::test]
+async fn should_partially_capture_authorized_payment() {
+    start_simulator();
+    let response = CONNECTOR
+        .authorize_and_capture_payment(
+            None,
+            Some(types::PaymentsCaptureData {
+                amount_to_capture: 50,
+                ..utils::PaymentCaptureType::default().0
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Charged);
+}
+
+// Voids a payment using the manual capture flow.
+#[actix_web::test]
+async fn should_void_authorized_payment() {
+    start_simulator();
+    let response = CONNECTOR
+        .authorize_and_void_payment(None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Voided);
+}
+
+// Synchronizes a payment using the automatic capture flow.
+#[actix_web::test]
+async fn should_sync_auto_captured_payment() {
+    start_simulator();
+    let authorize_response = CONNECTOR.make_payment(None, None).await.unwrap();
+    let txn_id = utils::get_connector_transaction_id(authorize_response.response);
+    let response = CONNECTOR
+        .psync_retry_till_status_matches(
+            enums::AttemptStatus::Charged,
+            Some(types::PaymentsSyncData {
+                connector_transaction_id: types::ResponseId::ConnectorTransactionId(
+                    txn_id.unwrap(),
+                ),
+                ..Default::default()
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Charged);
+}
+
+// Refunds a payment using the automatic capture flow and synchronizes the refund.
+#[actix_web::test]
+async fn should_refund_succeeded_payment_and_sync_refund() {
+    start_simulator();
+    let refund_response = CONNECTOR
+        .make_payment_and_refund(None, None, None)
+        .await
+        .unwrap();
+    let refund_id = refund_response.response.unwrap().connector_refund_id;
+    let response = CONNECTOR
+        .rsync_retry_till_status_matches(enums::RefundStatus::Success, refund_id, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        response.response.unwrap().refund_status,
+        enums::RefundStatus::Success,
+    );
+}
+
+// Creates a payment that the simulator declines because of the card used.
+#[actix_web::test]
+async fn should_fail_payment_for_declined_card() {
+    start_simulator();
+    let response = CONNECTOR
+        .make_payment(payment_method_details("4000000000000002", 100), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Failure);
+    assert_eq!(response.response.unwrap_err().code, "card_declined");
+}
+
+// Creates a payment that the simulator declines because of the amount used.
+#[actix_web::test]
+async fn should_fail_payment_for_declined_amount() {
+    start_simulator();
+    let response = CONNECTOR
+        .make_payment(payment_method_details("4242424242424242", 9901), None)
+        .await
+        .unwrap();
+    assert_eq!(response.response.unwrap_err().code, "do_not_honor");
+}
+
+// Creates a payment that requires a 3DS challenge.
+#[actix_web::test]
+async fn should_redirect_for_3ds_challenge() {
+    start_simulator();
+    let response = CONNECTOR
+        .make_payment(
+            None,
+            Some(PaymentInfo {
+                auth_type: Some(enums::AuthenticationType::ThreeDs),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::AuthenticationPending);
+    assert!(matches!(
+        response.response,
+        Ok(types::PaymentsResponseData::TransactionResponse {
+            redirection_data: Some(_),
+            ..
+        })
+    ));
+}
+
+// Creates a payment that stays pending until the simulator resolves it.
+#[actix_web::test]
+async fn should_resolve_pending_payment() {
+    start_simulator();
+    let authorize_response = CONNECTOR
+        .make_payment(payment_method_details("4000000000000077", 100), None)
+        .await
+        .unwrap();
+    assert_eq!(authorize_response.status, enums::AttemptStatus::Pending);
+    let txn_id = utils::get_connector_transaction_id(authorize_response.response);
+    let response = CONNECTOR
+        .psync_retry_till_status_matches(
+            enums::AttemptStatus::Charged,
+            Some(types::PaymentsSyncData {
+                connector_transaction_id: types::ResponseId::ConnectorTransactionId(
+                    txn_id.unwrap(),
+                ),
+                ..Default::default()
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Charged);
+}
+
+// Refunds a payment with an amount that the simulator fails refunds for.
+#[actix_web::test]
+async fn should_fail_refund_for_failing_amount() {
+    start_simulator();
+    let response = CONNECTOR
+        .make_payment_and_refund(
+            Some(types::PaymentsAuthorizeData {
+                amount: 10000,
+                ..utils::PaymentAuthorizeType::default().0
+            }),
+            Some(types::RefundsData {
+                refund_amount: 9911,
+                ..utils::PaymentRefundType::default().0
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.response.unwrap_err().code, "refund_failed");
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Debug, marker::PhantomData, time::Duration};
+use std::{fmt::Debug, marker::PhantomData};
 
 use async_trait::async_trait;
 use error_stack::Report;
@@ -6,13 +6,14 @@ use masking::Secret;
 use router::{
     configs::settings::Settings,
     core::{errors, errors::ConnectorError, payments},
-    db::StorageImpl,
     routes, services,
     types::{self, api, storage::enums, AccessToken, PaymentAddress, RouterData},
 };
 use tokio::sync::oneshot;
 use wiremock::{Mock, MockServer};
 
+use crate::recorder::{self, HttpMode};
+
 pub trait Connector {
     fn get_data(&self) -> types::api::ConnectorData;
     fn get_auth_token(&self) -> types::ConnectorAuthType;
@@ -32,6 +33,7 @@ pub struct PaymentInfo {
     pub auth_type: Option<enums::AuthenticationType>,
     pub access_token: Option<AccessToken>,
     pub connector_meta_data: Option<serde_json::Value>,
+    pub payment_method_token: Option<String>,
 }
 
 #[async_trait]
@@ -53,7 +55,7 @@ pub trait ConnectorActions: Connector {
         let tx: oneshot::Sender<()> = oneshot::channel().0;
         let state = routes::AppState::with_storage(
             Settings::new().unwrap(),
-            StorageImpl::PostgresqlTest,
+            HttpMode::current().storage(),
             tx,
         )
         .await;
@@ -78,7 +80,7 @@ pub trait ConnectorActions: Connector {
         let tx: oneshot::Sender<()> = oneshot::channel().0;
         let state = routes::AppState::with_storage(
             Settings::new().unwrap(),
-            StorageImpl::PostgresqlTest,
+            HttpMode::current().storage(),
             tx,
         )
         .await;
@@ -115,7 +117,7 @@ pub trait ConnectorActions: Connector {
             if (sync_res.status == status) || (curr_try == max_tries - 1) {
                 return Ok(sync_res);
             }
-            tokio::time::sleep(Duration::from_secs(self.get_request_interval())).await;
+            recorder::wait(self.get_request_interval()).await;
         }
         Err(errors::ConnectorError::ProcessingStepFailed(None).into())
     }
@@ -185,7 +187,7 @@ pub trait ConnectorActions: Connector {
             .unwrap();
         assert_eq!(authorize_response.status, enums::AttemptStatus::Authorized);
         let txn_id = get_connector_transaction_id(authorize_response.response);
-        tokio::time::sleep(Duration::from_secs(self.get_request_interval())).await; // to avoid 404 error
+        recorder::wait(self.get_request_interval()).await; // to avoid 404 error
         let response = self
             .void_payment(txn_id.unwrap(), void_data, payment_info)
             .await
@@ -225,7 +227,7 @@ pub trait ConnectorActions: Connector {
         let txn_id = self.get_connector_transaction_id_from_capture_data(response);
 
         //try refund for previous payment
-        tokio::time::sleep(Duration::from_secs(self.get_request_interval())).await; // to avoid 404 error
+        recorder::wait(self.get_request_interval()).await; // to avoid 404 error
         Ok(self
             .refund_payment(txn_id.unwrap(), refund_data, payment_info)
             .await
@@ -246,7 +248,7 @@ pub trait ConnectorActions: Connector {
 
         //try refund for previous payment
         let transaction_id = get_connector_transaction_id(response.response).unwrap();
-        tokio::time::sleep(Duration::from_secs(self.get_request_interval())).await; // to avoid 404 error
+        recorder::wait(self.get_request_interval()).await; // to avoid 404 error
         Ok(self
             .refund_payment(transaction_id, refund_data, payment_info)
             .await
@@ -267,7 +269,7 @@ pub trait ConnectorActions: Connector {
 
         //try refund for previous payment
         let transaction_id = get_connector_transaction_id(response.response).unwrap();
-        tokio::time::sleep(Duration::from_secs(self.get_request_interval())).await; // to avoid 404 error
+        recorder::wait(self.get_request_interval()).await; // to avoid 404 error
         Ok(self
             .refund_payment(transaction_id, refund_data, payment_info)
             .await
@@ -289,7 +291,7 @@ pub trait ConnectorActions: Connector {
         //try refund for previous payment
         let transaction_id = get_connector_transaction_id(response.response).unwrap();
         for _x in 0..2 {
-            tokio::time::sleep(Duration::from_secs(self.get_request_interval())).await; // to avoid 404 error
+            recorder::wait(self.get_request_interval()).await; // to avoid 404 error
             let refund_response = self
                 .refund_payment(
                     transaction_id.clone(),
@@ -351,7 +353,7 @@ pub trait ConnectorActions: Connector {
             {
                 return Ok(sync_res);
             }
-            tokio::time::sleep(Duration::from_secs(self.get_request_interval())).await;
+            recorder::wait(self.get_request_interval()).await;
         }
         Err(errors::ConnectorError::ProcessingStepFailed(None).into())
     }
@@ -390,10 +392,10 @@ pub trait ConnectorActions: Connector {
                 .clone()
                 .and_then(|a| a.connector_meta_data.map(masking::Secret::new)),
             amount_captured: None,
-            access_token: info.and_then(|a| a.access_token),
+            access_token: info.clone().and_then(|a| a.access_token),
             session_token: None,
             reference_id: None,
-            payment_method_token: None,
+            payment_method_token: info.and_then(|a| a.payment_method_token),
         }
     }
 
@@ -425,14 +427,22 @@ async fn call_connector<
 ) -> Result<RouterData<T, Req, Resp>, Report<ConnectorError>> {
     let conf = Settings::new().unwrap();
     let tx: oneshot::Sender<()> = oneshot::channel().0;
-    let state = routes::AppState::with_storage(conf, StorageImpl::PostgresqlTest, tx).await;
-    services::api::execute_connector_processing_step(
-        &state,
-        integration,
-        &request,
-        payments::CallConnectorAction::Trigger,
-    )
-    .await
+    let mode = HttpMode::current();
+    let state = routes::AppState::with_storage(conf, mode.storage(), tx).await;
+    match mode {
+        HttpMode::Live => {
+            services::api::execute_connector_processing_step(
+                &state,
+                integration,
+                &request,
+                payments::CallConnectorAction::Trigger,
+            )
+            .await
+        }
+        HttpMode::Record | HttpMode::Replay => {
+            recorder::call_connector(&state, integration, &request, mode).await
+        }
+    }
 }
 
 pub struct MockConfig {
@@ -497,10 +507,12 @@ impl Default for PaymentAuthorizeType {
             setup_mandate_details: None,
             browser_info: Some(BrowserInfoType::default().0),
             order_details: None,
+            purchase_details: None,
             email: None,
             session_token: None,
             enrolled_for_3ds: false,
             related_transaction_id: None,
+            network_token_cryptogram: None,
             payment_experience: None,
             payment_method_type: None,
             router_return_url: None,
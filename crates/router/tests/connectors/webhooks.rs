@@ -0,0 +1,545 @@
+use actix_web::http::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Method,
+};
+use api_models::{
+    payments::PaymentIdType,
+    webhooks::{ObjectReferenceId, RefundIdType},
+};
+use base64::Engine;
+use common_utils::crypto::{self, EncodeMessage, GenerateDigest, SignMessage};
+use router::{
+    connector,
+    types::api::{self, IncomingWebhook},
+};
+
+const MERCHANT_ID: &str = "merchant_1679303780";
+
+fn get_headers(headers: &[(&'static str, String)]) -> HeaderMap {
+    let mut header_map = HeaderMap::new();
+    for (key, value) in headers {
+        header_map.insert(
+            HeaderName::from_static(*key),
+            HeaderValue::from_str(value).unwrap(),
+        );
+    }
+    header_map
+}
+
+fn get_request_details<'a>(
+    headers: &'a HeaderMap,
+    body: &'a [u8],
+) -> api::IncomingWebhookRequestDetails<'a> {
+    api::IncomingWebhookRequestDetails {
+        method: Method::POST,
+        headers,
+        body,
+        query_params: String::new(),
+    }
+}
+
+fn assert_payment_reference(reference_id: ObjectReferenceId, expected: &str) {
+    match reference_id {
+        ObjectReferenceId::PaymentId(id) => {
+            assert_eq!(
+                id,
+                PaymentIdType::ConnectorTransactionId(expected.to_string())
+            )
+        }
+        ObjectReferenceId::RefundId(_) => panic!("Expected a payment reference"),
+    }
+}
+
+fn assert_refund_reference(reference_id: ObjectReferenceId, expected: &str) {
+    assert!(matches!(
+        reference_id,
+        ObjectReferenceId::RefundId(RefundIdType::ConnectorRefundId(id)) if id == expected
+    ));
+}
+
+fn assert_source_verified(
+    connector: &dyn IncomingWebhook,
+    request: &api::IncomingWebhookRequestDetails<'_>,
+    secret: &[u8],
+) {
+    let algorithm = connector
+        .get_webhook_source_verification_algorithm(request)
+        .unwrap();
+    let signature = connector
+        .get_webhook_source_verification_signature(request)
+        .unwrap();
+    let message = connector
+        .get_webhook_source_verification_message(request, MERCHANT_ID, secret)
+        .unwrap();
+    assert!(algorithm
+        .verify_signature(secret, &signature, &message)
+        .unwrap());
+    assert!(!algorithm
+        .verify_signature(secret, &signature, b"tampered")
+        .unwrap());
+}
+
+#[test]
+fn should_parse_paypal_capture_webhook() {
+    let body = include_bytes!("fixtures/webhooks/paypal_capture_completed.json");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Paypal;
+
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "42311647XV020574X",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(resource["status"], "COMPLETED");
+}
+
+#[test]
+fn should_parse_paypal_refund_webhook() {
+    let body = include_bytes!("fixtures/webhooks/paypal_capture_refunded.json");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Paypal;
+
+    assert_refund_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "1Y107995YT783435V",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::RefundSuccess
+    );
+}
+
+#[test]
+fn should_parse_klarna_fraud_webhook() {
+    let body = include_bytes!("fixtures/webhooks/klarna_fraud_risk_accepted.json");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Klarna;
+
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "f3392f8b-6116-4073-ab96-e330819e2c07",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(resource["fraud_status"], "ACCEPTED");
+}
+
+#[test]
+fn should_parse_mollie_webhook() {
+    let body = include_bytes!("fixtures/webhooks/mollie_payment.txt");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Mollie;
+
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "tr_WDqYK6vllg",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentProcessing
+    );
+}
+
+#[test]
+fn should_verify_and_parse_payu_order_webhook() {
+    let body = include_bytes!("fixtures/webhooks/payu_order_completed.json");
+    let secret = b"b6ca15b0d1020e8094d9b5f8d163db54";
+    let digest = crypto::Md5
+        .generate_digest(&[body.as_slice(), secret.as_slice()].concat())
+        .unwrap();
+    let headers = get_headers(&[(
+        "openpayu-signature",
+        format!(
+            "sender=checkout;signature={};algorithm=MD5;content=DOCUMENT",
+            hex::encode(digest)
+        ),
+    )]);
+    let request = get_request_details(&headers, body);
+    let connector = connector::Payu;
+
+    assert_source_verified(&connector, &request, secret);
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "LDLW5N7MF4140324GUEST000P01",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(resource["orders"][0]["status"], "COMPLETED");
+    assert_eq!(resource["status"]["statusCode"], "SUCCESS");
+}
+
+#[test]
+fn should_parse_payu_refund_webhook() {
+    let body = include_bytes!("fixtures/webhooks/payu_refund_finalized.json");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Payu;
+
+    assert_refund_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "912128",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::RefundSuccess
+    );
+}
+
+fn decode_aci_webhook(plain_body: &[u8]) -> Vec<u8> {
+    let secret = [7u8; 32];
+    let initialization_vector = [3u8; 12];
+    let (cipher_text, auth_tag) = crypto::GcmAes256::new(initialization_vector.to_vec())
+        .encode_message(&secret, plain_body)
+        .unwrap();
+    let headers = get_headers(&[
+        (
+            "x-initialization-vector",
+            hex::encode(initialization_vector),
+        ),
+        ("x-authentication-tag", hex::encode(auth_tag)),
+    ]);
+    let body = hex::encode(cipher_text);
+    let request = get_request_details(&headers, body.as_bytes());
+    let connector = connector::Aci;
+
+    let algorithm = connector
+        .get_webhook_body_decoding_algorithm(&request)
+        .unwrap();
+    let message = connector
+        .get_webhook_body_decoding_message(&request)
+        .unwrap();
+    algorithm.decode_message(&secret, &message).unwrap()
+}
+
+#[test]
+fn should_decode_and_parse_aci_payment_webhook() {
+    let plain_body = include_bytes!("fixtures/webhooks/aci_payment_debit.json");
+    let body = decode_aci_webhook(plain_body);
+    assert_eq!(body, plain_body);
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, &body);
+    let connector = connector::Aci;
+
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "8ac7a4a28702e2c601870a6efb6f4e0b",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(resource["result"]["code"], "000.100.110");
+}
+
+#[test]
+fn should_decode_and_parse_aci_refund_webhook() {
+    let body = decode_aci_webhook(include_bytes!("fixtures/webhooks/aci_refund.json"));
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, &body);
+    let connector = connector::Aci;
+
+    assert_refund_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "8ac7a49f8702e2c601870a7f5b4f2a31",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::RefundSuccess
+    );
+}
+
+#[test]
+fn should_verify_and_parse_authorizedotnet_payment_webhook() {
+    let body = include_bytes!("fixtures/webhooks/authorizedotnet_authcapture_created.json");
+    let secret = b"8F4B5E5D3C2A1F0E9D8C7B6A5F4E3D2C1B0A9F8E7D6C5B4A3F2E1D0C9B8A7F6E";
+    let signature = crypto::HmacSha512.sign_message(secret, body).unwrap();
+    let headers = get_headers(&[(
+        "x-anet-signature",
+        format!("sha512={}", hex::encode_upper(signature)),
+    )]);
+    let request = get_request_details(&headers, body);
+    let connector = connector::Authorizedotnet;
+
+    assert_source_verified(&connector, &request, secret);
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "60020981676",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(
+        resource["transaction"]["transactionStatus"],
+        "capturedPendingSettlement"
+    );
+}
+
+#[test]
+fn should_parse_authorizedotnet_refund_webhook() {
+    let body = include_bytes!("fixtures/webhooks/authorizedotnet_refund_created.json");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Authorizedotnet;
+
+    assert_refund_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "60020981792",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::RefundSuccess
+    );
+}
+
+#[test]
+fn should_parse_bambora_webhooks() {
+    let headers = HeaderMap::new();
+    let connector = connector::Bambora;
+
+    let request = get_request_details(
+        &headers,
+        include_bytes!("fixtures/webhooks/bambora_purchase.txt"),
+    );
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "10000470",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+
+    let request = get_request_details(
+        &headers,
+        include_bytes!("fixtures/webhooks/bambora_return.txt"),
+    );
+    assert_refund_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "10000482",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::RefundSuccess
+    );
+}
+
+#[test]
+fn should_verify_and_parse_dlocal_payment_webhook() {
+    let body = include_bytes!("fixtures/webhooks/dlocal_payment_paid.json");
+    let secret = b"dlocal_secret_key";
+    let x_login = "sak223k2wdksdl2";
+    let x_date = "2023-03-20T10:21:22.000Z";
+    let signature = crypto::HmacSha256
+        .sign_message(
+            secret,
+            &[x_login.as_bytes(), x_date.as_bytes(), body.as_slice()].concat(),
+        )
+        .unwrap();
+    let headers = get_headers(&[
+        ("x-login", x_login.to_string()),
+        ("x-date", x_date.to_string()),
+        (
+            "authorization",
+            format!("V2-HMAC-SHA256, Signature: {}", hex::encode(signature)),
+        ),
+    ]);
+    let request = get_request_details(&headers, body);
+    let connector = connector::Dlocal;
+
+    assert_source_verified(&connector, &request, secret);
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "D-4-e2d2b9a2-3b1f-4c8e-9a4b-0d4f3c2a1e55",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(resource["status"], "PAID");
+}
+
+#[test]
+fn should_parse_dlocal_refund_webhook() {
+    let body = include_bytes!("fixtures/webhooks/dlocal_refund_success.json");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Dlocal;
+
+    assert_refund_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "REF-15104-a9cc29fd-ee2f-4b46-9b36-e4a5b4a5c9f6",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::RefundSuccess
+    );
+}
+
+#[test]
+fn should_verify_and_parse_multisafepay_webhook() {
+    let body = include_bytes!("fixtures/webhooks/multisafepay_completed.json");
+    let secret = b"multisafepay_api_key";
+    let timestamp = "1679307711";
+    let signature = crypto::HmacSha512
+        .sign_message(
+            secret,
+            &[timestamp.as_bytes(), ":".as_bytes(), body.as_slice()].concat(),
+        )
+        .unwrap();
+    let auth = base64::engine::general_purpose::STANDARD.encode(format!(
+        "{}:{}",
+        timestamp,
+        hex::encode(signature)
+    ));
+    let headers = get_headers(&[("auth", auth)]);
+    let request = get_request_details(&headers, body);
+    let connector = connector::Multisafepay;
+
+    assert_source_verified(&connector, &request, secret);
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "pay_Yd3YbT4Yj2yUarHGSo5Q",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(resource["success"], true);
+    assert_eq!(resource["data"]["status"], "completed");
+}
+
+#[test]
+fn should_verify_and_parse_cybersource_payment_webhook() {
+    let body = include_bytes!("fixtures/webhooks/cybersource_payment_accepted.json");
+    let secret = b"cybersource_webhook_key";
+    let timestamp = "1684401219";
+    let signature = crypto::HmacSha256
+        .sign_message(
+            secret,
+            &[timestamp.as_bytes(), ".".as_bytes(), body.as_slice()].concat(),
+        )
+        .unwrap();
+    let headers = get_headers(&[(
+        "v-c-signature",
+        format!(
+            "t={};keyId=fb5b8a0f-0c5e-1b8b-e053-a2588e0a5e31;sig={}",
+            timestamp,
+            base64::engine::general_purpose::STANDARD.encode(signature)
+        ),
+    )]);
+    let request = get_request_details(&headers, body);
+    let connector = connector::Cybersource;
+
+    assert_source_verified(&connector, &request, secret);
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "6844012192696565904951",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(resource["id"], "6844012192696565904951");
+    assert_eq!(resource["applicationInformation"]["status"], "AUTHORIZED");
+}
+
+#[test]
+fn should_parse_cybersource_refund_webhook() {
+    let body = include_bytes!("fixtures/webhooks/cybersource_refund_accepted.json");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Cybersource;
+
+    assert_refund_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "6844041312396589904953",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::RefundSuccess
+    );
+}
+
+#[test]
+fn should_verify_and_parse_fiserv_payment_webhook() {
+    let body = include_bytes!("fixtures/webhooks/fiserv_charge_captured.json");
+    let secret = b"fiserv_api_secret";
+    let api_key = "fiserv_api_key";
+    let client_request_id = "4345791";
+    let timestamp = "1684401219218";
+    let signature = crypto::HmacSha256
+        .sign_message(
+            secret,
+            &[
+                api_key.as_bytes(),
+                client_request_id.as_bytes(),
+                timestamp.as_bytes(),
+                body.as_slice(),
+            ]
+            .concat(),
+        )
+        .unwrap();
+    let headers = get_headers(&[
+        ("api-key", api_key.to_string()),
+        ("client-request-id", client_request_id.to_string()),
+        ("timestamp", timestamp.to_string()),
+        (
+            "authorization",
+            base64::engine::general_purpose::STANDARD.encode(signature),
+        ),
+    ]);
+    let request = get_request_details(&headers, body);
+    let connector = connector::Fiserv;
+
+    assert_source_verified(&connector, &request, secret);
+    assert_payment_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "6f4b4f4a7c3e4e5b9a1d2c3e4f5a6b7c",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::PaymentIntentSuccess
+    );
+    let resource = connector.get_webhook_resource_object(&request).unwrap();
+    assert_eq!(
+        resource[0]["gatewayResponse"]["transactionState"],
+        "CAPTURED"
+    );
+}
+
+#[test]
+fn should_parse_fiserv_refund_webhook() {
+    let body = include_bytes!("fixtures/webhooks/fiserv_refund_captured.json");
+    let headers = HeaderMap::new();
+    let request = get_request_details(&headers, body);
+    let connector = connector::Fiserv;
+
+    assert_refund_reference(
+        connector.get_webhook_object_reference_id(&request).unwrap(),
+        "a1b2c3d4e5f6478990abcdef01234567",
+    );
+    assert_eq!(
+        connector.get_webhook_event_type(&request).unwrap(),
+        api::IncomingWebhookEvent::RefundSuccess
+    );
+}
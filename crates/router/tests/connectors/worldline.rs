@@ -82,10 +82,12 @@ impl WorldlineTest {
             capture_method: Some(capture_method),
             browser_info: None,
             order_details: None,
+            purchase_details: None,
             email: None,
             session_token: None,
             enrolled_for_3ds: false,
             related_transaction_id: None,
+            network_token_cryptogram: None,
             payment_experience: None,
             payment_method_type: None,
             router_return_url: None,
@@ -9,3 +9,20 @@ async fn health_check() {
 
     assert_eq!(client.health(&server).await, "health is good");
 }
+
+#[actix_web::test]
+async fn readiness_check() {
+    let server = mk_service().await;
+    let client = AppClient::guest();
+
+    let (status, report) = client.readiness(&server).await;
+
+    let expected_status = if report["status"] == "healthy" {
+        actix_http::StatusCode::OK
+    } else {
+        actix_http::StatusCode::SERVICE_UNAVAILABLE
+    };
+    assert_eq!(status, expected_status);
+    assert_eq!(report["checks"]["database_master"]["status"], "healthy");
+    assert!(report["checks"]["redis"]["status"].is_string());
+}
@@ -177,6 +177,18 @@ impl<T> AppClient<T> {
         let bytes = actix_web::test::call_and_read_body(app, request).await;
         String::from_utf8(bytes.to_vec()).unwrap()
     }
+
+    pub async fn readiness<S, B>(&self, app: &S) -> (actix_http::StatusCode, Value)
+    where
+        S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+        B: MessageBody,
+    {
+        let request = TestRequest::get().uri("/health/ready").to_request();
+        let response = actix_web::test::call_service(app, request).await;
+        let status = response.status();
+        let body = actix_web::test::read_body_json(response).await;
+        (status, body)
+    }
 }
 
 fn mk_merchant_account(merchant_id: Option<String>) -> Value {
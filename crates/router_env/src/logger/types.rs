@@ -78,6 +78,12 @@ pub enum Flow {
     ConfigKeyFetch,
     /// ConfigKey Update flow.
     ConfigKeyUpdate,
+    /// Cache invalidate flow.
+    CacheInvalidate,
+    /// Key rotation report flow.
+    KeyRotationReport,
+    /// Readiness health check flow.
+    HealthReadiness,
     /// Customers create flow.
     CustomersCreate,
     /// Customers retrieve flow.
@@ -108,6 +114,8 @@ pub enum Flow {
     PaymentMethodsUpdate,
     /// Payment methods delete flow.
     PaymentMethodsDelete,
+    /// Network token lifecycle notification flow.
+    NetworkTokenNotification,
     /// Payments create flow.
     PaymentsCreate,
     /// Payments Retrieve flow.
@@ -168,6 +176,10 @@ pub enum Flow {
     DisputesList,
     /// Cards Info flow
     CardsInfo,
+    /// Connector capabilities list flow
+    ConnectorCapabilitiesList,
+    /// Connector audit log retrieve flow
+    ConnectorAuditLogRetrieve,
     /// Create File flow
     CreateFile,
     /// Delete File flow
@@ -176,6 +188,32 @@ pub enum Flow {
     RetrieveFile,
     /// Dispute Evidence submission flow
     DisputesEvidenceSubmit,
+    /// Payment Link create flow
+    PaymentLinkCreate,
+    /// Payment Link retrieve flow
+    PaymentLinkRetrieve,
+    /// Payment Link checkout page flow
+    PaymentLinkCheckout,
+    /// Payment Link pay flow
+    PaymentLinkPay,
+    /// Simulator payment create flow
+    SimulatorPaymentsCreate,
+    /// Simulator payment retrieve flow
+    SimulatorPaymentsRetrieve,
+    /// Simulator payment capture flow
+    SimulatorPaymentsCapture,
+    /// Simulator payment void flow
+    SimulatorPaymentsVoid,
+    /// Simulator payment 3DS authentication flow
+    SimulatorPaymentsAuthenticate,
+    /// Simulator refund create flow
+    SimulatorRefundsCreate,
+    /// Simulator refund retrieve flow
+    SimulatorRefundsRetrieve,
+    /// Simulator dispute accept flow
+    SimulatorDisputesAccept,
+    /// Simulator dispute evidence submission flow
+    SimulatorDisputesEvidenceSubmit,
 }
 
 ///
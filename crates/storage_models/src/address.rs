@@ -29,9 +29,7 @@ pub struct AddressNew {
 #[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable, frunk::LabelledGeneric)]
 #[diesel(table_name = address)]
 pub struct Address {
-    #[serde(skip_serializing)]
     pub id: i32,
-    #[serde(skip_serializing)]
     pub address_id: String,
     pub city: Option<String>,
     pub country: Option<enums::CountryCode>,
@@ -44,17 +42,15 @@ pub struct Address {
     pub last_name: Option<Secret<String>>,
     pub phone_number: Option<Secret<String>>,
     pub country_code: Option<String>,
-    #[serde(skip_serializing)]
     #[serde(with = "custom_serde::iso8601")]
     pub created_at: PrimitiveDateTime,
-    #[serde(skip_serializing)]
     #[serde(with = "custom_serde::iso8601")]
     pub modified_at: PrimitiveDateTime,
     pub customer_id: String,
     pub merchant_id: String,
 }
 
-#[derive(Debug, frunk::LabelledGeneric)]
+#[derive(Clone, Debug, Deserialize, Serialize, frunk::LabelledGeneric)]
 pub enum AddressUpdate {
     Update {
         city: Option<String>,
@@ -121,6 +117,27 @@ impl From<AddressUpdate> for AddressUpdateInternal {
     }
 }
 
+impl AddressUpdate {
+    pub fn apply_changeset(self, source: Address) -> Address {
+        let address_update: AddressUpdateInternal = self.into();
+        Address {
+            city: address_update.city.or(source.city),
+            country: address_update.country.or(source.country),
+            line1: address_update.line1.or(source.line1),
+            line2: address_update.line2.or(source.line2),
+            line3: address_update.line3.or(source.line3),
+            state: address_update.state.or(source.state),
+            zip: address_update.zip.or(source.zip),
+            first_name: address_update.first_name.or(source.first_name),
+            last_name: address_update.last_name.or(source.last_name),
+            phone_number: address_update.phone_number.or(source.phone_number),
+            country_code: address_update.country_code.or(source.country_code),
+            modified_at: address_update.modified_at,
+            ..source
+        }
+    }
+}
+
 impl Default for AddressNew {
     fn default() -> Self {
         Self {
@@ -3,7 +3,7 @@ use time::PrimitiveDateTime;
 
 use crate::schema::api_keys;
 
-#[derive(Debug, Identifiable, Queryable)]
+#[derive(Clone, Debug, Identifiable, Queryable)]
 #[diesel(table_name = api_keys, primary_key(key_id))]
 pub struct ApiKey {
     pub key_id: String,
@@ -15,6 +15,8 @@ pub struct ApiKey {
     pub created_at: PrimitiveDateTime,
     pub expires_at: Option<PrimitiveDateTime>,
     pub last_used: Option<PrimitiveDateTime>,
+    /// Identifier of the key with which the API key was hashed
+    pub hash_key_id: String,
 }
 
 #[derive(Debug, Insertable)]
@@ -29,6 +31,7 @@ pub struct ApiKeyNew {
     pub created_at: PrimitiveDateTime,
     pub expires_at: Option<PrimitiveDateTime>,
     pub last_used: Option<PrimitiveDateTime>,
+    pub hash_key_id: String,
 }
 
 #[derive(Debug)]
@@ -42,6 +45,11 @@ pub enum ApiKeyUpdate {
     LastUsedUpdate {
         last_used: PrimitiveDateTime,
     },
+    /// Replaces the hash of an API key hashed with a key which has been rotated out
+    HashUpdate {
+        hashed_api_key: HashedApiKey,
+        hash_key_id: String,
+    },
 }
 
 #[derive(Debug, AsChangeset)]
@@ -51,6 +59,8 @@ pub(crate) struct ApiKeyUpdateInternal {
     pub description: Option<String>,
     pub expires_at: Option<Option<PrimitiveDateTime>>,
     pub last_used: Option<PrimitiveDateTime>,
+    pub hashed_api_key: Option<HashedApiKey>,
+    pub hash_key_id: Option<String>,
 }
 
 impl From<ApiKeyUpdate> for ApiKeyUpdateInternal {
@@ -66,18 +76,33 @@ impl From<ApiKeyUpdate> for ApiKeyUpdateInternal {
                 description,
                 expires_at,
                 last_used,
+                hashed_api_key: None,
+                hash_key_id: None,
             },
             ApiKeyUpdate::LastUsedUpdate { last_used } => Self {
                 last_used: Some(last_used),
                 name: None,
                 description: None,
                 expires_at: None,
+                hashed_api_key: None,
+                hash_key_id: None,
+            },
+            ApiKeyUpdate::HashUpdate {
+                hashed_api_key,
+                hash_key_id,
+            } => Self {
+                hashed_api_key: Some(hashed_api_key),
+                hash_key_id: Some(hash_key_id),
+                name: None,
+                description: None,
+                expires_at: None,
+                last_used: None,
             },
         }
     }
 }
 
-#[derive(Debug, AsExpression)]
+#[derive(Clone, Debug, AsExpression)]
 #[diesel(sql_type = diesel::sql_types::Text)]
 pub struct HashedApiKey(String);
 
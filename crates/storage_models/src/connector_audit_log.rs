@@ -0,0 +1,47 @@
+use common_utils::custom_serde;
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::schema::connector_audit_log;
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = connector_audit_log)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorAuditLogNew {
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub flow: String,
+    pub request_method: String,
+    pub request_url: String,
+    pub request_body: Option<serde_json::Value>,
+    pub status_code: Option<i32>,
+    pub response_body: Option<serde_json::Value>,
+    pub error_message: Option<String>,
+    pub latency_ms: i64,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = connector_audit_log)]
+pub struct ConnectorAuditLog {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub flow: String,
+    pub request_method: String,
+    pub request_url: String,
+    pub request_body: Option<serde_json::Value>,
+    pub status_code: Option<i32>,
+    pub response_body: Option<serde_json::Value>,
+    pub error_message: Option<String>,
+    pub latency_ms: i64,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
@@ -49,7 +49,7 @@ pub struct ConnectorResponseUpdateInternal {
     pub connector_name: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum ConnectorResponseUpdate {
     ResponseUpdate {
         connector_transaction_id: Option<String>,
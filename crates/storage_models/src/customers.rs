@@ -1,11 +1,14 @@
 use common_utils::pii;
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 use masking::Secret;
+use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
 use crate::schema::customers;
 
-#[derive(Default, Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[derive(
+    Default, Clone, Debug, Insertable, router_derive::DebugAsDisplay, Serialize, Deserialize,
+)]
 #[diesel(table_name = customers)]
 pub struct CustomerNew {
     pub customer_id: String,
@@ -18,7 +21,7 @@ pub struct CustomerNew {
     pub metadata: Option<pii::SecretSerdeValue>,
 }
 
-#[derive(Clone, Debug, Identifiable, Queryable)]
+#[derive(Clone, Debug, Identifiable, Queryable, Serialize, Deserialize)]
 #[diesel(table_name = customers)]
 pub struct Customer {
     pub id: i32,
@@ -29,12 +32,14 @@ pub struct Customer {
     pub phone: Option<Secret<String>>,
     pub phone_country_code: Option<String>,
     pub description: Option<String>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
     pub created_at: PrimitiveDateTime,
     pub metadata: Option<pii::SecretSerdeValue>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
     pub modified_at: PrimitiveDateTime,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum CustomerUpdate {
     Update {
         name: Option<String>,
@@ -80,3 +85,23 @@ impl From<CustomerUpdate> for CustomerUpdateInternal {
         }
     }
 }
+
+impl CustomerUpdate {
+    pub fn apply_changeset(self, source: Customer) -> Customer {
+        let customer_update: CustomerUpdateInternal = self.into();
+        Customer {
+            name: customer_update.name.or(source.name),
+            email: customer_update.email.or(source.email),
+            phone: customer_update.phone.or(source.phone),
+            description: customer_update.description.or(source.description),
+            phone_country_code: customer_update
+                .phone_country_code
+                .or(source.phone_country_code),
+            metadata: customer_update.metadata.or(source.metadata),
+            modified_at: customer_update
+                .modified_at
+                .unwrap_or_else(common_utils::date_time::now),
+            ..source
+        }
+    }
+}
@@ -2,10 +2,14 @@ use error_stack::{IntoReport, ResultExt};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    address::{Address, AddressNew, AddressUpdate},
+    connector_response::{ConnectorResponse, ConnectorResponseNew, ConnectorResponseUpdate},
+    customers::{Customer, CustomerNew, CustomerUpdate},
     errors,
     payment_attempt::{PaymentAttempt, PaymentAttemptNew, PaymentAttemptUpdate},
     payment_intent::{PaymentIntent, PaymentIntentNew, PaymentIntentUpdate},
     refund::{Refund, RefundNew, RefundUpdate},
+    reverse_lookup::ReverseLookupNew,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +43,10 @@ pub enum Insertable {
     PaymentIntent(PaymentIntentNew),
     PaymentAttempt(PaymentAttemptNew),
     Refund(RefundNew),
+    Address(Box<AddressNew>),
+    ConnectorResponse(ConnectorResponseNew),
+    Customer(CustomerNew),
+    ReverseLookUp(ReverseLookupNew),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -47,6 +55,9 @@ pub enum Updateable {
     PaymentIntentUpdate(PaymentIntentUpdateMems),
     PaymentAttemptUpdate(PaymentAttemptUpdateMems),
     RefundUpdate(RefundUpdateMems),
+    AddressUpdate(Box<AddressUpdateMems>),
+    ConnectorResponseUpdate(ConnectorResponseUpdateMems),
+    CustomerUpdate(CustomerUpdateMems),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -66,3 +77,21 @@ pub struct RefundUpdateMems {
     pub orig: Refund,
     pub update_data: RefundUpdate,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddressUpdateMems {
+    pub orig: Address,
+    pub update_data: AddressUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConnectorResponseUpdateMems {
+    pub orig: ConnectorResponse,
+    pub update_data: ConnectorResponseUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CustomerUpdateMems {
+    pub orig: Customer,
+    pub update_data: CustomerUpdate,
+}
@@ -2,6 +2,7 @@ pub mod address;
 pub mod api_keys;
 pub mod cards_info;
 pub mod configs;
+pub mod connector_audit_log;
 pub mod connector_response;
 pub mod customers;
 pub mod dispute;
@@ -16,8 +17,10 @@ pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
 pub mod merchant_connector_account;
+pub mod merchant_key_store;
 pub mod payment_attempt;
 pub mod payment_intent;
+pub mod payment_link;
 pub mod payment_method;
 pub mod process_tracker;
 pub mod query;
@@ -37,6 +37,10 @@ pub struct MerchantAccount {
     pub api_key: Option<StrongSecret<String>>,
     pub created_at: time::PrimitiveDateTime,
     pub modified_at: time::PrimitiveDateTime,
+    pub intent_fulfillment_time: Option<i64>,
+    /// Time at which KV was turned off for the merchant, while its entries in Redis are being
+    /// drained to the database
+    pub kv_drain_started_at: Option<time::PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, Default, Insertable, router_derive::DebugAsDisplay)]
@@ -58,6 +62,7 @@ pub struct MerchantAccountNew {
     pub routing_algorithm: Option<serde_json::Value>,
     pub primary_business_details: serde_json::Value,
     pub api_key: Option<StrongSecret<String>>,
+    pub intent_fulfillment_time: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -77,10 +82,12 @@ pub enum MerchantAccountUpdate {
         metadata: Option<pii::SecretSerdeValue>,
         routing_algorithm: Option<serde_json::Value>,
         primary_business_details: Option<serde_json::Value>,
+        intent_fulfillment_time: Option<i64>,
     },
     StorageSchemeUpdate {
         storage_scheme: storage_enums::MerchantStorageScheme,
     },
+    KvDrainStarted,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -102,6 +109,8 @@ pub struct MerchantAccountUpdateInternal {
     routing_algorithm: Option<serde_json::Value>,
     primary_business_details: Option<serde_json::Value>,
     modified_at: Option<time::PrimitiveDateTime>,
+    intent_fulfillment_time: Option<i64>,
+    kv_drain_started_at: Option<Option<time::PrimitiveDateTime>>,
 }
 
 impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
@@ -122,6 +131,7 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 locker_id,
                 metadata,
                 primary_business_details,
+                intent_fulfillment_time,
             } => Self {
                 merchant_name,
                 merchant_details,
@@ -137,11 +147,17 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 locker_id,
                 metadata,
                 primary_business_details,
+                intent_fulfillment_time,
                 modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
             MerchantAccountUpdate::StorageSchemeUpdate { storage_scheme } => Self {
                 storage_scheme: Some(storage_scheme),
+                kv_drain_started_at: Some(None),
+                ..Default::default()
+            },
+            MerchantAccountUpdate::KvDrainStarted => Self {
+                kv_drain_started_at: Some(Some(common_utils::date_time::now())),
                 ..Default::default()
             },
         }
@@ -0,0 +1,21 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use masking::StrongSecret;
+
+use crate::schema::merchant_key_store;
+
+/// Data key of a merchant, with which its PII is encrypted. The key is stored encrypted with the
+/// master key of the application.
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = merchant_key_store, primary_key(merchant_id))]
+pub struct MerchantKeyStore {
+    pub merchant_id: String,
+    pub key: StrongSecret<Vec<u8>>,
+    pub created_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = merchant_key_store)]
+pub struct MerchantKeyStoreNew {
+    pub merchant_id: String,
+    pub key: StrongSecret<Vec<u8>>,
+}
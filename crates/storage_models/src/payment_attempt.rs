@@ -46,6 +46,9 @@ pub struct PaymentAttempt {
     pub payment_method_data: Option<serde_json::Value>,
     pub business_sub_label: Option<String>,
     pub straight_through_algorithm: Option<serde_json::Value>,
+    pub settlement_amount: Option<i64>,
+    pub settlement_currency: Option<storage_enums::Currency>,
+    pub fx_rate: Option<String>,
 }
 
 #[derive(
@@ -91,6 +94,9 @@ pub struct PaymentAttemptNew {
     pub payment_method_data: Option<serde_json::Value>,
     pub business_sub_label: Option<String>,
     pub straight_through_algorithm: Option<serde_json::Value>,
+    pub settlement_amount: Option<i64>,
+    pub settlement_currency: Option<storage_enums::Currency>,
+    pub fx_rate: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,6 +112,8 @@ pub enum PaymentAttemptUpdate {
         payment_method_type: Option<storage_enums::PaymentMethodType>,
         payment_experience: Option<storage_enums::PaymentExperience>,
         business_sub_label: Option<String>,
+        settlement_amount: Option<i64>,
+        fx_rate: Option<String>,
     },
     UpdateTrackers {
         payment_token: Option<String>,
@@ -189,6 +197,8 @@ pub struct PaymentAttemptUpdateInternal {
     payment_experience: Option<storage_enums::PaymentExperience>,
     business_sub_label: Option<String>,
     straight_through_algorithm: Option<serde_json::Value>,
+    settlement_amount: Option<i64>,
+    fx_rate: Option<String>,
 }
 
 impl PaymentAttemptUpdate {
@@ -211,6 +221,8 @@ impl PaymentAttemptUpdate {
             browser_info: pa_update.browser_info.or(source.browser_info),
             modified_at: common_utils::date_time::now(),
             payment_token: pa_update.payment_token.or(source.payment_token),
+            settlement_amount: pa_update.settlement_amount.or(source.settlement_amount),
+            fx_rate: pa_update.fx_rate.or(source.fx_rate),
             ..source
         }
     }
@@ -231,6 +243,8 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 payment_method_type,
                 payment_experience,
                 business_sub_label,
+                settlement_amount,
+                fx_rate,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -244,6 +258,8 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 payment_method_type,
                 payment_experience,
                 business_sub_label,
+                settlement_amount,
+                fx_rate,
                 ..Default::default()
             },
             PaymentAttemptUpdate::AuthenticationTypeUpdate {
@@ -36,6 +36,9 @@ pub struct PaymentIntent {
     pub active_attempt_id: String,
     pub business_country: storage_enums::CountryCode,
     pub business_label: String,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub session_expiry: Option<PrimitiveDateTime>,
+    pub attempt_count: i16,
 }
 
 #[derive(
@@ -78,6 +81,9 @@ pub struct PaymentIntentNew {
     pub active_attempt_id: String,
     pub business_country: storage_enums::CountryCode,
     pub business_label: String,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub session_expiry: Option<PrimitiveDateTime>,
+    pub attempt_count: i16,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,6 +125,7 @@ pub enum PaymentIntentUpdate {
     },
     PaymentAttemptUpdate {
         active_attempt_id: String,
+        attempt_count: i16,
     },
 }
 
@@ -142,6 +149,7 @@ pub struct PaymentIntentUpdateInternal {
     pub active_attempt_id: Option<String>,
     pub business_country: Option<storage_enums::CountryCode>,
     pub business_label: Option<String>,
+    pub attempt_count: Option<i16>,
 }
 
 impl PaymentIntentUpdate {
@@ -169,6 +177,12 @@ impl PaymentIntentUpdate {
                 .shipping_address_id
                 .or(source.shipping_address_id),
             modified_at: common_utils::date_time::now(),
+            active_attempt_id: internal_update
+                .active_attempt_id
+                .unwrap_or(source.active_attempt_id),
+            attempt_count: internal_update
+                .attempt_count
+                .unwrap_or(source.attempt_count),
             ..source
         }
     }
@@ -260,8 +274,13 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
-            PaymentIntentUpdate::PaymentAttemptUpdate { active_attempt_id } => Self {
+            PaymentIntentUpdate::PaymentAttemptUpdate {
+                active_attempt_id,
+                attempt_count,
+            } => Self {
                 active_attempt_id: Some(active_attempt_id),
+                attempt_count: Some(attempt_count),
+                modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
         }
@@ -283,3 +302,9 @@ fn make_client_secret_null_based_on_status(
         | storage_enums::IntentStatus::RequiresCapture => None,
     }
 }
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PaymentIntentExpiryTrackingData {
+    pub payment_id: String,
+    pub merchant_id: String,
+}
@@ -0,0 +1,87 @@
+use common_utils::custom_serde;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::payment_link};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_link)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentLinkNew {
+    pub payment_link_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub link_to_pay: String,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub description: Option<String>,
+    pub return_url: Option<String>,
+    pub status: storage_enums::PaymentLinkStatus,
+    #[serde(with = "custom_serde::iso8601")]
+    pub expires_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = payment_link, primary_key(payment_link_id))]
+pub struct PaymentLink {
+    pub payment_link_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub link_to_pay: String,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub description: Option<String>,
+    pub return_url: Option<String>,
+    pub status: storage_enums::PaymentLinkStatus,
+    #[serde(with = "custom_serde::iso8601")]
+    pub expires_at: PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum PaymentLinkUpdate {
+    StatusUpdate {
+        status: storage_enums::PaymentLinkStatus,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_link)]
+pub struct PaymentLinkUpdateInternal {
+    status: Option<storage_enums::PaymentLinkStatus>,
+    modified_at: Option<PrimitiveDateTime>,
+}
+
+impl From<PaymentLinkUpdate> for PaymentLinkUpdateInternal {
+    fn from(payment_link_update: PaymentLinkUpdate) -> Self {
+        match payment_link_update {
+            PaymentLinkUpdate::StatusUpdate { status } => Self {
+                status: Some(status),
+                modified_at: Some(common_utils::date_time::now()),
+            },
+        }
+    }
+}
+
+impl PaymentLinkUpdate {
+    pub fn apply_changeset(self, source: PaymentLink) -> PaymentLink {
+        let payment_link_update: PaymentLinkUpdateInternal = self.into();
+        PaymentLink {
+            status: payment_link_update.status.unwrap_or(source.status),
+            modified_at: payment_link_update
+                .modified_at
+                .unwrap_or_else(common_utils::date_time::now),
+            ..source
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PaymentLinkExpiryTrackingData {
+    pub payment_link_id: String,
+    pub merchant_id: String,
+}
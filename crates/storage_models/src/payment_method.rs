@@ -32,6 +32,11 @@ pub struct PaymentMethod {
     pub payment_method_issuer: Option<String>,
     pub payment_method_issuer_code: Option<storage_enums::PaymentMethodIssuerCode>,
     pub metadata: Option<pii::SecretSerdeValue>,
+    pub network_token_ref_id: Option<String>,
+    pub network_token_locker_id: Option<String>,
+    pub network_token_exp_month: Option<Secret<String>>,
+    pub network_token_exp_year: Option<Secret<String>>,
+    pub network_token_status: Option<storage_enums::NetworkTokenStatus>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Insertable, Queryable, router_derive::DebugAsDisplay)]
@@ -57,6 +62,11 @@ pub struct PaymentMethodNew {
     pub created_at: PrimitiveDateTime,
     pub last_modified: PrimitiveDateTime,
     pub metadata: Option<pii::SecretSerdeValue>,
+    pub network_token_ref_id: Option<String>,
+    pub network_token_locker_id: Option<String>,
+    pub network_token_exp_month: Option<Secret<String>>,
+    pub network_token_exp_year: Option<Secret<String>>,
+    pub network_token_status: Option<storage_enums::NetworkTokenStatus>,
 }
 
 impl Default for PaymentMethodNew {
@@ -84,6 +94,11 @@ impl Default for PaymentMethodNew {
             created_at: now,
             last_modified: now,
             metadata: Option::default(),
+            network_token_ref_id: Option::default(),
+            network_token_locker_id: Option::default(),
+            network_token_exp_month: Option::default(),
+            network_token_exp_year: Option::default(),
+            network_token_status: Option::default(),
         }
     }
 }
@@ -92,23 +107,70 @@ impl Default for PaymentMethodNew {
 pub struct TokenizeCoreWorkflow {
     pub lookup_key: String,
     pub pm: storage_enums::PaymentMethod,
+    /// Identifier of the locker key pair the data was tokenized with, absent for data tokenized
+    /// before locker keys were tracked
+    #[serde(default)]
+    pub key_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum PaymentMethodUpdate {
-    MetadataUpdate { metadata: Option<serde_json::Value> },
+    MetadataUpdate {
+        metadata: Option<serde_json::Value>,
+    },
+    NetworkTokenUpdate {
+        network_token_ref_id: String,
+        network_token_locker_id: String,
+        network_token_exp_month: Secret<String>,
+        network_token_exp_year: Secret<String>,
+        network_token_status: storage_enums::NetworkTokenStatus,
+    },
+    NetworkTokenStatusUpdate {
+        network_token_status: storage_enums::NetworkTokenStatus,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
 #[diesel(table_name = payment_methods)]
 pub struct PaymentMethodUpdateInternal {
     metadata: Option<serde_json::Value>,
+    network_token_ref_id: Option<String>,
+    network_token_locker_id: Option<String>,
+    network_token_exp_month: Option<Secret<String>>,
+    network_token_exp_year: Option<Secret<String>>,
+    network_token_status: Option<storage_enums::NetworkTokenStatus>,
+    last_modified: Option<PrimitiveDateTime>,
 }
 
 impl From<PaymentMethodUpdate> for PaymentMethodUpdateInternal {
     fn from(payment_method_update: PaymentMethodUpdate) -> Self {
         match payment_method_update {
-            PaymentMethodUpdate::MetadataUpdate { metadata } => Self { metadata },
+            PaymentMethodUpdate::MetadataUpdate { metadata } => Self {
+                metadata,
+                ..Default::default()
+            },
+            PaymentMethodUpdate::NetworkTokenUpdate {
+                network_token_ref_id,
+                network_token_locker_id,
+                network_token_exp_month,
+                network_token_exp_year,
+                network_token_status,
+            } => Self {
+                network_token_ref_id: Some(network_token_ref_id),
+                network_token_locker_id: Some(network_token_locker_id),
+                network_token_exp_month: Some(network_token_exp_month),
+                network_token_exp_year: Some(network_token_exp_year),
+                network_token_status: Some(network_token_status),
+                last_modified: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+            PaymentMethodUpdate::NetworkTokenStatusUpdate {
+                network_token_status,
+            } => Self {
+                network_token_status: Some(network_token_status),
+                last_modified: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
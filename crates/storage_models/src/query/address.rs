@@ -75,6 +75,24 @@ impl Address {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_customer_id(
+        conn: &PgPooledConn,
+        customer_id: &str,
+        merchant_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::customer_id.eq(customer_id.to_owned())),
+            None,
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_by_address_id<'a>(
         conn: &PgPooledConn,
@@ -95,4 +113,22 @@ impl Address {
         )
         .await
     }
+
+    /// Lists the addresses with an ID greater than `id`, in the order of their IDs, to iterate over
+    /// all the rows of the table in batches.
+    #[instrument(skip(conn))]
+    pub async fn list_after_id(
+        conn: &PgPooledConn,
+        id: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id.gt(id),
+            Some(limit),
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
 }
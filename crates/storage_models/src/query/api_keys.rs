@@ -1,4 +1,8 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{
+    associations::HasTable, dsl::count_star, BoolExpressionMethods, ExpressionMethods, QueryDsl,
+};
+use error_stack::{IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 
 use super::generics;
@@ -115,4 +119,17 @@ impl ApiKey {
         )
         .await
     }
+
+    /// Returns the number of API keys hashed with each hash key, as `(hash_key_id, count)` pairs.
+    #[instrument(skip(conn))]
+    pub async fn count_by_hash_key_id(conn: &PgPooledConn) -> StorageResult<Vec<(String, i64)>> {
+        <Self as HasTable>::table()
+            .group_by(dsl::hash_key_id)
+            .select((dsl::hash_key_id, count_star()))
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error counting API keys by hash key ID")
+    }
 }
@@ -0,0 +1,38 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    connector_audit_log::{ConnectorAuditLog, ConnectorAuditLogNew},
+    schema::connector_audit_log::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl ConnectorAuditLogNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<ConnectorAuditLog> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl ConnectorAuditLog {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_payment_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_id: &str,
+        limit: i64,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_id.eq(payment_id.to_owned())),
+            Some(limit),
+            offset,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
+}
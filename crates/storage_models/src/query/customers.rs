@@ -85,4 +85,21 @@ impl Customer {
         )
         .await
     }
+
+    /// Lists the customers with an ID greater than `id`, ordered by ID.
+    #[instrument(skip(conn))]
+    pub async fn list_after_id(
+        conn: &PgPooledConn,
+        id: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id.gt(id),
+            Some(limit),
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
 }
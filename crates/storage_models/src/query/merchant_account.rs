@@ -92,4 +92,16 @@ impl MerchantAccount {
         )
         .await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn find_kv_draining(conn: &PgPooledConn) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::kv_drain_started_at.is_not_null(),
+            None,
+            None,
+            Some(dsl::kv_drain_started_at.asc()),
+        )
+        .await
+    }
 }
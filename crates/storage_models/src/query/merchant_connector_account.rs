@@ -123,4 +123,21 @@ impl MerchantConnectorAccount {
             .await
         }
     }
+
+    /// Lists the merchant connector accounts with an ID greater than `id`, ordered by ID.
+    #[instrument(skip(conn))]
+    pub async fn list_after_id(
+        conn: &PgPooledConn,
+        id: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id.gt(id),
+            Some(limit),
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
 }
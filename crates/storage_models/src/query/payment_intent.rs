@@ -1,9 +1,10 @@
 use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
 use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
 
 use super::generics;
 use crate::{
-    errors,
+    enums, errors,
     payment_intent::{
         PaymentIntent, PaymentIntentNew, PaymentIntentUpdate, PaymentIntentUpdateInternal,
     },
@@ -59,6 +60,25 @@ impl PaymentIntent {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_expired(
+        conn: &PgPooledConn,
+        expired_before: PrimitiveDateTime,
+        statuses: Vec<enums::IntentStatus>,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::session_expiry
+                .le(expired_before)
+                .and(dsl::status.eq_any(statuses)),
+            Some(limit),
+            None,
+            Some(dsl::session_expiry.asc()),
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_optional_by_payment_id_merchant_id(
         conn: &PgPooledConn,
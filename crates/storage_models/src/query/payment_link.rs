@@ -0,0 +1,72 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    errors,
+    payment_link::{PaymentLink, PaymentLinkNew, PaymentLinkUpdate, PaymentLinkUpdateInternal},
+    schema::payment_link::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl PaymentLinkNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<PaymentLink> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl PaymentLink {
+    #[instrument(skip(conn))]
+    pub async fn find_by_payment_link_id(
+        conn: &PgPooledConn,
+        payment_link_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::payment_link_id.eq(payment_link_id.to_owned()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_payment_link_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_link_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_link_id.eq(payment_link_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update(
+        self,
+        conn: &PgPooledConn,
+        payment_link: PaymentLinkUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::payment_link_id.eq(self.payment_link_id.to_owned()),
+            PaymentLinkUpdateInternal::from(payment_link),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NoFieldsToUpdate => Ok(self),
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+}
@@ -56,6 +56,18 @@ impl PaymentMethod {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_by_network_token_ref_id(
+        conn: &PgPooledConn,
+        network_token_ref_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::network_token_ref_id.eq(network_token_ref_id.to_owned()),
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_by_merchant_id(
         conn: &PgPooledConn,
@@ -1,4 +1,11 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, Table};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{
+    associations::HasTable,
+    dsl::{count_star, sql},
+    sql_types::{Nullable, Text},
+    BoolExpressionMethods, ExpressionMethods, QueryDsl, Table, TextExpressionMethods,
+};
+use error_stack::{IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 use time::PrimitiveDateTime;
 
@@ -118,6 +125,32 @@ impl ProcessTracker {
         Ok(x)
     }
 
+    /// Counts the processes of a runner except those whose business status starts with
+    /// `business_status_prefix`, as `(key_id, count)` pairs grouped by the `key_id` of their
+    /// tracking data.
+    #[instrument(skip(conn))]
+    pub async fn count_by_runner_and_key_id_excluding_business_status_prefix(
+        conn: &PgPooledConn,
+        runner: &str,
+        business_status_prefix: &str,
+    ) -> StorageResult<Vec<(Option<String>, i64)>> {
+        let key_id = sql::<Nullable<Text>>("tracking_data ->> 'key_id'");
+        <Self as HasTable>::table()
+            .filter(
+                dsl::runner.eq(runner.to_owned()).and(
+                    dsl::business_status
+                        .not_like(format!("{}%", escape_like_pattern(business_status_prefix))),
+                ),
+            )
+            .group_by(key_id.clone())
+            .select((key_id, count_star()))
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error counting processes by key ID")
+    }
+
     #[instrument(skip(conn))]
     pub async fn reinitialize_limbo_processes(
         conn: &PgPooledConn,
@@ -137,3 +170,11 @@ impl ProcessTracker {
         .await
     }
 }
+
+/// Escapes the wildcards of a `LIKE` pattern, so that `value` is matched literally.
+fn escape_like_pattern(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
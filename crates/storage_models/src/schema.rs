@@ -39,6 +39,7 @@ diesel::table! {
         created_at -> Timestamp,
         expires_at -> Nullable<Timestamp>,
         last_used -> Nullable<Timestamp>,
+        hash_key_id -> Varchar,
     }
 }
 
@@ -73,6 +74,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    connector_audit_log (id) {
+        id -> Int4,
+        merchant_id -> Varchar,
+        payment_id -> Varchar,
+        attempt_id -> Varchar,
+        connector -> Varchar,
+        flow -> Varchar,
+        request_method -> Varchar,
+        request_url -> Text,
+        request_body -> Nullable<Jsonb>,
+        status_code -> Nullable<Int4>,
+        response_body -> Nullable<Jsonb>,
+        error_message -> Nullable<Text>,
+        latency_ms -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -248,6 +271,8 @@ diesel::table! {
         api_key -> Nullable<Varchar>,
         created_at -> Timestamp,
         modified_at -> Timestamp,
+        intent_fulfillment_time -> Nullable<Int8>,
+        kv_drain_started_at -> Nullable<Timestamp>,
     }
 }
 
@@ -276,6 +301,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    merchant_key_store (merchant_id) {
+        merchant_id -> Varchar,
+        key -> Bytea,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -316,6 +352,9 @@ diesel::table! {
         payment_method_data -> Nullable<Jsonb>,
         business_sub_label -> Nullable<Varchar>,
         straight_through_algorithm -> Nullable<Jsonb>,
+        settlement_amount -> Nullable<Int8>,
+        settlement_currency -> Nullable<Currency>,
+        fx_rate -> Nullable<Varchar>,
     }
 }
 
@@ -349,6 +388,28 @@ diesel::table! {
         active_attempt_id -> Varchar,
         business_country -> CountryCode,
         business_label -> Varchar,
+        session_expiry -> Nullable<Timestamp>,
+        attempt_count -> Int2,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    payment_link (payment_link_id) {
+        payment_link_id -> Varchar,
+        merchant_id -> Varchar,
+        payment_id -> Varchar,
+        link_to_pay -> Varchar,
+        amount -> Int8,
+        currency -> Currency,
+        description -> Nullable<Varchar>,
+        return_url -> Nullable<Varchar>,
+        status -> Varchar,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
     }
 }
 
@@ -378,6 +439,11 @@ diesel::table! {
         payment_method_issuer -> Nullable<Varchar>,
         payment_method_issuer_code -> Nullable<PaymentMethodIssuerCode>,
         metadata -> Nullable<Json>,
+        network_token_ref_id -> Nullable<Varchar>,
+        network_token_locker_id -> Nullable<Varchar>,
+        network_token_exp_month -> Nullable<Varchar>,
+        network_token_exp_year -> Nullable<Varchar>,
+        network_token_status -> Nullable<Varchar>,
     }
 }
 
@@ -451,6 +517,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     api_keys,
     cards_info,
     configs,
+    connector_audit_log,
     connector_response,
     customers,
     dispute,
@@ -460,8 +527,10 @@ diesel::allow_tables_to_appear_in_same_query!(
     mandate,
     merchant_account,
     merchant_connector_account,
+    merchant_key_store,
     payment_attempt,
     payment_intent,
+    payment_link,
     payment_methods,
     process_tracker,
     refund,
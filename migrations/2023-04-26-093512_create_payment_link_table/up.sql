@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE payment_link (
+    payment_link_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    link_to_pay VARCHAR(255) NOT NULL,
+    amount BIGINT NOT NULL,
+    currency "Currency" NOT NULL,
+    description VARCHAR(255),
+    return_url VARCHAR(255),
+    status VARCHAR(64) NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (payment_link_id)
+);
+
+CREATE INDEX payment_link_merchant_id_payment_id_index ON payment_link (merchant_id, payment_id);
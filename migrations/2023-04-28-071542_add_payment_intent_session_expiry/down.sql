@@ -0,0 +1,14 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_account
+DROP COLUMN IF EXISTS intent_fulfillment_time;
+
+DROP INDEX IF EXISTS payment_intent_session_expiry_index;
+
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS session_expiry;
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'payment_cancelled'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+ALTER TABLE merchant_account
+ADD COLUMN IF NOT EXISTS intent_fulfillment_time BIGINT;
+
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS session_expiry TIMESTAMP;
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payment_cancelled';
+
+CREATE INDEX IF NOT EXISTS payment_intent_session_expiry_index ON payment_intent (session_expiry)
+WHERE session_expiry IS NOT NULL;
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS payment_methods_network_token_ref_id_index;
+
+ALTER TABLE payment_methods
+DROP COLUMN IF EXISTS network_token_ref_id,
+DROP COLUMN IF EXISTS network_token_locker_id,
+DROP COLUMN IF EXISTS network_token_exp_month,
+DROP COLUMN IF EXISTS network_token_exp_year,
+DROP COLUMN IF EXISTS network_token_status;
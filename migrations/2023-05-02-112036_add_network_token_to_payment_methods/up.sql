@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE payment_methods
+ADD COLUMN IF NOT EXISTS network_token_ref_id VARCHAR(128),
+ADD COLUMN IF NOT EXISTS network_token_locker_id VARCHAR(64),
+ADD COLUMN IF NOT EXISTS network_token_exp_month VARCHAR(2),
+ADD COLUMN IF NOT EXISTS network_token_exp_year VARCHAR(4),
+ADD COLUMN IF NOT EXISTS network_token_status VARCHAR(32);
+
+CREATE INDEX IF NOT EXISTS payment_methods_network_token_ref_id_index ON payment_methods (network_token_ref_id);
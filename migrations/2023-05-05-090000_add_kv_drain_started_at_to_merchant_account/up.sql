@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE merchant_account ADD COLUMN IF NOT EXISTS kv_drain_started_at TIMESTAMP;
@@ -0,0 +1,17 @@
+-- This file should undo anything in `up.sql`
+-- Fails if encrypted values are present, which have to be decrypted before reverting
+ALTER TABLE address
+    ALTER COLUMN line1 TYPE VARCHAR(255),
+    ALTER COLUMN line2 TYPE VARCHAR(255),
+    ALTER COLUMN line3 TYPE VARCHAR(255),
+    ALTER COLUMN state TYPE VARCHAR(128),
+    ALTER COLUMN zip TYPE VARCHAR(16),
+    ALTER COLUMN first_name TYPE VARCHAR(255),
+    ALTER COLUMN last_name TYPE VARCHAR(255),
+    ALTER COLUMN phone_number TYPE VARCHAR(32);
+
+ALTER TABLE customers
+    ALTER COLUMN email TYPE VARCHAR(255),
+    ALTER COLUMN phone TYPE VARCHAR(32);
+
+DROP TABLE IF EXISTS merchant_key_store;
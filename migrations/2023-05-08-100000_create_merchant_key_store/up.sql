@@ -0,0 +1,21 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS merchant_key_store (
+    merchant_id VARCHAR(64) PRIMARY KEY,
+    key BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+-- Encrypted values are longer than the plaintext they replace
+ALTER TABLE customers
+    ALTER COLUMN email TYPE VARCHAR(512),
+    ALTER COLUMN phone TYPE VARCHAR(512);
+
+ALTER TABLE address
+    ALTER COLUMN line1 TYPE VARCHAR(512),
+    ALTER COLUMN line2 TYPE VARCHAR(512),
+    ALTER COLUMN line3 TYPE VARCHAR(512),
+    ALTER COLUMN state TYPE VARCHAR(512),
+    ALTER COLUMN zip TYPE VARCHAR(512),
+    ALTER COLUMN first_name TYPE VARCHAR(512),
+    ALTER COLUMN last_name TYPE VARCHAR(512),
+    ALTER COLUMN phone_number TYPE VARCHAR(512);
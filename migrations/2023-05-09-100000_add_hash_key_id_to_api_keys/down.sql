@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX api_keys_hash_key_id_index;
+
+ALTER TABLE api_keys DROP COLUMN hash_key_id;
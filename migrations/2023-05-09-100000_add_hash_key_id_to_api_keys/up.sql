@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- API keys created before hash keys were versioned were hashed with the key identified by '1'
+ALTER TABLE api_keys ADD COLUMN hash_key_id VARCHAR(64) NOT NULL DEFAULT '1';
+
+CREATE INDEX api_keys_hash_key_id_index ON api_keys (hash_key_id);
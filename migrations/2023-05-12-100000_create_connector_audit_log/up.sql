@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TABLE connector_audit_log (
+    id SERIAL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    connector VARCHAR(64) NOT NULL,
+    flow VARCHAR(64) NOT NULL,
+    request_method VARCHAR(16) NOT NULL,
+    request_url TEXT NOT NULL,
+    request_body JSONB,
+    status_code INTEGER,
+    response_body JSONB,
+    error_message TEXT,
+    latency_ms BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX connector_audit_log_merchant_id_payment_id_index ON connector_audit_log (merchant_id, payment_id);
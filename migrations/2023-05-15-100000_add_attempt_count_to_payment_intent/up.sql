@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS attempt_count SMALLINT NOT NULL DEFAULT 1;